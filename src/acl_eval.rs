@@ -0,0 +1,293 @@
+//acl_eval.rs
+
+/// First-match evaluation of an [`AccessControlList`] against a single
+/// packet description, backing the `test access-list` command.
+use crate::network_config::{AccessControlList, AclEntry};
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+
+/// The outcome of testing a packet against an ACL.
+///
+/// `matched_rule` is the 1-based index of the entry that decided the
+/// outcome, or `None` when no entry matched and the implicit `deny any`
+/// applied.
+pub struct AclTestResult {
+    pub permit: bool,
+    pub matched_rule: Option<usize>,
+}
+
+/// The verdict [`AccessControlList::matches`] reaches for a packet: either a
+/// named rule decided it, or no entry matched and the implicit `deny any`
+/// at the end of every ACL applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Permit,
+    Deny,
+}
+
+/// The IANA special-use classification ([RFC 6890](https://www.rfc-editor.org/rfc/rfc6890))
+/// of an IPv4 address, so an ACL entry can match `special-use` as a keyword
+/// instead of spelling out every RFC 1918/6598/etc. range by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressScope {
+    Loopback,
+    PrivateUse,
+    SharedAddressSpace,
+    Benchmarking,
+    IetfProtocolAssignments,
+    LinkLocal,
+    Reserved,
+    Global,
+}
+
+impl AddressScope {
+    /// Whether this scope is anything other than ordinary global unicast --
+    /// i.e. what an ACL's `special-use` keyword is meant to match.
+    pub fn is_special_use(&self) -> bool {
+        *self != AddressScope::Global
+    }
+}
+
+/// Classifies `ip` into the IANA special-use range it falls in, or
+/// [`AddressScope::Global`] if it's ordinary global unicast space.
+pub fn classify(ip: Ipv4Addr) -> AddressScope {
+    let bits = u32::from(ip);
+    let covers = |network: Ipv4Addr, prefix_len: u32| {
+        let mask = if prefix_len == 0 { 0 } else { !0u32 << (32 - prefix_len) };
+        (bits & mask) == (u32::from(network) & mask)
+    };
+    if covers(Ipv4Addr::new(127, 0, 0, 0), 8) {
+        AddressScope::Loopback
+    } else if covers(Ipv4Addr::new(10, 0, 0, 0), 8)
+        || covers(Ipv4Addr::new(172, 16, 0, 0), 12)
+        || covers(Ipv4Addr::new(192, 168, 0, 0), 16)
+    {
+        AddressScope::PrivateUse
+    } else if covers(Ipv4Addr::new(169, 254, 0, 0), 16) {
+        AddressScope::LinkLocal
+    } else if covers(Ipv4Addr::new(100, 64, 0, 0), 10) {
+        AddressScope::SharedAddressSpace
+    } else if covers(Ipv4Addr::new(198, 18, 0, 0), 15) {
+        AddressScope::Benchmarking
+    } else if covers(Ipv4Addr::new(192, 0, 0, 0), 24) {
+        AddressScope::IetfProtocolAssignments
+    } else if covers(Ipv4Addr::new(240, 0, 0, 0), 4) {
+        AddressScope::Reserved
+    } else {
+        AddressScope::Global
+    }
+}
+
+/// Parses a dotted-quad IPv4 address into a `u32` in host byte order, so
+/// wildcard masking can be done with plain bitwise operators.
+fn parse_addr(addr: &str) -> Result<u32, String> {
+    Ipv4Addr::from_str(addr)
+        .map(|ip| u32::from(ip))
+        .map_err(|_| format!("Invalid IP address: {}", addr))
+}
+
+/// Standard Cisco wildcard matching: wildcard 1-bits are "don't care", so a
+/// field matches when the non-wildcard bits of `packet_addr` and
+/// `rule_addr` agree.
+fn wildcard_match(packet_addr: u32, rule_addr: u32, wildcard: u32) -> bool {
+    (packet_addr & !wildcard) == (rule_addr & !wildcard)
+}
+
+/// Evaluates a single port restriction (`eq`/`gt`/`lt`/`range`) against a
+/// packet's port. A rule with no operator or port configured is treated as
+/// "not restricted" and matches any packet; a rule that does restrict the
+/// port never matches a packet that supplies no port.
+fn port_matches(
+    operator: Option<&str>,
+    rule_port: Option<&str>,
+    packet_port: Option<u16>,
+) -> Result<bool, String> {
+    let (operator, rule_port) = match (operator, rule_port) {
+        (Some(operator), Some(rule_port)) => (operator, rule_port),
+        _ => return Ok(true),
+    };
+
+    let packet_port = match packet_port {
+        Some(port) => port,
+        None => return Ok(false),
+    };
+
+    if operator == "range" {
+        let (start, end) = rule_port
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid port range in ACL rule: {}", rule_port))?;
+        let start: u16 = start
+            .parse()
+            .map_err(|_| format!("Invalid port range in ACL rule: {}", rule_port))?;
+        let end: u16 = end
+            .parse()
+            .map_err(|_| format!("Invalid port range in ACL rule: {}", rule_port))?;
+        return Ok(packet_port >= start && packet_port <= end);
+    }
+
+    let rule_port: u16 = rule_port
+        .parse()
+        .map_err(|_| format!("Invalid port in ACL rule: {}", rule_port))?;
+    match operator {
+        "eq" => Ok(packet_port == rule_port),
+        "gt" => Ok(packet_port > rule_port),
+        "lt" => Ok(packet_port < rule_port),
+        other => Err(format!("Unsupported port operator: {}", other)),
+    }
+}
+
+/// Whether `address` satisfies an entry's source/destination keyword:
+/// `special-use` matches any IANA special-use scope (see [`classify`])
+/// rather than a literal address/wildcard pair.
+fn keyword_matches(keyword: &str, address: u32) -> Option<bool> {
+    if keyword.eq_ignore_ascii_case("special-use") {
+        Some(classify(Ipv4Addr::from(address)).is_special_use())
+    } else {
+        None
+    }
+}
+
+/// Whether a single ACL entry matches the given packet fields.
+///
+/// Standard entries (`protocol` is `None`) only ever carry a source address
+/// and, overloaded into the `destination` field by the `deny`/`permit`
+/// commands, a source wildcard mask. Extended entries carry a protocol, an
+/// exact destination address (no wildcard is stored for them), and optional
+/// port operators on both sides. Either address field may instead hold the
+/// `special-use` keyword, matched via [`classify`] rather than a wildcard.
+fn entry_matches(
+    entry: &AclEntry,
+    src_addr: u32,
+    dst_addr: Option<u32>,
+    protocol: Option<&str>,
+    sport: Option<u16>,
+    dport: Option<u16>,
+) -> Result<bool, String> {
+    if entry.protocol.is_none() {
+        if let Some(special_use_match) = keyword_matches(&entry.source, src_addr) {
+            return Ok(special_use_match);
+        }
+        let rule_addr = parse_addr(&entry.source)?;
+        let wildcard = parse_addr(&entry.destination).unwrap_or(0);
+        return Ok(wildcard_match(src_addr, rule_addr, wildcard));
+    }
+
+    if let Some(rule_protocol) = &entry.protocol {
+        if let Some(protocol) = protocol {
+            if !rule_protocol.eq_ignore_ascii_case(protocol) {
+                return Ok(false);
+            }
+        }
+    }
+
+    match keyword_matches(&entry.source, src_addr) {
+        Some(false) => return Ok(false),
+        Some(true) => {}
+        None => {
+            let rule_src_addr = parse_addr(&entry.source)?;
+            if !wildcard_match(src_addr, rule_src_addr, 0) {
+                return Ok(false);
+            }
+        }
+    }
+
+    if let Some(dst_addr) = dst_addr {
+        match keyword_matches(&entry.destination, dst_addr) {
+            Some(false) => return Ok(false),
+            Some(true) => {}
+            None => {
+                let rule_dst_addr = parse_addr(&entry.destination)?;
+                if !wildcard_match(dst_addr, rule_dst_addr, 0) {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    if !port_matches(
+        entry.source_operator.as_deref(),
+        entry.source_port.as_deref(),
+        sport,
+    )? {
+        return Ok(false);
+    }
+
+    if !port_matches(
+        entry.destination_operator.as_deref(),
+        entry.destination_port.as_deref(),
+        dport,
+    )? {
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Evaluates `acl` top-to-bottom against a packet described by
+/// `src`/`dst`/`protocol`/`dport`, returning the action of the first
+/// matching entry or an implicit deny if none match.
+pub fn evaluate_acl(
+    acl: &AccessControlList,
+    src: &str,
+    dst: Option<&str>,
+    protocol: Option<&str>,
+    dport: Option<&str>,
+) -> Result<AclTestResult, String> {
+    let src_addr = parse_addr(src)?;
+    let dst_addr = dst.map(parse_addr).transpose()?;
+    let dport = dport
+        .map(|port| {
+            port.parse::<u16>()
+                .map_err(|_| format!("Invalid destination port: {}", port))
+        })
+        .transpose()?;
+
+    for (index, entry) in acl.entries.iter().enumerate() {
+        if entry_matches(entry, src_addr, dst_addr, protocol, None, dport)? {
+            return Ok(AclTestResult {
+                permit: entry.action.eq_ignore_ascii_case("permit"),
+                matched_rule: Some(index + 1),
+            });
+        }
+    }
+
+    Ok(AclTestResult { permit: false, matched_rule: None })
+}
+
+impl AccessControlList {
+    /// Evaluates this ACL top-down against a packet's source/destination
+    /// addresses, protocol, and ports, in the style of a real packet
+    /// filter's match engine rather than the string-based [`evaluate_acl`]
+    /// the `test access-list` command uses. IPv6 packets always fall
+    /// through to the implicit deny: entries in this simulator only ever
+    /// carry IPv4 addresses/wildcards, so there's nothing for one to match
+    /// against.
+    pub fn matches(
+        &self,
+        src: IpAddr,
+        dst: IpAddr,
+        protocol: Option<&str>,
+        sport: Option<u16>,
+        dport: Option<u16>,
+    ) -> Action {
+        let (IpAddr::V4(src), IpAddr::V4(dst)) = (src, dst) else {
+            return Action::Deny;
+        };
+        let src_addr = u32::from(src);
+        let dst_addr = u32::from(dst);
+
+        for entry in &self.entries {
+            match entry_matches(entry, src_addr, Some(dst_addr), protocol, sport, dport) {
+                Ok(true) => {
+                    return if entry.action.eq_ignore_ascii_case("permit") {
+                        Action::Permit
+                    } else {
+                        Action::Deny
+                    };
+                }
+                Ok(false) | Err(_) => continue,
+            }
+        }
+        Action::Deny
+    }
+}
@@ -0,0 +1,337 @@
+//! A minimal RFC 8555 (ACME) client backing `crypto certificate acme enroll
+//! <fqdn> email <addr>`: walks the account -> order -> HTTP-01 authorization
+//! -> finalize -> download flow against a real ACME server, serving the
+//! challenge response itself from a short-lived embedded listener instead of
+//! requiring the operator to place the file by hand. Modeled on
+//! `cryptocommands.rs`'s direct use of `rsa`/`sha2` for everything else
+//! certificate-related; the one new dependency this pulls in is `ureq`
+//! (with its TLS feature) for the HTTPS calls to the CA, since nothing else
+//! in this crate talks to an outside HTTPS endpoint.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding};
+use rsa::traits::PublicKeyParts;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::cryptocommands::generate_certificate_request;
+use crate::cliconfig::CliConfig;
+
+/// The ACME server's directory URL. Let's Encrypt's production endpoint;
+/// operators who only want to exercise the flow against a local test CA
+/// (e.g. Pebble) can point this at their own server by building with a
+/// different constant.
+const ACME_DIRECTORY_URL: &str = "https://acme-v02.api.letsencrypt.org/directory";
+
+/// The port the embedded HTTP-01 challenge listener binds on. Port 80 is
+/// what a real ACME validator connects to; this assumes the device running
+/// the wizard owns that port (or has it forwarded) for the few seconds the
+/// challenge takes to validate.
+const CHALLENGE_PORT: u16 = 80;
+
+/// How long the embedded challenge listener waits for the CA's validation
+/// request before giving up.
+const CHALLENGE_SERVER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to poll an authorization/order before giving up on it reaching
+/// a terminal state.
+const POLL_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn b64url(data: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(data)
+}
+
+/// Builds the RFC 7638 JWK thumbprint input for `key`'s public half: the
+/// canonical (lexicographically key-ordered, no whitespace) JSON object ACME
+/// signs over to prove the account key controls a given authorization.
+fn jwk(key: &RsaPrivateKey) -> Value {
+    let public_key = key.to_public_key();
+    json!({
+        "e": b64url(&public_key.e().to_bytes_be()),
+        "kty": "RSA",
+        "n": b64url(&public_key.n().to_bytes_be()),
+    })
+}
+
+/// SHA-256 digest of the canonical JWK, base64url-encoded -- the "JWK
+/// thumbprint" both `newAccount` implicitly relies on and the HTTP-01 key
+/// authorization is built from directly.
+fn jwk_thumbprint(key: &RsaPrivateKey) -> String {
+    // `jwk` holds exactly the required RSA members, and (e, kty, n) is
+    // already their lexicographic order, which is also the order
+    // serde_json's map serializes keys in, so serializing it straight back
+    // out is already the RFC 7638 canonical form.
+    let canonical = serde_json::to_string(&jwk(key)).expect("JWK always serializes");
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.as_bytes());
+    b64url(&hasher.finalize())
+}
+
+fn sign_rs256(key: &RsaPrivateKey, signing_input: &str) -> Result<Vec<u8>, String> {
+    let mut hasher = Sha256::new();
+    hasher.update(signing_input.as_bytes());
+    let digest = hasher.finalize();
+    key.sign(Pkcs1v15Sign::new::<Sha256>(), &digest)
+        .map_err(|err| format!("Failed to sign ACME request: {}", err))
+}
+
+/// Identifies the account a JWS is signed on behalf of: by its full public
+/// key (`jwk`, only valid before the account exists) or by the `kid` URL the
+/// CA hands back from `newAccount`.
+enum Signer<'a> {
+    Jwk(&'a RsaPrivateKey),
+    Kid(&'a str),
+}
+
+/// Wraps `payload` in a JWS the way every ACME POST requires: a protected
+/// header naming the algorithm, replay-protection nonce, and target URL,
+/// plus either the account's JWK or its `kid`.
+fn jws_body(key: &RsaPrivateKey, url: &str, nonce: &str, signer: Signer, payload: &Value) -> Result<Value, String> {
+    let mut protected = json!({
+        "alg": "RS256",
+        "nonce": nonce,
+        "url": url,
+    });
+    match signer {
+        Signer::Jwk(key) => {
+            protected["jwk"] = jwk(key);
+        }
+        Signer::Kid(kid) => {
+            protected["kid"] = json!(kid);
+        }
+    }
+    let protected_b64 = b64url(serde_json::to_string(&protected).unwrap().as_bytes());
+    // `payload` is `Value::Null` for the POST-as-GET form ACME uses to
+    // fetch/poll a resource, sent as an empty payload segment; everything
+    // else is a JSON object.
+    let payload_b64 = if payload.is_null() {
+        String::new()
+    } else {
+        b64url(serde_json::to_string(payload).unwrap().as_bytes())
+    };
+    let signing_input = format!("{}.{}", protected_b64, payload_b64);
+    let signature = sign_rs256(key, &signing_input)?;
+
+    Ok(json!({
+        "protected": protected_b64,
+        "payload": payload_b64,
+        "signature": b64url(&signature),
+    }))
+}
+
+/// Reads the `Replay-Nonce` response header ACME requires every response
+/// (success or error) to carry, so the next request can reuse it.
+fn take_nonce(response: &ureq::Response) -> Result<String, String> {
+    response
+        .header("Replay-Nonce")
+        .map(str::to_string)
+        .ok_or_else(|| "ACME server response was missing Replay-Nonce".to_string())
+}
+
+/// Serves `key_authorization` at `/.well-known/acme-challenge/<token>` for
+/// up to [`CHALLENGE_SERVER_TIMEOUT`], in a background thread, so the
+/// caller can tell the CA the challenge is ready and poll the authorization
+/// without blocking the HTTP response itself.
+fn serve_http01_challenge(token: String, key_authorization: String) -> Result<thread::JoinHandle<()>, String> {
+    let listener = TcpListener::bind(("0.0.0.0", CHALLENGE_PORT))
+        .map_err(|err| format!("Failed to bind :{} for the HTTP-01 challenge: {}", CHALLENGE_PORT, err))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|err| format!("Failed to configure the HTTP-01 challenge listener: {}", err))?;
+
+    let expected_path = format!("GET /.well-known/acme-challenge/{} ", token);
+    Ok(thread::spawn(move || {
+        let deadline = Instant::now() + CHALLENGE_SERVER_TIMEOUT;
+        while Instant::now() < deadline {
+            match listener.accept() {
+                Ok((mut stream, _)) => {
+                    let mut buffer = [0u8; 1024];
+                    let read = stream.read(&mut buffer).unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buffer[..read]);
+                    let body = if request.starts_with(&expected_path) {
+                        key_authorization.as_str()
+                    } else {
+                        "not found"
+                    };
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nContent-Type: text/plain\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes());
+                }
+                Err(_) => thread::sleep(Duration::from_millis(100)),
+            }
+        }
+    }))
+}
+
+/// Runs the full ACME HTTP-01 enrollment flow for `fqdn`/`email` and returns
+/// the issued certificate chain as PEM, ready to store in `context.cert_store`
+/// alongside every other certificate this crate produces.
+pub fn acme_enroll(fqdn: &str, email: &str, config: &CliConfig) -> Result<String, String> {
+    let account_key = RsaPrivateKey::new(&mut rand::rngs::OsRng, 2048)
+        .map_err(|err| format!("Failed to generate ACME account key: {}", err))?;
+
+    let directory: Value = ureq::get(ACME_DIRECTORY_URL)
+        .call()
+        .map_err(|err| format!("Failed to fetch ACME directory: {}", err))?
+        .into_json()
+        .map_err(|err| format!("ACME directory response was not JSON: {}", err))?;
+    let new_nonce_url = directory["newNonce"].as_str().ok_or("ACME directory missing newNonce")?;
+    let new_account_url = directory["newAccount"].as_str().ok_or("ACME directory missing newAccount")?;
+    let new_order_url = directory["newOrder"].as_str().ok_or("ACME directory missing newOrder")?;
+
+    let nonce_response = ureq::head(new_nonce_url)
+        .call()
+        .map_err(|err| format!("Failed to fetch an ACME nonce: {}", err))?;
+    let mut nonce = take_nonce(&nonce_response)?;
+
+    // newAccount: registers (or looks up) the account and gives back a
+    // `kid` URL every later request signs with instead of the raw JWK.
+    let account_payload = json!({
+        "termsOfServiceAgreed": true,
+        "contact": [format!("mailto:{}", email)],
+    });
+    let account_body = jws_body(&account_key, new_account_url, &nonce, Signer::Jwk(&account_key), &account_payload)?;
+    let account_response = ureq::post(new_account_url)
+        .set("Content-Type", "application/jose+json")
+        .send_string(&account_body.to_string())
+        .map_err(|err| format!("ACME newAccount failed: {}", err))?;
+    nonce = take_nonce(&account_response)?;
+    let kid = account_response
+        .header("Location")
+        .ok_or("ACME newAccount response was missing its Location/kid header")?
+        .to_string();
+
+    // newOrder: declares the identifier we want a certificate for.
+    let order_payload = json!({ "identifiers": [{ "type": "dns", "value": fqdn }] });
+    let order_body = jws_body(&account_key, new_order_url, &nonce, Signer::Kid(&kid), &order_payload)?;
+    let order_response = ureq::post(new_order_url)
+        .set("Content-Type", "application/jose+json")
+        .send_string(&order_body.to_string())
+        .map_err(|err| format!("ACME newOrder failed: {}", err))?;
+    nonce = take_nonce(&order_response)?;
+    let order_url = order_response
+        .header("Location")
+        .ok_or("ACME newOrder response was missing its Location header")?
+        .to_string();
+    let order: Value = order_response
+        .into_json()
+        .map_err(|err| format!("ACME newOrder response was not JSON: {}", err))?;
+    let authorization_url = order["authorizations"]
+        .get(0)
+        .and_then(Value::as_str)
+        .ok_or("ACME order had no authorizations")?
+        .to_string();
+
+    // Fetch the authorization (a plain GET is allowed here) to find the
+    // HTTP-01 challenge and its token.
+    let authorization: Value = ureq::get(&authorization_url)
+        .call()
+        .map_err(|err| format!("Failed to fetch ACME authorization: {}", err))?
+        .into_json()
+        .map_err(|err| format!("ACME authorization response was not JSON: {}", err))?;
+    let challenge = authorization["challenges"]
+        .as_array()
+        .and_then(|challenges| challenges.iter().find(|challenge| challenge["type"] == "http-01"))
+        .ok_or("ACME authorization had no http-01 challenge")?;
+    let token = challenge["token"].as_str().ok_or("ACME challenge had no token")?.to_string();
+    let challenge_url = challenge["url"].as_str().ok_or("ACME challenge had no url")?.to_string();
+
+    let key_authorization = format!("{}.{}", token, jwk_thumbprint(&account_key));
+    let challenge_thread = serve_http01_challenge(token, key_authorization)?;
+
+    // Tell the CA the challenge is ready to be fetched.
+    let ready_body = jws_body(&account_key, &challenge_url, &nonce, Signer::Kid(&kid), &json!({}))?;
+    let ready_response = ureq::post(&challenge_url)
+        .set("Content-Type", "application/jose+json")
+        .send_string(&ready_body.to_string())
+        .map_err(|err| format!("Failed to notify the ACME server the challenge is ready: {}", err))?;
+    nonce = take_nonce(&ready_response)?;
+
+    // Poll the authorization until the CA reports it valid (or gives up).
+    let deadline = Instant::now() + POLL_TIMEOUT;
+    loop {
+        if Instant::now() > deadline {
+            return Err("Timed out waiting for the ACME authorization to become valid".into());
+        }
+        let poll_body = jws_body(&account_key, &authorization_url, &nonce, Signer::Kid(&kid), &Value::Null)?;
+        let poll_response = ureq::post(&authorization_url)
+            .set("Content-Type", "application/jose+json")
+            .send_string(&poll_body.to_string())
+            .map_err(|err| format!("Failed to poll the ACME authorization: {}", err))?;
+        nonce = take_nonce(&poll_response)?;
+        let authorization: Value = poll_response
+            .into_json()
+            .map_err(|err| format!("ACME authorization poll response was not JSON: {}", err))?;
+        match authorization["status"].as_str() {
+            Some("valid") => break,
+            Some("invalid") => return Err("The ACME server rejected the HTTP-01 challenge".into()),
+            _ => thread::sleep(Duration::from_secs(2)),
+        }
+    }
+    let _ = challenge_thread.join();
+
+    // Finalize: submit a CSR, built from the device's own key, for `fqdn`.
+    let csr_pem = generate_certificate_request(fqdn, config)?;
+    let csr_der = pem_body_to_der(&csr_pem)?;
+    let finalize_url = order["finalize"].as_str().ok_or("ACME order had no finalize url")?;
+    let finalize_payload = json!({ "csr": b64url(&csr_der) });
+    let finalize_body = jws_body(&account_key, finalize_url, &nonce, Signer::Kid(&kid), &finalize_payload)?;
+    let finalize_response = ureq::post(finalize_url)
+        .set("Content-Type", "application/jose+json")
+        .send_string(&finalize_body.to_string())
+        .map_err(|err| format!("ACME finalize failed: {}", err))?;
+    nonce = take_nonce(&finalize_response)?;
+
+    // Poll the order until it's valid and a certificate URL is available.
+    let deadline = Instant::now() + POLL_TIMEOUT;
+    let certificate_url = loop {
+        if Instant::now() > deadline {
+            return Err("Timed out waiting for the ACME order to be issued".into());
+        }
+        let poll_body = jws_body(&account_key, &order_url, &nonce, Signer::Kid(&kid), &Value::Null)?;
+        let poll_response = ureq::post(&order_url)
+            .set("Content-Type", "application/jose+json")
+            .send_string(&poll_body.to_string())
+            .map_err(|err| format!("Failed to poll the ACME order: {}", err))?;
+        nonce = take_nonce(&poll_response)?;
+        let order: Value = poll_response
+            .into_json()
+            .map_err(|err| format!("ACME order poll response was not JSON: {}", err))?;
+        match order["status"].as_str() {
+            Some("valid") => {
+                break order["certificate"].as_str().ok_or("ACME order was valid but had no certificate url")?.to_string();
+            }
+            Some("invalid") => return Err("The ACME server failed to issue the certificate".into()),
+            _ => thread::sleep(Duration::from_secs(2)),
+        }
+    };
+
+    ureq::get(&certificate_url)
+        .call()
+        .map_err(|err| format!("Failed to download the issued certificate chain: {}", err))?
+        .into_string()
+        .map_err(|err| format!("Issued certificate chain was not valid text: {}", err))
+}
+
+/// Strips a PEM's `-----BEGIN ...-----`/`-----END ...-----` banners and
+/// base64-decodes the body, the DER form ACME's `finalize` endpoint expects
+/// for the CSR.
+fn pem_body_to_der(pem: &str) -> Result<Vec<u8>, String> {
+    let base64_body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(base64_body)
+        .map_err(|err| format!("Failed to decode CSR PEM: {}", err))
+}
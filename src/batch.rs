@@ -0,0 +1,237 @@
+//! Non-interactive replay of a configuration file, line by line.
+//!
+//! This module lets a whole config file be fed through [`execute_command`]
+//! without a human typing it in -- mode changes made by one line (e.g.
+//! `configure terminal`) carry over to the next, exactly as in an
+//! interactive session. It backs the `load`/`source` CLI command and is also
+//! usable as a library entry point for provisioning scripts and tests.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+
+use crate::cliconfig::CliContext;
+use crate::clock_settings::Clock;
+use crate::commandcompleter::CommandCompleter;
+use crate::execute::{execute_command, is_resolution_error, Command};
+use crate::network_config::{
+    AccessControlList, AdminState, IfconfigEntry, OSPFConfig, OperState, PasswordStore,
+    RoutingTable, ACL_STORE, IFCONFIG_STATE, IP_ADDRESS_STATE, OPER_STATE_MAP, OSPF_CONFIG,
+    PASSWORD_STORAGE, ROUTING_TABLE, STATUS_MAP,
+};
+
+/// The outcome of replaying a single line from a batch.
+pub struct BatchLineResult {
+    /// The 1-based line number within the input, for error reporting.
+    pub line_number: usize,
+    /// The line as it was fed to `execute_command`.
+    pub command: String,
+    /// `Ok(())` if the line ran successfully, `Err(message)` otherwise.
+    pub result: Result<(), String>,
+}
+
+/// The full outcome of a [`run_batch`] call.
+pub struct BatchReport {
+    pub results: Vec<BatchLineResult>,
+    /// Set when `BatchMode::Atomic` rolled back state because a line failed.
+    pub rolled_back: bool,
+}
+
+impl BatchReport {
+    /// Whether any line in the batch failed.
+    pub fn has_errors(&self) -> bool {
+        self.results.iter().any(|line| line.result.is_err())
+    }
+
+    /// A process exit code distinguishing "every line resolved and ran" (0)
+    /// from "a line's command couldn't even be resolved" (2) from "every
+    /// line resolved, but at least one execute returned an error" (1) --
+    /// lets a calling harness tell "could not resolve command" apart from
+    /// "ran but failed" instead of collapsing both into one failure.
+    pub fn exit_code(&self) -> i32 {
+        if self
+            .results
+            .iter()
+            .any(|line| matches!(&line.result, Err(err) if is_resolution_error(err)))
+        {
+            2
+        } else if self.has_errors() {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// How a batch run should react to a failing line.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Run every line regardless of earlier failures and report them all.
+    Continue,
+    /// Snapshot all mutable state before the run and restore it if any line
+    /// fails, so the batch either fully applies or has no visible effect.
+    Atomic,
+}
+
+/// A point-in-time copy of every piece of state a config line can mutate,
+/// used to roll back an `Atomic` batch run.
+struct StateSnapshot {
+    context: CliContext,
+    ifconfig_state: HashMap<String, IfconfigEntry>,
+    status_map: HashMap<String, AdminState>,
+    oper_state_map: HashMap<String, OperState>,
+    ip_address_state: HashMap<String, (Ipv4Addr, Ipv4Addr)>,
+    routing_table: RoutingTable,
+    ospf_config: OSPFConfig,
+    acl_store: HashMap<String, AccessControlList>,
+    password_storage: PasswordStore,
+}
+
+impl StateSnapshot {
+    fn capture(context: &CliContext) -> Self {
+        StateSnapshot {
+            context: context.clone(),
+            ifconfig_state: IFCONFIG_STATE.lock().unwrap().clone(),
+            status_map: STATUS_MAP.lock().unwrap().clone(),
+            oper_state_map: OPER_STATE_MAP.lock().unwrap().clone(),
+            ip_address_state: IP_ADDRESS_STATE.lock().unwrap().clone(),
+            routing_table: ROUTING_TABLE.lock().unwrap().clone(),
+            ospf_config: OSPF_CONFIG.lock().unwrap().clone(),
+            acl_store: ACL_STORE.lock().unwrap().clone(),
+            password_storage: PASSWORD_STORAGE.lock().unwrap().clone(),
+        }
+    }
+
+    fn restore(self, context: &mut CliContext) {
+        *context = self.context;
+        *IFCONFIG_STATE.lock().unwrap() = self.ifconfig_state;
+        *STATUS_MAP.lock().unwrap() = self.status_map;
+        *OPER_STATE_MAP.lock().unwrap() = self.oper_state_map;
+        *IP_ADDRESS_STATE.lock().unwrap() = self.ip_address_state;
+        *ROUTING_TABLE.lock().unwrap() = self.routing_table;
+        *OSPF_CONFIG.lock().unwrap() = self.ospf_config;
+        *ACL_STORE.lock().unwrap() = self.acl_store;
+        *PASSWORD_STORAGE.lock().unwrap() = self.password_storage;
+    }
+}
+
+/// Handles the `load`/`source <file> [--continue|--atomic]` CLI command:
+/// reads `file`, replays it with [`run_batch`], and prints a report of
+/// which lines succeeded or failed (and whether an atomic run rolled back).
+///
+/// Defaults to [`BatchMode::Continue`] when neither flag is given.
+pub fn handle_load_command(
+    args: &[&str],
+    commands: &HashMap<&str, Command>,
+    context: &mut CliContext,
+    clock: &mut Option<Clock>,
+    completer: &mut CommandCompleter,
+) {
+    let mut path = None;
+    let mut mode = BatchMode::Continue;
+    for arg in args {
+        match *arg {
+            "--continue" => mode = BatchMode::Continue,
+            "--atomic" => mode = BatchMode::Atomic,
+            other => path = Some(other),
+        }
+    }
+
+    let path = match path {
+        Some(path) => path,
+        None => {
+            println!("Usage: load <file> [--continue|--atomic]");
+            return;
+        }
+    };
+
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            println!("Error: could not read '{}': {}", path, err);
+            return;
+        }
+    };
+    let lines: Vec<&str> = contents.lines().collect();
+
+    let report = run_batch(&lines, commands, context, clock, completer, mode);
+    for line in &report.results {
+        match &line.result {
+            Ok(()) => println!("{:>4}: {}", line.line_number, line.command),
+            Err(err) => println!("{:>4}: {}  -- Error: {}", line.line_number, line.command, err),
+        }
+    }
+
+    if report.rolled_back {
+        println!("\nBatch failed; running configuration rolled back ({} line(s) processed).", report.results.len());
+    } else if report.has_errors() {
+        let unresolved = report
+            .results
+            .iter()
+            .filter(|line| matches!(&line.result, Err(err) if is_resolution_error(err)))
+            .count();
+        if unresolved > 0 {
+            println!(
+                "\nBatch completed with errors ({} line(s) processed, {} could not be resolved to a command).",
+                report.results.len(),
+                unresolved
+            );
+        } else {
+            println!("\nBatch completed with errors ({} line(s) processed).", report.results.len());
+        }
+    } else {
+        println!("\nBatch completed successfully ({} line(s) processed).", report.results.len());
+    }
+}
+
+/// Feeds `lines` through [`execute_command`] one at a time, as if each had
+/// been typed interactively. Blank lines and lines starting with `!` (the
+/// IOS comment marker) are skipped.
+///
+/// In [`BatchMode::Continue`], every line runs regardless of earlier
+/// failures and every result is collected into the report. In
+/// [`BatchMode::Atomic`], replay stops at the first failing line and, since
+/// the batch did not fully apply, all state captured before the run is
+/// restored.
+pub fn run_batch(
+    lines: &[&str],
+    commands: &HashMap<&str, Command>,
+    context: &mut CliContext,
+    clock: &mut Option<Clock>,
+    completer: &mut CommandCompleter,
+    mode: BatchMode,
+) -> BatchReport {
+    let snapshot = match mode {
+        BatchMode::Atomic => Some(StateSnapshot::capture(context)),
+        BatchMode::Continue => None,
+    };
+
+    let mut results = Vec::new();
+    for (index, raw_line) in lines.iter().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('!') {
+            continue;
+        }
+
+        let result = execute_command(line, commands, context, clock, completer);
+        let failed = result.is_err();
+        results.push(BatchLineResult {
+            line_number: index + 1,
+            command: line.to_string(),
+            result,
+        });
+
+        if failed && mode == BatchMode::Atomic {
+            break;
+        }
+    }
+
+    let rolled_back = match snapshot {
+        Some(snapshot) if results.iter().any(|line| line.result.is_err()) => {
+            snapshot.restore(context);
+            true
+        }
+        _ => false,
+    };
+
+    BatchReport { results, rolled_back }
+}
@@ -1,7 +1,7 @@
 /// External crates for the CLI application
 use std::collections::HashMap;
-use std::net::Ipv4Addr;
-use std::path::Path;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
 use std::fs::{File};
 use std::io::Write;
 use std::io;
@@ -9,15 +9,171 @@ use std::str::FromStr;
 use rpassword::read_password;
 use std::process::Command as ProcessCommand;
 
-use crate::run_config::{get_running_config, default_startup_config};
+use crate::run_config::{get_running_config, default_startup_config, reload_crypto_and_tunnel_config, save_config, DEFAULT_STARTUP_CONFIG_PATH};
+use crate::config_archive::{snapshot_running_config, list_archive, diff_revisions, diff_running_vs_startup};
+use crate::acl_eval::evaluate_acl;
+use crate::commandcompleter::{generate_bash_completion, generate_fish_completion, generate_json_completion, generate_zsh_completion};
 //use crate::run_config::load_config;
 use crate::execute::Command;
 use crate::execute::Mode;
-use crate::clock_settings::{handle_clock_set, parse_clock_set_input, handle_show_clock, handle_show_uptime};
-use crate::network_config::{calculate_broadcast, STATUS_MAP, IFCONFIG_STATE, IP_ADDRESS_STATE, ROUTE_TABLE, OSPF_CONFIG, ACL_STORE, encrypt_password, PASSWORD_STORAGE, set_enable_password, set_enable_secret};
-use crate::network_config::{InterfaceConfig, OSPFConfig, AclEntry, AccessControlList, NtpAssociation};
-use crate::cryptocommands::{generate_crypto_key, delete_crypto_key, import_crypto_key, generate_self_signed_certificate, generate_certificate_request, import_certificate, extract_subject_from_cert, extract_issuer_from_cert};
-use crate::cryptocommands::{DynamicMapEntry, CryptoMapEntry};
+use crate::execute::ArgSpec;
+use crate::execute::ArgKind;
+use crate::execute::{command_allowed_in_mode, command_usage};
+use crate::clock_settings::{handle_clock_set, parse_clock_set_input, handle_show_clock, handle_show_clock_relative, handle_show_uptime, handle_show_uptime_detail};
+use crate::network_config::{netmask_to_prefix, is_contiguous_netmask, prefix_to_netmask, STATUS_MAP, IFCONFIG_STATE, IfconfigEntry, IP_ADDRESS_STATE, ROUTING_TABLE, ROUTE_TABLE_V6, Route, RouteSource, effective_routing_table, install_ospf_routes, OSPF_CONFIG, ACL_STORE, ROUTE_FILTERS, encrypt_password, verify_secret, PASSWORD_STORAGE, set_enable_password, set_enable_secret, AdminState, OperState, InterfaceType, OPER_STATE_MAP, INTERFACE_COUNTERS, InterfaceCounters, advance_interface_counters, interface_status_line, NAT_INTERFACE_ROLE, NAT_STATIC_MAPPINGS, NAT_OVERLOAD_RULES, NAT_TRANSLATIONS, rebuild_nat_translations};
+use crate::route_filter::{RouteFilter, FilterClause, ClauseAction, MatchCondition, SetActions};
+use crate::natcommands::{NatSide, NatStaticMapping, NatOverloadRule};
+use crate::network_config::{InterfaceConfig, OSPFConfig, AclEntry, AccessControlList, NtpAssociation, advance_ntp_poll, verify_ntp_association, TunnelInterface, TUNNEL_CONFIG, advance_snmp_stats, SNMP_STATS};
+use crate::ntp_auth::{NtpAuthAlgorithm, NtpAuthKey};
+use crate::icmp_ping::{run_icmp_ping, PingOptions, PingSummary, ProbeResult};
+use crate::config_io::{build_config_document, apply_config_document, validate_config_document, ConfigDocumentFormat};
+use crate::network_config::{Encapsulation, PppAuthentication, CompressionAlgorithm, LINK_CONFIG_STATE, set_user_password, VTY_CONFIG, TransportInput, BGP_CONFIG, RIP_CONFIG, ISIS_CONFIG, IsisLevel, OSPFV3_CONFIG};
+use crate::network_config::{SwitchportConfig, SwitchportMode, SWITCHPORT_STATE, vlan_members, MIN_VLAN_ID, MAX_VLAN_ID};
+use crate::cryptocommands::{generate_crypto_key, delete_crypto_key, import_crypto_key, generate_self_signed_certificate, generate_certificate_request, import_certificate, build_crypto_key, inspect_certificate, export_public_key};
+use crate::cryptocommands::{DynamicMapEntry, CryptoMapEntry, IsakmpPolicy, IsakmpClientGroup, AddressPool};
+use crate::keystore::{KeyStore, CertStore};
+use crate::dhcpcommands::{DhcpPool, DhcpBinding, format_lease_expiry, pseudo_mac_for, next_free_address};
+use crate::config_wizard::{run_config_wizard, run_crypto_wizard, run_ospf_acl_wizard};
+use crate::terminal_settings::{ColorMode, CompletionStyle, EditMode};
+use crate::dynamic_registry::{get_commands_for_mode, DYNAMIC_COMMANDS};
+use crate::walkup::ModeHierarchy;
+use crate::cliconfig::{CliContext, SnmpHost};
+
+/// Prints the mode-aware command hierarchy along the path from `UserMode`
+/// to `InterfaceMode` (`UserMode` -> `PrivilegedMode` -> `ConfigMode` ->
+/// `InterfaceMode`, the reverse of the order `ModeHierarchy` walks from
+/// `InterfaceMode` up to its root): under each of those modes, every static
+/// command declared for it, followed by the dynamic commands
+/// `get_commands_for_mode` reports for that mode. Modes off that path
+/// (VLAN, router, ACL and other sub-modes) are not listed. Backs both
+/// `show commands` and `help tree`.
+fn print_command_tree(context: &CliContext) {
+    let hierarchy = ModeHierarchy::new();
+    let mut modes = vec![Mode::InterfaceMode];
+    let mut current = Mode::InterfaceMode;
+    while let Some(Some(parent)) = hierarchy.parent_map.get(&current) {
+        modes.push(parent.clone());
+        current = parent.clone();
+    }
+    modes.reverse();
+
+    let registry = build_command_registry();
+
+    for mode in &modes {
+        println!("{}", mode);
+
+        let mut static_names: Vec<&str> = registry
+            .iter()
+            .filter(|(_, cmd)| cmd.modes.contains(mode))
+            .map(|(&name, _)| name)
+            .collect();
+        static_names.sort();
+
+        for name in &static_names {
+            if let Some(cmd) = registry.get(name) {
+                println!("  {:<20}- {}", cmd.name, cmd.description);
+                if let Some(suggestions) = &cmd.suggestions {
+                    println!("      suggestions: {}", suggestions.join(", "));
+                }
+                if let Some(options) = &cmd.options {
+                    for option in options {
+                        println!("      option: {}", option);
+                    }
+                }
+            }
+        }
+
+        let mut dynamic_names = get_commands_for_mode(mode);
+        dynamic_names.sort();
+        if let Ok(dynamic_commands) = DYNAMIC_COMMANDS.read() {
+            for name in &dynamic_names {
+                if let Some(cmd) = dynamic_commands.get(name) {
+                    println!("  {:<20}- {}", cmd.name, cmd.description);
+                    if let Some(suggestions) = &cmd.suggestions {
+                        println!("      suggestions: {}", suggestions.join(", "));
+                    }
+                }
+            }
+        }
+    }
+
+    println!(
+        "\nCommands available right now ({}): {}",
+        context.current_mode,
+        get_commands_for_mode(&context.current_mode).join(", ")
+    );
+}
+
+/// Renders a single `ifconfig` interface record in net-tools style, e.g.:
+/// ```text
+/// ens33: flags=4163<UP,BROADCAST,RUNNING,MULTICAST>  mtu 1500
+///     inet 10.0.0.1  netmask 255.255.255.0  broadcast 10.0.0.255
+///     inet6 2001:db8::1  prefixlen 64  scopeid 0x0<global>
+///     ether 00:0c:29:16:30:92  txqueuelen 1000  (Ethernet)
+/// ```
+fn print_ifconfig_entry(interface_name: &str, entry: &IfconfigEntry) {
+    println!("{}: flags={}  mtu {}", interface_name, entry.flags(), entry.mtu);
+    println!("    inet {}  netmask {}  broadcast {}", entry.ip_address, entry.netmask, entry.broadcast);
+    for (address, prefix_length) in &entry.ipv6_addresses {
+        println!("    inet6 {}  prefixlen {}  scopeid 0x0<global>", address, prefix_length);
+    }
+    println!("    ether {}  txqueuelen 1000  (Ethernet)", entry.hw_address);
+}
+
+/// Prints `show config sources`'s table: for each key
+/// [`crate::config_resolve::OVERRIDABLE_KEYS`] tracks, the effective value
+/// and which layer -- command, environment variable, override file, or
+/// default -- produced it.
+fn print_config_sources(context: &CliContext) {
+    let file = crate::config_resolve::load_overrides_file(Path::new(crate::config_resolve::DEFAULT_OVERRIDES_PATH));
+
+    println!("Configuration value sources (command > environment variable > override file > default):");
+    println!("{:<28}{:<24}{}", "Key", "Source", "Value");
+
+    let accelerator = context.config.crypto_engine_accelerator.map(|slot| slot.to_string());
+    let transform_set = context.config.crypto_transform_sets.keys().next().cloned();
+    let rows: [(&str, Option<&str>); 4] = [
+        ("tunnel_source", context.config.tunnel_source.as_deref()),
+        ("tunnel_destination", context.config.tunnel_destination.as_deref()),
+        ("crypto_engine_accelerator", accelerator.as_deref()),
+        ("transform_set", transform_set.as_deref()),
+    ];
+
+    for (key, command_value) in rows {
+        let (value, source) = crate::config_resolve::resolve(key, command_value, &file, None);
+        println!("{:<28}{:<24}{}", key, source.label(), value.as_deref().unwrap_or("-"));
+    }
+}
+
+/// Parses the optional `cn <common-name>`, `o <organization>`, and `days
+/// <validity-days>` keyword pairs that follow the `key <key-name>` argument
+/// of `crypto certificate generate`/`crypto certificate request`, in any
+/// order. Defaults `validity_days` to 365 when `days` isn't given.
+fn parse_certificate_subject_args(args: &[&str]) -> Result<(Option<String>, Option<String>, u32), String> {
+    let mut common_name = None;
+    let mut organization = None;
+    let mut validity_days = 365;
+
+    let mut i = 0;
+    while i < args.len() {
+        if i + 1 >= args.len() {
+            return Err(format!("Missing value for '{}'.", args[i]));
+        }
+        match args[i] {
+            "cn" => common_name = Some(args[i + 1].to_string()),
+            "o" => organization = Some(args[i + 1].to_string()),
+            "days" => {
+                validity_days = args[i + 1]
+                    .parse::<u32>()
+                    .map_err(|_| format!("Invalid validity days '{}'.", args[i + 1]))?;
+            }
+            other => return Err(format!("Unknown option '{}'. Expected 'cn', 'o', or 'days'.", other)),
+        }
+        i += 2;
+    }
+
+    Ok((common_name, organization, validity_days))
+}
 
 /// Builds and returns a `HashMap` of available commands, each represented by a `Command` structure.
 /// 
@@ -112,9 +268,15 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
     commands.insert("enable", Command {
         name: "enable",
         description: "Enter privileged EXEC mode",
+        args: vec![],
+        help: "Enter privileged EXEC mode",
+        usage: None,
+        modes: &[Mode::UserMode, Mode::ConfigMode],
         suggestions: Some(vec!["password", "secret"]),
         suggestions1: None,
+        require_subcommand: true,
         options: None,
+        aliases: vec!["en"],
         execute: |args, context, _| {
             if args.is_empty(){
                 if matches!(context.current_mode, Mode::UserMode) {
@@ -153,7 +315,7 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
                         let input_secret= read_password().unwrap_or_else(|_| "".to_string());
             
                         if let Some(ref stored_secret) = stored_secret {
-                            if input_secret == *stored_secret {
+                            if verify_secret(&input_secret, stored_secret) {
                                 // Correct enable password, proceed to privileged mode
                                 context.current_mode = Mode::PrivilegedMode;
                                 context.prompt = format!("{}#", context.config.hostname);
@@ -162,15 +324,15 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
                             }
                         }
                     }
-            
+
                     // If secret is stored, prompt for it if password check fails
                     if let (Some(ref stored_secret), Some(ref stored_password)) = (stored_secret, stored_password) {
                         println!("Enter password:");
                         let input_password = read_password().unwrap_or_else(|_| "".to_string());
                         println!("Enter secret:");
                         let input_secret = read_password().unwrap_or_else(|_| "".to_string());
-        
-                        if input_secret == *stored_secret && input_password == *stored_password {
+
+                        if verify_secret(&input_secret, stored_secret) && input_password == *stored_password {
                             // Correct enable secret, proceed to privileged mode
                             context.current_mode = Mode::PrivilegedMode;
                             context.prompt = format!("{}#", context.config.hostname);
@@ -208,7 +370,6 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
                             } else {
                                 let secret = &args[1];
                                 set_enable_secret(secret);
-                                context.config.enable_secret = Some(secret.to_string());
                                 println!("Enable secret password set.");
                                 Ok(())
                             }
@@ -225,14 +386,21 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
     commands.insert("configure", Command {
         name: "configure terminal",
         description: "Enter global configuration mode",
+        args: vec![],
+        help: "Enter global configuration mode",
+        usage: None,
+        modes: &[Mode::PrivilegedMode],
         suggestions: Some(vec!["terminal", "user"]),
         suggestions1: Some(vec!["terminal", "user"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec!["conf"],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::PrivilegedMode) {
                 if args.len() == 1 && args[0] == "terminal" {
                     context.current_mode = Mode::ConfigMode;
                     context.prompt = format!("{}(config)#", context.config.hostname);
+                    crate::commit_confirm::snapshot_on_enter_config();
                     println!("Enter configuration commands, one per line.  End with CNTL/Z");
                     Ok(())
                 } else if args.len() == 1 && args[0] == "user" {
@@ -253,10 +421,16 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
     commands.insert("interface", Command {
         name: "interface",
         description: "Enter Interface configuration mode or Interface Range configuration mode",
+        args: vec![ArgSpec::required("interface-name")],
+        help: "Enter Interface configuration mode or Interface Range configuration mode",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: Some(vec!["range"]),
         suggestions1: None,
-        options: Some(vec!["range", 
+        require_subcommand: true,
+        options: Some(vec!["range",
             "<interface-name>    - Specify a valid interface name"]),
+        aliases: vec!["int"],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::ConfigMode | Mode::InterfaceMode) {
                 if args.is_empty() {
@@ -307,9 +481,15 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
     commands.insert("exit", Command {
         name: "exit",
         description: "Exit the current mode and return to the previous mode.",
+        args: vec![],
+        help: "Exit the current mode and return to the previous mode.",
+        usage: None,
+        modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if args.is_empty() {
                 match context.current_mode {
@@ -331,6 +511,30 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
                         println!("Exiting Router Configuration Mode...");
                         Ok(())
                     }
+                    Mode::RouterBgpMode => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting Router BGP Configuration Mode...");
+                        Ok(())
+                    }
+                    Mode::RouterRipMode => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting Router RIP Configuration Mode...");
+                        Ok(())
+                    }
+                    Mode::RouterIsisMode => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting Router IS-IS Configuration Mode...");
+                        Ok(())
+                    }
+                    Mode::RouterOspfv3Mode => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting Router OSPFv3 Configuration Mode...");
+                        Ok(())
+                    }
                     Mode::ConfigStdNaclMode(_) => {
                         context.current_mode = Mode::ConfigMode;
                         context.prompt = format!("{}(config)#", context.config.hostname);
@@ -343,6 +547,30 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
                         println!("Exiting Extended ACL Mode...");
                         Ok(())
                     }
+                    Mode::LineVtyMode(_) => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting Line Configuration Mode...");
+                        Ok(())
+                    }
+                    Mode::CryptoIsakmpPolicyMode(_) => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting ISAKMP Policy Configuration Mode...");
+                        Ok(())
+                    }
+                    Mode::CryptoIsakmpGroupMode(_) => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting ISAKMP Client Configuration Group Mode...");
+                        Ok(())
+                    }
+                    Mode::DhcpPoolMode(_) => {
+                        context.current_mode = Mode::ConfigMode;
+                        context.prompt = format!("{}(config)#", context.config.hostname);
+                        println!("Exiting DHCP Pool Configuration Mode...");
+                        Ok(())
+                    }
                     Mode::ConfigMode => {
                         context.current_mode = Mode::PrivilegedMode;
                         context.prompt = format!("{}#", context.config.hostname);
@@ -380,9 +608,15 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
     commands.insert("reload", Command {
         name: "reload",
         description: "Reload the system",
+        args: vec![],
+        help: "Reload the system",
+        usage: None,
+        modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |_, context, _| {
             
             println!("System configuration has been modified. Save? [yes/no]:");
@@ -425,13 +659,273 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
             }
         },
     });
-    
+
+    commands.insert(
+        "commit",
+        Command {
+            name: "commit",
+            description: "Make the running configuration permanent, or schedule an automatic rollback",
+            args: vec![],
+            help: "Make the running configuration permanent, or schedule an automatic rollback",
+            usage: None,
+            modes: &[Mode::ConfigMode],
+            suggestions: Some(vec!["confirmed"]),
+            suggestions1: Some(vec!["confirmed"]),
+            require_subcommand: false,
+            options: Some(vec!["<1-120>       - Minutes before an unconfirmed commit is automatically rolled back"]),
+            aliases: vec![],
+            execute: |args, _, _| match args {
+                [] => {
+                    crate::commit_confirm::commit();
+                    println!("Configuration committed.");
+                    Ok(())
+                }
+                ["confirmed", minutes] => {
+                    let minutes = minutes.parse::<u64>().map_err(|_| "Invalid minutes value. It must be a positive integer.".to_string())?;
+                    if minutes == 0 {
+                        return Err("Invalid minutes value. It must be a positive integer.".into());
+                    }
+                    crate::commit_confirm::commit_confirmed(minutes);
+                    println!(
+                        "This configuration will be automatically rolled back in {} minute(s) unless a 'commit' is issued.",
+                        minutes
+                    );
+                    Ok(())
+                }
+                _ => Err("Usage: commit [confirmed <minutes>]".into()),
+            },
+        },
+    );
+
+    commands.insert(
+        "rollback",
+        Command {
+            name: "rollback",
+            description: "Discard uncommitted changes back to the last snapshot",
+            args: vec![],
+            help: "Discard uncommitted changes back to the last snapshot",
+            usage: None,
+            modes: &[Mode::ConfigMode],
+            suggestions: None,
+            suggestions1: None,
+            require_subcommand: false,
+            options: None,
+            aliases: vec![],
+            execute: |_, _, _| {
+                if crate::commit_confirm::rollback() {
+                    println!("Rolled back to the configuration in place at the last 'commit' (or on entering ConfigMode).");
+                    Ok(())
+                } else {
+                    Err("No snapshot to roll back to.".into())
+                }
+            },
+        },
+    );
+
+    commands.insert(
+        "line",
+        Command {
+            name: "line",
+            description: "Configure a line, e.g. the vty lines used by remote telnet/SSH sessions",
+            args: vec![
+                ArgSpec::required("vty").of_kind(ArgKind::Keyword(&["vty"])),
+                ArgSpec::required("first-line"),
+                ArgSpec::required("last-line"),
+            ],
+            help: "Configure a line, e.g. the vty lines used by remote telnet/SSH sessions",
+            usage: None,
+            modes: &[Mode::ConfigMode],
+            suggestions: Some(vec!["vty"]),
+            suggestions1: Some(vec!["vty"]),
+            require_subcommand: true,
+            options: Some(vec!["<0-15>       - First/last vty line number"]),
+            aliases: vec![],
+            execute: |args, context, _| match args {
+                ["vty", first, last] => {
+                    let first = first.parse::<u32>().map_err(|_| "Invalid line number.".to_string())?;
+                    let last = last.parse::<u32>().map_err(|_| "Invalid line number.".to_string())?;
+                    if first > last {
+                        return Err("First line number must not exceed last line number.".into());
+                    }
+                    VTY_CONFIG.lock().unwrap().line_range = Some((first, last));
+                    context.current_mode = Mode::LineVtyMode(format!("{} {}", first, last));
+                    context.prompt = format!("{}(config-line)#", context.config.hostname);
+                    crate::vty_server::ensure_started();
+                    println!("Configuring vty line(s) {} to {}.", first, last);
+                    Ok(())
+                }
+                _ => Err("Usage: line vty <first-line> <last-line>".into()),
+            },
+        },
+    );
+
+    commands.insert(
+        "transport",
+        Command {
+            name: "transport",
+            description: "Set the protocols permitted on the selected line",
+            args: vec![
+                ArgSpec::required("input").of_kind(ArgKind::Keyword(&["input"])),
+                ArgSpec::required("protocol").of_kind(ArgKind::Keyword(&["telnet", "ssh", "all"])),
+            ],
+            help: "Set the protocols permitted on the selected line",
+            usage: None,
+            modes: &[Mode::LineVtyMode(String::new())],
+            suggestions: Some(vec!["input telnet", "input ssh", "input all"]),
+            suggestions1: Some(vec!["input telnet", "input ssh", "input all"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| {
+                let transport_input = match args {
+                    ["input", "telnet"] => TransportInput::Telnet,
+                    ["input", "ssh"] => TransportInput::Ssh,
+                    ["input", "all"] => TransportInput::All,
+                    _ => return Err("Usage: transport input {telnet | ssh | all}".into()),
+                };
+                VTY_CONFIG.lock().unwrap().transport_input = transport_input;
+                println!("Transport input set to {}.", transport_input);
+                Ok(())
+            },
+        },
+    );
+
+    commands.insert(
+        "login",
+        Command {
+            name: "login",
+            description: "Configure how sessions on the selected line are authenticated",
+            args: vec![ArgSpec::required("mode").of_kind(ArgKind::Keyword(&["local"]))],
+            help: "Configure how sessions on the selected line are authenticated",
+            usage: None,
+            modes: &[Mode::LineVtyMode(String::new())],
+            suggestions: Some(vec!["local"]),
+            suggestions1: Some(vec!["local"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| match args {
+                ["local"] => {
+                    VTY_CONFIG.lock().unwrap().login_local = true;
+                    println!("Line authentication set to local username/password.");
+                    Ok(())
+                }
+                _ => Err("Usage: login local".into()),
+            },
+        },
+    );
+
+    commands.insert(
+        "websocket-server",
+        Command {
+            name: "websocket-server",
+            description: "Expose the CLI over a WebSocket control channel for remote driving",
+            args: vec![ArgSpec::required("state").of_kind(ArgKind::Keyword(&["enable"]))],
+            help: "Expose the CLI over a WebSocket control channel for remote driving",
+            usage: Some("websocket-server enable"),
+            modes: &[Mode::ConfigMode],
+            suggestions: Some(vec!["enable"]),
+            suggestions1: Some(vec!["enable"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| match args {
+                ["enable"] => {
+                    crate::ws_server::ensure_started();
+                    println!("WebSocket control channel enabled.");
+                    Ok(())
+                }
+                _ => Err("Usage: websocket-server enable".into()),
+            },
+        },
+    );
+
+    commands.insert(
+        "management",
+        Command {
+            name: "management websocket",
+            description: "Expose the CLI over a WebSocket channel on a chosen port, authenticated with a crypto key",
+            args: vec![
+                ArgSpec::required("subcommand").of_kind(ArgKind::Keyword(&["websocket"])),
+                ArgSpec::required("port").of_kind(ArgKind::U16),
+                ArgSpec::required("key").of_kind(ArgKind::Keyword(&["key"])),
+                ArgSpec::required("key-name"),
+            ],
+            help: "Expose the CLI over a WebSocket channel on a chosen port, authenticated with a crypto key",
+            usage: Some("management websocket <port> key <key-name>"),
+            modes: &[Mode::ConfigMode],
+            suggestions: Some(vec!["websocket"]),
+            suggestions1: Some(vec!["websocket"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, context, _| {
+                if args.len() == 4 && args[0] == "websocket" && args[2] == "key" {
+                    let port: u16 = args[1]
+                        .parse()
+                        .map_err(|_| "Invalid port. Must be an integer between 0 and 65535.".to_string())?;
+                    let key_name = args[3];
+                    let key = context
+                        .key_store
+                        .get(key_name)
+                        .ok_or_else(|| format!("Crypto key '{}' not found. Configure it with 'crypto key generate'/'crypto key import' first.", key_name))?;
+                    crate::ws_server::start_management_channel(port, key_name, &key.pem)?;
+                    println!("Management WebSocket channel listening on port {}, authenticated with key '{}'.", port, key_name);
+                    Ok(())
+                } else {
+                    Err("Usage: management websocket <port> key <key-name>".into())
+                }
+            },
+        },
+    );
+
+    commands.insert(
+        "kernel-apply",
+        Command {
+            name: "kernel-apply",
+            description: "Program real kernel interfaces/routes via netlink instead of only the simulation, or return to simulation-only",
+            args: vec![ArgSpec::required("state").of_kind(ArgKind::Keyword(&["enable", "disable"]))],
+            help: "Program real kernel interfaces/routes via netlink instead of only the simulation, or return to simulation-only",
+            usage: Some("kernel-apply enable|disable"),
+            modes: &[Mode::ConfigMode],
+            suggestions: Some(vec!["enable", "disable"]),
+            suggestions1: Some(vec!["enable", "disable"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| match args {
+                ["enable"] => match crate::host_backend::enable_kernel_backend() {
+                    Ok(()) => {
+                        println!("Kernel-apply enabled: interface/route commands now also program the host via netlink.");
+                        Ok(())
+                    }
+                    Err(err) => {
+                        println!("{}", err);
+                        Ok(())
+                    }
+                },
+                ["disable"] => {
+                    crate::host_backend::disable_kernel_backend();
+                    println!("Kernel-apply disabled: back to simulation-only.");
+                    Ok(())
+                }
+                _ => Err("Usage: kernel-apply enable|disable".into()),
+            },
+        },
+    );
+
     commands.insert("debug", Command {
         name: "debug all",
         description: "To turn on all the possible debug levels",
+        args: vec![],
+        help: "To turn on all the possible debug levels",
+        usage: None,
+        modes: &[Mode::PrivilegedMode],
         suggestions: Some(vec!["all"]),
         suggestions1: Some(vec!["all"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::PrivilegedMode) {
                 if args.len() == 1 && args[0] == "all" {
@@ -459,9 +953,15 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
     commands.insert("undebug", Command {
         name: "undebug all",
         description: "Turning off all possible debugging processes",
+        args: vec![],
+        help: "Turning off all possible debugging processes",
+        usage: None,
+        modes: &[Mode::PrivilegedMode],
         suggestions: Some(vec!["all"]),
         suggestions1: Some(vec!["all"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::PrivilegedMode) {
                 if args.len() == 1 && args[0] == "all" {
@@ -479,9 +979,15 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
     commands.insert("hostname", Command {
         name: "hostname",
         description: "Set the device hostname",
+        args: vec![ArgSpec::required("hostname")],
+        help: "Set the device hostname",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<new-hostname>    - Enter a new hostname"]),
+        aliases: vec![],
         execute: |args, context, _| {
             if let Mode::ConfigMode = context.current_mode {
                 if let Some(new_hostname) = args.get(0) {
@@ -516,39 +1022,104 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
         Command {
             name: "ifconfig",
             description: "Display or configure network details of the router",
+            args: vec![],
+            help: "Display or configure network details of the router",
+            usage: None,
+            modes: &[Mode::PrivilegedMode, Mode::ConfigMode],
             suggestions: None,
             suggestions1: None,
-            options: Some(vec!["<interface      - Enter the interface you need to change the ip-address of or need to add", 
-                "<ip-address>      - Enter the new ip-address"]),
+            require_subcommand: true,
+            options: Some(vec!["<interface      - Enter the interface you need to change the ip-address of or need to add",
+                "<ip-address>      - Enter the new ip-address",
+                "netmask <mask>    - Set the netmask, e.g. 255.255.255.0",
+                "<ip-address>/<prefix> - Set the address via CIDR notation",
+                "mtu <n>           - Set the interface MTU",
+                "hw ether <mac>    - Set the hardware (MAC) address",
+                "up                - Mark the interface up",
+                "down              - Mark the interface down",
+                "add <ipv6>/<prefix> - Add an IPv6 address",
+                "del <ipv6>        - Remove an IPv6 address"]),
+            aliases: vec![],
             execute: |args, _, _| {
                 let mut ifconfig_state = IFCONFIG_STATE.lock().unwrap();
-    
+
                 if args.is_empty() {
                     if ifconfig_state.is_empty() {
                         println!("No interfaces found.");
                     } else {
-                        for (interface_name, (ip_address, broadcast_address)) in ifconfig_state.iter() {
-                            println!("{}: flags=4163<UP,BROADCAST,RUNNING,MULTICAST>  mtu 1500", interface_name);
-                            println!("    inet {}  netmask 255.255.255.0  broadcast {}", ip_address, broadcast_address);
-                            println!("    inet6 fe80::6a01:72f9:adf2:3ffb  prefixlen 64  scopeid 0x20<link>");
-                            println!("    ether 00:0c:29:16:30:92  txqueuelen 1000  (Ethernet)");
+                        for (interface_name, entry) in ifconfig_state.iter() {
+                            print_ifconfig_entry(interface_name, entry);
                         }
                     }
-                } else if args.len() == 3 && args[2] == "up" {
-                    let new_interface = &args[0];
-                    let new_ip: Ipv4Addr = Ipv4Addr::from_str(&args[1]).expect("Invalid IP address format");
-                    let new_broadcast = calculate_broadcast(new_ip, 24);
-    
-                    ifconfig_state.insert(new_interface.to_string(), (new_ip, new_broadcast));
-    
-                    println!("Updated {}: flags=4163<UP,BROADCAST,RUNNING,MULTICAST>  mtu 1500", new_interface);
-                    println!("    inet {}  netmask 255.255.255.0  broadcast {}", new_ip, new_broadcast);
-                    println!("    inet6 fe80::6a01:72f9:adf2:3ffb  prefixlen 64  scopeid 0x20<link>");
-                    println!("    ether 00:0c:29:16:30:92  txqueuelen 1000  (Ethernet)");
-                } else {
-                    println!("Invalid arguments provided to 'ifconfig'. To create an entry 'ifconfig <interface> <ip-address> up");
+                    return Ok(());
                 }
-    
+
+                let interface = args[0].to_string();
+
+                match &args[1..] {
+                    [ip, "netmask", mask] => {
+                        let ip_address: Ipv4Addr = ip.parse().map_err(|_| "Invalid IP address format.".to_string())?;
+                        let netmask: Ipv4Addr = mask.parse().map_err(|_| "Invalid netmask format.".to_string())?;
+                        let prefix_length = netmask_to_prefix(netmask);
+                        let entry = ifconfig_state.entry(interface.clone()).or_insert_with(|| IfconfigEntry::new(ip_address, prefix_length));
+                        entry.set_address(ip_address, prefix_length);
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    [addr_and_prefix] if addr_and_prefix.contains('/') => {
+                        let (ip, prefix) = addr_and_prefix.split_once('/').unwrap();
+                        let ip_address: Ipv4Addr = ip.parse().map_err(|_| "Invalid IP address format.".to_string())?;
+                        let prefix_length: u32 = prefix.parse().map_err(|_| "Invalid prefix length.".to_string())?;
+                        let entry = ifconfig_state.entry(interface.clone()).or_insert_with(|| IfconfigEntry::new(ip_address, prefix_length));
+                        entry.set_address(ip_address, prefix_length);
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    [ip, "up"] => {
+                        let ip_address: Ipv4Addr = ip.parse().map_err(|_| "Invalid IP address format.".to_string())?;
+                        let entry = ifconfig_state.entry(interface.clone()).or_insert_with(|| IfconfigEntry::new(ip_address, 24));
+                        entry.set_address(ip_address, 24);
+                        entry.up = true;
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    ["up"] => {
+                        let entry = ifconfig_state.get_mut(&interface).ok_or_else(|| format!("No such interface: {}", interface))?;
+                        entry.up = true;
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    ["down"] => {
+                        let entry = ifconfig_state.get_mut(&interface).ok_or_else(|| format!("No such interface: {}", interface))?;
+                        entry.up = false;
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    ["mtu", n] => {
+                        let mtu: u16 = n.parse().map_err(|_| "Invalid MTU. It must be a positive integer.".to_string())?;
+                        let entry = ifconfig_state.get_mut(&interface).ok_or_else(|| format!("No such interface: {}", interface))?;
+                        entry.mtu = mtu;
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    ["hw", "ether", mac] => {
+                        let entry = ifconfig_state.get_mut(&interface).ok_or_else(|| format!("No such interface: {}", interface))?;
+                        entry.hw_address = mac.to_string();
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    ["add", addr_and_prefix] => {
+                        let (ip, prefix) = addr_and_prefix.split_once('/').ok_or("Usage: ifconfig <interface> add <ipv6-address>/<prefix-length>")?;
+                        let address: Ipv6Addr = ip.parse().map_err(|_| "Invalid IPv6 address format.".to_string())?;
+                        let prefix_length: u8 = prefix.parse().map_err(|_| "Invalid prefix length.".to_string())?;
+                        let entry = ifconfig_state.get_mut(&interface).ok_or_else(|| format!("No such interface: {}", interface))?;
+                        entry.ipv6_addresses.push((address, prefix_length));
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    ["del", ip] => {
+                        let address: Ipv6Addr = ip.parse().map_err(|_| "Invalid IPv6 address format.".to_string())?;
+                        let entry = ifconfig_state.get_mut(&interface).ok_or_else(|| format!("No such interface: {}", interface))?;
+                        entry.ipv6_addresses.retain(|(existing, _)| *existing != address);
+                        print_ifconfig_entry(&interface, entry);
+                    }
+                    _ => {
+                        return Err("Usage: ifconfig <interface> [<ip> netmask <mask> | <ip>/<prefix> | <ip> up] | <interface> {up|down|mtu <n>|hw ether <mac>|add <ipv6>/<prefix>|del <ipv6>}".into());
+                    }
+                }
+
                 Ok(())
             },
         },
@@ -558,27 +1129,55 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
         "write",
         Command {
             name: "write memory",
-            description: "Save the running configuration to the startup configuration",
-            suggestions: Some(vec!["memory"]),
-            suggestions1: Some(vec!["memory"]),
+            description: "Save the running configuration to the startup configuration, or to a structured JSON/YAML document with 'write config json|yaml'",
+            args: vec![],
+            help: "Save the running configuration to the startup configuration, or to a structured JSON/YAML document with 'write config json|yaml'",
+            usage: Some("write memory | write config json|yaml [<file>]"),
+            modes: &[Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode],
+            suggestions: Some(vec!["memory", "config json", "config yaml"]),
+            suggestions1: Some(vec!["memory", "config json", "config yaml"]),
+            require_subcommand: true,
             options: None,
-            execute: |args, context, _| {
-                if matches!(context.current_mode, Mode::PrivilegedMode | Mode::ConfigMode | Mode::InterfaceMode) {
-                    if args.len() == 1 && args[0] == "memory" {
-                        // Save the running configuration to the startup configuration
-                        let running_config = get_running_config(context);
-                        context.config.startup_config = Some(running_config.clone());
-        
-                        // Update the last written timestamp
-                        context.config.last_written = Some(chrono::Local::now().to_string());
-        
-                        println!("Configuration saved successfully.");
-                        Ok(())
+            aliases: vec![],
+            execute: |args, context, clock| {
+                if !matches!(context.current_mode, Mode::PrivilegedMode | Mode::ConfigMode | Mode::InterfaceMode) {
+                    return Err("The 'write' command is only available in Privileged EXEC mode.".into());
+                }
+
+                if args.len() == 1 && args[0] == "memory" {
+                    // Save the running configuration to the startup configuration
+                    let running_config = get_running_config(context);
+                    context.config.startup_config = Some(running_config.clone());
+
+                    // Update the last written timestamp
+                    context.config.last_written = Some(chrono::Local::now().to_string());
+
+                    // Persist the structured startup config (crypto keys and
+                    // device-state stores included) alongside the rendered text.
+                    let passphrase = if context.config.crypto_keys.is_empty() {
+                        String::new()
                     } else {
-                        Err("Invalid arguments provided to 'write memory'. This command does not accept additional arguments.".into())
-                    }
+                        println!("Enter a passphrase to encrypt stored private keys:");
+                        read_password().unwrap_or_default()
+                    };
+                    save_config(&context.config, Path::new(DEFAULT_STARTUP_CONFIG_PATH), &passphrase)?;
+
+                    // Archive a revision snapshot alongside the startup save.
+                    let revision = snapshot_running_config(context, &*clock);
+                    println!("Configuration saved successfully (archived as revision {}).", revision);
+                    Ok(())
+                } else if args.len() >= 2 && args[0] == "config" && (args[1] == "json" || args[1] == "yaml") {
+                    let format = if args[1] == "yaml" { ConfigDocumentFormat::Yaml } else { ConfigDocumentFormat::Json };
+                    let default_name = if args[1] == "yaml" { "running-config.yaml" } else { "running-config.json" };
+                    let file_name = args.get(2).copied().unwrap_or(default_name);
+                    let document = build_config_document(context);
+                    let contents = format.serialize(&document)?;
+                    std::fs::write(file_name, contents)
+                        .map_err(|err| format!("Failed to write '{}': {}", file_name, err))?;
+                    println!("Running configuration written to {} as {}.", file_name, args[1].to_uppercase());
+                    Ok(())
                 } else {
-                    Err("The 'write memory' command is only available in Privileged EXEC mode.".into())
+                    Err("Invalid arguments. Use 'write memory' or 'write config json|yaml [<file>]'.".into())
                 }
             },
         },
@@ -589,33 +1188,67 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
         "copy",
         Command {
             name: "copy",
-            description: "Copy running configuration",
-            suggestions: Some(vec!["running-config"]),
-            suggestions1: Some(vec!["running-config"]),
+            description: "Copy running configuration, or import a structured JSON/YAML document with 'copy json|yaml running-config'",
+            args: vec![],
+            help: "Copy running configuration, or import a structured JSON/YAML document with 'copy json|yaml running-config'",
+            usage: Some("copy running-config {startup-config|<file>} | copy json|yaml running-config [<file>]"),
+            modes: &[Mode::PrivilegedMode],
+            suggestions: Some(vec!["running-config", "json running-config", "yaml running-config"]),
+            suggestions1: Some(vec!["running-config", "json running-config", "yaml running-config"]),
+            require_subcommand: true,
             options: Some(vec!["startup-config"]),
+            aliases: vec![],
             execute: |args, context, _| {
                 if !matches!(context.current_mode, Mode::PrivilegedMode | Mode::ConfigMode | Mode::InterfaceMode) {
                     return Err("The 'copy' command is only available in Privileged EXEC mode, Config mode and interface mode".into());
                 }
 
+                if args[0] == "json" || args[0] == "yaml" {
+                    if args.get(1) != Some(&"running-config") {
+                        return Err("Invalid destination. Use 'copy json|yaml running-config [<file>]'.".into());
+                    }
+                    let format = if args[0] == "yaml" { ConfigDocumentFormat::Yaml } else { ConfigDocumentFormat::Json };
+                    let default_name = if args[0] == "yaml" { "running-config.yaml" } else { "running-config.json" };
+                    let file_name = args.get(2).copied().unwrap_or(default_name);
+                    let contents = std::fs::read_to_string(file_name)
+                        .map_err(|err| format!("Failed to read '{}': {}", file_name, err))?;
+                    let value = format.parse(&contents)?;
+                    validate_config_document(&value)?;
+                    let document = serde_json::from_value(value)
+                        .map_err(|err| format!("Failed to parse configuration document: {}", err))?;
+                    apply_config_document(&document, context);
+                    println!("Running configuration replaced from {}.", file_name);
+                    return Ok(());
+                }
+
                 // Handle both full and abbreviated versions of 'running-config'
                 let source = args[0];
                 if !source.starts_with("run") {
-                    return Err("Invalid source. Use 'running-config'".into());
+                    return Err("Invalid source. Use 'running-config' or 'json running-config'".into());
                 }
 
                 else if args[1] == "startup-config"{
-                    
+
                     // Save the running configuration to the startup configuration
                     let running_config = get_running_config(context);
                     context.config.startup_config = Some(running_config.clone());
-        
+
                     // Update the last written timestamp
                     context.config.last_written = Some(chrono::Local::now().to_string());
-        
+
+                    // Persist the structured startup config (crypto keys and
+                    // device-state stores included) alongside the rendered text.
+                    let passphrase = if context.config.crypto_keys.is_empty() {
+                        String::new()
+                    } else {
+                        println!("Enter a passphrase to encrypt stored private keys:");
+                        read_password().unwrap_or_default()
+                    };
+                    save_config(&context.config, Path::new(DEFAULT_STARTUP_CONFIG_PATH), &passphrase)?;
+
                     println!("Configuration saved successfully.");
                     Ok(())
-                    
+
                 }
 
                 else {
@@ -642,14 +1275,51 @@ pub fn build_command_registry() -> HashMap<&'static str, Command> {
         },
     );
 
+    commands.insert(
+        "config",
+        Command {
+            name: "config wizard",
+            description: "Interactively build a configuration (hostname, domain, interface IP, OSPF, crypto key) and optionally save it, 'config wizard ospf-acl' for just OSPF and ACL settings, or 'config reload <path>' to hot-reload crypto/tunnel settings from disk",
+            args: vec![],
+            help: "Interactively build a configuration (hostname, domain, interface IP, OSPF, crypto key) and optionally save it, 'config wizard ospf-acl' for just OSPF and ACL settings, or 'config reload <path>' to hot-reload crypto/tunnel settings from disk",
+            usage: None,
+            modes: &[Mode::PrivilegedMode],
+            suggestions: Some(vec!["wizard", "wizard ospf-acl", "reload"]),
+            suggestions1: Some(vec!["wizard", "wizard ospf-acl", "reload"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, context, _| {
+                if args.len() == 1 && args[0] == "wizard" {
+                    run_config_wizard(context)
+                } else if args.len() == 2 && args[0] == "wizard" && args[1] == "ospf-acl" {
+                    run_ospf_acl_wizard(context)
+                } else if args.len() == 2 && args[0] == "reload" {
+                    let path = Path::new(args[1]);
+                    println!("Enter the passphrase used to encrypt stored private keys in '{}':", args[1]);
+                    let passphrase = read_password().unwrap_or_default();
+                    reload_crypto_and_tunnel_config(context, path, &passphrase)
+                } else {
+                    Err("Invalid arguments provided to 'config'. Use 'config wizard', 'config wizard ospf-acl', or 'config reload <path>'.".into())
+                }
+            },
+        },
+    );
+
     commands.insert(
         "help",
         Command {
             name: "help",
             description: "Display available commands for current mode",
+            args: vec![],
+            help: "Display available commands for current mode",
+            usage: None,
+            modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
             suggestions: None,
             suggestions1: None,
+            require_subcommand: true,
             options: None,
+            aliases: vec![],
             execute: |args, context, _| {
                 println!("\n ");
                 println!(r#"Help may be requested at any point in a command by entering
@@ -781,33 +1451,117 @@ Two styles of help are provided:
             }
         },
     );
-    
+
+    commands.insert(
+        "complete",
+        Command {
+            name: "complete",
+            description: "Generate a shell tab-completion script from the command registry",
+            args: vec![ArgSpec::required("shell")],
+            help: "Generate a shell tab-completion script from the command registry",
+            usage: Some("complete <bash|zsh|fish>"),
+            modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
+            suggestions: Some(vec!["bash", "zsh", "fish"]),
+            suggestions1: Some(vec!["bash", "zsh", "fish"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| {
+                if args.len() != 1 {
+                    return Err("Usage: complete <bash|zsh|fish>".into());
+                }
+                let shell = crate::shell_completion::Shell::parse(args[0])?;
+                let program_name = std::env::args()
+                    .next()
+                    .and_then(|path| {
+                        std::path::Path::new(&path)
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                    })
+                    .unwrap_or_else(|| "pnfbox".to_string());
+                let registry = build_command_registry();
+                let script = crate::shell_completion::generate_completion_script(shell, &program_name, &registry);
+                println!("{}", script);
+                Ok(())
+            },
+        },
+    );
+
 
     commands.insert(
         "clock",
         Command {
             name: "clock set",
             description: "Change the clock date and time",
-            suggestions: Some(vec!["set"]),
-            suggestions1: Some(vec!["set"]),
+            args: vec![],
+            help: "Change the clock date and time",
+            usage: None,
+            modes: &[Mode::PrivilegedMode],
+            suggestions: Some(vec!["set", "timezone", "locale"]),
+            suggestions1: Some(vec!["set", "timezone", "locale"]),
+            require_subcommand: true,
             options: Some(vec!["<hh:mm:ss>      - Enter the time in this specified format",
                 "<day>      - Enter the day '1-31'",
                 "<month>    - Enter a valid month",
                 "<year>     - Enter the year"]),
+            aliases: vec![],
             execute: |args, context, clock| {
                 if matches!(context.current_mode, Mode::PrivilegedMode) {
-                    if args.len() > 1 && args[0] == "set" {   
+                    if args.len() > 1 && args[0] == "set" {
                         if let Some(clock) = clock {
 
                             let input = args.join(" ");
-            
-                            match parse_clock_set_input(&input) {
-                                Ok((time, day, month, year)) => {
-                        
-                                    handle_clock_set(time, day, month, year, clock);
+
+                            match parse_clock_set_input(&input, clock.locale()) {
+                                Ok(datetime) => handle_clock_set(datetime, clock),
+                                Err(err) => Err(err),
+                            }
+                        } else {
+                            Err("Clock functionality is unavailable.".to_string())
+                        }
+                    } else if args[0] == "locale" {
+                        if let Some(clock) = clock {
+                            if args.len() != 2 {
+                                return Err("Correct usage is 'clock locale <code>' (e.g. 'clock locale fr_FR').".into());
+                            }
+                            match args[1].parse::<chrono::Locale>() {
+                                Ok(locale) => {
+                                    clock.set_locale(locale);
+                                    println!("Clock locale set to {}.", args[1]);
+                                    Ok(())
+                                }
+                                Err(_) => Err(format!("Unknown locale '{}'.", args[1])),
+                            }
+                        } else {
+                            Err("Clock functionality is unavailable.".to_string())
+                        }
+                    } else if args[0] == "timezone" {
+                        if let Some(clock) = clock {
+                            match args.len() {
+                                2 => match args[1].parse::<chrono_tz::Tz>() {
+                                    Ok(tz) => {
+                                        clock.set_timezone(tz)?;
+                                        println!("Clock timezone set to {}.", args[1]);
+                                        Ok(())
+                                    }
+                                    Err(_) => Err(format!(
+                                        "Unknown timezone '{}'. Use an IANA zone name (e.g. 'America/New_York') or 'clock timezone <name> <offset-hours> <offset-minutes>'.",
+                                        args[1]
+                                    )),
+                                },
+                                4 => {
+                                    let name = args[1];
+                                    let hours: i32 = args[2]
+                                        .parse()
+                                        .map_err(|_| "Invalid offset-hours. Expected an integer.".to_string())?;
+                                    let minutes: i32 = args[3]
+                                        .parse()
+                                        .map_err(|_| "Invalid offset-minutes. Expected an integer.".to_string())?;
+                                    clock.set_timezone_fixed(name, hours, minutes)?;
+                                    println!("Clock timezone set to {} (UTC{:+03}:{:02}).", name, hours, minutes.abs());
                                     Ok(())
                                 }
-                                Err(err) => Err(err), 
+                                _ => Err("Correct usage is 'clock timezone <Area/City>' or 'clock timezone <name> <offset-hours> <offset-minutes>'.".into()),
                             }
                         } else {
                             Err("Clock functionality is unavailable.".to_string())
@@ -826,22 +1580,37 @@ Two styles of help are provided:
     commands.insert("ntp", Command {
         name: "ntp",
         description: "NTP configuration commands",
+        args: vec![],
+        help: "NTP configuration commands",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: Some(vec!["server", "master", "authenticate", "authentication-key", "trusted-key"]),
         suggestions1: Some(vec!["server", "master", "authenticate", "authentication-key", "trusted-key"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if !matches!(context.current_mode, Mode::ConfigMode) {
                 return Err("NTP commands are only available in configuration mode.".into());
             }
-    
+
             if args.is_empty() {
                 return Err("Subcommand required. Available subcommands: server, master, authenticate, authentication-key, trusted-key".into());
             }
-    
+
             match &args[0][..] {
                 "server" => {
-                    if args.len() == 2 {
+                    if args.len() == 2 || (args.len() == 4 && args[2] == "key") {
                         let ip_address = args[1].to_string();
+                        let key_id = if args.len() == 4 {
+                            match args[3].parse::<u32>() {
+                                Ok(key_number) if context.ntp_authentication_keys.contains_key(&key_number) => Some(key_number),
+                                Ok(_) => return Err("NTP authentication key not configured. Use 'ntp authentication-key' first.".into()),
+                                Err(_) => return Err("Invalid key number. Must be a positive integer.".into()),
+                            }
+                        } else {
+                            None
+                        };
                         if ip_address.parse::<Ipv4Addr>().is_ok() {
                             context.ntp_servers.insert(ip_address.clone());
                             // Assuming once the server is configured, we add it to NTP associations
@@ -855,6 +1624,8 @@ Two styles of help are provided:
                                 delay: 0.0,
                                 offset: 0.0,
                                 disp: 0.01,
+                                key_id,
+                                authenticated: false,
                             };
                             context.ntp_associations.push(association); // Adding the new server to the list
                             println!("NTP server {} configured.", ip_address);
@@ -863,11 +1634,26 @@ Two styles of help are provided:
                             Err("Invalid IP address format.".into())
                         }
                     } else {
-                        Err("Invalid arguments. Usage: ntp server {ip-address}".into())
+                        Err("Invalid arguments. Usage: ntp server {ip-address} [key <key-number>]".into())
                     }
                 },
                 "master" => {
                     context.ntp_master = true;
+                    if !context.ntp_associations.iter().any(|assoc| assoc.address == "127.127.1.1") {
+                        context.ntp_associations.push(NtpAssociation {
+                            address: "127.127.1.1".to_string(),
+                            ref_clock: ".LOCL.".to_string(),
+                            st: 16,
+                            when: "-".to_string(),
+                            poll: 64,
+                            reach: 0,
+                            delay: 0.0,
+                            offset: 0.0,
+                            disp: 0.01,
+                            key_id: None,
+                            authenticated: false,
+                        });
+                    }
                     println!("Device configured as NTP master.");
                     Ok(())
                 },
@@ -886,17 +1672,16 @@ Two styles of help are provided:
                     }
                 },
                 "authentication-key" => {
-                    if args.len() == 4 && args[2] == "md5" {
-                        if let Ok(key_number) = args[1].parse::<u32>() {
-                            let md5_key = args[3].to_string();
-                            context.ntp_authentication_keys.insert(key_number, md5_key.clone());
-                            println!("NTP authentication key {} configured with MD5 key: {}", key_number, md5_key);
-                            Ok(())
-                        } else {
-                            Err("Invalid key number. Must be a positive integer.".into())
-                        }
+                    if args.len() == 4 {
+                        let key_number = args[1].parse::<u32>().map_err(|_| "Invalid key number. Must be a positive integer.".to_string())?;
+                        let algorithm = NtpAuthAlgorithm::from_str(args[2])
+                            .ok_or_else(|| "Invalid algorithm. Use 'md5' or 'sha1'.".to_string())?;
+                        let key_value = args[3].to_string();
+                        context.ntp_authentication_keys.insert(key_number, NtpAuthKey { algorithm, key: key_value.clone() });
+                        println!("NTP authentication key {} configured with {} key: {}", key_number, algorithm.as_str().to_uppercase(), key_value);
+                        Ok(())
                     } else {
-                        Err("Invalid arguments. Use 'ntp authentication-key <key-number> md5 <key-value>'.".into())
+                        Err("Invalid arguments. Use 'ntp authentication-key <key-number> {md5|sha1} <key-value>'.".into())
                     }
                 },
                 "trusted-key" => {
@@ -916,96 +1701,432 @@ Two styles of help are provided:
             }
         }
     });
-  
+
+
+    commands.insert("snmp-server", Command {
+        name: "snmp-server",
+        description: "SNMP server configuration commands",
+        args: vec![],
+        help: "SNMP server configuration commands",
+        usage: None,
+        modes: &[Mode::ConfigMode],
+        suggestions: Some(vec!["community", "host", "location", "contact", "enable traps"]),
+        suggestions1: Some(vec!["community", "host", "location", "contact", "enable traps"]),
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            if !matches!(context.current_mode, Mode::ConfigMode) {
+                return Err("SNMP server commands are only available in configuration mode.".into());
+            }
+
+            if args.is_empty() {
+                return Err("Subcommand required. Available subcommands: community, host, location, contact, enable traps".into());
+            }
+
+            match &args[0][..] {
+                "community" => {
+                    if args.len() == 3 && (args[2] == "ro" || args[2] == "rw") {
+                        context.config.snmp_communities.insert(args[1].to_string(), args[2].to_string());
+                        println!("SNMP community '{}' configured with {} access.", args[1], args[2]);
+                        Ok(())
+                    } else {
+                        Err("Invalid arguments. Use 'snmp-server community <string> {ro|rw}'.".into())
+                    }
+                },
+                "host" => {
+                    if args.len() == 5 && args[2] == "version" && (args[3] == "1" || args[3] == "2c") {
+                        let address: Ipv4Addr = args[1]
+                            .parse()
+                            .map_err(|_| "Invalid IP address format.".to_string())?;
+                        context.config.snmp_hosts.push(SnmpHost {
+                            address: address.to_string(),
+                            version: args[3].to_string(),
+                            community: args[4].to_string(),
+                        });
+                        println!("SNMP trap host {} (version {}, community '{}') configured.", address, args[3], args[4]);
+                        Ok(())
+                    } else {
+                        Err("Invalid arguments. Use 'snmp-server host <ip-address> version {1|2c} <community>'.".into())
+                    }
+                },
+                "location" => {
+                    if args.len() >= 2 {
+                        let location = args[1..].join(" ");
+                        context.config.snmp_location = Some(location.clone());
+                        println!("SNMP sysLocation set to '{}'.", location);
+                        Ok(())
+                    } else {
+                        Err("Invalid arguments. Use 'snmp-server location <text>'.".into())
+                    }
+                },
+                "contact" => {
+                    if args.len() >= 2 {
+                        let contact = args[1..].join(" ");
+                        context.config.snmp_contact = Some(contact.clone());
+                        println!("SNMP sysContact set to '{}'.", contact);
+                        Ok(())
+                    } else {
+                        Err("Invalid arguments. Use 'snmp-server contact <text>'.".into())
+                    }
+                },
+                "enable" => {
+                    if args.len() == 2 && args[1] == "traps" {
+                        context.config.snmp_traps_enabled = true;
+                        println!("SNMP traps enabled.");
+                        Ok(())
+                    } else {
+                        Err("Invalid arguments. Use 'snmp-server enable traps'.".into())
+                    }
+                },
+                _ => Err("Invalid snmp-server subcommand. Available subcommands: community, host, location, contact, enable traps".into())
+            }
+        }
+    });
+
+
+    commands.insert("terminal", Command {
+        name: "terminal",
+        description: "Configure the editor (keybindings, color, completion, history)",
+        args: vec![],
+        help: "Configure the editor (keybindings, color, completion, history)",
+        usage: None,
+        modes: &[Mode::PrivilegedMode, Mode::ConfigMode],
+        suggestions: Some(vec!["editing-mode", "color-mode", "completion-type", "history", "monitor"]),
+        suggestions1: Some(vec![
+            "editing-mode", "emacs",
+            "editing-mode", "vi",
+            "color-mode", "on",
+            "color-mode", "off",
+            "color-mode", "forced",
+            "completion-type", "list",
+            "completion-type", "circular",
+            "history", "max-size",
+            "history", "duplicates", "on",
+            "history", "duplicates", "off",
+            "history", "file",
+            "monitor", "record",
+            "monitor", "stop",
+        ]),
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            match args {
+                ["editing-mode", "emacs"] => {
+                    context.config.terminal_settings.edit_mode = EditMode::Emacs;
+                    println!("Editing mode set to emacs.");
+                    Ok(())
+                }
+                ["editing-mode", "vi"] => {
+                    context.config.terminal_settings.edit_mode = EditMode::Vi;
+                    println!("Editing mode set to vi.");
+                    Ok(())
+                }
+                ["color-mode", "on"] => {
+                    context.config.terminal_settings.color_mode = ColorMode::Enabled;
+                    println!("Color mode set to enabled.");
+                    Ok(())
+                }
+                ["color-mode", "off"] => {
+                    context.config.terminal_settings.color_mode = ColorMode::Disabled;
+                    println!("Color mode set to disabled.");
+                    Ok(())
+                }
+                ["color-mode", "forced"] => {
+                    context.config.terminal_settings.color_mode = ColorMode::Forced;
+                    println!("Color mode set to forced.");
+                    Ok(())
+                }
+                ["completion-type", "list"] => {
+                    context.config.terminal_settings.completion_type = CompletionStyle::List;
+                    println!("Completion type set to list.");
+                    Ok(())
+                }
+                ["completion-type", "circular"] => {
+                    context.config.terminal_settings.completion_type = CompletionStyle::Circular;
+                    println!("Completion type set to circular.");
+                    Ok(())
+                }
+                ["history", "max-size", size] => {
+                    let size: usize = size.parse().map_err(|_| "Invalid size. Must be a positive integer.".to_string())?;
+                    context.config.terminal_settings.max_history_size = size;
+                    println!("History max size set to {}.", size);
+                    Ok(())
+                }
+                ["history", "duplicates", "on"] => {
+                    context.config.terminal_settings.history_duplicates = true;
+                    println!("History duplicates enabled.");
+                    Ok(())
+                }
+                ["history", "duplicates", "off"] => {
+                    context.config.terminal_settings.history_duplicates = false;
+                    println!("History duplicates disabled.");
+                    Ok(())
+                }
+                ["history", "file", path] => {
+                    context.config.terminal_settings.history_file = path.to_string();
+                    println!("History file set to {}.", path);
+                    Ok(())
+                }
+                ["monitor", "record", path] => {
+                    crate::session_recorder::start_recording(path)?;
+                    println!("Recording session to {} (asciicast v2). Use 'terminal monitor stop' to finish.", path);
+                    Ok(())
+                }
+                ["monitor", "stop"] => {
+                    crate::session_recorder::stop_recording()?;
+                    println!("Recording stopped.");
+                    Ok(())
+                }
+                _ => Err("Usage: terminal {editing-mode {emacs|vi} | color-mode {on|off|forced} | completion-type {list|circular} | history {max-size <n>|duplicates {on|off}|file <path>} | monitor {record <file>|stop}}".into())
+            }
+        }
+    });
 
     commands.insert("ping", Command {
         name: "ping",
         description: "Ping a specific IP address to check reachability",
+        args: vec![ArgSpec::required("ip-address").of_kind(ArgKind::Ip)],
+        help: "Ping a specific IP address to check reachability",
+        usage: Some("ping <ip-address> [repeat <n>] [size <bytes>] [timeout <seconds>] [source <interface>]"),
+        modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode],
         suggestions: None,
         suggestions1: None,
-        options: Some(vec!["<ip-address>    - Enter the ip-address"]),
-        execute: |args, _context, _| {
-            if args.len() == 1 {
-                let ip: String = args[0].to_string();
-                let route_table = ROUTE_TABLE.lock().unwrap();
-    
-                if route_table.contains_key(&ip) {
-                    println!("Pinging {} with 32 bytes of data:", ip);
-                    for _ in 0..4 {
-                        println!("Reply from {}: bytes=32 time<1ms TTL=128", ip);
-                    }
-                    println!("\nPing statistics for {}:", ip);
-                    println!("    Packets: Sent = 4, Received = 4, Lost = 0 (0% loss),");
-                    println!("Approximate round trip times in milli-seconds:");
-                    println!("    Minimum = 0ms, Maximum = 1ms, Average = 0ms");
-                    Ok(())
-                } else {
-                    println!("Pinging {} with 32 bytes of data:", ip);
-                    for _ in 0..4 {
-                        println!("Request timed out.");
+        require_subcommand: true,
+        options: Some(vec![
+            "<ip-address>    - Enter the ip-address",
+            "repeat <n>      - Number of echo requests to send (default 4)",
+            "size <bytes>    - Datagram size in bytes (default 32)",
+            "timeout <sec>   - Seconds to wait for each reply (default 2)",
+            "source <iface>  - Interface whose IP address to ping from",
+        ]),
+        aliases: vec![],
+        execute: |args, context, _| {
+            if args.is_empty() {
+                return Err("Invalid syntax. Usage: ping <ip-address> [repeat <n>] [size <bytes>] [timeout <seconds>] [source <interface>]".into());
+            }
+            let target: Ipv4Addr = args[0]
+                .parse()
+                .map_err(|_| "Invalid IP address format.".to_string())?;
+
+            let mut options = PingOptions::default();
+            let mut index = 1;
+            while index < args.len() {
+                match args[index] {
+                    "repeat" => {
+                        let value = args.get(index + 1).ok_or("Usage: ping <ip-address> repeat <n>")?;
+                        options.count = value.parse().map_err(|_| "Invalid repeat count.".to_string())?;
+                        index += 2;
+                    }
+                    "size" => {
+                        let value = args.get(index + 1).ok_or("Usage: ping <ip-address> size <bytes>")?;
+                        options.size = value.parse().map_err(|_| "Invalid size.".to_string())?;
+                        index += 2;
+                    }
+                    "timeout" => {
+                        let value = args.get(index + 1).ok_or("Usage: ping <ip-address> timeout <seconds>")?;
+                        let seconds: u64 = value.parse().map_err(|_| "Invalid timeout.".to_string())?;
+                        options.timeout = std::time::Duration::from_secs(seconds);
+                        index += 2;
+                    }
+                    "source" => {
+                        let iface = args.get(index + 1).ok_or("Usage: ping <ip-address> source <interface>")?.to_string();
+                        let source_ip = {
+                            let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
+                            ip_address_state
+                                .get(&iface)
+                                .map(|(ip, _)| *ip)
+                                .ok_or_else(|| format!("Interface {} has no IP address configured.", iface))?
+                        };
+                        options.source = Some(source_ip);
+                        index += 2;
+                    }
+                    other => return Err(format!("Invalid ping option '{}'.", other)),
+                }
+            }
+
+            println!("Pinging {} with {} bytes of data:", target, options.size);
+
+            match run_icmp_ping(target, &options) {
+                Some(results) => {
+                    for result in &results {
+                        match result {
+                            ProbeResult::Reply { rtt, ttl } => {
+                                println!("Reply from {}: bytes={} time={}ms TTL={}", target, options.size, rtt.as_millis().max(1), ttl);
+                            }
+                            ProbeResult::Timeout => println!("Request timed out."),
+                        }
+                    }
+                    let summary = PingSummary::from_results(&results);
+                    println!("\nPing statistics for {}:", target);
+                    println!(
+                        "    Packets: Sent = {}, Received = {}, Lost = {} ({}% loss),",
+                        summary.sent,
+                        summary.received,
+                        summary.sent - summary.received,
+                        summary.loss_percent()
+                    );
+                    if summary.received > 0 {
+                        println!("Approximate round trip times in milli-seconds:");
+                        println!(
+                            "    Minimum = {}ms, Maximum = {}ms, Average = {}ms",
+                            summary.min.as_millis(), summary.max.as_millis(), summary.avg.as_millis()
+                        );
+                        Ok(())
+                    } else {
+                        Err(format!("IP address {} is not reachable.", target))
+                    }
+                }
+                // No raw-socket permission (e.g. not running as root) --
+                // fall back to the historical simulated reachability check.
+                None => {
+                    let routing_table = ROUTING_TABLE.lock().unwrap();
+                    let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
+                    let status_map = STATUS_MAP.lock().unwrap();
+                    let table = effective_routing_table(&routing_table, &ip_address_state, &status_map);
+                    let reachable = table.lookup(target).is_some();
+                    if reachable {
+                        for _ in 0..options.count {
+                            println!("Reply from {}: bytes={} time<1ms TTL=128", target, options.size);
+                        }
+                        println!("\nPing statistics for {}:", target);
+                        println!("    Packets: Sent = {}, Received = {}, Lost = 0 (0% loss),", options.count, options.count);
+                        println!("Approximate round trip times in milli-seconds:");
+                        println!("    Minimum = 0ms, Maximum = 1ms, Average = 0ms");
+                        Ok(())
+                    } else {
+                        for _ in 0..options.count {
+                            println!("Request timed out.");
+                        }
+                        println!("\nPing statistics for {}:", target);
+                        println!("    Packets: Sent = {}, Received = 0, Lost = {} (100% loss),", options.count, options.count);
+                        Err(format!("IP address {} is not reachable.", target))
                     }
-                    println!("\nPing statistics for {}:", ip);
-                    println!("    Packets: Sent = 4, Received = 0, Lost = 4 (100% loss),");
-                    Err(format!("IP address {} is not reachable.", ip).into())
                 }
-            } else {
-                Err("Invalid syntax. Usage: ping <ip>".into())
             }
         },
     });
-    
+
+    commands.insert("replay", Command {
+        name: "replay",
+        description: "Re-emit a session recorded with 'terminal monitor record' at its original pace",
+        args: vec![ArgSpec::required("file")],
+        help: "Re-emit a session recorded with 'terminal monitor record' at its original pace",
+        usage: Some("replay <file>"),
+        modes: &[Mode::UserMode, Mode::PrivilegedMode],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: Some(vec!["<file>   - Path to an asciicast v2 recording"]),
+        aliases: vec![],
+        execute: |args, _, _| {
+            let Some(path) = args.get(0) else {
+                return Err("Usage: replay <file>".into());
+            };
+            crate::session_recorder::replay_session(path)
+        },
+    });
+
     //Show commands
-    
+
     commands.insert(
         "show",
         Command {
             name: "show",
             description: "Display all the show commands when specific command is passed",
+            args: vec![],
+            help: "Display all the show commands when specific command is passed",
+            usage: None,
+            // `show commands` is also valid deeper in the hierarchy (e.g.
+            // InterfaceMode) so it can report what's reachable from there;
+            // every other subcommand still gates itself to User/Privileged
+            // EXEC mode below.
+            modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode],
             suggestions: Some(vec![
                 "running-config",
                 "startup-config",
                 "access-lists",
                 "ip",
+                "ip protocols",
+                "ipv6 interface brief",
+                "ipv6 route",
                 "version",
                 "ntp",
+                "ntp associations",
+                "ntp status",
+                "snmp",
                 "processes",
                 "clock",
+                "clock relative",
                 "vlan",
                 "interfaces",
+                "interfaces status",
                 "uptime",
+                "uptime detail",
                 "login",
                 "crypto key",
                 "crypto certificate",
                 "crypto dynamic-map",
                 "crypto map",
-                "crypto engine"
+                "crypto engine",
+                "archive",
+                "archive diff",
+                "archive config differences",
+                "config sources",
+                "commands"
             ]),
             suggestions1: Some(vec![
                 "running-config",
                 "startup-config",
                 "access-lists",
                 "ip",
+                "ip protocols",
+                "ipv6 interface brief",
+                "ipv6 route",
                 "version",
                 "ntp",
+                "ntp associations",
+                "ntp status",
+                "snmp",
                 "processes",
                 "clock",
+                "clock relative",
                 "vlan",
                 "interfaces",
+                "interfaces status",
                 "uptime",
+                "uptime detail",
                 "login",
                 "crypto key",
                 "crypto certificate",
                 "crypto dynamic-map",
                 "crypto map",
-                "crypto engine"
+                "crypto engine",
+                "archive",
+                "archive diff",
+                "archive config differences",
+                "config sources",
+                "commands"
             ]),
+            require_subcommand: true,
             options: None,
+            aliases: vec!["sh"],
             execute: |args, context, clock| {
+                if args.get(0) == Some(&"commands") {
+                    print_command_tree(context);
+                    return Ok(());
+                }
+
                 if matches!(context.current_mode, Mode::UserMode | Mode ::PrivilegedMode){
                     match args.get(0) {
                         Some(&"clock") => {
                             if let Some(clock) = clock {
-                                handle_show_clock(clock);
+                                if args.get(1) == Some(&"relative") {
+                                    handle_show_clock_relative(clock);
+                                } else {
+                                    handle_show_clock(clock);
+                                }
                                 Ok(())
                             } else {
                                 Err("Clock functionality is unavailable.".to_string())
@@ -1013,7 +2134,11 @@ Two styles of help are provided:
                         },
                         Some(&"uptime") => {
                             if let Some(clock) = clock {
-                                handle_show_uptime(clock);
+                                if args.get(1) == Some(&"detail") {
+                                    handle_show_uptime_detail(clock);
+                                } else {
+                                    handle_show_uptime(clock);
+                                }
                                 Ok(())
                             } else {
                                 Err("Clock functionality is unavailable.".to_string())
@@ -1035,26 +2160,69 @@ Two styles of help are provided:
                             Ok(())
                         },
                         Some(&"interfaces") => {
+                            if args.get(1) == Some(&"status") {
+                                let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
+                                let status_map = STATUS_MAP.lock().unwrap();
+                                let oper_state_map = OPER_STATE_MAP.lock().unwrap();
+
+                                println!(
+                                    "{:<22} {:<16} {:<10} {:<18} {}",
+                                    "Interface", "Type", "Admin", "Oper", "Notes"
+                                );
+                                for interface_name in ip_address_state.keys() {
+                                    let admin_state = status_map.get(interface_name).copied().unwrap_or(AdminState::Down);
+                                    let oper_state = oper_state_map.get(interface_name).copied().unwrap_or(OperState::NotPresent);
+                                    let interface_type = InterfaceType::classify(interface_name);
+                                    let note = if admin_state == AdminState::Up && oper_state != OperState::Up {
+                                        "admin up, oper not up"
+                                    } else {
+                                        ""
+                                    };
+                                    println!(
+                                        "{:<22} {:<16} {:<10} {:<18} {}",
+                                        interface_name, interface_type, admin_state, oper_state, note
+                                    );
+                                }
+                                return Ok(());
+                            }
+
                             let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
                             let Some(interface_name) = &context.selected_interface else {
                                 return Err("No interface selected. Use the 'interface' command first.".into());
                             };
-                    
+
                             if ip_address_state.is_empty() {
                                 println!("No interfaces found.");
                                 return Ok(());
                             } else {
+                                let link_config = LINK_CONFIG_STATE.lock().unwrap();
+                                let status_map = STATUS_MAP.lock().unwrap();
+                                let oper_state_map = OPER_STATE_MAP.lock().unwrap();
+                                let mut interface_counters = INTERFACE_COUNTERS.lock().unwrap();
                                 for (interface_name, (ip_address, _)) in ip_address_state.iter() {
-                                    println!("{} is up, line protocol is up", interface_name);
+                                    let admin_state = status_map.get(interface_name).copied().unwrap_or(AdminState::Down);
+                                    let oper_state = oper_state_map.get(interface_name).copied().unwrap_or(OperState::NotPresent);
+                                    let counters = interface_counters.entry(interface_name.clone()).or_insert_with(InterfaceCounters::default);
+                                    advance_interface_counters(counters, oper_state);
+
+                                    println!("{} is {}", interface_name, interface_status_line(admin_state, oper_state));
                                     println!("  Internet address is {}, subnet mask 255.255.255.0", ip_address);
                                     println!("  MTU 1500 bytes, BW 10000 Kbit, DLY 100000 usec");
-                                    println!("  Encapsulation ARPA, loopback not set, keepalive set (10 sec)");
+                                    if let Some(link) = link_config.get(interface_name) {
+                                        print!("  Encapsulation {}", link.encapsulation);
+                                        if let Some(auth) = link.ppp_authentication {
+                                            print!(", authentication {}", auth);
+                                        }
+                                        println!(", loopback not set, keepalive set (10 sec)");
+                                    } else {
+                                        println!("  Encapsulation ARPA, loopback not set, keepalive set (10 sec)");
+                                    }
                                     println!("  Last clearing of \"show interface\" counters: never");
                                     println!("  Input queue: 0/2000/0/0 (size/max/drops/flushes); Total output drops: 0");
                                     println!("  5 minute input rate 1000 bits/sec, 10 packets/sec");
                                     println!("  5 minute output rate 500 bits/sec, 5 packets/sec");
-                                    println!("  100 packets input, 1000 bytes, 10 no buffer");
-                                    println!("  50 packets output, 500 bytes, 0 underruns");
+                                    println!("  {} packets input, {} bytes, 0 no buffer", counters.input_packets, counters.input_bytes);
+                                    println!("  {} packets output, {} bytes, 0 underruns", counters.output_packets, counters.output_bytes);
                                 }
                             }
                     
@@ -1062,6 +2230,46 @@ Two styles of help are provided:
                         },
                         Some(&"ip") => {
                             match args.get(1) {
+                                Some(&"protocols") => {
+                                    let ospf_config = OSPF_CONFIG.lock().unwrap();
+                                    if let Some(process_id) = ospf_config.process_id {
+                                        println!("Routing Protocol is \"ospf {}\"", process_id);
+                                        println!("  Router ID: {}", ospf_config.router_id.clone().unwrap_or("Not set".to_string()));
+                                        println!("  Distance: {}", ospf_config.distance.unwrap_or(110));
+                                    }
+
+                                    let bgp_config = BGP_CONFIG.lock().unwrap();
+                                    if let Some(asn) = bgp_config.asn {
+                                        println!("Routing Protocol is \"bgp {}\"", asn);
+                                        println!("  Neighbors: {}", bgp_config.neighbors.len());
+                                        println!("  Networks: {}", bgp_config.networks.len());
+                                        let mut redistributing = Vec::new();
+                                        if bgp_config.redistribute_ospf { redistributing.push("ospf"); }
+                                        if bgp_config.redistribute_connected { redistributing.push("connected"); }
+                                        if bgp_config.redistribute_static { redistributing.push("static"); }
+                                        println!("  Redistributing: {}", if redistributing.is_empty() { "none".to_string() } else { redistributing.join(", ") });
+                                    }
+
+                                    let rip_config = RIP_CONFIG.lock().unwrap();
+                                    if rip_config.enabled {
+                                        println!("Routing Protocol is \"rip\"");
+                                        println!("  Sending updates every 30 seconds, version {}", rip_config.version);
+                                        println!("  Automatic network summarization is {}", if rip_config.auto_summary { "in effect" } else { "not in effect" });
+                                        println!("  Routing for Networks: {:?}", rip_config.networks);
+                                    }
+
+                                    let isis_config = ISIS_CONFIG.lock().unwrap();
+                                    if let Some(tag) = isis_config.tag.clone() {
+                                        println!("Routing Protocol is \"isis {}\"", tag);
+                                        println!("  NET: {}", isis_config.net.clone().unwrap_or("Not set".to_string()));
+                                        println!("  IS-IS Level: {}", isis_config.is_type);
+                                    }
+
+                                    if ospf_config.process_id.is_none() && bgp_config.asn.is_none() && !rip_config.enabled && isis_config.tag.is_none() {
+                                        println!("No routing protocols configured.");
+                                    }
+                                    Ok(())
+                                },
                                 Some(&"ospf") => {
                                     match args.get(2) {
                                         Some(&"neighbor") => {
@@ -1077,8 +2285,34 @@ Two styles of help are provided:
                                     }
                                 },
                                 Some(&"route") => {
-                                    let route_table = ROUTE_TABLE.lock().unwrap();
-            
+                                    let routing_table = ROUTING_TABLE.lock().unwrap();
+                                    let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
+                                    let status_map = STATUS_MAP.lock().unwrap();
+                                    let mut table = effective_routing_table(&routing_table, &ip_address_state, &status_map);
+                                    let ospf_config = OSPF_CONFIG.lock().unwrap();
+                                    install_ospf_routes(&mut table, &ospf_config);
+                                    drop(ospf_config);
+
+                                    if args.get(2) == Some(&"ospf") {
+                                        let mut entries: Vec<_> = table
+                                            .entries()
+                                            .into_iter()
+                                            .filter(|(_, _, route)| route.source == RouteSource::Ospf)
+                                            .collect();
+                                        if entries.is_empty() {
+                                            println!("No OSPF routes configured.");
+                                        } else {
+                                            entries.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+                                            for (network, prefix_len, route) in entries {
+                                                println!(
+                                                    "O\t{}/{} [{}/{}] via {}",
+                                                    network, prefix_len, route.source.distance(), route.metric, route.next_hop
+                                                );
+                                            }
+                                        }
+                                        return Ok(());
+                                    }
+
                                     if args.len() == 2 {
                                         println!("Codes: L - local, C - connected, S - static, R - RIP, M - mobile, B - BGP");
                                         println!("       D - EIGRP, EX - EIGRP external, O - OSPF, IA - OSPF inter area");
@@ -1088,43 +2322,44 @@ Two styles of help are provided:
                                         println!("       * - candidate default, U - per-user static route, o - ODR");
                                         println!("       P - periodic downloaded static route");
                                         println!();
-                        
-                                        if route_table.is_empty() {
+
+                                        let mut entries = table.entries();
+                                        if entries.is_empty() {
                                             println!("No routes configured.");
                                         } else {
-                                            for (destination, (netmask, next_hop_or_iface)) in route_table.iter() {
-                                                let route_type = if next_hop_or_iface.contains("exit_interface") {
-                                                    "C"
-                                                } else {
-                                                    "S"
-                                                };
-                        
+                                            entries.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+                                            for (network, prefix_len, route) in entries {
                                                 println!(
-                                                    "{}\t{} {} via {}",
-                                                    route_type, destination, netmask, next_hop_or_iface
+                                                    "{}\t{}/{} [{}/{}] via {}",
+                                                    route.source.code(), network, prefix_len, route.source.distance(), route.metric, route.next_hop
                                                 );
                                             }
                                         }
                                     } else if args.len() == 3 {
-                                        let destination_ip = args[2];
-                                        if let Some((netmask, next_hop_or_iface)) = route_table.get(destination_ip) {
-                                            let route_type = if next_hop_or_iface.contains("exit_interface") {
-                                                "connected"
-                                            } else {
-                                                "static"
+                                        let destination_ip: Ipv4Addr = match args[2].parse() {
+                                            Ok(ip) => ip,
+                                            Err(_) => return Err("Invalid IP address format.".into()),
+                                        };
+                                        if let Some((network, prefix_len, route)) = table.lookup(destination_ip) {
+                                            let netmask = prefix_to_netmask(prefix_len as u32);
+                                            let route_type = match route.source {
+                                                RouteSource::Connected => "connected",
+                                                RouteSource::Static => "static",
+                                                RouteSource::Ospf => "ospf",
+                                                RouteSource::Rip => "rip",
                                             };
-                        
-                                            println!("Routing entry for {}/{}", destination_ip, netmask);
-                                            println!("Known via \"{}\"", route_type);
+
+                                            println!("Routing entry for {}/{}", network, netmask);
+                                            println!("Known via \"{}\", distance {}, metric {}", route_type, route.source.distance(), route.metric);
                                             println!("  Routing Descriptor Blocks:");
-                                            println!("  * {}", next_hop_or_iface);
+                                            println!("  * {}", route.next_hop);
                                         } else {
-                                            println!("No route found for {}.", destination_ip);
+                                            println!("% Network not in table");
                                         }
                                     } else {
                                         println!("Invalid arguments. Use 'show ip route' or 'show ip route <ip-address>'.");
                                     }
-                        
+
                                     Ok(())
                                 },
                                 Some(&"interface") => {
@@ -1132,25 +2367,27 @@ Two styles of help are provided:
                                         Some(&"brief") => {
                                             let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
                                             let status_map = STATUS_MAP.lock().unwrap();
-                                
+                                            let oper_state_map = OPER_STATE_MAP.lock().unwrap();
+
                                             println!(
                                                 "{:<22} {:<15} {:<8} {:<20} {:<20} {:<10}",
                                                 "Interface", "IP-Address", "OK?", "Method", "Status", "Protocol"
                                             );
-                                
+
                                             for (interface_name, (ip_address, _)) in ip_address_state.iter() {
-                                                let is_up = status_map.get(interface_name).copied().unwrap_or(false);
-                                                let status = if is_up {
-                                                    "up"
-                                                } else {
+                                                let admin_state = status_map.get(interface_name).copied().unwrap_or(AdminState::Down);
+                                                let oper_state = oper_state_map.get(interface_name).copied().unwrap_or(OperState::NotPresent);
+                                                let status = if admin_state != AdminState::Up {
                                                     "administratively down"
-                                                };
-                                                let protocol = if is_up {
-                                                    "up"
                                                 } else {
-                                                    "down"
+                                                    match oper_state {
+                                                        OperState::Up => "up",
+                                                        OperState::Testing => "testing",
+                                                        _ => "down",
+                                                    }
                                                 };
-                                
+                                                let protocol = if oper_state == OperState::Up { "up" } else { "down" };
+
                                                 println!(
                                                     "{:<22} {:<15} YES     unset/manual        {}         {}",
                                                     interface_name, ip_address, status, protocol
@@ -1161,36 +2398,185 @@ Two styles of help are provided:
                                         _ => Err("Invalid interface subcommand. Use 'brief'".into())
                                     }
                                 },
-                                _ => Err("Invalid IP subcommand. Use 'ospf neighbor', 'route', or 'interface brief'".into())
+                                Some(&"dhcp") => {
+                                    if args.get(2) != Some(&"binding") {
+                                        return Err("Invalid dhcp subcommand. Use 'binding'".into());
+                                    }
+                                    if context.config.dhcp_pools.is_empty() {
+                                        println!("No DHCP pools configured.");
+                                        return Ok(());
+                                    }
+                                    println!(
+                                        "{:<17} {:<15} {:<30}",
+                                        "IP address", "Hardware address", "Lease expires"
+                                    );
+                                    let mut pool_names: Vec<&String> = context.config.dhcp_pools.keys().collect();
+                                    pool_names.sort();
+                                    for pool_name in pool_names {
+                                        let pool = &context.config.dhcp_pools[pool_name];
+                                        let bound_addresses: Vec<std::net::Ipv4Addr> = context.config.dhcp_bindings
+                                            .get(pool_name)
+                                            .map(|bindings| bindings.iter().map(|b| b.ip_address).collect())
+                                            .unwrap_or_default();
+                                        if let Some(address) = next_free_address(pool.network, &context.config.dhcp_excluded_addresses, &bound_addresses) {
+                                            let binding = DhcpBinding {
+                                                ip_address: address,
+                                                mac_address: pseudo_mac_for(&address),
+                                                lease_expires: format_lease_expiry(pool.lease),
+                                            };
+                                            println!("{:<17} {:<15} {:<30}", binding.ip_address, binding.mac_address, binding.lease_expires);
+                                            context.config.dhcp_bindings.entry(pool_name.clone()).or_insert_with(Vec::new).push(binding);
+                                        }
+                                    }
+                                    Ok(())
+                                },
+                                Some(&"nat") => {
+                                    if args.get(2) != Some(&"translations") {
+                                        return Err("Invalid nat subcommand. Use 'translations'".into());
+                                    }
+                                    let translations = NAT_TRANSLATIONS.lock().unwrap();
+                                    println!(
+                                        "{:<5} {:<21} {:<21} {:<21} {:<21}",
+                                        "Pro", "Inside global", "Inside local", "Outside local", "Outside global"
+                                    );
+                                    let format_endpoint = |(address, port): (Ipv4Addr, Option<u16>)| match port {
+                                        Some(port) => format!("{}:{}", address, port),
+                                        None => address.to_string(),
+                                    };
+                                    for translation in translations.iter() {
+                                        let inside_global = format_endpoint(translation.inside_global);
+                                        let inside_local = format_endpoint(translation.inside_local);
+                                        println!(
+                                            "{:<5} {:<21} {:<21} {:<21} {:<21}",
+                                            translation.protocol, inside_global, inside_local, inside_local, inside_global
+                                        );
+                                    }
+                                    if translations.is_empty() {
+                                        println!("(no translations)");
+                                    }
+                                    Ok(())
+                                },
+                                Some(&"bgp") => {
+                                    if args.get(2) != Some(&"summary") {
+                                        return Err("Invalid bgp subcommand. Use 'summary'".into());
+                                    }
+                                    let bgp_config = BGP_CONFIG.lock().unwrap();
+                                    let Some(asn) = bgp_config.asn else {
+                                        return Err("BGP is not running.".into());
+                                    };
+                                    println!("BGP router identifier 0.0.0.0, local AS number {}", asn);
+                                    println!("{} network entries, {} neighbor(s)", bgp_config.networks.len(), bgp_config.neighbors.len());
+                                    println!("{:<16} {:<4} {:<11} {}", "Neighbor", "AS", "State", "Description");
+                                    for (neighbor, remote_as) in bgp_config.neighbors.iter() {
+                                        let description = bgp_config.neighbor_descriptions.get(neighbor).map(String::as_str).unwrap_or("");
+                                        println!("{:<16} {:<4} {:<11} {}", neighbor, remote_as, "Active", description);
+                                    }
+                                    Ok(())
+                                },
+                                _ => Err("Invalid IP subcommand. Use 'ospf neighbor', 'route', 'interface brief', 'dhcp binding', 'nat translations', or 'bgp summary'".into())
+                            }
+                        },
+                        Some(&"ipv6") => {
+                            match (args.get(1), args.get(2)) {
+                                (Some(&"interface"), Some(&"brief")) => {
+                                    let link_config = LINK_CONFIG_STATE.lock().unwrap();
+
+                                    println!("{:<22} {:<45} {:<8}", "Interface", "IPv6-Address", "Status");
+
+                                    if link_config.values().all(|c| c.ipv6_addresses.is_empty() && !c.ipv6_enabled) {
+                                        println!("No IPv6 interfaces found.");
+                                        return Ok(());
+                                    }
+
+                                    for (interface_name, interface_config) in link_config.iter() {
+                                        if !interface_config.ipv6_enabled && interface_config.ipv6_addresses.is_empty() {
+                                            continue;
+                                        }
+                                        let status = if interface_config.ipv6_enabled { "up" } else { "administratively down" };
+                                        if interface_config.ipv6_addresses.is_empty() {
+                                            println!("{:<22} {:<45} {:<8}", interface_name, "unassigned", status);
+                                        } else {
+                                            for (address, prefix_length) in &interface_config.ipv6_addresses {
+                                                println!("{:<22} {:<45} {:<8}", interface_name, format!("{}/{}", address, prefix_length), status);
+                                            }
+                                        }
+                                    }
+                                    Ok(())
+                                },
+                                (Some(&"route"), None) => {
+                                    let link_config = LINK_CONFIG_STATE.lock().unwrap();
+                                    let ospfv3_config = OSPFV3_CONFIG.lock().unwrap();
+                                    let route_table_v6 = ROUTE_TABLE_V6.lock().unwrap();
+
+                                    println!("IPv6 Routing Table");
+                                    let mut any = false;
+                                    for (interface_name, interface_config) in link_config.iter() {
+                                        for (address, prefix_length) in &interface_config.ipv6_addresses {
+                                            any = true;
+                                            println!("C   {}/{} [0/0] via ::, {}", address, prefix_length, interface_name);
+                                        }
+                                        if let Some(area) = interface_config.ospfv3_area {
+                                            if let Some(process_id) = ospfv3_config.process_id {
+                                                any = true;
+                                                println!("O   {} area {} process {} via {}", "::/0", area, process_id, interface_name);
+                                            }
+                                        }
+                                    }
+                                    for ((address, prefix_length), route) in route_table_v6.iter() {
+                                        any = true;
+                                        println!("S   {}/{} [1/0] via {}", address, prefix_length, route.next_hop);
+                                    }
+                                    if !any {
+                                        println!("No IPv6 routes configured.");
+                                    }
+                                    Ok(())
+                                },
+                                _ => Err("Invalid IPv6 subcommand. Use 'interface brief' or 'route'".into())
                             }
                         },
                         Some(&"vlan") => {
+                            let switchport_state = SWITCHPORT_STATE.lock().unwrap();
+
+                            // Renders one VLAN row, wrapping its member-port
+                            // list onto blank-columned continuation lines
+                            // once it overflows a single row, the way a real
+                            // `show vlan` does for a heavily-trunked VLAN.
+                            let print_vlan_row = |vlan_id: u16, vlan_name: &str, status: &str| {
+                                let members = vlan_members(vlan_id, &switchport_state);
+                                if members.is_empty() {
+                                    println!("{:<6} {:<30} {:<10} {}", vlan_id, vlan_name, status, "");
+                                    return;
+                                }
+                                for (index, chunk) in members.chunks(4).enumerate() {
+                                    if index == 0 {
+                                        println!("{:<6} {:<30} {:<10} {}", vlan_id, vlan_name, status, chunk.join(", "));
+                                    } else {
+                                        println!("{:<6} {:<30} {:<10} {}", "", "", "", chunk.join(", "));
+                                    }
+                                }
+                            };
+
                             if let (Some(vlan_names), Some(vlan_states)) = (&context.vlan_names, &context.vlan_states) {
                                 // Display table header for VLANs
                                 println!("{:<6} {:<30} {:<10} {}", "VLAN", "Name", "Status", "Ports");
-        
+
                                 for (vlan_id_str, vlan_name) in vlan_names {
-                                    let vlan_id: u16 = vlan_id_str.parse().unwrap_or_default(); 
+                                    let vlan_id: u16 = vlan_id_str.parse().unwrap_or_default();
                                     let unknown_status = "active".to_string();
-                                    let status = vlan_states.get(&vlan_id).unwrap_or(&unknown_status); 
-                                    let ports = " ";  // temporary
-            
-                                    println!("{:<6} {:<30} {:<10} {}", vlan_id, vlan_name, status, ports);
+                                    let status = vlan_states.get(&vlan_id).unwrap_or(&unknown_status);
+                                    print_vlan_row(vlan_id, vlan_name, status);
                                 }
-            
+
                                 Ok(())
                             } else if let Some(vlan_names) = &context.vlan_names {
                                 println!("{:<6} {:<30} {:<10} {}", "VLAN", "Name", "Status", "Ports");
-        
+
                                 for vlan_id_str in vlan_names.keys() {
                                     let vlan_id: u16 = vlan_id_str.parse().unwrap_or_default();
                                     let vlan_name = format!("VLAN{}", vlan_id);
-                                    let status = "active"; 
-                                    let ports = " "; // temporary
-            
-                                    println!("{:<6} {:<30} {:<10} {}", vlan_id, vlan_name, status, ports);
+                                    print_vlan_row(vlan_id, &vlan_name, "active");
                                 }
-        
+
                                 Ok(())
                             } else {
                                 Err("No VLAN information available.".into())
@@ -1223,6 +2609,51 @@ Two styles of help are provided:
                             }
                             Ok(())
                         },
+                        Some(&"archive") => {
+                            match args.get(1) {
+                                Some(&"diff") => {
+                                    let rev_a = args.get(2).and_then(|s| s.parse::<usize>().ok());
+                                    let rev_b = args.get(3).and_then(|s| s.parse::<usize>().ok());
+                                    match (rev_a, rev_b) {
+                                        (Some(rev_a), Some(rev_b)) => {
+                                            let diff = diff_revisions(rev_a, rev_b)?;
+                                            print!("{}", diff);
+                                            Ok(())
+                                        }
+                                        _ => Err("Usage: show archive diff <rev-a> <rev-b>".into())
+                                    }
+                                },
+                                Some(&"config") => {
+                                    match args.get(2) {
+                                        Some(&"differences") => {
+                                            print!("{}", diff_running_vs_startup(context));
+                                            Ok(())
+                                        }
+                                        _ => Err("Usage: show archive config differences".into())
+                                    }
+                                },
+                                None => {
+                                    let archive = list_archive();
+                                    if archive.is_empty() {
+                                        println!("No configuration archive entries. Use 'write memory' to create one.");
+                                    } else {
+                                        println!("Configuration archive:");
+                                        for (revision, timestamp) in archive {
+                                            println!("  rev {}  {}", revision, timestamp);
+                                        }
+                                    }
+                                    Ok(())
+                                },
+                                _ => Err("Invalid archive subcommand. Use 'diff <rev-a> <rev-b>', 'config differences', or no subcommand".into())
+                            }
+                        },
+                        Some(&"config") => {
+                            if args.get(1) != Some(&"sources") {
+                                return Err("Usage: show config sources".into());
+                            }
+                            print_config_sources(context);
+                            Ok(())
+                        },
                         Some(&"login") => {
                             println!("A default login delay of 1 seconds is applied.");
                             println!("No Quiet-Mode access list has been configured.");
@@ -1232,32 +2663,63 @@ Two styles of help are provided:
                         },
                         
                         Some(&"ntp") => {
+                            // Each display simulates one more poll tick, so
+                            // `reach` climbs towards 377 octal over repeated
+                            // `show ntp` calls instead of appearing fully
+                            // synchronized immediately.
+                            for assoc in context.ntp_associations.iter_mut() {
+                                if assoc.address == "127.127.1.1" {
+                                    advance_ntp_poll(assoc, 0, true);
+                                } else {
+                                    advance_ntp_poll(assoc, 1, false);
+                                }
+                                verify_ntp_association(assoc, &context.ntp_authentication_keys, &context.ntp_trusted_keys);
+                            }
                             match args.get(1) {
                                 Some(&"associations") => {
                                     if context.ntp_associations.is_empty() {
                                         println!("No NTP associations configured.");
                                     } else {
-                                        println!("address         ref clock       st   when     poll    reach  delay          offset            disp");
+                                        println!("address         ref clock       st   when     poll    reach  delay          offset            disp          auth");
                                         for assoc in &context.ntp_associations {
-                                            println!(" ~{}       {}          {}   {}        {}      {}      {:.2}           {:.2}              {:.2}",
+                                            let auth_status = match assoc.key_id {
+                                                Some(_) if assoc.authenticated => "authenticated",
+                                                Some(_) => "unauthenticated",
+                                                None => "none",
+                                            };
+                                            println!(" ~{}       {}          {}   {}        {}      {}      {:.2}           {:.2}              {:.2}      {}",
                                                 assoc.address, assoc.ref_clock, assoc.st, assoc.when, assoc.poll,
-                                                assoc.reach, assoc.delay, assoc.offset, assoc.disp);
+                                                assoc.reach, assoc.delay, assoc.offset, assoc.disp, auth_status);
                                         }
                                         println!(" * sys.peer, # selected, + candidate, - outlyer, x falseticker, ~ configured");
                                     }
                                     Ok(())
                                 },
+                                Some(&"status") => {
+                                    let synced = context.ntp_associations.iter().find(|assoc| assoc.reach != 0);
+                                    match synced {
+                                        Some(assoc) => {
+                                            println!("Clock is synchronized, stratum {}, reference is {}", assoc.st, assoc.ref_clock);
+                                            println!("nominal freq is 250.0000 Hz, actual freq is 250.0000 Hz, precision is 2**24");
+                                            println!("reference time is {} (reach {:o}, poll {})", assoc.address, assoc.reach, assoc.poll);
+                                            println!("clock offset is {:.2} msec, root delay is {:.2} msec", assoc.offset, assoc.delay);
+                                            println!("root dispersion is {:.2} msec", assoc.disp);
+                                        },
+                                        None => println!("Clock is unsynchronized, no reachable NTP associations."),
+                                    }
+                                    Ok(())
+                                },
                                 None => {
                                     println!("NTP Master: {}", if context.ntp_master { "Enabled" } else { "Disabled" });
                                     println!("NTP Authentication: {}", if context.ntp_authentication_enabled { "Enabled" } else { "Disabled" });
-                                    
+
                                     if !context.ntp_authentication_keys.is_empty() {
                                         println!("NTP Authentication Keys:");
                                         for (key_number, key) in &context.ntp_authentication_keys {
-                                            println!("Key {}: {}", key_number, key);
+                                            println!("Key {} ({}): {}", key_number, key.algorithm.as_str().to_uppercase(), key.key);
                                         }
                                     }
-                                    
+
                                     if !context.ntp_trusted_keys.is_empty() {
                                         println!("NTP Trusted Keys:");
                                         for key_number in &context.ntp_trusted_keys {
@@ -1266,10 +2728,44 @@ Two styles of help are provided:
                                     }
                                     Ok(())
                                 },
-                                _ => Err("Invalid NTP subcommand. Use 'associations' or no subcommand".into())
+                                _ => Err("Invalid NTP subcommand. Use 'associations', 'status', or no subcommand".into())
                             }
                         },
-                        
+
+                        Some(&"snmp") => {
+                            let mut stats = SNMP_STATS.lock().unwrap();
+                            advance_snmp_stats(&mut stats);
+                            println!("Chassis: {}", context.config.hostname);
+                            println!("{} SNMP packets input", stats.packets_in);
+                            println!("{} SNMP packets output", stats.packets_out);
+                            println!("{} Get-request PDUs", stats.get_requests);
+                            println!("{} Get-next PDUs", stats.get_nexts);
+                            println!("{} Bad community name errors", stats.bad_community_errors);
+                            println!();
+                            if context.config.snmp_communities.is_empty() {
+                                println!("No SNMP communities configured.");
+                            } else {
+                                println!("Community name                access");
+                                for (community, access) in &context.config.snmp_communities {
+                                    println!("{:<30}  {}", community, access);
+                                }
+                            }
+                            if let Some(location) = &context.config.snmp_location {
+                                println!("Location: {}", location);
+                            }
+                            if let Some(contact) = &context.config.snmp_contact {
+                                println!("Contact: {}", contact);
+                            }
+                            println!("SNMP traps: {}", if context.config.snmp_traps_enabled { "enabled" } else { "disabled" });
+                            if !context.config.snmp_hosts.is_empty() {
+                                println!("\nNotification host(s):");
+                                for host in &context.config.snmp_hosts {
+                                    println!("    {}  version {}  community {}", host.address, host.version, host.community);
+                                }
+                            }
+                            Ok(())
+                        },
+
                         Some(&"access-lists") => {
                             let acl_store = ACL_STORE.lock().unwrap();
                             if acl_store.is_empty() {
@@ -1372,43 +2868,52 @@ Two styles of help are provided:
                             }
                             match args[1] {
                                 "key" => {
-                                    if context.config.crypto_keys.is_empty() {
+                                    let key_names = context.key_store.list();
+                                    if key_names.is_empty() {
                                         println!("No crypto keys found.");
                                         Ok(())
                                     } else {
                                         println!("Crypto keys:");
                                         println!("------------");
-                                        for (key_name, key_data) in &context.config.crypto_keys {
+                                        for key_name in &key_names {
+                                            let key = context.key_store.get(key_name).expect("listed key must exist");
                                             println!("Key: {}", key_name);
-                                            // Show key type by parsing the key data
-                                            if key_data.contains("BEGIN RSA") {
-                                                println!("Type: RSA");
-                                            } else if key_data.contains("BEGIN DSA") {
-                                                println!("Type: DSA");
-                                            }
+                                            println!("Type: {}", key.algorithm);
+                                            println!("Modulus Size: {} bits", key.bits);
+                                            println!("Fingerprint: {}", key.fingerprint);
+                                            println!("Created: {}", key.created);
                                             println!("Usage: General Purpose");
+                                            println!("Exportable: {}", if key.exportable { "yes" } else { "no" });
                                             println!("------------");
                                         }
                                         Ok(())
                                     }
                                 },
                                 "certificate" => {
-                                    if context.config.certificates.is_empty() {
+                                    let cert_names = context.cert_store.list();
+                                    if cert_names.is_empty() {
                                         println!("No certificates found.");
                                         Ok(())
                                     } else {
                                         println!("Certificates:");
                                         println!("-------------");
-                                        for (cert_name, cert_data) in &context.config.certificates {
+                                        for cert_name in &cert_names {
+                                            let cert_data = context.cert_store.get(cert_name).expect("listed certificate must exist");
                                             println!("Certificate: {}", cert_name);
-                                            // Parse and display certificate details
-                                            if let Some(subject) = extract_subject_from_cert(cert_data) {
-                                                println!("Subject: {}", subject);
-                                            }
-                                            if let Some(issuer) = extract_issuer_from_cert(cert_data) {
-                                                println!("Issuer: {}", issuer);
+                                            match inspect_certificate(cert_data) {
+                                                Ok(info) => {
+                                                    println!("Subject: {}", info.subject);
+                                                    println!("Issuer: {}", info.issuer);
+                                                    println!("Serial Number: {}", info.serial);
+                                                    println!("Validity Date:");
+                                                    println!("    Not Before: {}", info.not_before);
+                                                    println!("    Not After: {}", info.not_after);
+                                                    println!("Status: {}", info.status);
+                                                }
+                                                Err(err) => {
+                                                    println!("Error: {}", err);
+                                                }
                                             }
-                                            println!("Status: Active");
                                             println!("-------------");
                                         }
                                         Ok(())
@@ -1423,14 +2928,47 @@ Two styles of help are provided:
                                 },
                                 "map" => {
                                     println!("Crypto map entries:");
-                                    for (name, entry) in &context.config.crypto_maps {
+                                    for (_, entry) in &context.config.crypto_maps {
                                         println!("Crypto map '{}' sequence {}", entry.name, entry.seq_num);
+                                        if let Some(map_type) = &entry.map_type {
+                                            println!("  Type: {}", map_type);
+                                        }
                                         if let Some(interface_id) = &entry.interface_id {
                                             println!("  Interface: {}", interface_id);
                                         }
                                         if let Some(local_addr) = context.config.crypto_local_addresses.get(&entry.name) {
                                             println!("  Local address: {}", local_addr);
                                         }
+                                        if let Some(peer) = &entry.peer {
+                                            println!("  Peer: {}", peer);
+                                        }
+                                        if let Some(transform_set) = &entry.transform_set {
+                                            println!("  Transform set: {}", transform_set);
+                                        }
+                                        if let Some(match_acl) = &entry.match_acl {
+                                            println!("  Extended IP access list {}", match_acl);
+                                        }
+                                    }
+                                    Ok(())
+                                },
+                                "isakmp" => {
+                                    if args.get(2) != Some(&"policy") {
+                                        return Err("Usage: show crypto isakmp policy".to_string());
+                                    }
+                                    if context.config.isakmp_policies.is_empty() {
+                                        println!("No ISAKMP policies configured.");
+                                        return Ok(());
+                                    }
+                                    let mut priorities: Vec<&u32> = context.config.isakmp_policies.keys().collect();
+                                    priorities.sort();
+                                    for priority in priorities {
+                                        let policy = &context.config.isakmp_policies[priority];
+                                        println!("Protection suite of priority {}", priority);
+                                        println!("        encryption algorithm:   {}", policy.encryption.as_deref().unwrap_or("DES (default)"));
+                                        println!("        hash algorithm:         {}", policy.hash.as_deref().unwrap_or("Secure Hash Standard"));
+                                        println!("        authentication method:  {}", policy.authentication.as_deref().unwrap_or("Rivest-Shamir-Adleman Signature"));
+                                        println!("        Diffie-Hellman group:   {}", policy.group.map_or("#1 (768 bit)".to_string(), |g| g.to_string()));
+                                        println!("        lifetime:               {} seconds", policy.lifetime.unwrap_or(86400));
                                     }
                                     Ok(())
                                 },
@@ -1443,6 +2981,25 @@ Two styles of help are provided:
                                     }
                                     Ok(())
                                 },
+                                "ipsec" => {
+                                    if args.get(2) != Some(&"sa") {
+                                        return Err("Usage: show crypto ipsec sa".to_string());
+                                    }
+                                    match crate::ipsec_sim::active_sa() {
+                                        Some(sa) => {
+                                            println!("interface: {}", sa.map_name);
+                                            println!("    local  ident: {}", sa.local_address);
+                                            println!("    remote ident: {}", sa.peer);
+                                            println!("    current_peer: {}", sa.peer);
+                                            println!("    PERMIT, state: {}", sa.state);
+                                            println!("    transform: {}, {}", sa.cipher, sa.auth);
+                                            println!("    key fingerprint: {}", sa.key_fingerprint);
+                                            println!("    sa timing: remaining key lifetime (sec): {}", sa.rekey_seconds);
+                                        }
+                                        None => println!("No IPsec security associations negotiated."),
+                                    }
+                                    Ok(())
+                                },
                                 _ => Err("Invalid crypto show command. Use 'show crypto key' or 'show crypto certificate'.".to_string())
                             }
                         },
@@ -1466,25 +3023,47 @@ Two styles of help are provided:
         Command {
             name: "ip",
             description: "Define all the ip commands",
+            args: vec![],
+            help: "Define all the ip commands",
+            usage: None,
+            modes: &[Mode::ConfigMode, Mode::InterfaceMode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new())],
             suggestions: Some(vec![
                 // InterfaceMode commands
                 "address",
                 "ospf",
+                "nat inside",
+                "nat outside",
                 // ConfigMode commands
                 "route",
                 "domain-name",
-                "access-list"
+                "access-list",
+                "local",
+                "dhcp",
+                "dhcp pool",
+                "dhcp excluded-address",
+                "nat inside source static",
+                "nat inside source list"
             ]),
             suggestions1: Some(vec![
                 // InterfaceMode commands
                 "address",
                 "ospf",
+                "nat inside",
+                "nat outside",
                 // ConfigMode commands
                 "route",
                 "domain-name",
-                "access-list"
+                "access-list",
+                "local",
+                "dhcp",
+                "dhcp pool",
+                "dhcp excluded-address",
+                "nat inside source static",
+                "nat inside source list"
             ]),
+            require_subcommand: true,
             options: None,
+            aliases: vec![],
             execute: |args, context, _| {
                 if args.is_empty() {
                     return Err("Incomplete command. Use 'ip ?' for help.".into());
@@ -1525,6 +3104,21 @@ Two styles of help are provided:
                                     ip_address, netmask, interface
                                 );
                             }
+
+                            crate::hooks::run_hook(
+                                &context.config.hook_scripts,
+                                "interface-address-changed",
+                                &[
+                                    ("INTERFACE", interface.clone()),
+                                    ("IP_ADDRESS", ip_address.to_string()),
+                                    ("NETMASK", netmask.to_string()),
+                                ],
+                            );
+
+                            if let Err(err) = crate::host_backend::ACTIVE_BACKEND.lock().unwrap().add_address(interface, ip_address, netmask) {
+                                println!("Warning: kernel-apply failed to add the address: {}", err);
+                            }
+
                             Ok(())
                         } else {
                             Err("No interface selected. Use the 'interface' command first.".into())
@@ -1552,13 +3146,12 @@ Two styles of help are provided:
                                 }
                                 "retransmit-interval" => {
                                     if args.len() == 3 {
-                                        let interval = args[2].parse::<u32>();
-                                        match interval {
+                                        match crate::clock_settings::parse_duration_seconds(args[2]) {
                                             Ok(seconds) => {
                                                 println!("OSPF retransmit interval set to {} seconds.", seconds);
                                                 Ok(())
                                             }
-                                            _ => Err("Invalid retransmit interval. It must be a positive integer.".into()),
+                                            Err(err) => Err(format!("Invalid retransmit interval: {}", err)),
                                         }
                                     } else {
                                         Err("Usage: ip ospf retransmit-interval <seconds>".into())
@@ -1566,13 +3159,12 @@ Two styles of help are provided:
                                 }
                                 "transmit-delay" => {
                                     if args.len() == 3 {
-                                        let delay = args[2].parse::<u32>();
-                                        match delay {
+                                        match crate::clock_settings::parse_duration_seconds(args[2]) {
                                             Ok(seconds) => {
                                                 println!("OSPF transmit delay set to {} seconds.", seconds);
                                                 Ok(())
                                             }
-                                            _ => Err("Invalid transmit delay. It must be a positive integer.".into()),
+                                            Err(err) => Err(format!("Invalid transmit delay: {}", err)),
                                         }
                                     } else {
                                         Err("Usage: ip ospf transmit-delay <seconds>".into())
@@ -1594,13 +3186,12 @@ Two styles of help are provided:
                                 }
                                 "hello-interval" => {
                                     if args.len() == 3 {
-                                        let interval = args[2].parse::<u32>();
-                                        match interval {
+                                        match crate::clock_settings::parse_duration_seconds(args[2]) {
                                             Ok(seconds) => {
                                                 println!("OSPF hello interval set to {} seconds.", seconds);
                                                 Ok(())
                                             }
-                                            _ => Err("Invalid hello interval. It must be a positive integer.".into()),
+                                            Err(err) => Err(format!("Invalid hello interval: {}", err)),
                                         }
                                     } else {
                                         Err("Usage: ip ospf hello-interval <seconds>".into())
@@ -1608,13 +3199,12 @@ Two styles of help are provided:
                                 }
                                 "dead-interval" => {
                                     if args.len() == 3 {
-                                        let interval = args[2].parse::<u32>();
-                                        match interval {
+                                        match crate::clock_settings::parse_duration_seconds(args[2]) {
                                             Ok(seconds) => {
                                                 println!("OSPF dead interval set to {} seconds.", seconds);
                                                 Ok(())
                                             }
-                                            _ => Err("Invalid dead interval. It must be a positive integer.".into()),
+                                            Err(err) => Err(format!("Invalid dead interval: {}", err)),
                                         }
                                     } else {
                                         Err("Usage: ip ospf dead-interval <seconds>".into())
@@ -1663,45 +3253,87 @@ Two styles of help are provided:
                         }
                     },
                     ("route", Mode::ConfigMode) => {
-                        let mut route_table = ROUTE_TABLE.lock().unwrap();
+                        let mut routing_table = ROUTING_TABLE.lock().unwrap();
                         if args.len() == 1 {
                             println!("Invalid command. The correct command is 'ip route <destination_ip> <netmask> <next_hop>");
-                        } 
-                        
+                        }
+
                         else if args.len() == 4 {
                             let destination_ip: Ipv4Addr = Ipv4Addr::from_str(&args[1]).expect("Invalid IP address format");
                             let netmask: Ipv4Addr = Ipv4Addr::from_str(&args[2]).expect("Invalid IP address format");
-                            
+                            if !is_contiguous_netmask(netmask) {
+                                return Err(format!("Invalid netmask '{}': bits must be a contiguous run of leading ones.", netmask));
+                            }
+                            let prefix_len = netmask_to_prefix(netmask) as u8;
+
                             if let Ok(next_hop) = Ipv4Addr::from_str(&args[3]) {
                                 // Scenario 1: ip route <destination-ip> <netmask> <next-hop>
-                                route_table.insert(destination_ip.to_string(), (netmask, next_hop.to_string()));
+                                routing_table.insert(destination_ip, prefix_len, Route { next_hop: next_hop.to_string(), source: RouteSource::Static, metric: 1, distance_override: None, tag: None });
                                 println!("Added route: ip route {} {} {}", destination_ip, netmask, next_hop);
+
+                                crate::hooks::run_hook(
+                                    &context.config.hook_scripts,
+                                    "route-added",
+                                    &[
+                                        ("DESTINATION", destination_ip.to_string()),
+                                        ("NETMASK", netmask.to_string()),
+                                        ("NEXT_HOP", next_hop.to_string()),
+                                    ],
+                                );
+
+                                if let Err(err) = crate::host_backend::ACTIVE_BACKEND.lock().unwrap().add_route(destination_ip, prefix_len, next_hop) {
+                                    println!("Warning: kernel-apply failed to add the route: {}", err);
+                                }
                             }
                             else {
                                 // Scenario 2: ip route <destination-ip> <netmask> <exit interface>
                                 let exit_interface: String = args[3].to_string();
                                 println!("Added route: ip route {} {} {}", destination_ip, netmask, exit_interface);
-                                route_table.insert(destination_ip.to_string(), (netmask, exit_interface));
-                            }   
-                        } 
-                        
+                                routing_table.insert(destination_ip, prefix_len, Route { next_hop: exit_interface.clone(), source: RouteSource::Static, metric: 1, distance_override: None, tag: None });
+
+                                crate::hooks::run_hook(
+                                    &context.config.hook_scripts,
+                                    "route-added",
+                                    &[
+                                        ("DESTINATION", destination_ip.to_string()),
+                                        ("NETMASK", netmask.to_string()),
+                                        ("NEXT_HOP", exit_interface),
+                                    ],
+                                );
+                            }
+                        }
+
                         else if args.len() == 5 {
                             // Scenario 3: ip route <destination-ip> <netmask> <exit interface> <next-hop>
                             let destination_ip: Ipv4Addr = Ipv4Addr::from_str(&args[1]).expect("Invalid IP address format");
                             let netmask: Ipv4Addr = Ipv4Addr::from_str(&args[2]).expect("Invalid IP address format");
-                            let exit_interface: String = args[2].to_string();
+                            if !is_contiguous_netmask(netmask) {
+                                return Err(format!("Invalid netmask '{}': bits must be a contiguous run of leading ones.", netmask));
+                            }
+                            let prefix_len = netmask_to_prefix(netmask) as u8;
+                            let exit_interface: String = args[3].to_string();
                             let next_hop: Ipv4Addr = Ipv4Addr::from_str(&args[4]).expect("Invalid IP address format");
-            
+
                             // Insert the route in the route table with exit interface and next hop
-                            route_table.insert(destination_ip.to_string(), (netmask, format!("{} {}", exit_interface, next_hop)));
+                            routing_table.insert(destination_ip, prefix_len, Route { next_hop: format!("{} {}", exit_interface, next_hop), source: RouteSource::Static, metric: 1, distance_override: None, tag: None });
                             println!("Added route: ip route {} {} {} {}", destination_ip, netmask, exit_interface, next_hop);
-                        } 
-                        
+
+                            crate::hooks::run_hook(
+                                &context.config.hook_scripts,
+                                "route-added",
+                                &[
+                                    ("DESTINATION", destination_ip.to_string()),
+                                    ("NETMASK", netmask.to_string()),
+                                    ("NEXT_HOP", format!("{} {}", exit_interface, next_hop)),
+                                ],
+                            );
+                        }
+
                         else {
                             println!("Invalid arguments provided to 'ip route'. Expected: ip route <ip-address> <netmask> <next-hop | exit-interface> <next-hop>.");
                             return Err("Usage: ip route <ip-address> <netmask> <next-hop | exit-interface> <next-hop>".into());
                         }
-            
+
                         Ok(())
                     },
                     ("domain-name", Mode::ConfigMode) => {
@@ -1749,7 +3381,259 @@ Two styles of help are provided:
                             Err("Usage: ip access-list standard|extended <acl_name|number>".into())
                         }
                     },
-    
+                    ("dhcp", Mode::ConfigMode) => {
+                        if args.len() < 2 {
+                            return Err("Usage: ip dhcp pool <name> | ip dhcp excluded-address <start> [<end>]".into());
+                        }
+                        match args[1] {
+                            "pool" => {
+                                if args.len() != 3 {
+                                    return Err("Usage: ip dhcp pool <name>".into());
+                                }
+                                let pool_name = args[2].to_string();
+                                context.config.dhcp_pools.entry(pool_name.clone()).or_insert_with(DhcpPool::default);
+                                context.current_mode = Mode::DhcpPoolMode(pool_name.clone());
+                                context.prompt = format!("{}(dhcp-config)#", context.config.hostname);
+                                println!("Entering DHCP pool configuration mode for '{}'.", pool_name);
+                                Ok(())
+                            }
+                            "excluded-address" => {
+                                if args.len() != 3 && args.len() != 4 {
+                                    return Err("Usage: ip dhcp excluded-address <start-ip> [<end-ip>]".into());
+                                }
+                                let start: Ipv4Addr = args[2]
+                                    .parse()
+                                    .map_err(|_| "Invalid start IP address format.".to_string())?;
+                                let end: Ipv4Addr = if args.len() == 4 {
+                                    args[3].parse().map_err(|_| "Invalid end IP address format.".to_string())?
+                                } else {
+                                    start
+                                };
+                                context.config.dhcp_excluded_addresses.push((start, end));
+                                println!("Excluded DHCP address range {} - {}.", start, end);
+                                Ok(())
+                            }
+                            other => Err(format!("Invalid dhcp subcommand: '{}'. Use 'pool' or 'excluded-address'.", other)),
+                        }
+                    },
+                    ("local", Mode::ConfigMode) => {
+                        if args.len() != 5 || args[1] != "pool" {
+                            return Err("Usage: ip local pool <name> <start-ip> <end-ip>".into());
+                        }
+                        let pool_name = args[2].to_string();
+                        let start: Ipv4Addr = args[3]
+                            .parse()
+                            .map_err(|_| "Invalid start IP address format.".to_string())?;
+                        let end: Ipv4Addr = args[4]
+                            .parse()
+                            .map_err(|_| "Invalid end IP address format.".to_string())?;
+                        context.config.local_pools.insert(
+                            pool_name.clone(),
+                            AddressPool { start: start.to_string(), end: end.to_string() },
+                        );
+                        println!("Local address pool '{}' configured: {} - {}", pool_name, start, end);
+                        Ok(())
+                    },
+                    ("nat", Mode::InterfaceMode) => {
+                        let interface = context.selected_interface.clone()
+                            .ok_or_else(|| "No interface selected. Use the 'interface' command first.".to_string())?;
+                        match args.get(1) {
+                            Some(&"inside") => {
+                                NAT_INTERFACE_ROLE.lock().unwrap().insert(interface.clone(), NatSide::Inside);
+                                println!("Interface {} designated as NAT inside.", interface);
+                                Ok(())
+                            }
+                            Some(&"outside") => {
+                                NAT_INTERFACE_ROLE.lock().unwrap().insert(interface.clone(), NatSide::Outside);
+                                println!("Interface {} designated as NAT outside.", interface);
+                                Ok(())
+                            }
+                            _ => Err("Usage: ip nat inside | ip nat outside".into()),
+                        }
+                    },
+                    ("nat", Mode::ConfigMode) => {
+                        if args.get(1) != Some(&"inside") || args.get(2) != Some(&"source") {
+                            return Err("Usage: ip nat inside source static <local> <global> | ip nat inside source list <acl> interface <interface> overload".into());
+                        }
+                        match args.get(3) {
+                            Some(&"static") => {
+                                if args.len() != 6 {
+                                    return Err("Usage: ip nat inside source static <local> <global>".into());
+                                }
+                                let local: Ipv4Addr = args[4].parse().map_err(|_| "Invalid local IP address format.".to_string())?;
+                                let global: Ipv4Addr = args[5].parse().map_err(|_| "Invalid global IP address format.".to_string())?;
+                                NAT_STATIC_MAPPINGS.lock().unwrap().push(NatStaticMapping { local, global });
+                                rebuild_nat_translations();
+                                println!("Static NAT mapping added: {} -> {}", local, global);
+                                Ok(())
+                            }
+                            Some(&"list") => {
+                                if args.len() != 8 || args[5] != "interface" || args[7] != "overload" {
+                                    return Err("Usage: ip nat inside source list <acl> interface <interface> overload".into());
+                                }
+                                let acl_name = args[4].to_string();
+                                let interface = args[6].to_string();
+                                NAT_OVERLOAD_RULES.lock().unwrap().push(NatOverloadRule { acl: acl_name.clone(), interface: interface.clone() });
+                                rebuild_nat_translations();
+                                println!("PAT overload configured: ACL {} via interface {}", acl_name, interface);
+                                Ok(())
+                            }
+                            _ => Err("Usage: ip nat inside source static <local> <global> | ip nat inside source list <acl> interface <interface> overload".into()),
+                        }
+                    },
+
+                    _ => Err("Command not available in current mode or invalid command".into())
+                }
+            },
+        }
+    );
+
+
+    commands.insert(
+        "ipv6",
+        Command {
+            name: "ipv6",
+            description: "Define all the ipv6 commands",
+            args: vec![],
+            help: "Define all the ipv6 commands",
+            usage: None,
+            modes: &[Mode::ConfigMode, Mode::InterfaceMode],
+            suggestions: Some(vec![
+                // InterfaceMode commands
+                "address",
+                "enable",
+                "ospf",
+                // ConfigMode commands
+                "unicast-routing",
+                "router",
+                "route",
+            ]),
+            suggestions1: Some(vec![
+                "address",
+                "enable",
+                "ospf",
+                "unicast-routing",
+                "router",
+                "route",
+            ]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, context, _| {
+                if args.is_empty() {
+                    return Err("Incomplete command. Use 'ipv6 ?' for help.".into());
+                }
+
+                match (args[0], &context.current_mode) {
+                    ("address", Mode::InterfaceMode) => {
+                        if args.len() != 2 {
+                            return Err("Usage: ipv6 address <address>/<prefix-length>".into());
+                        }
+                        let (addr, prefix) = args[1]
+                            .split_once('/')
+                            .ok_or("Usage: ipv6 address <address>/<prefix-length>")?;
+                        let address: Ipv6Addr = addr
+                            .parse()
+                            .map_err(|_| "Invalid IPv6 address format.".to_string())?;
+                        let prefix_length: u8 = prefix
+                            .parse()
+                            .map_err(|_| "Invalid prefix length.".to_string())?;
+
+                        let Some(interface) = &context.selected_interface else {
+                            return Err("No interface selected. Use the 'interface' command first.".into());
+                        };
+
+                        let mut link_config = LINK_CONFIG_STATE.lock().unwrap();
+                        let interface_config = link_config.entry(interface.clone()).or_insert_with(InterfaceConfig::default);
+                        interface_config.ipv6_addresses.push((address, prefix_length));
+                        interface_config.ipv6_enabled = true;
+                        println!("Assigned IPv6 address {}/{} to interface {}", address, prefix_length, interface);
+                        Ok(())
+                    },
+                    ("enable", Mode::InterfaceMode) => {
+                        let Some(interface) = &context.selected_interface else {
+                            return Err("No interface selected. Use the 'interface' command first.".into());
+                        };
+
+                        let mut link_config = LINK_CONFIG_STATE.lock().unwrap();
+                        let interface_config = link_config.entry(interface.clone()).or_insert_with(InterfaceConfig::default);
+                        interface_config.ipv6_enabled = true;
+                        println!("IPv6 processing enabled on interface {}", interface);
+                        Ok(())
+                    },
+                    ("ospf", Mode::InterfaceMode) => {
+                        if args.len() == 4 && args[2] == "area" {
+                            let process_id = args[1].parse::<u32>();
+                            let area_id = args[3].parse::<u32>();
+                            match (process_id, area_id) {
+                                (Ok(process_id), Ok(area_id)) => {
+                                    let Some(interface) = &context.selected_interface else {
+                                        return Err("No interface selected. Use the 'interface' command first.".into());
+                                    };
+
+                                    let ospfv3_config = OSPFV3_CONFIG.lock().unwrap();
+                                    if ospfv3_config.process_id != Some(process_id) {
+                                        return Err(format!("OSPFv3 process {} has not been configured. Use 'ipv6 router ospf {}' first.", process_id, process_id));
+                                    }
+                                    drop(ospfv3_config);
+
+                                    let mut link_config = LINK_CONFIG_STATE.lock().unwrap();
+                                    let interface_config = link_config.entry(interface.clone()).or_insert_with(InterfaceConfig::default);
+                                    interface_config.ospfv3_area = Some(area_id);
+                                    println!("Interface {} enrolled in OSPFv3 process {}, area {}.", interface, process_id, area_id);
+                                    Ok(())
+                                }
+                                _ => Err("Invalid process-id or area-id. Both must be positive integers.".into()),
+                            }
+                        } else {
+                            Err("Usage: ipv6 ospf <process-id> area <area-id>".into())
+                        }
+                    },
+                    ("unicast-routing", Mode::ConfigMode) => {
+                        context.config.ipv6_unicast_routing = true;
+                        println!("IPv6 unicast routing enabled.");
+                        Ok(())
+                    },
+                    ("route", Mode::ConfigMode) => {
+                        if args.len() != 3 {
+                            return Err("Usage: ipv6 route <ipv6-prefix>/<prefix-length> <next-hop>".into());
+                        }
+                        let (addr, prefix) = args[1]
+                            .split_once('/')
+                            .ok_or("Usage: ipv6 route <ipv6-prefix>/<prefix-length> <next-hop>")?;
+                        let destination: Ipv6Addr = addr
+                            .parse()
+                            .map_err(|_| "Invalid IPv6 address format.".to_string())?;
+                        let prefix_length: u8 = prefix
+                            .parse()
+                            .map_err(|_| "Invalid prefix length.".to_string())?;
+                        let next_hop = args[2].to_string();
+
+                        ROUTE_TABLE_V6.lock().unwrap().insert(
+                            (destination, prefix_length),
+                            Route { next_hop: next_hop.clone(), source: RouteSource::Static, metric: 1, distance_override: None, tag: None },
+                        );
+                        println!("Added route: ipv6 route {}/{} {}", destination, prefix_length, next_hop);
+                        Ok(())
+                    },
+                    ("router", Mode::ConfigMode) => {
+                        if args.len() == 3 && args[1] == "ospf" {
+                            let process_id = args[2].parse::<u32>();
+                            match process_id {
+                                Ok(id) if id > 0 => {
+                                    let mut ospfv3_config = OSPFV3_CONFIG.lock().unwrap();
+                                    ospfv3_config.process_id = Some(id);
+                                    context.current_mode = Mode::RouterOspfv3Mode;
+                                    context.prompt = format!("{}(config-rtr)#", context.config.hostname);
+                                    println!("OSPFv3 routing enabled with process ID {}.", id);
+                                    Ok(())
+                                }
+                                _ => Err("Invalid process ID provided. It must be a positive integer.".into()),
+                            }
+                        } else {
+                            Err("Usage: ipv6 router ospf <process-id>".into())
+                        }
+                    },
                     _ => Err("Command not available in current mode or invalid command".into())
                 }
             },
@@ -1762,34 +3646,56 @@ Two styles of help are provided:
         Command {
             name: "shutdown",
             description: "Disable the selected network interface.",
+            args: vec![],
+            help: "Disable the selected network interface.",
+            usage: None,
+            modes: &[Mode::InterfaceMode],
             suggestions: None,
             suggestions1: None,
+            require_subcommand: true,
             options: None,
+            aliases: vec![],
             execute: |_, context, _| {
                 if matches!(context.current_mode, Mode::InterfaceMode) {
                     if let Some(interface) = &context.selected_interface {
-                        let mut network_state = IP_ADDRESS_STATE.lock().unwrap();
+                        let network_state = IP_ADDRESS_STATE.lock().unwrap();
                         let mut status_map = STATUS_MAP.lock().unwrap();
-                        if let Some(interface_config) = network_state.get_mut(interface) {
-                            
-                            let ip_address = interface_config.0.clone();
-                            
-                            let mut interface_config = InterfaceConfig {
-                                ip_address: Ipv4Addr::new(0, 0, 0, 0),
-                                is_up: false,
-                            };
-                            
-                            interface_config.is_up = true;
-                            status_map.insert(interface.clone(), false);
-    
-                            println!(
-                                "Interface {} has been shut down. IP address set to 0.0.0.0",
-                                interface
-                            );
+                        let mut oper_state_map = OPER_STATE_MAP.lock().unwrap();
+                        if network_state.contains_key(interface) {
+                            let old_oper_state = oper_state_map.get(interface).copied().unwrap_or(OperState::Down);
+
+                            status_map.insert(interface.clone(), AdminState::Down);
+                            oper_state_map.insert(interface.clone(), OperState::Down);
+
+                            if let Err(err) = crate::host_backend::ACTIVE_BACKEND.lock().unwrap().set_link_admin_state(interface, false) {
+                                println!("Warning: kernel-apply failed to bring {} down: {}", interface, err);
+                            }
+
+                            if old_oper_state != OperState::Down {
+                                crate::hooks::run_hook(
+                                    &context.config.hook_scripts,
+                                    "interface-state-changed",
+                                    &[
+                                        ("INTERFACE", interface.clone()),
+                                        ("OLD_STATE", "up".to_string()),
+                                        ("NEW_STATE", "down".to_string()),
+                                    ],
+                                );
+
+                                println!(
+                                    "%LINK-5-CHANGED: Interface {}, changed state to administratively down",
+                                    interface
+                                );
+                                println!(
+                                    "%LINEPROTO-5-UPDOWN: Line protocol on Interface {}, changed state to down",
+                                    interface
+                                );
+                            }
+                            Ok(())
                         } else {
                             println!("Interface {} not found.", interface);
+                            Err("Invalid interface.".into())
                         }
-                        Ok(())
                     } else {
                         Err("No interface selected. Use the 'interface' command first.".into())
                     }
@@ -1799,39 +3705,129 @@ Two styles of help are provided:
             },
         },
     );
-    
+
+    commands.insert(
+        "switchport",
+        Command {
+            name: "switchport",
+            description: "Configure Layer 2 switching parameters on the selected interface.",
+            args: vec![],
+            help: "Configure Layer 2 switching parameters on the selected interface.",
+            usage: Some("switchport mode access|trunk | switchport access vlan <id> | switchport trunk allowed vlan <id>[,<id>...]"),
+            modes: &[Mode::InterfaceMode],
+            suggestions: Some(vec!["mode access", "mode trunk", "access vlan", "trunk allowed vlan"]),
+            suggestions1: Some(vec!["mode access", "mode trunk", "access vlan", "trunk allowed vlan"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, context, _| {
+                let interface = match &context.selected_interface {
+                    Some(interface) => interface.clone(),
+                    None => return Err("No interface selected. Use the 'interface' command first.".into()),
+                };
+
+                let parse_vlan_id = |raw: &str| -> Result<u16, String> {
+                    raw.parse::<u16>()
+                        .ok()
+                        .filter(|id| (MIN_VLAN_ID..=MAX_VLAN_ID).contains(id))
+                        .ok_or_else(|| format!("Invalid VLAN id '{}'. Must be between {} and {}.", raw, MIN_VLAN_ID, MAX_VLAN_ID))
+                };
+
+                let mut switchport_state = SWITCHPORT_STATE.lock().unwrap();
+
+                match args {
+                    ["mode", "access"] => {
+                        let config = switchport_state.entry(interface.clone()).or_default();
+                        config.mode = SwitchportMode::Access;
+                        println!("Interface {} set to access mode.", interface);
+                        Ok(())
+                    }
+                    ["mode", "trunk"] => {
+                        let config = switchport_state.entry(interface.clone()).or_default();
+                        config.mode = SwitchportMode::Trunk;
+                        println!("Interface {} set to trunk mode.", interface);
+                        Ok(())
+                    }
+                    ["access", "vlan", vlan_id] => {
+                        let vlan_id = parse_vlan_id(vlan_id)?;
+                        let config = switchport_state.entry(interface.clone()).or_default();
+                        config.access_vlan = vlan_id;
+                        println!("Interface {} assigned to access VLAN {}.", interface, vlan_id);
+                        Ok(())
+                    }
+                    ["trunk", "allowed", "vlan", vlan_list] => {
+                        let vlan_ids = vlan_list
+                            .split(',')
+                            .map(parse_vlan_id)
+                            .collect::<Result<Vec<u16>, String>>()?;
+                        let config = switchport_state.entry(interface.clone()).or_default();
+                        config.trunk_allowed_vlans = vlan_ids;
+                        println!("Interface {} trunk allowed VLANs set to {}.", interface, vlan_list);
+                        Ok(())
+                    }
+                    _ => Err("Usage: switchport mode access|trunk | switchport access vlan <id> | switchport trunk allowed vlan <id>[,<id>...]".into()),
+                }
+            },
+        },
+    );
+
     commands.insert(
         "no",
         Command {
             name: "no shutdown",
             description: "Negate a command or set its defaults",
+            args: vec![],
+            help: "Negate a command or set its defaults",
+            usage: None,
+            modes: &[Mode::ConfigMode, Mode::InterfaceMode, Mode::RouterRipMode],
             suggestions: Some(vec!["shutdown", "ntp", "crypto dynamic-map", "crypto engine accelerator",
                 "crypto ipsec security-association lifetime", "crypto ipsec transform-set",
-                "crypto map"]),
+                "crypto map", "auto-summary"]),
             suggestions1: Some(vec!["shutdown", "ntp", "crypto dynamic-map", "crypto engine accelerator",
                 "crypto ipsec security-association lifetime", "crypto ipsec transform-set",
-                "crypto map"]),
+                "crypto map", "auto-summary"]),
+            require_subcommand: true,
             options: None,
+            aliases: vec![],
             execute: |args, context, _| {
                 if args.len() == 1 && args[0] == "shutdown" {
                     if matches!(context.current_mode, Mode::InterfaceMode) {
                         if let Some(interface) = &context.selected_interface {
-                            let mut network_state = IP_ADDRESS_STATE.lock().unwrap();
+                            let network_state = IP_ADDRESS_STATE.lock().unwrap();
                             let mut status_map = STATUS_MAP.lock().unwrap();
-        
+                            let mut oper_state_map = OPER_STATE_MAP.lock().unwrap();
+
                             // Check if the interface exists in `NETWORK_STATE`
-                            if let Some((ip_address, broadcast_address)) = network_state.get(interface) {
-                                // Update the administrative status to "up" in `STATUS_MAP`
-                                status_map.insert(interface.clone(), true);
-        
-                                println!(
-                                    "%LINK-5-CHANGED: Interface {}, changed state to up",
-                                    interface
-                                );
-                                println!(
-                                    "%LINEPROTO-5-UPDOWN: Line protocol on Interface {}, changed state to up",
-                                    interface
-                                );
+                            if network_state.contains_key(interface) {
+                                let old_oper_state = oper_state_map.get(interface).copied().unwrap_or(OperState::Down);
+
+                                status_map.insert(interface.clone(), AdminState::Up);
+                                oper_state_map.insert(interface.clone(), OperState::Up);
+
+                                if let Err(err) = crate::host_backend::ACTIVE_BACKEND.lock().unwrap().set_link_admin_state(interface, true) {
+                                    println!("Warning: kernel-apply failed to bring {} up: {}", interface, err);
+                                }
+
+                                if old_oper_state != OperState::Up {
+                                    crate::hooks::run_hook(
+                                        &context.config.hook_scripts,
+                                        "interface-state-changed",
+                                        &[
+                                            ("INTERFACE", interface.clone()),
+                                            ("OLD_STATE", "down".to_string()),
+                                            ("NEW_STATE", "up".to_string()),
+                                        ],
+                                    );
+
+                                    println!(
+                                        "%LINK-5-CHANGED: Interface {}, changed state to up",
+                                        interface
+                                    );
+                                    println!(
+                                        "%LINEPROTO-5-UPDOWN: Line protocol on Interface {}, changed state to up",
+                                        interface
+                                    );
+                                }
                                 Ok(())
                             } else {
                                 println!("Interface {} not found.", interface);
@@ -1921,6 +3917,25 @@ Two styles of help are provided:
                                             Err("Transform set not found".into())
                                         }
                                     },
+                                    Some("profile") => {
+                                        match context.config.crypto_ipsec_profile.take() {
+                                            Some(name) => {
+                                                crate::nat_traversal::disable(&name);
+                                                println!("Removed crypto IPsec profile '{}'", name);
+                                                Ok(())
+                                            }
+                                            None => Err("No crypto IPsec profile configured".into()),
+                                        }
+                                    },
+                                    Some("nat-traversal") => {
+                                        match &context.config.crypto_ipsec_profile {
+                                            Some(name) if crate::nat_traversal::disable(name) => {
+                                                println!("NAT traversal disabled for IPsec profile '{}'", name);
+                                                Ok(())
+                                            }
+                                            _ => Err("NAT traversal is not enabled for the active IPsec profile".into()),
+                                        }
+                                    },
                                     _ => Err("Invalid ipsec command to negate".into())
                                 }
                             },
@@ -1952,28 +3967,206 @@ Two styles of help are provided:
                     } else {
                         Err("The 'no crypto' commanda are only available in Global Configuration mode.".into())
                     }
+                } else if args.len() == 1 && args[0] == "auto-summary" {
+                    if matches!(context.current_mode, Mode::RouterRipMode) {
+                        RIP_CONFIG.lock().unwrap().auto_summary = false;
+                        println!("RIP auto-summary disabled.");
+                        Ok(())
+                    } else {
+                        Err("The 'no auto-summary' command is only available in Router RIP Configuration mode.".into())
+                    }
+                }
+                else {
+                    Err("Invalid arguments provided to 'no'.".into())
+                }
+
+            },
+        },
+    );
+
+
+    // Data-link layer commands (serial/WAN interfaces)
+
+    commands.insert(
+        "encapsulation",
+        Command {
+            name: "encapsulation",
+            description: "Set the data-link encapsulation of the selected interface",
+            args: vec![ArgSpec::required("protocol").of_kind(ArgKind::Keyword(&["ppp", "hdlc"]))],
+            help: "Set the data-link encapsulation of the selected interface",
+            usage: None,
+            modes: &[Mode::InterfaceMode],
+            suggestions: Some(vec!["ppp", "hdlc"]),
+            suggestions1: Some(vec!["ppp", "hdlc"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, context, _| {
+                let Some(interface) = &context.selected_interface else {
+                    return Err("No interface selected. Use the 'interface' command first.".into());
+                };
+                let encapsulation = match args {
+                    ["ppp"] => Encapsulation::Ppp,
+                    ["hdlc"] => Encapsulation::Hdlc,
+                    _ => return Err("Usage: encapsulation {ppp | hdlc}".into()),
+                };
+                let mut link_config = LINK_CONFIG_STATE.lock().unwrap();
+                link_config.entry(interface.clone()).or_insert_with(InterfaceConfig::default).encapsulation = encapsulation;
+                println!("Interface {} encapsulation set to {}.", interface, encapsulation);
+                Ok(())
+            },
+        },
+    );
+
+    commands.insert(
+        "ppp",
+        Command {
+            name: "ppp",
+            description: "Configure PPP authentication, multilink, and link quality monitoring",
+            args: vec![],
+            help: "Configure PPP authentication, multilink, and link quality monitoring",
+            usage: None,
+            modes: &[Mode::InterfaceMode],
+            suggestions: Some(vec!["authentication chap", "authentication pap", "authentication chap pap", "authentication pap chap", "multilink", "quality"]),
+            suggestions1: Some(vec!["authentication chap", "authentication pap", "authentication chap pap", "authentication pap chap", "multilink", "quality"]),
+            require_subcommand: true,
+            options: Some(vec!["<0-99>       - Minimum acceptable link quality percentage"]),
+            aliases: vec![],
+            execute: |args, context, _| {
+                let Some(interface) = &context.selected_interface else {
+                    return Err("No interface selected. Use the 'interface' command first.".into());
+                };
+                let mut link_config = LINK_CONFIG_STATE.lock().unwrap();
+                let link_config = link_config.entry(interface.clone()).or_insert_with(InterfaceConfig::default);
+                match args {
+                    ["authentication", "chap"] => {
+                        link_config.ppp_authentication = Some(PppAuthentication::Chap);
+                        println!("PPP authentication on {} set to CHAP.", interface);
+                        Ok(())
+                    }
+                    ["authentication", "pap"] => {
+                        link_config.ppp_authentication = Some(PppAuthentication::Pap);
+                        println!("PPP authentication on {} set to PAP.", interface);
+                        Ok(())
+                    }
+                    ["authentication", "chap", "pap"] => {
+                        link_config.ppp_authentication = Some(PppAuthentication::ChapThenPap);
+                        println!("PPP authentication on {} set to CHAP, falling back to PAP.", interface);
+                        Ok(())
+                    }
+                    ["authentication", "pap", "chap"] => {
+                        link_config.ppp_authentication = Some(PppAuthentication::PapThenChap);
+                        println!("PPP authentication on {} set to PAP, falling back to CHAP.", interface);
+                        Ok(())
+                    }
+                    ["multilink"] => {
+                        link_config.ppp_multilink = true;
+                        println!("PPP multilink enabled on {}.", interface);
+                        Ok(())
+                    }
+                    ["quality", percentage] => {
+                        let percentage = percentage.parse::<u8>().map_err(|_| "Invalid quality percentage. It must be between 0 and 99.".to_string())?;
+                        if percentage > 99 {
+                            return Err("Invalid quality percentage. It must be between 0 and 99.".into());
+                        }
+                        link_config.ppp_quality = Some(percentage);
+                        println!("PPP link quality monitoring on {} set to {}%.", interface, percentage);
+                        Ok(())
+                    }
+                    _ => Err("Usage: ppp {authentication {chap | pap | chap pap | pap chap} | multilink | quality <0-99>}".into()),
                 }
-                else {
-                    Err("Invalid arguments provided to 'no'.".into())
+            },
+        },
+    );
+
+    commands.insert(
+        "compress",
+        Command {
+            name: "compress",
+            description: "Enable link compression on the selected interface",
+            args: vec![ArgSpec::required("algorithm").of_kind(ArgKind::Keyword(&["predictor", "stack"]))],
+            help: "Enable link compression on the selected interface",
+            usage: None,
+            modes: &[Mode::InterfaceMode],
+            suggestions: Some(vec!["predictor", "stack"]),
+            suggestions1: Some(vec!["predictor", "stack"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, context, _| {
+                let Some(interface) = &context.selected_interface else {
+                    return Err("No interface selected. Use the 'interface' command first.".into());
+                };
+                let algorithm = match args {
+                    ["predictor"] => CompressionAlgorithm::Predictor,
+                    ["stack"] => CompressionAlgorithm::Stack,
+                    _ => return Err("Usage: compress {predictor | stack}".into()),
+                };
+                let mut link_config = LINK_CONFIG_STATE.lock().unwrap();
+                link_config.entry(interface.clone()).or_insert_with(InterfaceConfig::default).compression = Some(algorithm);
+                println!("Interface {} compression set to {}.", interface, algorithm);
+                Ok(())
+            },
+        },
+    );
+
+    commands.insert(
+        "username",
+        Command {
+            name: "username",
+            description: "Configure a local username and password for PPP PAP/CHAP authentication",
+            args: vec![
+                ArgSpec::required("name"),
+                ArgSpec::required("password").of_kind(ArgKind::Keyword(&["password"])),
+                ArgSpec::required("secret"),
+            ],
+            help: "Configure a local username and password for PPP PAP/CHAP authentication",
+            usage: None,
+            modes: &[Mode::ConfigMode],
+            suggestions: None,
+            suggestions1: None,
+            require_subcommand: true,
+            options: Some(vec!["<name>          - Enter the username", "<password>      - Enter the password"]),
+            aliases: vec![],
+            execute: |args, _, _| {
+                match args {
+                    [name, "password", password] => {
+                        set_user_password(name, password);
+                        println!("Username '{}' added.", name);
+                        Ok(())
+                    }
+                    _ => Err("Usage: username <name> password <password>".into()),
                 }
-                
             },
         },
     );
 
 
-    // Routing commands 
+    // Routing commands
 
     commands.insert("router", Command {
         name: "router",
-        description: "Enable OSPF routing and enter router configuration mode",
-        suggestions: Some(vec!["ospf"]),
-        suggestions1: Some(vec!["ospf"]),
-        options: Some(vec!["<process-id>       - Enter the ospf process-id"]),
+        description: "Enable a routing protocol (OSPF, BGP, RIP, or IS-IS) and enter its router configuration mode",
+        args: vec![],
+        help: "Enable a routing protocol (OSPF, BGP, RIP, or IS-IS) and enter its router configuration mode",
+        usage: None,
+        modes: &[Mode::ConfigMode],
+        suggestions: Some(vec!["ospf", "bgp", "rip", "isis"]),
+        suggestions1: Some(vec!["ospf", "bgp", "rip", "isis"]),
+        require_subcommand: true,
+        options: Some(vec![
+            "<process-id>       - Enter the ospf process-id",
+            "<asn>              - Enter the BGP autonomous system number",
+            "<tag>              - Enter the IS-IS process tag",
+        ]),
+        aliases: vec![],
         execute: |args, context, _| {
-            if matches!(context.current_mode, Mode::ConfigMode) {
-                if args.len() == 2 && args[0] == "ospf"  {
-                    let process_id = args[1].parse::<u32>();
+            if !matches!(context.current_mode, Mode::ConfigMode) {
+                return Err("The 'router' command is only available in Global Configuration mode.".into());
+            }
+            match args {
+                ["ospf", process_id] => {
+                    let process_id = process_id.parse::<u32>();
                     match process_id {
                         Ok(id) if id > 0 => {
                             let mut ospf_config = OSPF_CONFIG.lock().unwrap();
@@ -1985,68 +4178,283 @@ Two styles of help are provided:
                         }
                         _ => Err("Invalid process ID provided. It must be a positive integer.".into()),
                     }
-                } else {
-                    Err("The 'router ospf' command requires exactly one argument: the process ID.".into())
                 }
-            } else {
-                Err("The 'router ospf' command is only available in Global Configuration mode.".into())
+                ["bgp", asn] => {
+                    let asn = asn.parse::<u32>();
+                    match asn {
+                        Ok(asn) if asn > 0 => {
+                            let mut bgp_config = BGP_CONFIG.lock().unwrap();
+                            bgp_config.asn = Some(asn);
+                            context.current_mode = Mode::RouterBgpMode;
+                            context.prompt = format!("{}(config-router)#", context.config.hostname);
+                            println!("BGP routing enabled with AS number {}.", asn);
+                            Ok(())
+                        }
+                        _ => Err("Invalid AS number provided. It must be a positive integer.".into()),
+                    }
+                }
+                ["rip"] => {
+                    RIP_CONFIG.lock().unwrap().enabled = true;
+                    context.current_mode = Mode::RouterRipMode;
+                    context.prompt = format!("{}(config-router)#", context.config.hostname);
+                    println!("RIP routing enabled.");
+                    Ok(())
+                }
+                ["isis", tag] => {
+                    let mut isis_config = ISIS_CONFIG.lock().unwrap();
+                    isis_config.tag = Some(tag.to_string());
+                    context.current_mode = Mode::RouterIsisMode;
+                    context.prompt = format!("{}(config-router)#", context.config.hostname);
+                    println!("IS-IS routing enabled with process tag '{}'.", tag);
+                    Ok(())
+                }
+                _ => Err("Usage: router {ospf <process-id> | bgp <asn> | rip | isis <tag>}".into()),
             }
         },
     });
 
     commands.insert("network", Command {
         name: "network",
-        description: "Define an OSPF network and associate it with an area ID",
+        description: "Define a network to be advertised by the current routing protocol",
+        args: vec![],
+        help: "Define a network to be advertised by the current routing protocol",
+        usage: None,
+        modes: &[Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::DhcpPoolMode(String::new())],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<ip-address>        - Enter the ip-address",
             "<wildcard-mask>      - Enter the wildcard-mask",
             "<area-id>          - Enter the area-id"]),
+        aliases: vec![],
         execute: |args, context, _| {
-            if matches!(context.current_mode, Mode::RouterConfigMode) {
-                if args.len() == 4 {
-                    let ip_address = args[0].clone();
-                    let wildcard_mask = args[1].clone();
-                    let area_id = args[3].parse::<u32>();
-    
-                    if area_id.is_err() || ip_address.is_empty() || wildcard_mask.is_empty() {
-                        Err("Invalid arguments provided. Usage: network <ip-address> <wildcard-mask> area <area-id>".into())
+            match &context.current_mode {
+                Mode::DhcpPoolMode(pool_name) => {
+                    let pool_name = pool_name.clone();
+                    if args.len() != 2 {
+                        return Err("Usage: network <ip-address> <netmask>".into());
+                    }
+                    let network_address: Ipv4Addr = args[0]
+                        .parse()
+                        .map_err(|_| "Invalid IP address format.".to_string())?;
+                    let netmask: Ipv4Addr = args[1]
+                        .parse()
+                        .map_err(|_| "Invalid netmask format.".to_string())?;
+                    let pool = context.config.dhcp_pools.entry(pool_name).or_insert_with(DhcpPool::default);
+                    pool.network = Some((network_address, netmask));
+                    println!("DHCP pool network set to {} {}.", network_address, netmask);
+                    Ok(())
+                }
+                Mode::RouterConfigMode => {
+                    if args.len() == 4 {
+                        let ip_address = args[0].clone();
+                        let wildcard_mask = args[1].clone();
+                        let area_id = args[3].parse::<u32>();
+
+                        if area_id.is_err() || ip_address.is_empty() || wildcard_mask.is_empty() {
+                            Err("Invalid arguments provided. Usage: network <ip-address> <wildcard-mask> area <area-id>".into())
+                        } else {
+                            let area_id = area_id.unwrap();
+                            let key = format!("{} {}", ip_address, wildcard_mask);
+                            let mut ospf_config = OSPF_CONFIG.lock().unwrap();
+                            ospf_config.networks.insert(key, area_id);
+                            println!(
+                                "Network {} {} added to OSPF area {}.",
+                                ip_address, wildcard_mask, area_id
+                            );
+                            Ok(())
+                        }
                     } else {
-                        let area_id = area_id.unwrap();
-                        let key = format!("{} {}", ip_address, wildcard_mask);
-                        let mut ospf_config = OSPF_CONFIG.lock().unwrap();
-                        ospf_config.networks.insert(key, area_id);
-                        println!(
-                            "Network {} {} added to OSPF area {}.",
-                            ip_address, wildcard_mask, area_id
-                        );
-                        Ok(())
+                        Err("The 'network' command requires three arguments: <ip-address> <wildcard-mask> area <area-id>.".into())
                     }
-                } else {
-                    Err("The 'network' command requires three arguments: <ip-address> <wildcard-mask> area <area-id>.".into())
                 }
-            } else {
-                Err("The 'network' command is only available in Router Configuration mode.".into())
+                Mode::RouterBgpMode => match args {
+                    [prefix, "mask", mask] => {
+                        let mut bgp_config = BGP_CONFIG.lock().unwrap();
+                        bgp_config.networks.insert(prefix.to_string(), mask.to_string());
+                        println!("Network {} mask {} added to BGP.", prefix, mask);
+                        Ok(())
+                    }
+                    _ => Err("Usage: network <prefix> mask <mask>".into()),
+                },
+                Mode::RouterRipMode => match args {
+                    [classful] => {
+                        RIP_CONFIG.lock().unwrap().networks.push(classful.to_string());
+                        println!("Network {} added to RIP.", classful);
+                        Ok(())
+                    }
+                    _ => Err("Usage: network <classful-address>".into()),
+                },
+                _ => Err("The 'network' command is only available in Router Configuration mode.".into()),
+            }
+        },
+    });
+
+    commands.insert("default-router", Command {
+        name: "default-router",
+        description: "Set the default gateway pushed to clients by the current DHCP pool",
+        args: vec![ArgSpec::required("ip-address").of_kind(ArgKind::Ip)],
+        help: "Set the default gateway pushed to clients by the current DHCP pool",
+        usage: None,
+        modes: &[Mode::DhcpPoolMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let pool_name = match &context.current_mode {
+                Mode::DhcpPoolMode(name) => name.clone(),
+                _ => return Err("The 'default-router' command is only available in DHCP pool configuration mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: default-router <ip-address>".into());
+            }
+            let address: Ipv4Addr = args[0].parse().map_err(|_| "Invalid IP address format.".to_string())?;
+            let pool = context.config.dhcp_pools.entry(pool_name).or_insert_with(DhcpPool::default);
+            pool.default_router = Some(address);
+            println!("Default router set to {}.", address);
+            Ok(())
+        },
+    });
+
+    commands.insert("dns-server", Command {
+        name: "dns-server",
+        description: "Set the DNS server(s) pushed to clients by the current DHCP pool",
+        args: vec![ArgSpec::variadic("ip-address")],
+        help: "Set the DNS server(s) pushed to clients by the current DHCP pool",
+        usage: None,
+        modes: &[Mode::DhcpPoolMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let pool_name = match &context.current_mode {
+                Mode::DhcpPoolMode(name) => name.clone(),
+                _ => return Err("The 'dns-server' command is only available in DHCP pool configuration mode.".into()),
+            };
+            if args.is_empty() {
+                return Err("Usage: dns-server <ip-address> [ip-address2] ...".into());
+            }
+            let mut addresses = Vec::with_capacity(args.len());
+            for arg in args {
+                addresses.push(arg.parse::<Ipv4Addr>().map_err(|_| "Invalid IP address format.".to_string())?);
+            }
+            let pool = context.config.dhcp_pools.entry(pool_name).or_insert_with(DhcpPool::default);
+            pool.dns_servers = addresses;
+            println!("DNS server(s) set to: {}", args.join(", "));
+            Ok(())
+        },
+    });
+
+    commands.insert("domain-name", Command {
+        name: "domain-name",
+        description: "Set the domain name pushed to clients by the current DHCP pool",
+        args: vec![ArgSpec::required("name")],
+        help: "Set the domain name pushed to clients by the current DHCP pool",
+        usage: None,
+        modes: &[Mode::DhcpPoolMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let pool_name = match &context.current_mode {
+                Mode::DhcpPoolMode(name) => name.clone(),
+                _ => return Err("The 'domain-name' command is only available in DHCP pool configuration mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: domain-name <name>".into());
+            }
+            let pool = context.config.dhcp_pools.entry(pool_name).or_insert_with(DhcpPool::default);
+            pool.domain_name = Some(args[0].to_string());
+            println!("Domain name set to: {}", args[0]);
+            Ok(())
+        },
+    });
+
+    commands.insert("lease", Command {
+        name: "lease",
+        description: "Set the lease duration for the current DHCP pool",
+        args: vec![
+            ArgSpec::required("days"),
+            ArgSpec::optional("hours"),
+            ArgSpec::optional("minutes"),
+        ],
+        help: "Set the lease duration for the current DHCP pool",
+        usage: None,
+        modes: &[Mode::DhcpPoolMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let pool_name = match &context.current_mode {
+                Mode::DhcpPoolMode(name) => name.clone(),
+                _ => return Err("The 'lease' command is only available in DHCP pool configuration mode.".into()),
+            };
+            if args.is_empty() || args.len() > 3 {
+                return Err("Usage: lease <days> [hours] [minutes]".into());
             }
+            let days = args[0].parse::<u32>().map_err(|_| "Invalid days value.".to_string())?;
+            let hours = args.get(1).map_or(Ok(0), |v| v.parse::<u32>()).map_err(|_| "Invalid hours value.".to_string())?;
+            let minutes = args.get(2).map_or(Ok(0), |v| v.parse::<u32>()).map_err(|_| "Invalid minutes value.".to_string())?;
+            let pool = context.config.dhcp_pools.entry(pool_name).or_insert_with(DhcpPool::default);
+            pool.lease = Some((days, hours, minutes));
+            println!("DHCP lease set to {} days {} hours {} minutes.", days, hours, minutes);
+            Ok(())
         },
     });
 
     commands.insert("neighbor", Command {
         name: "neighbor",
-        description: "Specify a neighbor and optionally assign a cost.",
+        description: "Specify a neighbor and optionally assign a cost, or a BGP peer's remote AS.",
+        args: vec![
+            ArgSpec::required("neighbor-ip").of_kind(ArgKind::Ip),
+            ArgSpec::optional("cost").of_kind(ArgKind::U16),
+        ],
+        help: "Specify a neighbor and optionally assign a cost, or a BGP peer's remote AS.",
+        usage: None,
+        modes: &[Mode::RouterConfigMode, Mode::RouterBgpMode],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<ip-address>       - Emnter the ip-address",
             "<cost>       - Enter the cost"]),
+        aliases: vec![],
         execute: |args, context, _| {
+            if matches!(context.current_mode, Mode::RouterBgpMode) {
+                return match args {
+                    [ip_address, "remote-as", asn] => {
+                        let ip_address = Ipv4Addr::from_str(ip_address).map_err(|_| "Invalid IP address format".to_string())?;
+                        let asn = asn.parse::<u32>().map_err(|_| "Invalid AS number. It must be a positive integer.".to_string())?;
+                        BGP_CONFIG.lock().unwrap().neighbors.insert(ip_address, asn);
+                        println!("Neighbor {} configured with remote AS {}.", ip_address, asn);
+                        Ok(())
+                    }
+                    [ip_address, "description", description @ ..] if !description.is_empty() => {
+                        let ip_address = Ipv4Addr::from_str(ip_address).map_err(|_| "Invalid IP address format".to_string())?;
+                        let description = description.join(" ");
+                        BGP_CONFIG.lock().unwrap().neighbor_descriptions.insert(ip_address, description.clone());
+                        println!("Neighbor {} description set to \"{}\".", ip_address, description);
+                        Ok(())
+                    }
+                    _ => Err("Usage: neighbor <ip-address> remote-as <asn> | neighbor <ip-address> description <text>".into()),
+                };
+            }
+
             if matches!(context.current_mode, Mode::RouterConfigMode) {
                 if args.is_empty() {
                     return Err("Usage: neighbor <ip-address> [cost <number>]".into());
                 }
-    
+
                 let ip_address = Ipv4Addr::from_str(&args[0]).expect("Invalid IP address format");
                 let mut cost: Option<u32> = None;
-    
+
                 // Parse optional "cost <number>" arguments
                 if args.len() == 3 && args[1] == "cost" {
                     match args[2].parse::<u32>() {
@@ -2062,15 +4470,26 @@ Two styles of help are provided:
                 }
 
                 let mut ospf_config = OSPF_CONFIG.lock().unwrap();
+                let old_cost = ospf_config.neighbors.get(&ip_address).copied().flatten();
                 ospf_config.neighbors.insert(ip_address, cost);
-                
+
+                crate::hooks::run_hook(
+                    &context.config.hook_scripts,
+                    "ospf-adjacency-changed",
+                    &[
+                        ("NEIGHBOR", ip_address.to_string()),
+                        ("OLD_STATE", old_cost.map_or("none".to_string(), |c| c.to_string())),
+                        ("NEW_STATE", cost.map_or("default".to_string(), |c| c.to_string())),
+                    ],
+                );
+
                 if let Some(cost_value) = cost {
                     println!("Neighbor {} configured with cost {}.", ip_address, cost_value);
                 } else {
                     println!("Neighbor {} configured with default cost.", ip_address);
                 }
                 Ok(())
-                
+
             } else {
                 Err("The 'neighbor' command is only available in Router Configuration mode.".into())
             }
@@ -2080,9 +4499,19 @@ Two styles of help are provided:
     commands.insert("area", Command {
         name: "area",
         description: "Configure OSPF area options.",
+        args: vec![
+            ArgSpec::required("area-id"),
+            ArgSpec::optional("authentication | stub | default-cost")
+                .of_kind(ArgKind::Keyword(&["authentication", "stub", "default-cost"])),
+        ],
+        help: "Configure OSPF area options.",
+        usage: None,
+        modes: &[Mode::RouterConfigMode],
         suggestions: Some(vec!["authentication", "stub", "default-cost"]),
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<area-id>       - Enter the area-id"]),
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::RouterConfigMode) {
                 if args.is_empty() {
@@ -2133,12 +4562,136 @@ Two styles of help are provided:
         },
     });
 
+    commands.insert(
+        "redistribute",
+        Command {
+            name: "redistribute",
+            description: "Redistribute routes from another routing protocol into BGP",
+            args: vec![ArgSpec::required("protocol").of_kind(ArgKind::Keyword(&["ospf", "connected", "static"]))],
+            help: "Redistribute routes from another routing protocol into BGP",
+            usage: None,
+            modes: &[Mode::RouterBgpMode],
+            suggestions: Some(vec!["ospf", "connected", "static"]),
+            suggestions1: Some(vec!["ospf", "connected", "static"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| match args {
+                ["ospf"] => {
+                    BGP_CONFIG.lock().unwrap().redistribute_ospf = true;
+                    println!("Redistributing OSPF routes into BGP.");
+                    Ok(())
+                }
+                ["connected"] => {
+                    BGP_CONFIG.lock().unwrap().redistribute_connected = true;
+                    println!("Redistributing connected routes into BGP.");
+                    Ok(())
+                }
+                ["static"] => {
+                    BGP_CONFIG.lock().unwrap().redistribute_static = true;
+                    println!("Redistributing static routes into BGP.");
+                    Ok(())
+                }
+                _ => Err("Usage: redistribute {ospf|connected|static}".into()),
+            },
+        },
+    );
+
+    commands.insert(
+        "version",
+        Command {
+            name: "version",
+            description: "Set the RIP version advertised and accepted",
+            args: vec![ArgSpec::required("version").of_kind(ArgKind::Keyword(&["1", "2"]))],
+            help: "Set the RIP version advertised and accepted",
+            usage: None,
+            modes: &[Mode::RouterRipMode],
+            suggestions: Some(vec!["1", "2"]),
+            suggestions1: Some(vec!["1", "2"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| match args {
+                ["1"] => {
+                    RIP_CONFIG.lock().unwrap().version = 1;
+                    println!("RIP version set to 1.");
+                    Ok(())
+                }
+                ["2"] => {
+                    RIP_CONFIG.lock().unwrap().version = 2;
+                    println!("RIP version set to 2.");
+                    Ok(())
+                }
+                _ => Err("Usage: version {1 | 2}".into()),
+            },
+        },
+    );
+
+    commands.insert(
+        "net",
+        Command {
+            name: "net",
+            description: "Configure the IS-IS Network Entity Title (NET)",
+            args: vec![ArgSpec::required("nsap")],
+            help: "Configure the IS-IS Network Entity Title (NET)",
+            usage: None,
+            modes: &[Mode::RouterIsisMode],
+            suggestions: None,
+            suggestions1: None,
+            require_subcommand: true,
+            options: Some(vec!["<nsap>       - Enter the NSAP address, e.g. 49.0001.1921.6800.1001.00"]),
+            aliases: vec![],
+            execute: |args, _, _| match args {
+                [nsap] => {
+                    ISIS_CONFIG.lock().unwrap().net = Some(nsap.to_string());
+                    println!("IS-IS NET set to {}.", nsap);
+                    Ok(())
+                }
+                _ => Err("Usage: net <nsap>".into()),
+            },
+        },
+    );
+
+    commands.insert(
+        "is-type",
+        Command {
+            name: "is-type",
+            description: "Set the IS-IS level(s) this process operates at",
+            args: vec![ArgSpec::required("level").of_kind(ArgKind::Keyword(&["level-1", "level-2", "level-1-2"]))],
+            help: "Set the IS-IS level(s) this process operates at",
+            usage: None,
+            modes: &[Mode::RouterIsisMode],
+            suggestions: Some(vec!["level-1", "level-2", "level-1-2"]),
+            suggestions1: Some(vec!["level-1", "level-2", "level-1-2"]),
+            require_subcommand: true,
+            options: None,
+            aliases: vec![],
+            execute: |args, _, _| {
+                let is_type = match args {
+                    ["level-1"] => IsisLevel::Level1,
+                    ["level-2"] => IsisLevel::Level2,
+                    ["level-1-2"] => IsisLevel::Level1Level2,
+                    _ => return Err("Usage: is-type {level-1 | level-2 | level-1-2}".into()),
+                };
+                ISIS_CONFIG.lock().unwrap().is_type = is_type;
+                println!("IS-IS level set to {}.", is_type);
+                Ok(())
+            },
+        },
+    );
+
     commands.insert("passive-interface", Command {
         name: "passive-interface",
         description: "Disables sending OSPF Hello packets on an interface",
+        args: vec![],
+        help: "Disables sending OSPF Hello packets on an interface",
+        usage: None,
+        modes: &[Mode::RouterConfigMode],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<interface>     - Enter the interface name"]),
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::RouterConfigMode) {
                 if let Some(interface) = args.get(0) {
@@ -2159,9 +4712,15 @@ Two styles of help are provided:
     commands.insert("distance", Command {
         name: "distance",
         description: "Set administrative distance for OSPF",
+        args: vec![ArgSpec::required("distance").of_kind(ArgKind::U16)],
+        help: "Set administrative distance for OSPF",
+        usage: None,
+        modes: &[Mode::RouterConfigMode],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<distance>      - Set the distance"]),
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::RouterConfigMode) {
                 if let Some(distance) = args.get(0) {
@@ -2185,12 +4744,19 @@ Two styles of help are provided:
     commands.insert("default-information", Command {
         name: "default-information",
         description: "Originate a default route in OSPF",
+        args: vec![],
+        help: "Originate a default route in OSPF",
+        usage: None,
+        modes: &[Mode::RouterConfigMode],
         suggestions: Some(vec!["originate"]),
         suggestions1: Some(vec!["originate"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::RouterConfigMode) {
                 if args.get(0).map(|s| &s[..]) == Some("originate") {
+                    OSPF_CONFIG.lock().unwrap().default_information_originate = true;
                     println!("Default-information originate command executed.");
                     Ok(())
                 } else {
@@ -2202,34 +4768,122 @@ Two styles of help are provided:
         },
     });
 
+    commands.insert("export-filter", Command {
+        name: "export-filter",
+        description: "Attach a route-map to OSPF's default-route export point",
+        args: vec![ArgSpec::required("route-map-name")],
+        help: "Attach a route-map to OSPF's default-route export point",
+        usage: None,
+        modes: &[Mode::RouterConfigMode],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: Some(vec!["<route-map-name>  - Name of a route-map configured with 'route-map'"]),
+        aliases: vec![],
+        execute: |args, context, _| {
+            if !matches!(context.current_mode, Mode::RouterConfigMode) {
+                return Err("The 'export-filter' command is only available in Router OSPF mode.".into());
+            }
+            let Some(name) = args.get(0) else {
+                return Err("Usage: export-filter <route-map-name>".into());
+            };
+            OSPF_CONFIG.lock().unwrap().export_filter = Some(name.to_string());
+            println!("OSPF export filter set to: {}", name);
+            Ok(())
+        },
+    });
+
+    commands.insert("import-filter", Command {
+        name: "import-filter",
+        description: "Attach a route-map to routes OSPF's SPF computation installs",
+        args: vec![ArgSpec::required("route-map-name")],
+        help: "Attach a route-map to routes OSPF's SPF computation installs",
+        usage: None,
+        modes: &[Mode::RouterConfigMode],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: Some(vec!["<route-map-name>  - Name of a route-map configured with 'route-map'"]),
+        aliases: vec![],
+        execute: |args, context, _| {
+            if !matches!(context.current_mode, Mode::RouterConfigMode) {
+                return Err("The 'import-filter' command is only available in Router OSPF mode.".into());
+            }
+            let Some(name) = args.get(0) else {
+                return Err("Usage: import-filter <route-map-name>".into());
+            };
+            OSPF_CONFIG.lock().unwrap().import_filter = Some(name.to_string());
+            println!("OSPF import filter set to: {}", name);
+            Ok(())
+        },
+    });
+
     commands.insert("router-id", Command {
         name: "router-id",
         description: "Set the router ID for the OSPF process",
+        args: vec![ArgSpec::required("router-id").of_kind(ArgKind::Ip)],
+        help: "Set the router ID for the OSPF process",
+        usage: None,
+        modes: &[Mode::RouterConfigMode, Mode::RouterOspfv3Mode],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<router-id>       - Enter the router-id"]),
+        aliases: vec![],
         execute: |args, context, _| {
-            if matches!(context.current_mode, Mode::RouterConfigMode) {
-                if let Some(router_id) = args.get(0) {
-                    let mut ospf_config = OSPF_CONFIG.lock().unwrap();
-                    ospf_config.router_id = Some(router_id.to_string());
+            let Some(router_id) = args.get(0) else {
+                return Err("Usage: router-id <id>".into());
+            };
+            match context.current_mode {
+                Mode::RouterConfigMode => {
+                    let old_router_id = OSPF_CONFIG.lock().unwrap().router_id.clone();
+                    OSPF_CONFIG.lock().unwrap().router_id = Some(router_id.to_string());
                     println!("Router ID set to: {}", router_id);
+
+                    crate::hooks::run_hook(
+                        &context.config.hook_scripts,
+                        "ospf-config-changed",
+                        &[
+                            ("PARAMETER", "router-id".to_string()),
+                            ("OLD_VALUE", old_router_id.unwrap_or_default()),
+                            ("NEW_VALUE", router_id.to_string()),
+                        ],
+                    );
                     Ok(())
-                } else {
-                    Err("Usage: router-id <id>".into())
                 }
-            } else {
-                Err("The 'router-id' command is only available in Router OSPF mode.".into())
+                Mode::RouterOspfv3Mode => {
+                    let old_router_id = OSPFV3_CONFIG.lock().unwrap().router_id.clone();
+                    OSPFV3_CONFIG.lock().unwrap().router_id = Some(router_id.to_string());
+                    println!("Router ID set to: {}", router_id);
+
+                    crate::hooks::run_hook(
+                        &context.config.hook_scripts,
+                        "ospf-config-changed",
+                        &[
+                            ("PARAMETER", "router-id".to_string()),
+                            ("OLD_VALUE", old_router_id.unwrap_or_default()),
+                            ("NEW_VALUE", router_id.to_string()),
+                        ],
+                    );
+                    Ok(())
+                }
+                _ => Err("The 'router-id' command is only available in Router OSPF mode.".into()),
             }
         },
     });
 
     commands.insert("clear", Command {
         name: "clear",
-        description: "Reset all OSPF processes",
-        suggestions: Some(vec!["ip ospf process"]),
+        description: "Reset all OSPF processes, clear the NAT translation table, or tear down an IPsec SA",
+        args: vec![],
+        help: "Reset all OSPF processes, clear the NAT translation table, or tear down an IPsec SA",
+        usage: None,
+        modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
+        suggestions: Some(vec!["ip ospf process", "ip nat translation *", "crypto ipsec sa"]),
         suggestions1: None,
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if args.is_empty() {
                 // Cross-platform clear screen
@@ -2246,7 +4900,14 @@ Two styles of help are provided:
                 Ok(())
             }
             else if matches!(context.current_mode, Mode::PrivilegedMode) {
-                if args.len() == 3 && args[0] == "ip" && args[1] == "ospf" && args[2] == "process"  {
+                if args.len() == 4 && args[0] == "ip" && args[1] == "nat" && args[2] == "translation" && args[3] == "*" {
+                    NAT_STATIC_MAPPINGS.lock().unwrap().clear();
+                    NAT_OVERLOAD_RULES.lock().unwrap().clear();
+                    rebuild_nat_translations();
+                    println!("NAT translation table cleared.");
+                    Ok(())
+                }
+                else if args.len() == 3 && args[0] == "ip" && args[1] == "ospf" && args[2] == "process"  {
                     print!("Reset ALL OSPF processes? [no]: ");
                     io::stdout().flush().unwrap();
                     
@@ -2264,6 +4925,15 @@ Two styles of help are provided:
                         println!("Clear process cancelled.");
                         Ok(())
                     }
+                } else if args.len() == 3 && args[0] == "crypto" && args[1] == "ipsec" && args[2] == "sa" {
+                    crate::ipsec_sim::clear();
+                    match &context.config.crypto_ipsec_profile {
+                        Some(name) if crate::nat_traversal::disable(name) => {
+                            println!("IPsec SA cleared; NAT traversal mapping for profile '{}' torn down.", name);
+                        }
+                        _ => println!("IPsec SA cleared."),
+                    }
+                    Ok(())
                 } else {
                     Err("Invalid arguments provided to 'clear ip ospf process'. This command does not accept additional arguments.".into())
                 }
@@ -2279,9 +4949,21 @@ Two styles of help are provided:
     commands.insert("access-list", Command {
         name: "access-list",
         description: "Configure a standard numbered ACL",
+        args: vec![
+            ArgSpec::required("acl-number"),
+            ArgSpec::required("protocol"),
+            ArgSpec::required("deny | permit"),
+            ArgSpec::required("source-ip").of_kind(ArgKind::Ip),
+            ArgSpec::optional("wildcard-mask").of_kind(ArgKind::Ip),
+        ],
+        help: "Configure a standard numbered ACL",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::ConfigMode) {
                 if args.len() >= 3 {
@@ -2324,13 +5006,136 @@ Two styles of help are provided:
         },
     });
 
+    commands.insert("route-map", Command {
+        name: "route-map",
+        description: "Define or update a BIRD-style route-map clause, attachable to OSPF's import/export filter points",
+        args: vec![
+            ArgSpec::required("name"),
+            ArgSpec::required("deny | permit").of_kind(ArgKind::Keyword(&["permit", "deny"])),
+            ArgSpec::required("sequence").of_kind(ArgKind::U16),
+            ArgSpec::variadic("match|set clauses"),
+        ],
+        help: "Define or update a route-map clause, attachable to OSPF via 'import-filter'/'export-filter'",
+        usage: Some("route-map <name> {permit|deny} <sequence> [match ...] [set ...]"),
+        modes: &[Mode::ConfigMode],
+        suggestions: Some(vec!["permit", "deny"]),
+        suggestions1: Some(vec!["permit", "deny"]),
+        require_subcommand: true,
+        options: Some(vec![
+            "match prefix-equals <network> <prefix-len>      - Match an exact prefix",
+            "match prefix-within <network> <prefix-len>      - Match a prefix nested inside a supernet",
+            "match prefix-longer-than <prefix-len>            - Match a more specific prefix length",
+            "match next-hop <address>                         - Match the route's next-hop",
+            "match source-protocol {connected|static|ospf|rip} - Match the route's learned protocol",
+            "set distance <value>                             - Override the route's administrative distance",
+            "set metric <value>                               - Override the route's metric",
+            "set tag <value>                                  - Attach a tag to the route",
+        ]),
+        aliases: vec![],
+        execute: |args, context, _| {
+            if !matches!(context.current_mode, Mode::ConfigMode) {
+                return Err("The 'route-map' command is only available in global configuration mode.".into());
+            }
+            if args.len() < 3 {
+                return Err("Usage: route-map <name> {permit|deny} <sequence> [match ...] [set ...]".into());
+            }
+            let name = args[0].to_string();
+            let action = match args[1] {
+                "permit" => ClauseAction::Permit,
+                "deny" => ClauseAction::Deny,
+                other => return Err(format!("Invalid route-map action '{}'. Use 'permit' or 'deny'.", other)),
+            };
+            let sequence: u32 = args[2].parse().map_err(|_| format!("Invalid sequence number: {}", args[2]))?;
+
+            let rest = &args[3..];
+            let mut conditions = Vec::new();
+            let mut set = SetActions::default();
+            let mut i = 0;
+            while i < rest.len() {
+                match rest[i] {
+                    "match" => {
+                        let kind = *rest.get(i + 1).ok_or("Usage: match {prefix-equals|prefix-within|prefix-longer-than|next-hop|source-protocol} ...")?;
+                        match kind {
+                            "prefix-equals" | "prefix-within" => {
+                                let network = *rest.get(i + 2).ok_or("Usage: match prefix-equals|prefix-within <network> <prefix-len>")?;
+                                let prefix_len = *rest.get(i + 3).ok_or("Usage: match prefix-equals|prefix-within <network> <prefix-len>")?;
+                                let network = Ipv4Addr::from_str(network).map_err(|_| format!("Invalid network address: {}", network))?;
+                                let prefix_len: u8 = prefix_len.parse().map_err(|_| format!("Invalid prefix length: {}", prefix_len))?;
+                                conditions.push(if kind == "prefix-equals" {
+                                    MatchCondition::PrefixEquals(network, prefix_len)
+                                } else {
+                                    MatchCondition::PrefixIsWithin(network, prefix_len)
+                                });
+                                i += 4;
+                            }
+                            "prefix-longer-than" => {
+                                let prefix_len = *rest.get(i + 2).ok_or("Usage: match prefix-longer-than <prefix-len>")?;
+                                let prefix_len: u8 = prefix_len.parse().map_err(|_| format!("Invalid prefix length: {}", prefix_len))?;
+                                conditions.push(MatchCondition::PrefixLongerThan(prefix_len));
+                                i += 3;
+                            }
+                            "next-hop" => {
+                                let next_hop = *rest.get(i + 2).ok_or("Usage: match next-hop <address>")?;
+                                conditions.push(MatchCondition::NextHopEquals(next_hop.to_string()));
+                                i += 3;
+                            }
+                            "source-protocol" => {
+                                let protocol = *rest.get(i + 2).ok_or("Usage: match source-protocol {connected|static|ospf|rip}")?;
+                                let source = match protocol.to_lowercase().as_str() {
+                                    "connected" => RouteSource::Connected,
+                                    "static" => RouteSource::Static,
+                                    "ospf" => RouteSource::Ospf,
+                                    "rip" => RouteSource::Rip,
+                                    other => return Err(format!("Invalid source-protocol: {}", other)),
+                                };
+                                conditions.push(MatchCondition::SourceProtocolEquals(source));
+                                i += 3;
+                            }
+                            other => return Err(format!("Invalid match keyword: {}", other)),
+                        }
+                    }
+                    "set" => {
+                        let kind = *rest.get(i + 1).ok_or("Usage: set {distance|metric|tag} <value>")?;
+                        let value = *rest.get(i + 2).ok_or("Usage: set {distance|metric|tag} <value>")?;
+                        let value: u32 = value.parse().map_err(|_| format!("Invalid value: {}", value))?;
+                        match kind {
+                            "distance" => set.distance = Some(value),
+                            "metric" => set.metric = Some(value),
+                            "tag" => set.tag = Some(value),
+                            other => return Err(format!("Invalid set keyword: {}", other)),
+                        }
+                        i += 3;
+                    }
+                    other => return Err(format!("Unexpected token in route-map clause: {}", other)),
+                }
+            }
+
+            let mut filters = ROUTE_FILTERS.lock().unwrap();
+            filters
+                .entry(name.clone())
+                .or_insert_with(|| RouteFilter::new(&name))
+                .set_clause(FilterClause { sequence, action, conditions, set });
+
+            println!("route-map {} {} {} updated.", name, args[1], sequence);
+            Ok(())
+        },
+    });
 
     commands.insert("deny", Command {
         name: "deny",
         description: "Add a deny entry to the ACL (standard or extended)",
+        args: vec![
+            ArgSpec::required("source-ip"),
+            ArgSpec::optional("wildcard-mask"),
+        ],
+        help: "Add a deny entry to the ACL (standard or extended)",
+        usage: None,
+        modes: &[Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new())],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             match &context.current_mode {
                 // Standard ACL Mode
@@ -2422,9 +5227,18 @@ Two styles of help are provided:
     commands.insert("permit", Command {
         name: "permit",
         description: "Add a permit entry to the ACL (standard or extended)",
+        args: vec![
+            ArgSpec::required("source-ip"),
+            ArgSpec::optional("wildcard-mask"),
+        ],
+        help: "Add a permit entry to the ACL (standard or extended)",
+        usage: None,
+        modes: &[Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new())],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             match &context.current_mode {
                 // Standard ACL Mode
@@ -2482,59 +5296,268 @@ Two styles of help are provided:
                             destination_port = args.get(6).map(|p| p.to_string()); 
                         }
 
-                        let entry = AclEntry {
-                            action: "permit".to_string(),
-                            protocol,
-                            source,
-                            source_operator,
-                            source_port,
-                            destination,
-                            destination_operator,
-                            destination_port,
-                            matches: None,
-                        };
+                        let entry = AclEntry {
+                            action: "permit".to_string(),
+                            protocol,
+                            source,
+                            source_operator,
+                            source_port,
+                            destination,
+                            destination_operator,
+                            destination_port,
+                            matches: None,
+                        };
+
+                        let mut acl_store = ACL_STORE.lock().unwrap();
+                        if let Some(acl) = acl_store.get_mut(acl_name) {
+                            acl.entries.push(entry);
+                            println!("Permit entry added to extended ACL '{}'.", acl_name);
+                            Ok(())
+                        } else {
+                            Err(format!("ACL '{}' not found.", acl_name).into())
+                        }
+                    } else {
+                        Err("Invalid syntax. Use 'permit <protocol> <src_ip> <dest_ip>' or 'permit <protocol> <src_ip> <eq|gt|lt> <src_port> <dest_ip> <eq|gt|lt> <dest_port>'.".into())
+                    }
+                }
+                
+                _ => Err("This command is only available in ACL configuration mode.".into()),
+            }
+        },
+    });
+
+
+    commands.insert("test", Command {
+        name: "test",
+        description: "Test a packet against a configured ACL and report the matching rule",
+        args: vec![
+            ArgSpec::required("access-list"),
+            ArgSpec::required("acl-name"),
+            ArgSpec::required("source-ip"),
+            ArgSpec::optional("destination-ip"),
+            ArgSpec::optional("protocol"),
+            ArgSpec::optional("destination-port"),
+        ],
+        help: "Test a packet against a configured ACL and report the matching rule",
+        usage: None,
+        modes: &[Mode::PrivilegedMode],
+        suggestions: Some(vec!["access-list"]),
+        suggestions1: Some(vec!["access-list"]),
+        require_subcommand: true,
+        options: Some(vec!["<acl-name> <source-ip> [destination-ip] [protocol] [destination-port]  - Evaluate the packet against the ACL"]),
+        aliases: vec![],
+        execute: |args, _context, _| {
+            if args.len() < 3 || args[0] != "access-list" {
+                return Err("Invalid syntax. Use 'test access-list <name> <src-ip> [dst-ip] [proto] [dport]'.".into());
+            }
+
+            let acl_name = args[1];
+            let source_ip = args[2];
+            let destination_ip = args.get(3).copied();
+            let protocol = args.get(4).copied();
+            let destination_port = args.get(5).copied();
+
+            let mut acl_store = ACL_STORE.lock().unwrap();
+            let acl = acl_store
+                .get(acl_name)
+                .ok_or_else(|| format!("ACL '{}' not found.", acl_name))?;
+
+            let result = evaluate_acl(acl, source_ip, destination_ip, protocol, destination_port)?;
+            if let Some(rule_index) = result.matched_rule {
+                let acl = acl_store.get_mut(acl_name).unwrap();
+                let entry = &mut acl.entries[rule_index - 1];
+                entry.matches = Some(entry.matches.unwrap_or(0) + 1);
+            }
+            match result.matched_rule {
+                Some(rule_index) => println!(
+                    "{} (matched rule {} of ACL {})",
+                    if result.permit { "PERMIT" } else { "DENY" },
+                    rule_index,
+                    acl_name
+                ),
+                None => println!("DENY (implicit deny, no rule in ACL {} matched)", acl_name),
+            }
+            Ok(())
+        },
+    });
+
+
+    commands.insert("completions", Command {
+        name: "completions",
+        description: "Print a shell completion script for this CLI's command registry",
+        args: vec![ArgSpec::required("shell")],
+        help: "Print a shell completion script for this CLI's command registry",
+        usage: None,
+        modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
+        suggestions: Some(vec!["bash", "zsh", "fish"]),
+        suggestions1: Some(vec!["bash", "zsh", "fish"]),
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, _context, _| {
+            if args.len() != 1 {
+                return Err("Usage: completions <bash|zsh|fish>".into());
+            }
+
+            let registry = build_command_registry();
+            let script = match args[0] {
+                "bash" => generate_bash_completion(&registry),
+                "zsh" => generate_zsh_completion(&registry),
+                "fish" => generate_fish_completion(&registry),
+                other => return Err(format!("Unsupported shell '{}'. Use bash, zsh, or fish.", other)),
+            };
+            print!("{}", script);
+            Ok(())
+        },
+    });
+
+    // Same data as `completions`, but written straight to a file -- for
+    // wiring the registry into a host shell's completion directory, or for
+    // feeding the JSON tree to external tooling, instead of piping stdout.
+    commands.insert("generate-completions", Command {
+        name: "generate-completions",
+        description: "Write a shell completion script (or a JSON dump of the command tree) to a directory",
+        args: vec![ArgSpec::required("shell"), ArgSpec::required("dir")],
+        help: "Write a shell completion script (or a JSON dump of the command tree) to a directory",
+        usage: None,
+        modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
+        suggestions: Some(vec!["bash", "zsh", "fish", "json"]),
+        suggestions1: Some(vec!["bash", "zsh", "fish", "json"]),
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, _context, _| {
+            if args.len() != 2 {
+                return Err("Usage: generate-completions <bash|zsh|fish|json> <output-dir>".into());
+            }
+
+            let registry = build_command_registry();
+            let (file_name, contents) = match args[0] {
+                "bash" => ("cli-completion.bash", generate_bash_completion(&registry)),
+                "zsh" => ("_cli", generate_zsh_completion(&registry)),
+                "fish" => ("cli.fish", generate_fish_completion(&registry)),
+                "json" => ("cli-commands.json", generate_json_completion(&registry)),
+                other => return Err(format!("Unsupported shell '{}'. Use bash, zsh, fish, or json.", other)),
+            };
 
-                        let mut acl_store = ACL_STORE.lock().unwrap();
-                        if let Some(acl) = acl_store.get_mut(acl_name) {
-                            acl.entries.push(entry);
-                            println!("Permit entry added to extended ACL '{}'.", acl_name);
-                            Ok(())
-                        } else {
-                            Err(format!("ACL '{}' not found.", acl_name).into())
+            let out_dir = Path::new(args[1]);
+            std::fs::create_dir_all(out_dir)
+                .map_err(|err| format!("Failed to create '{}': {}", out_dir.display(), err))?;
+            let out_path = out_dir.join(file_name);
+            std::fs::write(&out_path, contents)
+                .map_err(|err| format!("Failed to write '{}': {}", out_path.display(), err))?;
+            println!("Wrote {}", out_path.display());
+            Ok(())
+        },
+    });
+
+    // Structured discovery companion to `?`: with no args, lists every
+    // command available in the current mode (split into those with
+    // subcommands and plain ones) alongside its one-line description; with
+    // an argument, drills into that command's subcommands, options, and
+    // usage string. Shares its metadata with `?` rather than duplicating it.
+    commands.insert("help", Command {
+        name: "help",
+        description: "List available commands, or show detailed help for one",
+        args: vec![ArgSpec::optional("command")],
+        help: "List available commands, or show detailed help for one",
+        modes: &[Mode::UserMode, Mode::PrivilegedMode, Mode::ConfigMode, Mode::InterfaceMode, Mode::VlanMode, Mode::RouterConfigMode, Mode::RouterBgpMode, Mode::RouterRipMode, Mode::RouterIsisMode, Mode::RouterOspfv3Mode, Mode::ConfigStdNaclMode(String::new()), Mode::ConfigExtNaclMode(String::new()), Mode::LineVtyMode(String::new()), Mode::CryptoIsakmpPolicyMode(0), Mode::CryptoIsakmpGroupMode(String::new()), Mode::DhcpPoolMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: false,
+        options: None,
+        aliases: vec![],
+        usage: Some("help [command]"),
+        execute: |args, context, _| {
+            if args.first() == Some(&"tree") {
+                print_command_tree(context);
+                return Ok(());
+            }
+
+            let registry = build_command_registry();
+
+            if let Some(&name) = args.first() {
+                let cmd = registry
+                    .get(name)
+                    .ok_or_else(|| format!("No such command: '{}'.", name))?;
+                println!("{} - {}", cmd.name, cmd.description);
+                println!("Usage: {}", command_usage(cmd));
+                if let Some(chains) = &cmd.suggestions1 {
+                    if !chains.is_empty() {
+                        println!("Subcommands:");
+                        for chain in chains {
+                            println!("  {}", chain);
                         }
-                    } else {
-                        Err("Invalid syntax. Use 'permit <protocol> <src_ip> <dest_ip>' or 'permit <protocol> <src_ip> <eq|gt|lt> <src_port> <dest_ip> <eq|gt|lt> <dest_port>'.".into())
                     }
                 }
-                
-                _ => Err("This command is only available in ACL configuration mode.".into()),
+                if let Some(options) = &cmd.options {
+                    println!("Options:");
+                    for option in options {
+                        println!("  {}", option);
+                    }
+                }
+                return Ok(());
+            }
+
+            let mut names: Vec<&str> = registry.keys().copied().collect();
+            names.sort();
+            let available: Vec<&Command> = names
+                .into_iter()
+                .filter_map(|name| registry.get(name))
+                .filter(|cmd| command_allowed_in_mode(cmd, &context.current_mode))
+                .collect();
+
+            let (with_subcommands, plain): (Vec<&Command>, Vec<&Command>) = available
+                .into_iter()
+                .partition(|cmd| cmd.suggestions1.as_ref().is_some_and(|chains| !chains.is_empty()));
+
+            println!("Commands with subcommands:");
+            for cmd in &with_subcommands {
+                println!("  {:<20}- {}", cmd.name, cmd.description);
+            }
+
+            println!("\nCommands:");
+            for cmd in &plain {
+                println!("  {:<20}- {}", cmd.name, cmd.description);
             }
+
+            println!("\nType 'help <command>' for subcommands, options, and usage.");
+            Ok(())
         },
     });
 
 
-    // Crypto commands 
+    // Crypto commands
 
     commands.insert("crypto", Command {
         name: "crypto",
         description: "Crypto configuration commands",
+        args: vec![],
+        help: "Crypto configuration commands",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: Some(vec!["ipsec", "key", "certificate", "dynamic-map",
             "engine accelerator", "ipsec security-association lifetime",
-            "ipsec transform-set", "map", "map local-address"]),
+            "ipsec transform-set", "map", "map local-address", "map ipsec-isakmp",
+            "isakmp policy", "isakmp client configuration group", "wizard"]),
         suggestions1: Some(vec!["ipsec", "key", "certificate", "dynamic-map",
             "engine accelerator", "ipsec security-association lifetime",
-            "ipsec transform-set", "map", "map local-address"]),
+            "ipsec transform-set", "map", "map local-address", "map ipsec-isakmp",
+            "isakmp policy", "isakmp client configuration group", "wizard"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if !matches!(context.current_mode, Mode::ConfigMode) {
                 return Err("Crypto commands are only available in Config mode.".into());
             }
-    
+
             if args.is_empty() {
-                return Err("Subcommand required. Available subcommands: 'ipsec profile', 'key'.".into());
+                return Err("Subcommand required. Available subcommands: 'ipsec profile', 'key', 'wizard'.".into());
             }
-    
+
             match &args[0][..] {
+                "wizard" => run_crypto_wizard(context),
                 "ipsec" => {
                     if args.len() >= 2 && args[1] == "profile" {
                         if args.len() == 3 {
@@ -2573,12 +5596,26 @@ Two styles of help are provided:
                         let name = args[2].to_string();
                         let transforms: Vec<String> = args[3..].iter().map(|&s| s.to_string()).collect();
                         context.config.crypto_transform_sets.insert(name.clone(), transforms.clone());
-                        println!("Created transform set '{}' with transforms: {}", 
+                        crate::config_resolve::mark_set_by_command("transform_set");
+                        println!("Created transform set '{}' with transforms: {}",
                             name, transforms.join(", "));
+                        crate::ipsec_sim::try_negotiate(context)?;
+                        Ok(())
+                    } else if args[1] == "nat-traversal" {
+                        if args.len() != 3 || args[2] != "igd" {
+                            return Err("Usage: crypto ipsec nat-traversal igd".into());
+                        }
+                        let profile_name = context.config.crypto_ipsec_profile.clone()
+                            .ok_or("No active IPsec profile. Use 'crypto ipsec profile <profile-name>' first.")?;
+                        let mapping = crate::nat_traversal::enable(&profile_name)?;
+                        println!(
+                            "NAT traversal enabled for IPsec profile '{}': external address {}, IKE port {}, NAT-T port {}.",
+                            profile_name, mapping.external_address, mapping.ike_external_port, mapping.nat_t_external_port
+                        );
                         Ok(())
                     }
                     else {
-                        Err("Invalid ipsec subcommand. Use 'crypto ipsec profile <profile-name>' or 'crypto ipsec security-association lifetime <s/kb>'.".into())
+                        Err("Invalid ipsec subcommand. Use 'crypto ipsec profile <profile-name>', 'crypto ipsec security-association lifetime <s/kb>', or 'crypto ipsec nat-traversal igd'.".into())
                     }
                 },
                 "key" => {
@@ -2590,49 +5627,57 @@ Two styles of help are provided:
                         "generate" => {
                             if args.len() > 2 && (args[2] == "rsa" || args[2] == "dsa") {
                                 let key_type = args[2];
-                                println!("Enter key size (default is 2048 bits):");
-                                let key_size = 2048; // In production, get this from user input
-                                
+                                let rest = &args[3..];
+                                let exportable = rest.iter().any(|arg| *arg == "exportable");
+                                let key_size = match rest.iter().find(|arg| arg.parse::<u32>().is_ok()) {
+                                    Some(modulus) => modulus
+                                        .parse::<u32>()
+                                        .ok()
+                                        .filter(|bits| (512..=4096).contains(bits))
+                                        .ok_or_else(|| format!("Invalid modulus size '{}'. Must be between 512 and 4096.", modulus))?,
+                                    None => 2048,
+                                };
+
                                 let domain_name = context.config.domain_name.clone();
-                                let key_name = format!("{}.{}", 
-                                    context.config.hostname, 
+                                let key_name = format!("{}.{}",
+                                    context.config.hostname,
                                     domain_name.unwrap_or("default_domain".to_string())
                                 );
-    
+
                                 println!("The name for the keys will be: {}", key_name);
-                                println!("Generating {}-bit {} keys, keys will be non-exportable...", key_size, key_type.to_uppercase());
-    
+                                println!("Generating {}-bit {} keys, keys will be {}exportable...",
+                                    key_size, key_type.to_uppercase(), if exportable { "" } else { "non-" });
+
                                 // Simulate key generation
-                                match generate_crypto_key(&key_name, key_type, key_size) {
-                                    Ok(key_data) => {
-                                        // Store the generated key in context
-                                        context.config.crypto_keys.insert(key_name.clone(), key_data);
+                                match generate_crypto_key(&key_name, key_type, key_size).and_then(|pem| build_crypto_key(pem, exportable)) {
+                                    Ok(key) => {
+                                        // Store the generated key in the active key store
+                                        context.key_store.put(key_name.clone(), key)?;
                                         println!("[OK] {} keys generated successfully.", key_type.to_uppercase());
                                         Ok(())
                                     },
                                     Err(e) => Err(format!("Failed to generate keys: {}", e))
                                 }
                             } else {
-                                Err("Invalid generate command. Use 'crypto key generate <rsa|dsa>'.".into())
+                                Err("Invalid generate command. Use 'crypto key generate <rsa|dsa> [modulus] [exportable]'.".into())
                             }
                         },
                         "zeroize" => {
                             if args.len() > 2 && (args[2] == "rsa" || args[2] == "dsa") {
                                 let key_type = args[2];
                                 let domain_name = context.config.domain_name.clone();
-                                let key_name = format!("{}.{}", 
-                                    context.config.hostname, 
+                                let key_name = format!("{}.{}",
+                                    context.config.hostname,
                                     domain_name.unwrap_or("default_domain".to_string())
                                 );
-    
-                                match delete_crypto_key(&key_name) {
-                                    Ok(_) => {
-                                        // Remove the key from context
-                                        context.config.crypto_keys.remove(&key_name);
+
+                                match context.key_store.delete(&key_name) {
+                                    Some(key) => {
+                                        delete_crypto_key(&key_name, key)?;
                                         println!("[OK] {} keys deleted successfully.", key_type.to_uppercase());
                                         Ok(())
                                     },
-                                    Err(e) => Err(format!("Failed to delete keys: {}", e))
+                                    None => Err(format!("No {} key found for '{}'.", key_type.to_uppercase(), key_name))
                                 }
                             } else {
                                 Err("Invalid zeroize command. Use 'crypto key zeroize <rsa|dsa>'.".into())
@@ -2642,12 +5687,12 @@ Two styles of help are provided:
                             if args.len() > 2 && (args[2] == "rsa" || args[2] == "dsa") {
                                 let key_type = args[2];
                                 println!("Enter the key data (paste the key content, end with a blank line):");
-                                
+
                                 // In production, implement actual key import logic
-                                match import_crypto_key(key_type) {
-                                    Ok(key_data) => {
+                                match import_crypto_key(key_type).and_then(|pem| build_crypto_key(pem, false)) {
+                                    Ok(key) => {
                                         let key_name = format!("imported_{}", key_type);
-                                        context.config.crypto_keys.insert(key_name.clone(), key_data);
+                                        context.key_store.put(key_name.clone(), key)?;
                                         println!("[OK] {} key imported successfully.", key_type.to_uppercase());
                                         Ok(())
                                     },
@@ -2657,7 +5702,44 @@ Two styles of help are provided:
                                 Err("Invalid import command. Use 'crypto key import <rsa|dsa>'.".into())
                             }
                         },
-                        _ => Err("Invalid key subcommand. Available subcommands: 'generate rsa', 'zeroize rsa'.".into())
+                        "export" => {
+                            if args.len() > 2 && (args[2] == "rsa" || args[2] == "dsa") {
+                                let domain_name = context.config.domain_name.clone();
+                                let key_name = format!("{}.{}",
+                                    context.config.hostname,
+                                    domain_name.unwrap_or("default_domain".to_string())
+                                );
+
+                                match context.key_store.get(&key_name) {
+                                    Some(key) if key.exportable => {
+                                        let public_pem = export_public_key(&key.pem)?;
+                                        println!("{}", public_pem);
+                                        Ok(())
+                                    },
+                                    Some(_) => Err(format!("Key '{}' was generated non-exportable; it cannot be exported.", key_name)),
+                                    None => Err(format!("No key found for '{}'.", key_name)),
+                                }
+                            } else {
+                                Err("Invalid export command. Use 'crypto key export <rsa|dsa>'.".into())
+                            }
+                        },
+                        "storage" => {
+                            if args.len() != 4 || args[2] != "file" {
+                                return Err("Usage: crypto key storage file <path>".into());
+                            }
+                            let path = PathBuf::from(args[3]);
+                            println!("Enter the passphrase to encrypt/decrypt the key store at '{}':", args[3]);
+                            let passphrase = read_password().unwrap_or_default();
+                            let mut store = crate::keystore::EncryptedFileKeyStore::open(path, passphrase)
+                                .map_err(|e| format!("Failed to open encrypted key store: {}", e))?;
+                            if store.snapshot().is_empty() {
+                                store.load_snapshot(context.key_store.snapshot());
+                            }
+                            context.key_store = Box::new(store);
+                            println!("[OK] Crypto key storage switched to encrypted file '{}'.", args[3]);
+                            Ok(())
+                        },
+                        _ => Err("Invalid key subcommand. Available subcommands: 'generate rsa', 'zeroize rsa', 'export rsa', 'storage file <path>'.".into())
                     }
                 },
                 "certificate" => {
@@ -2667,14 +5749,22 @@ Two styles of help are provided:
     
                     match &args[1][..] {
                         "generate" => {
-                            if args.len() < 3 {
-                                return Err("Certificate name required. Use 'crypto certificate generate <name>'.".into());
+                            if args.len() < 5 || args[3] != "key" {
+                                return Err("Usage: crypto certificate generate <name> key <key-name> [cn <common-name>] [o <organization>] [days <validity-days>]".into());
                             }
                             let cert_name = &args[2];
-                            
-                            match generate_self_signed_certificate(cert_name, &context.config) {
+                            let key_name = &args[4];
+                            let (common_name, organization, validity_days) = match parse_certificate_subject_args(&args[5..]) {
+                                Ok(parsed) => parsed,
+                                Err(e) => return Err(e),
+                            };
+                            let key = context.key_store.get(key_name).cloned().ok_or_else(|| {
+                                format!("Crypto key '{}' not found. Generate or import it with 'crypto key generate'/'crypto key import' first.", key_name)
+                            })?;
+
+                            match generate_self_signed_certificate(cert_name, &context.config, key_name, &key, common_name.as_deref(), organization.as_deref(), validity_days) {
                                 Ok(cert_data) => {
-                                    context.config.certificates.insert(cert_name.to_string(), cert_data);
+                                    context.cert_store.put(cert_name.to_string(), cert_data);
                                     println!("[OK] Self-signed certificate '{}' generated successfully.", cert_name);
                                     Ok(())
                                 },
@@ -2682,12 +5772,20 @@ Two styles of help are provided:
                             }
                         },
                         "request" => {
-                            if args.len() < 3 {
-                                return Err("Certificate name required. Use 'crypto certificate request <name>'.".into());
+                            if args.len() < 5 || args[3] != "key" {
+                                return Err("Usage: crypto certificate request <name> key <key-name> [cn <common-name>] [o <organization>] [days <validity-days>]".into());
                             }
                             let cert_name = &args[2];
-                            
-                            match generate_certificate_request(cert_name, &context.config) {
+                            let key_name = &args[4];
+                            let (common_name, organization, validity_days) = match parse_certificate_subject_args(&args[5..]) {
+                                Ok(parsed) => parsed,
+                                Err(e) => return Err(e),
+                            };
+                            let key = context.key_store.get(key_name).cloned().ok_or_else(|| {
+                                format!("Crypto key '{}' not found. Generate or import it with 'crypto key generate'/'crypto key import' first.", key_name)
+                            })?;
+
+                            match generate_certificate_request(cert_name, &context.config, key_name, &key, common_name.as_deref(), organization.as_deref(), validity_days) {
                                 Ok(csr_data) => {
                                     println!("Certificate signing request for '{}' generated:", cert_name);
                                     println!("{}", csr_data);
@@ -2701,18 +5799,48 @@ Two styles of help are provided:
                                 return Err("Certificate name required. Use 'crypto certificate import <name>'.".into());
                             }
                             let cert_name = &args[2];
-                            
+
                             println!("Enter the certificate data (paste the certificate content, end with a blank line):");
                             match import_certificate(cert_name) {
                                 Ok(cert_data) => {
-                                    context.config.certificates.insert(cert_name.to_string(), cert_data);
+                                    context.cert_store.put(cert_name.to_string(), cert_data);
                                     println!("[OK] Certificate '{}' imported successfully.", cert_name);
                                     Ok(())
                                 },
                                 Err(e) => Err(format!("Failed to import certificate: {}", e))
                             }
                         },
-                        _ => Err("Invalid key subcommand. Available subcommands: 'generate rsa', 'zeroize rsa'.".into())
+                        "acme" => {
+                            if args.get(2) != Some(&"enroll") || args.len() != 6 || args[4] != "email" {
+                                return Err("Usage: crypto certificate acme enroll <fqdn> email <address>".into());
+                            }
+                            let fqdn = args[3];
+                            let email = args[5];
+                            println!("Enrolling '{}' via ACME HTTP-01 (this polls the CA and can take a while)...", fqdn);
+                            match crate::acme::acme_enroll(fqdn, email, &context.config) {
+                                Ok(chain_pem) => {
+                                    context.cert_store.put(fqdn.to_string(), chain_pem);
+                                    println!("[OK] ACME certificate for '{}' issued and stored.", fqdn);
+                                    Ok(())
+                                },
+                                Err(e) => Err(format!("ACME enrollment failed: {}", e))
+                            }
+                        },
+                        "storage" => {
+                            if args.len() != 4 || args[2] != "file" {
+                                return Err("Usage: crypto certificate storage file <path>".into());
+                            }
+                            let path = PathBuf::from(args[3]);
+                            let mut store = crate::keystore::FileCertStore::open(path)
+                                .map_err(|e| format!("Failed to open certificate store: {}", e))?;
+                            if store.snapshot().is_empty() {
+                                store.load_snapshot(context.cert_store.snapshot());
+                            }
+                            context.cert_store = Box::new(store);
+                            println!("[OK] Certificate storage switched to file '{}'.", args[3]);
+                            Ok(())
+                        },
+                        _ => Err("Invalid key subcommand. Available subcommands: 'generate rsa', 'zeroize rsa', 'storage file <path>'.".into())
                     }
                 },
                 "dynamic-map" => {
@@ -2742,18 +5870,19 @@ Two styles of help are provided:
                         None
                     };
                     context.config.crypto_engine_accelerator = slot;
-                    println!("IPSec accelerator {} configured", 
+                    crate::config_resolve::mark_set_by_command("crypto_engine_accelerator");
+                    println!("IPSec accelerator {} configured",
                         slot.map_or("default".to_string(), |s| s.to_string()));
                     Ok(())
                 },
                 "map" => {
                     if args.len() < 3 {
-                        return Err("Usage: crypto map <map-name> <seq-num> ipsec-manual".into());
+                        return Err("Usage: crypto map <map-name> <seq-num> {ipsec-manual|ipsec-isakmp}".into());
                     }
                     let name = args[1].to_string();
                     let seq_num = args[2].parse::<u32>()
                         .map_err(|_| "Invalid sequence number")?;
-    
+
                     if args.get(3) == Some(&"local-address") {
                         if args.len() < 5 {
                             return Err("Usage: crypto map <map-name> <seq-num> local-address <interface-id>".into());
@@ -2761,29 +5890,352 @@ Two styles of help are provided:
                         let interface_id = args[4].to_string();
                         context.config.crypto_local_addresses.insert(name.clone(), interface_id.clone());
                         println!("Set local address interface '{}' for crypto map '{}'", interface_id, name);
+                        Ok(())
+                    } else if args.get(3) == Some(&"ipsec-isakmp") {
+                        let mut peer = None;
+                        let mut transform_set = None;
+                        let mut match_acl = None;
+                        let mut index = 4;
+                        while index < args.len() {
+                            match args[index] {
+                                "peer" if index + 1 < args.len() => {
+                                    peer = Some(args[index + 1].to_string());
+                                    index += 2;
+                                }
+                                "transform-set" if index + 1 < args.len() => {
+                                    let ts_name = args[index + 1].to_string();
+                                    if !context.config.crypto_transform_sets.contains_key(&ts_name) {
+                                        return Err(format!(
+                                            "Transform set '{}' does not exist. Use 'crypto ipsec transform-set' first.",
+                                            ts_name
+                                        ));
+                                    }
+                                    transform_set = Some(ts_name);
+                                    index += 2;
+                                }
+                                "match" if args.get(index + 1) == Some(&"address") && index + 2 < args.len() => {
+                                    let acl_name = args[index + 2].to_string();
+                                    if !ACL_STORE.lock().unwrap().contains_key(&acl_name) {
+                                        return Err(format!(
+                                            "Access list '{}' does not exist. Use 'ip access-list' first.",
+                                            acl_name
+                                        ));
+                                    }
+                                    match_acl = Some(acl_name);
+                                    index += 3;
+                                }
+                                other => {
+                                    return Err(format!("Invalid crypto map keyword: '{}'.", other));
+                                }
+                            }
+                        }
+                        let entry = CryptoMapEntry {
+                            name: name.clone(),
+                            seq_num,
+                            interface_id: None,
+                            map_type: Some("ipsec-isakmp".to_string()),
+                            peer,
+                            transform_set,
+                            match_acl,
+                        };
+                        context.config.crypto_maps.insert(name.clone(), entry);
+                        println!("Created crypto map entry '{}' with sequence number {} (ipsec-isakmp)", name, seq_num);
+                        crate::ipsec_sim::try_negotiate(context)?;
+                        Ok(())
                     } else {
                         let entry = CryptoMapEntry {
                             name: name.clone(),
                             seq_num,
                             interface_id: None,
+                            map_type: None,
+                            peer: None,
+                            transform_set: None,
+                            match_acl: None,
                         };
                         context.config.crypto_maps.insert(name.clone(), entry);
                         println!("Created crypto map entry '{}' with sequence number {}", name, seq_num);
+                        Ok(())
+                    }
+                },
+                "isakmp" => {
+                    if args.len() < 2 {
+                        return Err("Usage: crypto isakmp policy <priority> | crypto isakmp client configuration group <name>".into());
+                    }
+                    match &args[1][..] {
+                        "policy" => {
+                            if args.len() != 3 {
+                                return Err("Usage: crypto isakmp policy <priority>".into());
+                            }
+                            let priority = args[2].parse::<u32>()
+                                .map_err(|_| "Invalid policy priority. Must be a positive integer.")?;
+                            context.config.isakmp_policies.entry(priority).or_insert_with(IsakmpPolicy::default);
+                            context.current_mode = Mode::CryptoIsakmpPolicyMode(priority);
+                            context.prompt = format!("{}(config-isakmp)#", context.config.hostname);
+                            println!("Entering ISAKMP policy configuration mode for policy {}.", priority);
+                            Ok(())
+                        }
+                        "client" => {
+                            if args.len() != 5 || args[2] != "configuration" || args[3] != "group" {
+                                return Err("Usage: crypto isakmp client configuration group <name>".into());
+                            }
+                            let group_name = args[4].to_string();
+                            context.config.isakmp_client_groups.entry(group_name.clone()).or_insert_with(IsakmpClientGroup::default);
+                            context.current_mode = Mode::CryptoIsakmpGroupMode(group_name.clone());
+                            context.prompt = format!("{}(config-isakmp-group)#", context.config.hostname);
+                            println!("Entering ISAKMP client configuration group mode for '{}'.", group_name);
+                            Ok(())
+                        }
+                        _ => Err("Invalid isakmp subcommand. Use 'policy <priority>' or 'client configuration group <name>'.".into())
                     }
-                    Ok(())
                 },
 
                 _ => Err("Invalid crypto subcommand. Available subcommands: 'ipsec profile', 'key'.".into())
             }
         }
     });
-    
+
+    // ISAKMP policy sub-mode commands, entered via 'crypto isakmp policy <n>'.
+
+    commands.insert("encryption", Command {
+        name: "encryption",
+        description: "Set the encryption algorithm for the current ISAKMP policy",
+        args: vec![ArgSpec::required("algorithm").of_kind(ArgKind::Keyword(&["aes", "3des"]))],
+        help: "Set the encryption algorithm for the current ISAKMP policy",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpPolicyMode(0)],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let priority = match context.current_mode {
+                Mode::CryptoIsakmpPolicyMode(priority) => priority,
+                _ => return Err("The 'encryption' command is only available in ISAKMP policy configuration mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: encryption {aes|3des}".into());
+            }
+            let policy = context.config.isakmp_policies.entry(priority).or_insert_with(IsakmpPolicy::default);
+            policy.encryption = Some(args[0].to_string());
+            println!("Encryption algorithm set to {}.", args[0]);
+            Ok(())
+        },
+    });
+
+    commands.insert("hash", Command {
+        name: "hash",
+        description: "Set the hash algorithm for the current ISAKMP policy",
+        args: vec![ArgSpec::required("algorithm").of_kind(ArgKind::Keyword(&["sha", "md5"]))],
+        help: "Set the hash algorithm for the current ISAKMP policy",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpPolicyMode(0)],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let priority = match context.current_mode {
+                Mode::CryptoIsakmpPolicyMode(priority) => priority,
+                _ => return Err("The 'hash' command is only available in ISAKMP policy configuration mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: hash {sha|md5}".into());
+            }
+            let policy = context.config.isakmp_policies.entry(priority).or_insert_with(IsakmpPolicy::default);
+            policy.hash = Some(args[0].to_string());
+            println!("Hash algorithm set to {}.", args[0]);
+            Ok(())
+        },
+    });
+
+    commands.insert("authentication", Command {
+        name: "authentication",
+        description: "Set the authentication method for the current ISAKMP policy",
+        args: vec![ArgSpec::required("method").of_kind(ArgKind::Keyword(&["pre-share", "rsa-sig"]))],
+        help: "Set the authentication method for the current ISAKMP policy",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpPolicyMode(0)],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let priority = match context.current_mode {
+                Mode::CryptoIsakmpPolicyMode(priority) => priority,
+                _ => return Err("The 'authentication' command is only available in ISAKMP policy configuration mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: authentication {pre-share|rsa-sig}".into());
+            }
+            let policy = context.config.isakmp_policies.entry(priority).or_insert_with(IsakmpPolicy::default);
+            policy.authentication = Some(args[0].to_string());
+            println!("Authentication method set to {}.", args[0]);
+            Ok(())
+        },
+    });
+
+    commands.insert("group", Command {
+        name: "group",
+        description: "Set the Diffie-Hellman group for the current ISAKMP policy",
+        args: vec![ArgSpec::required("group-number").of_kind(ArgKind::Keyword(&["2", "5", "14"]))],
+        help: "Set the Diffie-Hellman group for the current ISAKMP policy",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpPolicyMode(0)],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let priority = match context.current_mode {
+                Mode::CryptoIsakmpPolicyMode(priority) => priority,
+                _ => return Err("The 'group' command is only available in ISAKMP policy configuration mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: group {2|5|14}".into());
+            }
+            let group = args[0].parse::<u32>().map_err(|_| "Invalid group number.".to_string())?;
+            let policy = context.config.isakmp_policies.entry(priority).or_insert_with(IsakmpPolicy::default);
+            policy.group = Some(group);
+            println!("Diffie-Hellman group set to {}.", group);
+            Ok(())
+        },
+    });
+
+    commands.insert("lifetime", Command {
+        name: "lifetime",
+        description: "Set the lifetime (in seconds) for the current ISAKMP policy",
+        args: vec![ArgSpec::required("seconds")],
+        help: "Set the lifetime (in seconds) for the current ISAKMP policy",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpPolicyMode(0)],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let priority = match context.current_mode {
+                Mode::CryptoIsakmpPolicyMode(priority) => priority,
+                _ => return Err("The 'lifetime' command is only available in ISAKMP policy configuration mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: lifetime <seconds>".into());
+            }
+            let seconds = args[0].parse::<u32>().map_err(|_| "Invalid lifetime value. Must be a positive integer.".to_string())?;
+            let policy = context.config.isakmp_policies.entry(priority).or_insert_with(IsakmpPolicy::default);
+            policy.lifetime = Some(seconds);
+            println!("ISAKMP policy lifetime set to {} seconds.", seconds);
+            Ok(())
+        },
+    });
+
+    // ISAKMP client configuration group sub-mode commands, entered via
+    // 'crypto isakmp client configuration group <name>'.
+
+    commands.insert("pool", Command {
+        name: "pool",
+        description: "Bind a local address pool to the current ISAKMP client configuration group",
+        args: vec![ArgSpec::required("pool-name")],
+        help: "Bind a local address pool to the current ISAKMP client configuration group",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpGroupMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let group_name = match &context.current_mode {
+                Mode::CryptoIsakmpGroupMode(name) => name.clone(),
+                _ => return Err("The 'pool' command is only available in ISAKMP client configuration group mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: pool <pool-name>".into());
+            }
+            let pool_name = args[0].to_string();
+            if !context.config.local_pools.contains_key(&pool_name) {
+                return Err(format!(
+                    "Address pool '{}' does not exist. Use 'ip local pool' first.",
+                    pool_name
+                ));
+            }
+            let group = context.config.isakmp_client_groups.entry(group_name).or_insert_with(IsakmpClientGroup::default);
+            group.pool = Some(pool_name.clone());
+            println!("Address pool '{}' bound to this client configuration group.", pool_name);
+            Ok(())
+        },
+    });
+
+    commands.insert("dns", Command {
+        name: "dns",
+        description: "Set the DNS server pushed to clients by the current ISAKMP client configuration group",
+        args: vec![ArgSpec::required("ip-address").of_kind(ArgKind::Ip)],
+        help: "Set the DNS server pushed to clients by the current ISAKMP client configuration group",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpGroupMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let group_name = match &context.current_mode {
+                Mode::CryptoIsakmpGroupMode(name) => name.clone(),
+                _ => return Err("The 'dns' command is only available in ISAKMP client configuration group mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: dns <ip-address>".into());
+            }
+            let group = context.config.isakmp_client_groups.entry(group_name).or_insert_with(IsakmpClientGroup::default);
+            group.dns = Some(args[0].to_string());
+            println!("DNS server set to {} for this client configuration group.", args[0]);
+            Ok(())
+        },
+    });
+
+    commands.insert("key", Command {
+        name: "key",
+        description: "Set the pre-shared key for the current ISAKMP client configuration group",
+        args: vec![ArgSpec::required("secret")],
+        help: "Set the pre-shared key for the current ISAKMP client configuration group",
+        usage: None,
+        modes: &[Mode::CryptoIsakmpGroupMode(String::new())],
+        suggestions: None,
+        suggestions1: None,
+        require_subcommand: true,
+        options: None,
+        aliases: vec![],
+        execute: |args, context, _| {
+            let group_name = match &context.current_mode {
+                Mode::CryptoIsakmpGroupMode(name) => name.clone(),
+                _ => return Err("The 'key' command is only available in ISAKMP client configuration group mode.".into()),
+            };
+            if args.len() != 1 {
+                return Err("Usage: key <secret>".into());
+            }
+            let group = context.config.isakmp_client_groups.entry(group_name).or_insert_with(IsakmpClientGroup::default);
+            group.key = Some(args[0].to_string());
+            println!("Pre-shared key configured for this client configuration group.");
+            Ok(())
+        },
+    });
+
     commands.insert("set", Command {
         name: "set transform-set",
         description: "Specifies which transform sets can be used with the crypto map entry.",
+        args: vec![],
+        help: "Specifies which transform sets can be used with the crypto map entry.",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: Some(vec!["transform-set"]),
         suggestions1: Some(vec!["transform-set"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::ConfigMode) {
                 if args.len() == 1 {
@@ -2802,9 +6254,15 @@ Two styles of help are provided:
     commands.insert("service", Command {
         name: "service password-encryption",
         description: "Enable password encryption",
+        args: vec![],
+        help: "Enable password encryption",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: Some(vec!["password-encryption"]),
         suggestions1: Some(vec!["password-encryption"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |args, context, _| {
             if matches!(context.current_mode, Mode::ConfigMode) {
                 if args.len() == 1 && args[0] == "password-encryption" {
@@ -2820,8 +6278,10 @@ Two styles of help are provided:
                     }
                     
                     if let Some(secret) = stored_secret {
-                        let encrypted_secret = encrypt_password(&secret);
-                        context.config.encrypted_secret = Some(encrypted_secret);  // Update encrypted secret
+                        // `enable_secret` is already a Type 9 scrypt hash from
+                        // `set_enable_secret`, so it's shown as-is rather than
+                        // hashed a second time.
+                        context.config.encrypted_secret = Some(secret);
                     }
         
                     context.config.password_encryption = true;
@@ -2842,60 +6302,146 @@ Two styles of help are provided:
     commands.insert("tunnel", Command {
         name: "tunnel",
         description: "Configures the tunnel interface with multiple parameters (mode, source, destination, protection, virtual-template).",
-        suggestions: Some(vec!["mode", "source", "destination", "protection"]),
-        suggestions1: Some(vec!["mode", "source", "destination", "protection"]),
+        args: vec![],
+        help: "Configures the tunnel interface with multiple parameters (mode, source, destination, protection, virtual-template).",
+        usage: None,
+        modes: &[Mode::ConfigMode, Mode::InterfaceMode],
+        suggestions: Some(vec!["mode", "source", "destination", "protection", "key", "ttl"]),
+        suggestions1: Some(vec!["mode", "source", "destination", "protection", "key", "ttl"]),
+        require_subcommand: true,
         options: None,
+        aliases: vec![],
         execute: |_args, context, _| {
-            if matches!(context.current_mode, Mode::ConfigMode) {
-                if _args.is_empty() {
-                    return Err("Invalid arguments. Please specify a subcommand like 'mode', 'source', 'destination', 'protection', or 'virtual-template'.".into());
-                }
-    
-                match &_args[0] as &str {
-                    "mode" => {
-                        if _args.len() == 3 && _args[1] == "ipsec" && _args[2] == "ipv4" {
-                            context.config.tunnel_mode = Some("ipsec ipv4".to_string());
-                            println!("Tunnel mode set to IPsec IPv4.");
-                            Ok(())
-                        } else {
-                            Err("Invalid arguments for 'mode'. Use 'mode ipsec ipv4'.".into())
-                        }
+            match context.current_mode {
+                Mode::ConfigMode => {
+                    if _args.is_empty() {
+                        return Err("Invalid arguments. Please specify a subcommand like 'mode', 'source', 'destination', 'protection', or 'virtual-template'.".into());
                     }
-                    "source" => {
-                        if _args.len() == 2 {
-                            let source_interface = &_args[1];
-                            context.config.tunnel_source = Some(source_interface.to_string());
-                            println!("Tunnel source interface set to '{}'.", source_interface);
-                            Ok(())
-                        } else {
-                            Err("Invalid arguments for 'source'. Use 'source <interface>'.".into())
-                        }
-                    },
-                    "destination" => {
-                        if _args.len() == 2 {
-                            let destination_ip: Ipv4Addr = Ipv4Addr::from_str(&_args[1]).expect("Invalid IP address format");
-                            context.config.tunnel_destination = Some(destination_ip.to_string());
-                            println!("Tunnel destination IP address set to '{}'.", destination_ip);
-                            Ok(())
-                        } else {
-                            Err("Invalid arguments for 'destination'. Use 'destination <ip-address>'.".into())
+
+                    match &_args[0] as &str {
+                        "mode" => {
+                            if _args.len() == 3 && _args[1] == "ipsec" && _args[2] == "ipv4" {
+                                context.config.tunnel_mode = Some("ipsec ipv4".to_string());
+                                println!("Tunnel mode set to IPsec IPv4.");
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'mode'. Use 'mode ipsec ipv4'.".into())
+                            }
                         }
-                    },
-                    "protection" => {
-                        if _args.len() == 4 && _args[1] == "ipsec" && _args[2] == "profile" {
-                            let profile_name = &_args[3];
-                            context.config.tunnel_protection_profile = Some(profile_name.to_string());
-                            println!("Tunnel protection associated with IPsec profile '{}'.", profile_name);
+                        "source" => {
+                            if _args.len() == 2 {
+                                let source_interface = &_args[1];
+                                context.config.tunnel_source = Some(source_interface.to_string());
+                                crate::config_resolve::mark_set_by_command("tunnel_source");
+                                println!("Tunnel source interface set to '{}'.", source_interface);
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'source'. Use 'source <interface>'.".into())
+                            }
+                        },
+                        "destination" => {
+                            if _args.len() == 2 {
+                                let destination_ip: Ipv4Addr = Ipv4Addr::from_str(&_args[1]).expect("Invalid IP address format");
+                                context.config.tunnel_destination = Some(destination_ip.to_string());
+                                crate::config_resolve::mark_set_by_command("tunnel_destination");
+                                println!("Tunnel destination IP address set to '{}'.", destination_ip);
+                                crate::ipsec_sim::try_negotiate(context)?;
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'destination'. Use 'destination <ip-address>'.".into())
+                            }
+                        },
+                        "protection" => {
+                            if _args.len() == 4 && _args[1] == "ipsec" && _args[2] == "profile" {
+                                let profile_name = &_args[3];
+                                context.config.tunnel_protection_profile = Some(profile_name.to_string());
+                                println!("Tunnel protection associated with IPsec profile '{}'.", profile_name);
+                                crate::ipsec_sim::try_negotiate(context)?;
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'protection'. Use 'protection ipsec profile <profile-name>'.".into())
+                            }
+                        },
+
+                        _ => Err("Invalid subcommand. Use 'mode', 'source', 'destination' or 'protection'.".into()),
+                    }
+                },
+                Mode::InterfaceMode => {
+                    let interface = match &context.selected_interface {
+                        Some(interface) if interface.to_lowercase().starts_with("tunnel") => interface.clone(),
+                        Some(_) => return Err("The 'tunnel' command is only available on a tunnel interface.".into()),
+                        None => return Err("No interface selected. Use the 'interface' command first.".into()),
+                    };
+                    if _args.is_empty() {
+                        return Err("Invalid arguments. Please specify a subcommand: 'mode', 'source', 'destination', 'key', or 'ttl'.".into());
+                    }
+
+                    let mut tunnels = TUNNEL_CONFIG.lock().unwrap();
+                    let tunnel = tunnels.entry(interface.clone()).or_insert_with(TunnelInterface::default);
+
+                    match &_args[0] as &str {
+                        "mode" => {
+                            let mode = match &_args[1..] {
+                                ["gre"] => "gre".to_string(),
+                                ["ipip"] => "ipip".to_string(),
+                                ["gre", "multipoint"] => "gre multipoint".to_string(),
+                                _ => return Err("Invalid arguments for 'mode'. Use 'tunnel mode gre', 'tunnel mode ipip', or 'tunnel mode gre multipoint'.".into()),
+                            };
+                            tunnel.mode = Some(mode.clone());
+                            println!("Tunnel interface {} mode set to {}.", interface, mode);
                             Ok(())
-                        } else {
-                            Err("Invalid arguments for 'protection'. Use 'protection ipsec profile <profile-name>'.".into())
-                        }
-                    },
-                    
-                    _ => return Err("Invalid subcommand. Use 'mode', 'source', 'destination' or 'protection'.".into()),
-                }
-            } else {
-                Err("The 'tunnel' command is only available in Config mode.".into())
+                        },
+                        "source" => {
+                            if _args.len() == 2 {
+                                tunnel.source = Some(_args[1].to_string());
+                                tunnel.up = tunnel.source.is_some() && tunnel.destination.is_some();
+                                println!("Tunnel source set to '{}'.", _args[1]);
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'source'. Use 'tunnel source {<ip-address>|<interface>}'.".into())
+                            }
+                        },
+                        "destination" => {
+                            if _args.len() == 2 {
+                                let destination_ip: Ipv4Addr = _args[1]
+                                    .parse()
+                                    .map_err(|_| "Invalid IP address format.".to_string())?;
+                                tunnel.destination = Some(destination_ip);
+                                tunnel.up = tunnel.source.is_some() && tunnel.destination.is_some();
+                                println!("Tunnel destination set to '{}'.", destination_ip);
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'destination'. Use 'tunnel destination <ip-address>'.".into())
+                            }
+                        },
+                        "key" => {
+                            if _args.len() == 2 {
+                                let key_id: u32 = _args[1]
+                                    .parse()
+                                    .map_err(|_| "Invalid key. Must be a positive integer.".to_string())?;
+                                tunnel.key = Some(key_id);
+                                println!("Tunnel key set to {}.", key_id);
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'key'. Use 'tunnel key <key-id>'.".into())
+                            }
+                        },
+                        "ttl" => {
+                            if _args.len() == 2 {
+                                let ttl: u8 = _args[1]
+                                    .parse()
+                                    .map_err(|_| "Invalid TTL. Must be between 0 and 255.".to_string())?;
+                                tunnel.ttl = Some(ttl);
+                                println!("Tunnel TTL set to {}.", ttl);
+                                Ok(())
+                            } else {
+                                Err("Invalid arguments for 'ttl'. Use 'tunnel ttl <ttl-value>'.".into())
+                            }
+                        },
+                        _ => Err("Invalid subcommand. Use 'mode', 'source', 'destination', 'key', or 'ttl'.".into()),
+                    }
+                },
+                _ => Err("The 'tunnel' command is only available in Config mode or Interface Configuration mode.".into()),
             }
         },
     });
@@ -2903,9 +6449,15 @@ Two styles of help are provided:
     commands.insert("virtual-template", Command {
         name: "virtual-template",
         description: "Enter interface configuration mode for a virtual-template interface",
+        args: vec![ArgSpec::required("template-number")],
+        help: "Enter interface configuration mode for a virtual-template interface",
+        usage: None,
+        modes: &[Mode::ConfigMode],
         suggestions: None,
         suggestions1: None,
+        require_subcommand: true,
         options: Some(vec!["<template-number>       - Enter the template number"]),
+        aliases: vec![],
         execute: |_args, context, _| {
             if matches!(context.current_mode, Mode::ConfigMode) {
                 if _args.len() == 1 {
@@ -3,7 +3,11 @@ use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use crate::execute::Mode;
 use crate::network_config::NtpAssociation;
-use crate::cryptocommands::{DynamicMapEntry,IPSecLifetime, CryptoMapEntry};
+use crate::cryptocommands::{DynamicMapEntry, IPSecLifetime, CryptoMapEntry, IsakmpPolicy, IsakmpClientGroup, AddressPool, CryptoKey};
+use crate::dhcpcommands::DhcpPool;
+use crate::ntp_auth::NtpAuthKey;
+use std::net::Ipv4Addr;
+use crate::terminal_settings::TerminalSettings;
 
 
 /// Represents the configuration for the CLI application.
@@ -32,14 +36,17 @@ pub struct CliConfig {
     pub tunnel_destination: Option<String>,  
     pub tunnel_protection_profile: Option<String>, 
     pub virtual_template: Option<String>,  
-    pub enable_password: Option<String>,          
-    pub enable_secret: Option<String>,  
-    pub encrypted_password: Option<String>,          
+    pub enable_password: Option<String>,
+    pub encrypted_password: Option<String>,
     pub encrypted_secret: Option<String>,          
     pub password_encryption: bool,
+    /// Whether `ipv6 unicast-routing` has been configured, gating IPv6
+    /// forwarding the same way `ip routing` gates IPv4 (the Cisco default
+    /// for IPv6 is disabled until explicitly enabled).
+    pub ipv6_unicast_routing: bool,
     pub domain_name: Option<String>,
     pub last_written: Option<String>, 
-    pub crypto_keys: HashMap<String, String>,
+    pub crypto_keys: HashMap<String, CryptoKey>,
     pub certificates: HashMap<String, String>,  
     pub crypto_dynamic_maps: HashMap<String, DynamicMapEntry>,
     pub crypto_engine_accelerator: Option<u32>, 
@@ -47,7 +54,55 @@ pub struct CliConfig {
     pub crypto_transform_sets: HashMap<String, Vec<String>>,
     pub crypto_maps: HashMap<String, CryptoMapEntry>,
     pub crypto_local_addresses: HashMap<String, String>,
-  
+    /// Event name (e.g. `"config-saved"`, `"interface-state-changed"`,
+    /// `"ospf-adjacency-changed"`) to the external script invoked when that
+    /// event occurs, via [`crate::hooks::run_hook`]. Persisted so hooks
+    /// survive a reload.
+    pub hook_scripts: HashMap<String, String>,
+    /// rustyline editor settings configured via the `terminal` command,
+    /// re-applied to the running `Editor` after every command.
+    pub terminal_settings: TerminalSettings,
+    /// SNMP community strings configured via `snmp-server community <string>
+    /// {ro|rw}`, mapping the community string to its access level (`"ro"` or
+    /// `"rw"`).
+    pub snmp_communities: HashMap<String, String>,
+    /// Trap/notification destinations configured via `snmp-server host <ip>
+    /// version {1|2c} <community>`.
+    pub snmp_hosts: Vec<SnmpHost>,
+    /// The sysLocation string configured via `snmp-server location <text>`.
+    pub snmp_location: Option<String>,
+    /// The sysContact string configured via `snmp-server contact <text>`.
+    pub snmp_contact: Option<String>,
+    /// Whether `snmp-server enable traps` has been configured.
+    pub snmp_traps_enabled: bool,
+    /// IKE policies configured via `crypto isakmp policy <n>`, keyed by
+    /// policy number.
+    pub isakmp_policies: HashMap<u32, IsakmpPolicy>,
+    /// IKE mode-config client groups configured via `crypto isakmp client
+    /// configuration group <name>`, keyed by group name.
+    pub isakmp_client_groups: HashMap<String, IsakmpClientGroup>,
+    /// Address pools configured via `ip local pool <name> <start> <end>`,
+    /// keyed by pool name, and referenced by an `IsakmpClientGroup`'s `pool`.
+    pub local_pools: HashMap<String, AddressPool>,
+    /// DHCP server pools configured via `ip dhcp pool <name>`, keyed by pool name.
+    pub dhcp_pools: HashMap<String, DhcpPool>,
+    /// Address ranges excluded from DHCP allocation via `ip dhcp
+    /// excluded-address <start> [<end>]`, as `(start, end)` pairs (`start`
+    /// repeated as `end` when no range was given).
+    pub dhcp_excluded_addresses: Vec<(Ipv4Addr, Ipv4Addr)>,
+    /// Simulated leases handed out by `show ip dhcp binding`, keyed by the
+    /// owning pool's name.
+    pub dhcp_bindings: HashMap<String, Vec<crate::dhcpcommands::DhcpBinding>>,
+
+}
+
+/// A single SNMP trap destination configured via `snmp-server host <ip>
+/// version {1|2c} <community>`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SnmpHost {
+    pub address: String,
+    pub version: String,
+    pub community: String,
 }
 
 
@@ -65,10 +120,21 @@ impl Default for CliConfig {
     /// - `tunnel_destination: None`,
     /// - `tunnel_protection_profile: None`,
     /// - `virtual_template: None`,
-    /// - `enable_password: None`,          
-    /// - `enable_secret: None`,            
-    /// - `password_encryption: false`, 
+    /// - `enable_password: None`,
+    /// - `password_encryption: false`,
+    /// - `ipv6_unicast_routing: false`,
     /// - `domain_name: None`,
+    /// - `snmp_communities: HashMap::new()`,
+    /// - `snmp_hosts: Vec::new()`,
+    /// - `snmp_location: None`,
+    /// - `snmp_contact: None`,
+    /// - `snmp_traps_enabled: false`,
+    /// - `isakmp_policies: HashMap::new()`,
+    /// - `isakmp_client_groups: HashMap::new()`,
+    /// - `local_pools: HashMap::new()`,
+    /// - `dhcp_pools: HashMap::new()`,
+    /// - `dhcp_excluded_addresses: Vec::new()`,
+    /// - `dhcp_bindings: HashMap::new()`,
     fn default() -> Self {
         Self {
             running_config: None,
@@ -81,11 +147,11 @@ impl Default for CliConfig {
             tunnel_destination: None,
             tunnel_protection_profile: None,
             virtual_template: None,
-            enable_password: None,          
-            enable_secret: None,   
-            encrypted_password: None,          
+            enable_password: None,
+            encrypted_password: None,
             encrypted_secret: None,         
-            password_encryption: false, 
+            password_encryption: false,
+            ipv6_unicast_routing: false,
             domain_name: None,
             last_written: None,
             crypto_keys: HashMap::new(),
@@ -96,8 +162,20 @@ impl Default for CliConfig {
             crypto_transform_sets: HashMap::new(),
             crypto_maps: HashMap::new(),
             crypto_local_addresses: HashMap::new(),
-            
-            
+            hook_scripts: HashMap::new(),
+            terminal_settings: TerminalSettings::default(),
+            snmp_communities: HashMap::new(),
+            snmp_hosts: Vec::new(),
+            snmp_location: None,
+            snmp_contact: None,
+            snmp_traps_enabled: false,
+            isakmp_policies: HashMap::new(),
+            isakmp_client_groups: HashMap::new(),
+            local_pools: HashMap::new(),
+            dhcp_pools: HashMap::new(),
+            dhcp_excluded_addresses: Vec::new(),
+            dhcp_bindings: HashMap::new(),
+
         }
     }
 }
@@ -113,7 +191,8 @@ impl Default for CliConfig {
 /// let context = CliContext::default();
 /// assert_eq!(context.prompt, "SEM>");
 /// ```
-/// 
+///
+#[derive(Clone)]
 pub struct CliContext {
     pub current_mode: Mode,
     pub prompt: String,
@@ -128,10 +207,16 @@ pub struct CliContext {
     pub allowed_vlans: HashSet<u16>,
     pub ntp_servers: HashSet<String>,  
     pub ntp_associations: Vec<NtpAssociation>,  
-    pub ntp_authentication_enabled: bool,   
-    pub ntp_authentication_keys: HashMap<u32, String>, 
-    pub ntp_trusted_keys: HashSet<u32>,     
-    pub ntp_master: bool, 
+    pub ntp_authentication_enabled: bool,
+    pub ntp_authentication_keys: HashMap<u32, NtpAuthKey>,
+    pub ntp_trusted_keys: HashSet<u32>,
+    pub ntp_master: bool,
+    /// Backing store for `crypto key`/`crypto certificate` commands --
+    /// in-memory by default, swappable to an encrypted/plain file with
+    /// `crypto key storage file <path>`/`crypto certificate storage file
+    /// <path>`. See [`crate::keystore`].
+    pub key_store: Box<dyn crate::keystore::KeyStore>,
+    pub cert_store: Box<dyn crate::keystore::CertStore>,
 }
 
 
@@ -154,8 +239,9 @@ impl Default for CliContext {
     /// - `ntp_associations: Vec::new()`,
     /// - `ntp_authentication_enabled: false`,   
     /// - `ntp_authentication_keys: HashMap::new()`, 
-    /// - `ntp_trusted_keys: HashSet::new()`,     
+    /// - `ntp_trusted_keys: HashSet::new()`,
     /// - `ntp_master: false,
+    /// - `key_store`/`cert_store`: in-memory, per [`crate::keystore`].
     fn default() -> Self {
         Self {
             current_mode: Mode::UserMode,
@@ -173,8 +259,10 @@ impl Default for CliContext {
             ntp_associations: Vec::new(),
             ntp_authentication_enabled: false,   
             ntp_authentication_keys: HashMap::new(), 
-            ntp_trusted_keys: HashSet::new(),     
+            ntp_trusted_keys: HashSet::new(),
             ntp_master: false,
+            key_store: Box::new(crate::keystore::InMemoryKeyStore::default()),
+            cert_store: Box::new(crate::keystore::InMemoryCertStore::default()),
         }
     }
 }
\ No newline at end of file
@@ -1,40 +1,103 @@
-use chrono::{DateTime, Local, NaiveDateTime, Duration};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, LocalResult, Locale, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Full English month names, indexed by `month - 1`. Shared by `set_date`'s
+/// day-count validation and by the datetime parser/formatter below.
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// Returns `locale`'s long month names (its equivalents of "January"
+/// .."December"), computed via `NaiveDate::format_localized` rather than a
+/// hardcoded-per-locale table, so any locale chrono's `unstable-locales`
+/// feature supports is covered without listing its months here.
+fn locale_month_names(locale: Locale) -> [String; 12] {
+    std::array::from_fn(|i| {
+        NaiveDate::from_ymd_opt(2023, (i + 1) as u32, 1)
+            .expect("month index 0..12 is always a valid NaiveDate day 1")
+            .format_localized("%B", locale)
+            .to_string()
+    })
+}
+
+/// Resolves `token` (a month name in either `locale`'s language or English)
+/// to its 1-based month number, matching case-insensitively against both
+/// the locale's long month names and [`MONTH_NAMES`]. Used so `clock set`
+/// keeps accepting English month names even after `clock locale` switches
+/// the configured display/input locale.
+fn resolve_month_number(token: &str, locale: Locale) -> Option<u32> {
+    let localized = locale_month_names(locale);
+    localized
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(token))
+        .or_else(|| MONTH_NAMES.iter().position(|name| name.eq_ignore_ascii_case(token)))
+        .map(|index| index as u32 + 1)
+}
+
+/// The configured display timezone for a [`Clock`]: either a named IANA zone
+/// looked up in the `chrono-tz` database, or a fixed UTC offset for zones
+/// `clock timezone` is given that aren't in that database.
+#[derive(Clone, Copy)]
+enum ClockTimezone {
+    Named(Tz),
+    Fixed(FixedOffset),
+}
 
 /// Represents a clock with time, date, and additional metadata.
 ///
-/// This struct provides fields for storing the current time, date, and other 
-/// associated details. Some fields are public, while others are internal 
+/// This struct provides fields for storing the current time, date, and other
+/// associated details. Some fields are public, while others are internal
 /// and intended for internal use only.
 pub struct Clock {
     /// The current time as a `String`.
     ///
-    /// This field is publicly accessible and is expected to follow a 
+    /// This field is publicly accessible and is expected to follow a
     /// specific time format (e.g., "HH:MM:SS").
     pub time: String,
 
     /// The current date as a `String`.
     ///
-    /// This field is publicly accessible and is expected to follow a 
+    /// This field is publicly accessible and is expected to follow a
     /// specific date format (e.g., "YYYY-MM-DD").
     pub date: String,
 
-    /// An optional custom date and time value.
-    ///
-    /// This field is used internally to store an optional custom datetime 
-    /// value. It uses the `DateTime` type from the `chrono` crate with the 
-    /// local timezone.
-    custom_datetime: Option<DateTime<Local>>,
+    /// An optional custom date and time value, stored in UTC so it can be
+    /// rendered in whatever `timezone` is currently configured.
+    custom_datetime: Option<DateTime<Utc>>,
 
-    /// The time when the clock started, as a `DateTime<Local>`.
+    /// The time when the clock started, as a UTC instant.
     ///
-    /// This field is used internally to store the start time of the clock. 
+    /// This field is used internally to store the start time of the clock.
     /// It is initialized when the clock is created.
-    start_time: DateTime<Local>,
+    start_time: DateTime<Utc>,
 
     /// The model of the device associated with the clock.
     ///
     /// This is a string identifier for the specific clock model.
     device_model: String,
+
+    /// The zone `show clock`/uptime render in. `None` means "use the host's
+    /// local zone", matching this struct's behavior before `clock timezone`
+    /// existed.
+    timezone: Option<ClockTimezone>,
+
+    /// Display label for a `ClockTimezone::Fixed` zone (the `<name>` given to
+    /// `clock timezone <name> <offset-hours> <offset-minutes>`). Named zones
+    /// don't need this -- their IANA identifier is already a label.
+    timezone_name: Option<String>,
+
+    /// The source of "now" this clock consults instead of calling
+    /// `Utc::now()` inline, so tests can freeze/advance time deterministically
+    /// via [`Clock::with_now_provider`]/[`Clock::set_now_provider`]. Defaults
+    /// to `Utc::now`.
+    now_provider: fn() -> DateTime<Utc>,
+
+    /// The locale `show clock` renders month names in, and `clock set`
+    /// additionally accepts month names from, set via `clock locale`.
+    /// `None` means English, matching this struct's behavior before `clock
+    /// locale` existed.
+    locale: Option<Locale>,
 }
 
 impl Clock {
@@ -42,21 +105,45 @@ impl Clock {
     /// Creates a new instance of `Clock`.
     ///
     /// The clock is initialized with empty `time` and `date` fields, no custom
-    /// datetime, the current local time as the start time, and a default device model
-    /// of "PNF".
+    /// datetime, the current time as the start time, no configured timezone
+    /// (host local time is used until `clock timezone` is run), and a default
+    /// device model of "PNF".
     ///
     /// # Returns
     /// A new `Clock` instance.
     pub fn new() -> Self {
+        Self::with_now_provider(Utc::now)
+    }
+
+    /// Creates a new `Clock` whose notion of "now" comes from `now_provider`
+    /// instead of `Utc::now`, so tests can set a fixed "current time",
+    /// advance it, and assert on uptime/`show clock` output without
+    /// sleeping. `start_time` is taken from `now_provider()` at construction.
+    pub fn with_now_provider(now_provider: fn() -> DateTime<Utc>) -> Self {
         Clock {
             time: String::new(),
             date: String::new(),
             custom_datetime: None,
-            start_time: Local::now(),  
+            start_time: now_provider(),
             device_model: "PNF".to_string(),
+            timezone: None,
+            timezone_name: None,
+            now_provider,
+            locale: None,
         }
     }
 
+    /// Swaps this clock's "now" source after construction, e.g. to freeze
+    /// time in a test. Does not retroactively change `start_time`.
+    pub fn set_now_provider(&mut self, now_provider: fn() -> DateTime<Utc>) {
+        self.now_provider = now_provider;
+    }
+
+    /// The current instant according to this clock's `now_provider`.
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        (self.now_provider)()
+    }
+
     /// Sets the time for the clock.
     ///
     /// The time must be in the format `HH:MM:SS`, where:
@@ -68,20 +155,21 @@ impl Clock {
     /// - `time`: A string slice representing the time to set.
     ///
     /// # Errors
-    /// Returns an error if the time format is invalid or if the values
-    /// exceed their respective ranges.
+    /// Returns an error if the time format is invalid, if the values
+    /// exceed their respective ranges, or if the resulting date/time falls
+    /// in a DST gap or ambiguous transition in the configured timezone.
     pub fn set_time(&mut self, time: &str) -> Result<(), String> {
         if !time.contains(':') || time.split(':').count() != 3 {
             return Err("Invalid time format. Expected HH:MM:SS".to_string());
         }
-        
+
         let parts: Vec<&str> = time.split(':').collect();
         let (hours, minutes, seconds) = (
             parts[0].parse::<u32>().map_err(|_| "Invalid hours")?,
             parts[1].parse::<u32>().map_err(|_| "Invalid minutes")?,
             parts[2].parse::<u32>().map_err(|_| "Invalid seconds")?
         );
-        
+
         if hours >= 24 || minutes >= 60 || seconds >= 60 {
             return Err("Invalid time values".to_string());
         }
@@ -90,9 +178,7 @@ impl Clock {
         self.time = time.to_string();
 
         // Try to update custom_datetime if we have a date
-        self.update_custom_datetime();
-        
-        Ok(())
+        self.update_custom_datetime()
     }
 
     /// Sets the date for the clock.
@@ -107,7 +193,9 @@ impl Clock {
     /// - `year`: The year as a 4-digit number.
     ///
     /// # Errors
-    /// Returns an error if the day is invalid for the given month and year.
+    /// Returns an error if the day is invalid for the given month and year, or
+    /// if the resulting date/time falls in a DST gap or ambiguous transition
+    /// in the configured timezone.
     pub fn set_date(&mut self, day: u8, month: &str, year: u16) -> Result<(), String>  {
         let max_days = match month {
             "February" => if year % 4 == 0 { 29 } else { 28 },
@@ -123,38 +211,121 @@ impl Clock {
         self.date = format!("{} {} {}", day, month, year);
 
         // Try to update custom_datetime if we have a time
-        self.update_custom_datetime();
-        
-        Ok(())
+        self.update_custom_datetime()
+    }
+
+    /// Configures the clock to render in the named IANA timezone (e.g.
+    /// `"America/New_York"`), corresponding to Cisco's
+    /// `clock timezone <Area/City>`.
+    ///
+    /// Re-interprets any already-set `custom_datetime` in the new zone,
+    /// which can fail if that date/time falls in a DST gap or is ambiguous
+    /// there.
+    pub fn set_timezone(&mut self, tz: Tz) -> Result<(), String> {
+        self.timezone = Some(ClockTimezone::Named(tz));
+        self.timezone_name = None;
+        self.update_custom_datetime()
+    }
+
+    /// Configures the clock to render at a fixed `hours`:`minutes` offset
+    /// from UTC under the display label `name`, for zones not present in
+    /// the `chrono-tz` database. Corresponds to Cisco's
+    /// `clock timezone <name> <offset-hours> <offset-minutes>`.
+    pub fn set_timezone_fixed(&mut self, name: &str, hours: i32, minutes: i32) -> Result<(), String> {
+        let sign = if hours < 0 { -1 } else { 1 };
+        let offset_seconds = hours * 3600 + sign * minutes.abs() * 60;
+        let offset = FixedOffset::east_opt(offset_seconds)
+            .ok_or_else(|| format!("Invalid timezone offset {}:{:02}.", hours, minutes.abs()))?;
+
+        self.timezone = Some(ClockTimezone::Fixed(offset));
+        self.timezone_name = Some(name.to_string());
+        self.update_custom_datetime()
+    }
+
+    /// The label `show clock` should print alongside the time, if any: the
+    /// IANA name for a named zone, or the operator-supplied `<name>` for a
+    /// fixed-offset zone.
+    pub fn timezone_label(&self) -> Option<String> {
+        match &self.timezone {
+            Some(ClockTimezone::Named(tz)) => Some(tz.to_string()),
+            Some(ClockTimezone::Fixed(_)) => self.timezone_name.clone(),
+            None => None,
+        }
+    }
+
+    /// Selects the locale `show clock` renders month names in and `clock
+    /// set` additionally accepts month names from, corresponding to Cisco's
+    /// `clock locale <code>`.
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = Some(locale);
+    }
+
+    /// The active locale, defaulting to `Locale::en_US` when `clock locale`
+    /// hasn't been run.
+    pub fn locale(&self) -> Locale {
+        self.locale.unwrap_or(Locale::en_US)
     }
 
     /// Updates the custom datetime field if both the time and date are set.
     ///
     /// This method attempts to parse the `time` and `date` fields into a
-    /// combined `DateTime<Local>` value and updates the `custom_datetime` field.
-    pub fn update_custom_datetime(&mut self) {
-        if !self.time.is_empty() && !self.date.is_empty() {
-            if let Ok(naive_time) = NaiveDateTime::parse_from_str(
-                &format!("{} {}", self.date, self.time),
-                "%d %B %Y %H:%M:%S"
-            ) {
-                self.custom_datetime = Some(DateTime::from_naive_utc_and_offset(
-                    naive_time,
-                    Local::now().offset().clone()
-                ));
-            }
+    /// combined naive datetime, then interprets it in the configured
+    /// timezone (host local time if none is configured) using
+    /// `TimeZone::from_local_datetime` and stores the result as UTC.
+    ///
+    /// # Errors
+    /// Returns an error if the naive date/time is ambiguous (occurs twice,
+    /// during a "fall back" DST transition) or doesn't exist (falls in a
+    /// "spring forward" DST gap) in the configured timezone.
+    fn update_custom_datetime(&mut self) -> Result<(), String> {
+        if self.time.is_empty() || self.date.is_empty() {
+            return Ok(());
         }
+
+        let Ok(naive_time) = NaiveDateTime::parse_from_str(
+            &format!("{} {}", self.date, self.time),
+            "%d %B %Y %H:%M:%S",
+        ) else {
+            return Ok(());
+        };
+
+        let resolve = |result: LocalResult<DateTime<Utc>>| match result {
+            LocalResult::Single(dt) => Ok(dt),
+            LocalResult::Ambiguous(_, _) => Err(format!(
+                "{} is ambiguous in the configured timezone (it occurs twice due to a DST transition).",
+                naive_time
+            )),
+            LocalResult::None => Err(format!(
+                "{} does not exist in the configured timezone (it falls in a DST gap).",
+                naive_time
+            )),
+        };
+
+        let utc = match &self.timezone {
+            Some(ClockTimezone::Named(tz)) => resolve(tz.from_local_datetime(&naive_time).map(|dt| dt.with_timezone(&Utc)))?,
+            Some(ClockTimezone::Fixed(offset)) => resolve(offset.from_local_datetime(&naive_time).map(|dt| dt.with_timezone(&Utc)))?,
+            None => resolve(Local.from_local_datetime(&naive_time).map(|dt| dt.with_timezone(&Utc)))?,
+        };
+
+        self.custom_datetime = Some(utc);
+        Ok(())
     }
 
-    /// Gets the current datetime.
+    /// Gets the current datetime, rendered in the configured timezone (host
+    /// local time if none has been set via `clock timezone`).
     ///
     /// If a custom datetime is set, it is returned. Otherwise, the current
-    /// local time is returned.
+    /// time is returned.
     ///
     /// # Returns
-    /// A `DateTime<Local>` representing the current datetime.
-    pub fn get_current_datetime(&self) -> DateTime<Local> {
-        self.custom_datetime.unwrap_or_else(Local::now)
+    /// A `DateTime<FixedOffset>` representing the current datetime.
+    pub fn get_current_datetime(&self) -> DateTime<FixedOffset> {
+        let utc = self.custom_datetime.unwrap_or_else(|| self.now());
+        match &self.timezone {
+            Some(ClockTimezone::Named(tz)) => utc.with_timezone(tz).fixed_offset(),
+            Some(ClockTimezone::Fixed(offset)) => utc.with_timezone(offset),
+            None => utc.with_timezone(&Local).fixed_offset(),
+        }
     }
 
     /// Calculates the uptime of the clock.
@@ -164,7 +335,7 @@ impl Clock {
     /// # Returns
     /// A `Duration` representing the uptime.
     pub fn get_uptime(&self) -> Duration {
-        Local::now().signed_duration_since(self.start_time)
+        self.now().signed_duration_since(self.start_time)
     }
 
     /// Formats the uptime as a human-readable string.
@@ -177,7 +348,7 @@ impl Clock {
     pub fn format_uptime(&self) -> String {
         let duration = self.get_uptime();
         let total_seconds = duration.num_seconds();
-        
+
         let hours = total_seconds / 3600;
         let minutes = (total_seconds % 3600) / 60;
         let seconds = total_seconds % 60;
@@ -189,115 +360,215 @@ impl Clock {
             seconds
         )
     }
-}
 
+    /// Formats the uptime rolled up into the coarsest sensible unit (e.g.
+    /// "3 days, 4 hours" rather than [`Clock::format_uptime`]'s "76 hours,
+    /// 0 minutes, 0 seconds").
+    ///
+    /// # Returns
+    /// A `String` representing the humanized uptime.
+    pub fn format_uptime_human(&self) -> String {
+        format!("{} uptime is {}", self.device_model, humanize_duration(self.get_uptime()))
+    }
+}
 
-/// Handles the `clock set` command to update the date and time in the `CustomClock` structure.
-///
-/// This function takes an input string in the format `clock set <date> <time>`
-/// and updates the provided `CustomClock` instance with the new values.
-///
-/// # Arguments
-/// - `input`: A string slice containing the command and parameters.
-/// - `clock`: A mutable reference to the `CustomClock` instance to update.
-///
-/// # Usage
-/// ```
-/// let mut clock = CustomClock {
-///     date: "2024-06-01".to_string(),
-///     time: "12:00".to_string(),
-/// };
-/// handle_clock_set("clock set 2024-12-25 08:30", &mut clock);
-/// assert_eq!(clock.date, "2024-12-25");
-/// assert_eq!(clock.time, "08:30");
-/// ```
-///
-/// # Errors
-/// Prints a usage message if the input is not in the expected format.
-/// 
-pub fn handle_clock_set(time: &str, day: u8, month: &str, year: u16, clock: &mut Clock) -> Result<(), String> {
-    if !time.is_empty() {
-        clock.set_time(time)?;
+/// Expresses a `Duration` as a human-readable string in its coarsest
+/// sensible unit, pairing hours, days and weeks with the next-finer unit
+/// (e.g. "3 days, 4 hours") the way `chrono-humanize` rolls up relative
+/// times; seconds, minutes, months and years are given alone. Thresholds:
+/// seconds < 60, minutes < 60, hours < 24, days < 7, weeks < 4, then months
+/// (of 30 days) and years (of 365 days).
+fn humanize_duration(duration: Duration) -> String {
+    let total_seconds = duration.num_seconds().abs();
+
+    fn unit(n: i64, name: &str) -> String {
+        format!("{} {}{}", n, name, if n == 1 { "" } else { "s" })
     }
-    if day != 0 {
-        clock.set_date(day, month, year)?;
+
+    if total_seconds < 60 {
+        unit(total_seconds, "second")
+    } else if total_seconds < 3_600 {
+        unit(total_seconds / 60, "minute")
+    } else if total_seconds < 86_400 {
+        format!("{}, {}", unit(total_seconds / 3_600, "hour"), unit((total_seconds % 3_600) / 60, "minute"))
+    } else if total_seconds < 7 * 86_400 {
+        format!("{}, {}", unit(total_seconds / 86_400, "day"), unit((total_seconds % 86_400) / 3_600, "hour"))
+    } else if total_seconds < 28 * 86_400 {
+        format!("{}, {}", unit(total_seconds / (7 * 86_400), "week"), unit((total_seconds % (7 * 86_400)) / 86_400, "day"))
+    } else if total_seconds < 365 * 86_400 {
+        unit(total_seconds / (30 * 86_400), "month")
+    } else {
+        unit(total_seconds / (365 * 86_400), "year")
     }
-    
-    println!("Clock updated successfully to {} {} {} {}.", time, day, month, year);
-    Ok(())
+}
 
+/// Same as [`humanize_duration`], but framed relative to "now" the way
+/// `chrono-humanize` does: "in <duration>" for a future instant, "<duration>
+/// ago" for a past one.
+fn humanize_relative(duration: Duration) -> String {
+    match duration.num_seconds() {
+        0 => "now".to_string(),
+        n if n < 0 => format!("{} ago", humanize_duration(duration)),
+        _ => format!("in {}", humanize_duration(duration)),
+    }
 }
 
 
-/// Parses a clock set command input and validates its components.
+/// Handles the `clock set` command to update the date and time on the
+/// provided [`Clock`] from an already-parsed [`NaiveDateTime`].
 ///
-/// This function takes a command input string in the format `clock set <hh:mm:ss> <day> <month> <year>`,
-/// splits the string into parts, validates each part, and returns the parsed time, day, month, and year 
-/// as a tuple. If the input is invalid, it returns an error message.
+/// # Errors
+/// Propagates any error from `Clock::set_time`/`Clock::set_date`, e.g. if
+/// the date/time falls in a DST gap or ambiguous transition in the
+/// configured timezone.
+pub fn handle_clock_set(datetime: NaiveDateTime, clock: &mut Clock) -> Result<(), String> {
+    let time = datetime.format("%H:%M:%S").to_string();
+    let month = MONTH_NAMES[datetime.month0() as usize];
+
+    clock.set_time(&time)?;
+    clock.set_date(datetime.day() as u8, month, datetime.year() as u16)?;
+
+    println!("Clock updated successfully to {} {} {} {}.", time, datetime.day(), month, datetime.year());
+    Ok(())
+}
+
+
+/// Prioritized chrono format strings tried by [`parse_clock_set_input`], in
+/// order; the first one that parses the input wins.
+const CLOCK_SET_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%:z", // RFC 3339 / ISO 8601 with a UTC offset
+    "%Y-%m-%d %H:%M:%S",    // ISO 8601 date and time
+    "%d %B %Y %H:%M:%S",    // day, full month name, year, time
+    "%d %b %Y %H:%M:%S",    // day, abbreviated month name, year, time
+];
+
+/// Parses a `clock set` command input into a [`NaiveDateTime`].
 ///
-/// # Arguments
-/// 
-/// * `input` - A string slice representing the clock set command. The expected format is:
-///   `"clock set <hh:mm:ss> <day> <month> <year>"`.
-/// 
-/// # Returns
-/// 
-/// * `Ok` - A tuple with the parsed time (as `&str`), day (as `u8`), month (as `&str`), and year (as `u16`).
-/// * `Err` - A `String` containing an error message if any part of the input is invalid.
+/// Accepts the legacy Cisco-style `clock set <hh:mm:ss> <day> <month>
+/// <year>` argument order (time first; reordered internally to match the
+/// formats below), as well as ISO 8601 / RFC 3339 and `<day> <month> <year>
+/// <hh:mm:ss>` input, by trying each of [`CLOCK_SET_FORMATS`] in turn and
+/// returning the first that parses. `<month>` may also be spelled in
+/// `locale`'s language (falling back to English when `locale` is
+/// `Locale::en_US`, i.e. when `clock locale` hasn't been run) via
+/// [`resolve_month_number`], since chrono's own `%B`/`%b` parsing only ever
+/// recognizes English names.
 ///
 /// # Errors
-/// 
-/// This function can return errors for:
-/// * An incomplete command with fewer than 4 parts.
-/// * An invalid time format (does not contain `:` or not in `hh:mm:ss` format).
-/// * An invalid day (not between 1 and 31).
-/// * An invalid month (not a valid month name).
-/// * An invalid year (not between 1993 and 2035).
-/// 
+/// Returns an error naming every format that was tried if none of them
+/// accept the input.
+///
 /// # Example
-/// 
+///
 /// ```rust
-/// let input = "clock set 12:30:45 15 January 2025";
-/// let result = parse_clock_set_input(input);
-/// assert_eq!(result, Ok(("12:30:45", 15, "January", 2025)));
+/// let input = "set 12:30:45 15 January 2025";
+/// let result = parse_clock_set_input(input, chrono::Locale::en_US);
+/// assert!(result.is_ok());
 /// ```
-pub fn parse_clock_set_input(input: &str) -> Result<(&str, u8, &str, u16), String> {
-
+pub fn parse_clock_set_input(input: &str, locale: Locale) -> Result<NaiveDateTime, String> {
     let parts: Vec<&str> = input.split_whitespace().collect();
 
-    if parts.len() < 5 {
-        return Err("Incomplete command. Usage: clock set <hh:mm:ss> <day> <month> <year>".to_string());
+    if parts.len() < 2 {
+        return Err(
+            "Incomplete command. Usage: clock set <hh:mm:ss> <day> <month> <year> (ISO 8601 / RFC 3339 also accepted).".to_string(),
+        );
+    }
+
+    let rest = &parts[1..];
+    let candidate = if rest.len() == 4 && rest[0].contains(':') {
+        // Legacy order: <hh:mm:ss> <day> <month> <year> -> reorder to
+        // <day> <month> <year> <hh:mm:ss> to match CLOCK_SET_FORMATS.
+        format!("{} {} {} {}", rest[1], rest[2], rest[3], rest[0])
+    } else {
+        rest.join(" ")
+    };
+
+    for format in CLOCK_SET_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(&candidate, format) {
+            return Ok(naive);
+        }
     }
 
-    let time = parts[1];
-    if !time.contains(':') || time.split(':').count() != 3 {
-        return Err("Invalid time format. Expected hh:mm:ss.".to_string());
+    // None of the numeric/English-month formats matched; if the four-token
+    // <day> <month> <year> <hh:mm:ss> shape is there, try its month token
+    // against the configured locale's month names before giving up.
+    let reordered: Vec<&str> = candidate.split_whitespace().collect();
+    if let [day, month, year, time] = reordered[..] {
+        if let Some(month_number) = resolve_month_number(month, locale) {
+            let day: u32 = day.parse().map_err(|_| invalid_datetime_error(&rest.join(" ")))?;
+            let year: i32 = year.parse().map_err(|_| invalid_datetime_error(&rest.join(" ")))?;
+            let date = NaiveDate::from_ymd_opt(year, month_number, day)
+                .ok_or_else(|| invalid_datetime_error(&rest.join(" ")))?;
+            let time = NaiveTime::parse_from_str(time, "%H:%M:%S")
+                .map_err(|_| invalid_datetime_error(&rest.join(" ")))?;
+            return Ok(NaiveDateTime::new(date, time));
+        }
     }
 
-    let day: u8 = parts[2].parse().map_err(|_| "Invalid day. Expected a number between 1 and 31.".to_string())?;
-    if !(1..=31).contains(&day) {
-        return Err("Invalid day. Expected a number between 1 and 31.".to_string());
+    Err(invalid_datetime_error(&rest.join(" ")))
+}
+
+fn invalid_datetime_error(input: &str) -> String {
+    format!(
+        "Could not parse datetime '{}'; accepted formats are: {}.",
+        input,
+        CLOCK_SET_FORMATS.join(", ")
+    )
+}
+
+/// Parses a timer value for OSPF and other per-interface timer commands,
+/// accepting either a bare integer number of seconds or a suffixed duration
+/// like `90s`, `1m`, `1m30s`, `2h`, or `1d`, the way the OpenEthereum CLI's
+/// duration flags do. Scans left to right accumulating digit runs; a run
+/// followed by a `s`/`m`/`h`/`d` suffix is multiplied by the unit's seconds
+/// and added to the total, while a trailing run with no suffix counts as
+/// seconds outright.
+///
+/// # Errors
+/// Rejects an empty string, a component with no digits, an unknown unit
+/// suffix, or a total that overflows `u32`.
+pub fn parse_duration_seconds(input: &str) -> Result<u32, String> {
+    if input.is_empty() {
+        return Err("Duration must not be empty.".to_string());
     }
 
-    let month = parts[3];
-    let valid_months = [
-        "January", "February", "March", "April", "May", "June", "July", "August", "September",
-        "October", "November", "December",
-    ];
-    if !valid_months.contains(&month) {
-        return Err("Invalid month. Expected a valid month name.".to_string());
+    let mut total: u32 = 0;
+    let mut digits = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+
+        if digits.is_empty() {
+            return Err(format!("Invalid duration '{}': expected a number before '{}'.", input, ch));
+        }
+        let value: u32 = digits.parse().map_err(|_| format!("Invalid duration '{}': number out of range.", input))?;
+        digits.clear();
+
+        let multiplier = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => 86_400,
+            other => return Err(format!("Invalid duration '{}': unknown unit '{}'.", input, other)),
+        };
+        let component = value.checked_mul(multiplier).ok_or_else(|| format!("Duration '{}' overflows.", input))?;
+        total = total.checked_add(component).ok_or_else(|| format!("Duration '{}' overflows.", input))?;
     }
 
-    let year: u16 = parts[4].parse().map_err(|_| "Invalid year. Expected a number between 1993 and 2035.".to_string())?;
-    if !(1993..=2035).contains(&year) {
-        return Err("Invalid year. Expected a number between 1993 and 2035.".to_string());
+    if !digits.is_empty() {
+        // A trailing run with no suffix counts as seconds.
+        let value: u32 = digits.parse().map_err(|_| format!("Invalid duration '{}': number out of range.", input))?;
+        total = total.checked_add(value).ok_or_else(|| format!("Duration '{}' overflows.", input))?;
     }
 
-    Ok((time, day, month, year))
+    Ok(total)
 }
 
 
-
 /// Handles the `show clock` command to display the current date and time stored in the `CustomClock` structure.
 ///
 /// # Arguments
@@ -314,14 +585,32 @@ pub fn parse_clock_set_input(input: &str) -> Result<(&str, u8, &str, u16), Strin
 /// ```
 pub fn handle_show_clock(clock: &Clock) {
     let current = clock.get_current_datetime();
+    let zone_suffix = clock.timezone_label().map(|label| format!(" {}", label)).unwrap_or_default();
     println!(
-        "Current clock: {} {}",
-        current.format("%d %B %Y"),
-        current.format("%H:%M:%S")
+        "Current clock: {} {}{}",
+        current.format_localized("%d %B %Y", clock.locale()),
+        current.format("%H:%M:%S"),
+        zone_suffix
     );
 }
 
 
+/// Handles the `show clock relative` variant: the same timestamp as
+/// `show clock`, alongside how long ago the device booted (or, if the
+/// clock was moved away from real time with `clock set`, how long ago/until
+/// that manually set time sits relative to the host's actual clock).
+pub fn handle_show_clock_relative(clock: &Clock) {
+    handle_show_clock(clock);
+    match clock.custom_datetime {
+        Some(custom) => {
+            let offset = custom.signed_duration_since(clock.now());
+            println!("Configured time is {} real time.", humanize_relative(offset));
+        }
+        None => println!("Booted {}.", humanize_relative(-clock.get_uptime())),
+    }
+}
+
+
 /// Handles the display of the system's uptime.
 ///
 /// This function retrieves the system uptime from the provided [`Clock`] instance
@@ -344,3 +633,11 @@ pub fn handle_show_clock(clock: &Clock) {
 pub fn handle_show_uptime(clock: &Clock) {
     println!("{}", clock.format_uptime());
 }
+
+/// Handles the `show uptime detail` variant, printing the rolled-up,
+/// human-readable uptime (e.g. "3 days, 4 hours") via
+/// [`Clock::format_uptime_human`] instead of `show uptime`'s raw
+/// hours/minutes/seconds breakdown.
+pub fn handle_show_uptime_detail(clock: &Clock) {
+    println!("{}", clock.format_uptime_human());
+}
@@ -9,6 +9,7 @@ use rustyline::completion::{Completer, Pair};
 use rustyline::highlight::Highlighter;
 use rustyline::validate::{Validator, ValidationContext, ValidationResult};
 use rustyline::error::ReadlineError;
+use serde::Serialize;
 use std::collections::HashMap;
 
 
@@ -25,6 +26,70 @@ use std::collections::HashMap;
 pub struct CommandCompleter {
     pub commands: HashMap<String, Vec<String>>,
     pub current_mode: Mode,
+    /// Recently entered full command lines, most recent last, bounded to
+    /// `MAX_RECENT_HISTORY` entries. Used to prefer history-based hints over
+    /// registry-based ones when both apply.
+    pub recent_history: Vec<String>,
+    /// When `true`, top-level command completion falls back to subsequence
+    /// fuzzy matching (e.g. `shw` -> `show`) whenever no candidate is a
+    /// strict prefix match. When `false`, only strict prefix matching is used.
+    pub fuzzy: bool,
+}
+
+/// Maximum number of previously entered command lines kept for hinting purposes.
+const MAX_RECENT_HISTORY: usize = 200;
+
+/// Minimum score (see [`fuzzy_score`]) a candidate must reach to be
+/// considered a fuzzy match.
+const FUZZY_SCORE_THRESHOLD: i32 = 1;
+
+/// Scores `candidate` against `query` as an ordered subsequence match.
+///
+/// Every character of `query` must appear in `candidate`, in order, or
+/// `None` is returned. Otherwise a score is returned that rewards
+/// consecutive matches, matches immediately following a separator
+/// (`-`, `_`, or whitespace), and matches at the very start of `candidate`.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let query_chars: Vec<char> = query.chars().collect();
+
+    let mut score = 0i32;
+    let mut candidate_idx = 0usize;
+    let mut previous_matched_idx: Option<usize> = None;
+
+    for &qc in &query_chars {
+        let mut found = None;
+        while candidate_idx < candidate_chars.len() {
+            if candidate_chars[candidate_idx].eq_ignore_ascii_case(&qc) {
+                found = Some(candidate_idx);
+                break;
+            }
+            candidate_idx += 1;
+        }
+
+        let idx = found?;
+
+        score += 1;
+        if idx == 0 {
+            score += 3;
+        } else if matches!(candidate_chars[idx - 1], '-' | '_' | ' ') {
+            score += 2;
+        }
+        if let Some(prev) = previous_matched_idx {
+            if idx == prev + 1 {
+                score += 2;
+            }
+        }
+
+        previous_matched_idx = Some(idx);
+        candidate_idx = idx + 1;
+    }
+
+    Some(score)
 }
 
 /// Implementation of the `CommandCompleter` struct.
@@ -57,6 +122,26 @@ impl CommandCompleter {
         CommandCompleter {
             commands,
             current_mode,
+            recent_history: Vec::new(),
+            fuzzy: true,
+        }
+    }
+
+    /// Records a full command line that was just entered, for use by `hint()`.
+    ///
+    /// Keeps at most `MAX_RECENT_HISTORY` entries, dropping the oldest ones
+    /// first. The most recently entered line is pushed to the back, so a
+    /// reverse scan finds the most recent match first.
+    pub fn record_history(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        self.recent_history.retain(|existing| existing != line);
+        self.recent_history.push(line.to_string());
+        if self.recent_history.len() > MAX_RECENT_HISTORY {
+            let overflow = self.recent_history.len() - MAX_RECENT_HISTORY;
+            self.recent_history.drain(0..overflow);
         }
     }
 
@@ -87,79 +172,240 @@ impl Completer for CommandCompleter {
         pos: usize,
         _ctx: &rustyline::Context<'_>,
     ) -> Result<(usize, Vec<Self::Candidate>), rustyline::error::ReadlineError> {
-        let suggestions = build_command_registry();
-        let mut candidates = Vec::new();
-
         let query = if pos <= line.len() {
             &line[..pos]
         } else {
             line
         };
 
-        let parts: Vec<&str> = query.trim_end().split_whitespace().collect();
+        let (tokens_before, partial, start) = split_last_token(query);
+        let trie = self.build_command_trie();
 
-        // Filter commands allowed in the current mode
-        let allowed_commands: Vec<(&str, &Command)> = suggestions
-            .iter()
-            .filter(|(&command, _)| is_command_allowed_in_mode(&command.to_string(), &self.current_mode))
-            .map(|(command, cmd)| (*command, cmd))
-            .collect();
+        // Resolve an alias in the leading token to its canonical command
+        // name so the trie walk (built on canonical names) still finds it.
+        let registry = build_command_registry();
+        let mut resolved_tokens: Vec<String> = Vec::with_capacity(tokens_before.len());
+        for (i, token) in tokens_before.iter().enumerate() {
+            if i == 0 {
+                resolved_tokens.push(
+                    resolve_alias(&registry, token)
+                        .unwrap_or(token)
+                        .to_string(),
+                );
+            } else {
+                resolved_tokens.push(token.to_string());
+            }
+        }
+
+        let mut candidates = Vec::new();
+
+        if let Some(node) = trie.walk(&resolved_tokens) {
+            let mut children: Vec<&str> = node
+                .children
+                .keys()
+                .map(|s| s.as_str())
+                .filter(|child| child.starts_with(partial))
+                .collect();
+
+            // Fall back to fuzzy subsequence matching on typos when no
+            // strict prefix matched.
+            if children.is_empty() && self.fuzzy && !partial.is_empty() {
+                let mut scored: Vec<(i32, &str)> = node
+                    .children
+                    .keys()
+                    .filter_map(|child| {
+                        fuzzy_score(partial, child)
+                            .filter(|&score| score >= FUZZY_SCORE_THRESHOLD)
+                            .map(|score| (score, child.as_str()))
+                    })
+                    .collect();
+                scored.sort_by(|a, b| b.0.cmp(&a.0));
+                children = scored.into_iter().map(|(_, child)| child).collect();
+            } else {
+                children.sort();
+            }
+
+            for child in children {
+                // At the top level `child` is itself a command name, so the
+                // registry has structured args/help to show in the menu.
+                // Deeper in the trie `child` is just a subcommand token with
+                // no standalone Command entry, so it's shown plain.
+                let display = if resolved_tokens.is_empty() {
+                    match registry.get(child) {
+                        Some(command) => render_candidate_display(child, command),
+                        None => child.to_string(),
+                    }
+                } else {
+                    child.to_string()
+                };
 
-        if parts.is_empty() {
-            // No input yet: Show all allowed commands
-            for (command_name, _) in allowed_commands.iter() {
                 candidates.push(Pair {
-                    display: command_name.to_string(),
-                    replacement: command_name.to_string(),
+                    display,
+                    replacement: child.to_string(),
                 });
             }
-        } else if parts.len() == 1 && !query.ends_with(' ') {
-            // First tab: Suggest commands matching the input
-            for (command_name, _) in allowed_commands.iter() {
-                if command_name.starts_with(parts[0]) {
-                    candidates.push(Pair {
-                        display: command_name.to_string(),
-                        replacement: command_name.to_string(),
-                    });
-                }
-            }
-        } else if parts.len() == 1 && query.ends_with(' ') {
-            // Suggest subcommands for the main command
-            if let Some(subcommands) = suggestions.get(parts[0]) {
-                for subcmd in subcommands.suggestions.iter() {
-                    candidates.push(Pair {
-                        display: subcmd.join(" "),
-                        replacement: format!("{} {}", parts[0], subcmd.join(" ")),
-                    });
+        }
+
+        Ok((start, candidates))
+    }
+}
+
+/// Renders a completion menu entry as `"<name>  <arg1> <arg2>  -- help"`,
+/// surfacing a command's expected arguments and one-line help without
+/// affecting what actually gets inserted (`Pair.replacement` stays the bare
+/// token).
+fn render_candidate_display(name: &str, command: &Command) -> String {
+    if command.args.is_empty() {
+        format!("{}  -- {}", name, command.help)
+    } else {
+        let args: Vec<String> = command
+            .args
+            .iter()
+            .map(|arg| {
+                let rendered = if arg.variadic {
+                    format!("<{}>...", arg.name)
+                } else {
+                    format!("<{}>", arg.name)
+                };
+                if arg.optional {
+                    format!("[{}]", rendered)
+                } else {
+                    rendered
                 }
+            })
+            .collect();
+        format!("{}  {}  -- {}", name, args.join(" "), command.help)
+    }
+}
+
+/// Splits `query` into the tokens already fully typed and the partial final
+/// token still being completed.
+///
+/// # Returns
+/// A tuple of `(tokens_before, partial, start)` where `start` is the byte
+/// offset in `query` at which `partial` begins — this is also the byte
+/// offset `rustyline` should splice the replacement at, so multi-word
+/// completions only replace the last token.
+fn split_last_token(query: &str) -> (Vec<&str>, &str, usize) {
+    if query.is_empty() {
+        return (Vec::new(), "", 0);
+    }
+
+    if query.ends_with(' ') {
+        let tokens: Vec<&str> = query.split_whitespace().collect();
+        (tokens, "", query.len())
+    } else {
+        let start = query.rfind(' ').map(|i| i + 1).unwrap_or(0);
+        let partial = &query[start..];
+        let tokens_before: Vec<&str> = query[..start].split_whitespace().collect();
+        (tokens_before, partial, start)
+    }
+}
+
+/// A node in the mode-aware command trie built from `build_command_registry()`.
+///
+/// Each edge is a legal next token: the top level holds every command name
+/// allowed in the current mode, and each command's subtree is built from its
+/// `suggestions1` chains (each chain may itself be multiple tokens, e.g.
+/// `"crypto key"`), so completion can walk arbitrarily deep (`show ip ospf
+/// neighbor`) instead of being capped at two tokens.
+#[derive(Default)]
+struct CommandTrieNode {
+    children: HashMap<String, CommandTrieNode>,
+}
+
+impl CommandTrieNode {
+    fn insert_path(&mut self, path: &[&str]) {
+        let mut node = self;
+        for token in path {
+            node = node.children.entry(token.to_string()).or_default();
+        }
+    }
+
+    /// Walks `path` from this node, returning the node reached, or `None` if
+    /// any token along the way has no matching edge.
+    fn walk(&self, path: &[String]) -> Option<&CommandTrieNode> {
+        let mut node = self;
+        for token in path {
+            node = node.children.get(token)?;
+        }
+        Some(node)
+    }
+}
+
+impl CommandCompleter {
+    /// Builds the command trie for the current mode from `build_command_registry()`.
+    fn build_command_trie(&self) -> CommandTrieNode {
+        let registry = build_command_registry();
+        let mut root = CommandTrieNode::default();
+
+        for (command_name, command) in registry.iter() {
+            if !is_command_allowed_in_mode(&command_name.to_string(), &self.current_mode) {
+                continue;
             }
-        } else if parts.len() == 2 && !query.ends_with(' ') {
-            // Suggest specific subcommands that start with the entered prefix
-            if let Some(command) = suggestions.get(parts[0]) {
-                if let Some(subcommands) = &command.suggestions {
-                    for &subcmd in subcommands {
-                        if subcmd.starts_with(parts[1]) {
-                            candidates.push(Pair {
-                                display: subcmd.to_string(),
-                                replacement: subcmd.to_string(),
-                            });
+
+            root.insert_path(&[command_name]);
+
+            if let Some(chains) = &command.suggestions1 {
+                for chain in chains {
+                    let mut path: Vec<&str> = vec![command_name];
+                    path.extend(chain.split_whitespace());
+                    root.insert_path(&path);
+
+                    // Bare literal keywords in `options` (no `<placeholder>`
+                    // markup) are completable alternatives for the token
+                    // right after this chain, e.g. `copy running-config` ->
+                    // `startup-config`.
+                    if let Some(options) = &command.options {
+                        for option in options {
+                            if !option.contains('<') {
+                                let mut deeper = path.clone();
+                                deeper.push(option);
+                                root.insert_path(&deeper);
+                            }
                         }
                     }
                 }
+            } else if let Some(suggestions) = &command.suggestions {
+                // Commands with no `suggestions1` chain (e.g. `interface`'s
+                // `range` keyword) still list their single-token
+                // alternatives in `suggestions`; complete those too.
+                for suggestion in suggestions {
+                    let mut path: Vec<&str> = vec![command_name];
+                    path.extend(suggestion.split_whitespace());
+                    root.insert_path(&path);
+                }
             }
         }
 
-        let new_pos = if parts.len() > 1 {
-            query.rfind(' ').unwrap_or(0) + 1
-        } else {
-            0
-        };
+        // External `pnfbox-<name>` plugins discovered on $PATH/the plugin
+        // directory complete at the top level too, same as a built-in --
+        // their own arguments aren't modeled here, so completion stops there.
+        for plugin_name in crate::plugins::discover_plugin_names() {
+            root.insert_path(&[plugin_name.as_str()]);
+        }
 
-        Ok((new_pos, candidates))
+        root
     }
 }
 
 
+/// Resolves `token` to the canonical registry key it names, whether `token`
+/// is already a canonical command name or one of its `aliases`.
+///
+/// # Returns
+/// `Some(canonical_name)` if `token` matches a command or one of its
+/// aliases, `None` otherwise.
+fn resolve_alias<'a>(registry: &'a HashMap<&'static str, Command>, token: &str) -> Option<&'a str> {
+    if let Some((&name, _)) = registry.get_key_value(token) {
+        return Some(name);
+    }
+    registry
+        .iter()
+        .find(|(_, command)| command.aliases.contains(&token))
+        .map(|(&name, _)| name)
+}
+
 /// Determines if a command is allowed in the current CLI mode.
 ///
 /// This function checks whether a given command is valid and permitted for execution
@@ -203,6 +449,12 @@ impl Completer for CommandCompleter {
 /// The function uses the `matches!` macro to provide concise and efficient pattern matching
 /// for the commands within each mode.
 fn is_command_allowed_in_mode(command: &String, mode: &Mode) -> bool {
+    // Accept either the canonical command name or one of its aliases
+    // (e.g. "conf" resolves to "configure" before the mode check below).
+    let canonical = resolve_alias(&build_command_registry(), command)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| command.clone());
+    let command = &canonical;
     match mode {
         Mode::UserMode => matches!(command.as_str(), "enable" | "reload" | "exit" | "clear" | "help" | "show" | "ping"),
         Mode::PrivilegedMode => matches!(command.as_str(), "configure" | "reload" | "debug" | "undebug" | "exit" | "clear" | "help" | "write" | "copy" | "clock" | "clear" | "ping" | "show" | "ifconfig"),
@@ -225,38 +477,469 @@ impl Helper for CommandCompleter {}
 impl Hinter for CommandCompleter {
     type Hint = String;
 
-    /// Provides hints for the current input line.
+    /// Provides a live inline ("ghost text") hint for the current input line.
     ///
     /// # Arguments
-    /// - `_line`: The current input line from the user.
-    /// - `_pos`: The cursor position within the line.
+    /// - `line`: The current input line from the user.
+    /// - `pos`: The cursor position within the line.
     /// - `_ctx`: The rustyline context.
     ///
     /// # Returns
-    /// Always returns `None` in this implementation as hints are not used.
-    fn hint(&self, _line: &str, _pos: usize, _ctx: &rustyline::Context<'_>) -> Option<String> {
-        None 
+    /// The unmatched suffix of the single best mode-legal command/subcommand
+    /// chain whose prefix matches `line[..pos]`, or `None` if there is no
+    /// unique match. A match in `recent_history` is preferred over one found
+    /// in the command registry, so frequently typed long commands complete
+    /// first.
+    fn hint(&self, line: &str, pos: usize, _ctx: &rustyline::Context<'_>) -> Option<String> {
+        if pos < line.len() || line.is_empty() {
+            return None;
+        }
+
+        // Prefer a match against recently entered full command lines.
+        if let Some(hint) = self.hint_from_history(line) {
+            return Some(hint);
+        }
+
+        self.hint_from_registry(line)
     }
 }
 
+impl CommandCompleter {
+    /// Looks for the single most recent history entry that starts with
+    /// `line` (and is longer than it) and returns its unmatched suffix.
+    fn hint_from_history(&self, line: &str) -> Option<String> {
+        self.recent_history
+            .iter()
+            .rev()
+            .find(|entry| entry.len() > line.len() && entry.starts_with(line))
+            .map(|entry| entry[line.len()..].to_string())
+    }
+
+    /// Walks the mode-legal command registry -- the static registry plus
+    /// `get_commands_for_mode`'s dynamically registered commands (plugins,
+    /// `register_command` callers) -- to find the single command/subcommand
+    /// chain whose prefix matches `line`, and returns the unmatched suffix.
+    /// Returns `None` when there are zero or multiple candidates.
+    fn hint_from_registry(&self, line: &str) -> Option<String> {
+        let registry = build_command_registry();
+        let mut candidates: Vec<String> = Vec::new();
+
+        for (command_name, command) in registry.iter() {
+            if !is_command_allowed_in_mode(&command_name.to_string(), &self.current_mode) {
+                continue;
+            }
+
+            // The bare command name is itself a candidate chain.
+            Self::collect_chain_candidate(command_name, line, &mut candidates);
+
+            if let Some(subcommands) = &command.suggestions1 {
+                for subcmd in subcommands {
+                    let chain = format!("{} {}", command_name, subcmd);
+                    Self::collect_chain_candidate(&chain, line, &mut candidates);
+                }
+            }
+        }
+
+        if let Ok(dynamic_commands) = crate::dynamic_registry::DYNAMIC_COMMANDS.read() {
+            for command_name in crate::dynamic_registry::get_commands_for_mode(&self.current_mode) {
+                let Some(command) = dynamic_commands.get(&command_name) else { continue };
+
+                Self::collect_chain_candidate(&command_name, line, &mut candidates);
+
+                if let Some(subcommands) = &command.suggestions1 {
+                    for subcmd in subcommands {
+                        let chain = format!("{} {}", command_name, subcmd);
+                        Self::collect_chain_candidate(&chain, line, &mut candidates);
+                    }
+                }
+            }
+        }
+
+        candidates.sort();
+        candidates.dedup();
+
+        match candidates.as_slice() {
+            [only] => Some(only[line.len()..].to_string()),
+            _ => None,
+        }
+    }
+
+    fn collect_chain_candidate(chain: &str, line: &str, candidates: &mut Vec<String>) {
+        if chain.len() > line.len() && chain.starts_with(line) {
+            candidates.push(chain.to_string());
+        }
+    }
+}
+
+/// ANSI escape code used to color the leading command token when it is legal
+/// in the current mode.
+const COLOR_COMMAND_OK: &str = "\x1b[32m";
+/// ANSI escape code used to color the leading command token when it is NOT
+/// legal in the current mode (a warning color).
+const COLOR_COMMAND_BAD: &str = "\x1b[31m";
+/// ANSI escape code used to color known subcommands.
+const COLOR_SUBCOMMAND: &str = "\x1b[36m";
+/// ANSI escape code used to color numeric and IP-address arguments.
+const COLOR_ARGUMENT: &str = "\x1b[33m";
+/// Resets the terminal color back to the default.
+const COLOR_RESET: &str = "\x1b[0m";
+
+/// Returns `true` when `token` looks like a number or an IPv4/IPv6 address.
+fn is_numeric_or_address(token: &str) -> bool {
+    !token.is_empty()
+        && token
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ':' || c == '/')
+        && token.chars().any(|c| c.is_ascii_digit())
+}
+
 /// Implements the `Highlighter` trait for the `CommandCompleter` struct.
-impl Highlighter for CommandCompleter {}
+impl Highlighter for CommandCompleter {
+    /// Colorizes the live input line by token role: the leading command
+    /// token (colored by whether it is legal in `current_mode`), known
+    /// subcommands, and numeric/IP-address arguments each get their own
+    /// ANSI color.
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> std::borrow::Cow<'l, str> {
+        if line.is_empty() {
+            return std::borrow::Cow::Borrowed(line);
+        }
+
+        let registry = build_command_registry();
+        let mut out = String::with_capacity(line.len() + 16);
+        let mut first_token = true;
+        let mut command_name: Option<&str> = None;
+        let mut chars = line.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            if c.is_whitespace() {
+                out.push(c);
+                continue;
+            }
+            let mut end = start + c.len_utf8();
+            while let Some(&(next_start, next_c)) = chars.peek() {
+                if next_c.is_whitespace() {
+                    break;
+                }
+                end = next_start + next_c.len_utf8();
+                chars.next();
+            }
+            let token = &line[start..end];
+
+            if first_token {
+                first_token = false;
+                command_name = Some(token);
+                let allowed = is_command_allowed_in_mode(&token.to_string(), &self.current_mode);
+                let color = if allowed { COLOR_COMMAND_OK } else { COLOR_COMMAND_BAD };
+                out.push_str(color);
+                out.push_str(token);
+                out.push_str(COLOR_RESET);
+            } else if is_numeric_or_address(token) {
+                out.push_str(COLOR_ARGUMENT);
+                out.push_str(token);
+                out.push_str(COLOR_RESET);
+            } else if command_name
+                .and_then(|name| registry.get(name))
+                .and_then(|cmd| cmd.suggestions1.as_ref())
+                .map(|subs| subs.contains(&token))
+                .unwrap_or(false)
+            {
+                out.push_str(COLOR_SUBCOMMAND);
+                out.push_str(token);
+                out.push_str(COLOR_RESET);
+            } else {
+                out.push_str(token);
+            }
+        }
+
+        std::borrow::Cow::Owned(out)
+    }
+
+    /// Ensures the highlighter is re-run on every keystroke so the colored
+    /// spans stay in sync with the live input line.
+    fn highlight_char(&self, _line: &str, _pos: usize, _forced: bool) -> bool {
+        true
+    }
+}
+
 
+/// Returns `false` when `line` has unbalanced `(`/`)`, `[`/`]`, or `"` delimiters.
+fn delimiters_balanced(line: &str) -> bool {
+    let mut parens = 0i32;
+    let mut brackets = 0i32;
+    let mut in_quotes = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => parens += 1,
+            ')' if !in_quotes => parens -= 1,
+            '[' if !in_quotes => brackets += 1,
+            ']' if !in_quotes => brackets -= 1,
+            _ => {}
+        }
+        if parens < 0 || brackets < 0 {
+            return false;
+        }
+    }
+
+    parens == 0 && brackets == 0 && !in_quotes
+}
 
 /// Implements the `Validator` trait for the `CommandCompleter` struct.
 impl Validator for CommandCompleter {
 
-    /// Validates the current input line.
+    /// Validates the current input line before `rustyline` submits it.
     ///
-    /// # Arguments
-    /// - `_ctx`: A mutable reference to the validation context.
-    ///
-    /// # Returns
-    /// Always returns `ValidationResult::Valid` in this implementation.
+    /// Two checks are performed:
+    /// 1. Balanced delimiters — unbalanced `(`/`)`, `[`/`]`, or `"` yields
+    ///    `ValidationResult::Incomplete` so the prompt waits for the closing
+    ///    token.
+    /// 2. Arity/keyword validation — if the first token is a mode-legal
+    ///    command but the remaining tokens don't match any known subcommand
+    ///    chain, returns `ValidationResult::Invalid` with a short message.
     fn validate(
         &self,
-        _ctx: &mut ValidationContext<'_>,
+        ctx: &mut ValidationContext<'_>,
     ) -> Result<ValidationResult, ReadlineError> {
-        Ok(ValidationResult::Valid(None)) 
+        let line = ctx.input();
+
+        if !delimiters_balanced(line) {
+            return Ok(ValidationResult::Incomplete);
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.is_empty() {
+            return Ok(ValidationResult::Valid(None));
+        }
+
+        let registry = build_command_registry();
+        let command_name = parts[0];
+
+        if !is_command_allowed_in_mode(&command_name.to_string(), &self.current_mode) {
+            // Not a known/legal command here; let execution report the error
+            // rather than blocking submission on an unrecognized first word.
+            return Ok(ValidationResult::Valid(None));
+        }
+
+        if let Some(command) = registry.get(command_name) {
+            if let Some(subcommands) = &command.suggestions1 {
+                if !subcommands.is_empty() && parts.len() >= 2 {
+                    let subcommand = parts[1];
+                    let matches = subcommands
+                        .iter()
+                        .any(|known| known.starts_with(subcommand));
+                    if !matches {
+                        return Ok(ValidationResult::Invalid(Some(format!(
+                            "  ^ unknown subcommand '{}' for '{}'",
+                            subcommand, command_name
+                        ))));
+                    }
+                }
+            }
+        }
+
+        Ok(ValidationResult::Valid(None))
+    }
+}
+
+/// Returns the first-level subcommand words for `command`, derived from its
+/// `suggestions1` chains (each chain's first token, deduplicated) falling
+/// back to `suggestions`. Returns `None` when the command has no known
+/// subcommands, in which case a shell-completion script should just stop
+/// descending after the command name.
+fn first_level_subcommands(command: &Command) -> Option<Vec<&'static str>> {
+    let chains = command
+        .suggestions1
+        .as_ref()
+        .or(command.suggestions.as_ref())?;
+
+    let mut seen = Vec::new();
+    for chain in chains {
+        if let Some(first) = chain.split_whitespace().next() {
+            if !seen.contains(&first) {
+                seen.push(first);
+            }
+        }
+    }
+    if seen.is_empty() {
+        None
+    } else {
+        Some(seen)
+    }
+}
+
+/// Generates a static bash completion script for the CLI's own command
+/// registry, for use outside the interactive session (e.g. wiring up tab
+/// completion when the simulator is driven from a real terminal or test
+/// harness).
+///
+/// Unlike interactive `Tab`/`?` completion, this script has no notion of the
+/// simulator's current mode, so it offers every registered command name and
+/// descends one level into each command's `suggestions1`/`suggestions`.
+pub fn generate_bash_completion(commands: &HashMap<&'static str, Command>) -> String {
+    let mut names: Vec<&str> = commands.keys().copied().collect();
+    names.sort();
+
+    let mut script = String::new();
+    script.push_str("# Bash completion for this CLI. Generated by `completions bash`.\n");
+    script.push_str("_cli_complete() {\n");
+    script.push_str("    local cur prev words cword\n");
+    script.push_str("    _get_comp_words_by_ref -n : cur prev words cword\n\n");
+    script.push_str("    if [ \"$cword\" -eq 1 ]; then\n");
+    script.push_str(&format!(
+        "        COMPREPLY=( $(compgen -W \"{}\" -- \"$cur\") )\n",
+        names.join(" ")
+    ));
+    script.push_str("        return 0\n");
+    script.push_str("    fi\n\n");
+    script.push_str("    case \"${words[1]}\" in\n");
+    for name in &names {
+        if let Some(subcommands) = commands.get(name).and_then(first_level_subcommands) {
+            script.push_str(&format!("        {})\n", name));
+            script.push_str(&format!(
+                "            COMPREPLY=( $(compgen -W \"{}\" -- \"$cur\") )\n",
+                subcommands.join(" ")
+            ));
+            script.push_str("            ;;\n");
+        }
     }
+    script.push_str("    esac\n");
+    script.push_str("}\n");
+    script.push_str("complete -F _cli_complete cli\n");
+    script
+}
+
+/// Generates a static zsh completion script, structured the same way as
+/// [`generate_bash_completion`] but using zsh's `compadd`/`_describe` idioms.
+pub fn generate_zsh_completion(commands: &HashMap<&'static str, Command>) -> String {
+    let mut names: Vec<&str> = commands.keys().copied().collect();
+    names.sort();
+
+    let mut script = String::new();
+    script.push_str("#compdef cli\n");
+    script.push_str("# Zsh completion for this CLI. Generated by `completions zsh`.\n");
+    script.push_str("_cli_complete() {\n");
+    script.push_str("    local -a top_level\n");
+    script.push_str(&format!(
+        "    top_level=({})\n\n",
+        names.join(" ")
+    ));
+    script.push_str("    if (( CURRENT == 2 )); then\n");
+    script.push_str("        compadd -- \"${top_level[@]}\"\n");
+    script.push_str("        return 0\n");
+    script.push_str("    fi\n\n");
+    script.push_str("    case \"${words[2]}\" in\n");
+    for name in &names {
+        if let Some(subcommands) = commands.get(name).and_then(first_level_subcommands) {
+            script.push_str(&format!("        {})\n", name));
+            script.push_str(&format!(
+                "            compadd -- {}\n",
+                subcommands.join(" ")
+            ));
+            script.push_str("            ;;\n");
+        }
+    }
+    script.push_str("    esac\n");
+    script.push_str("}\n\n");
+    script.push_str("_cli_complete \"$@\"\n");
+    script
+}
+
+/// Generates a static fish completion script. Fish completions are
+/// declarative (one `complete` call per candidate) rather than a dispatch
+/// function, so top-level names and subcommands are emitted as separate
+/// `complete` lines gated with `-n`/`-a`.
+pub fn generate_fish_completion(commands: &HashMap<&'static str, Command>) -> String {
+    let mut names: Vec<&str> = commands.keys().copied().collect();
+    names.sort();
+
+    let mut script = String::new();
+    script.push_str("# Fish completion for this CLI. Generated by `completions fish`.\n");
+    for name in &names {
+        let command = &commands[name];
+        script.push_str(&format!(
+            "complete -c cli -n \"__fish_use_subcommand\" -a '{}' -d '{}'\n",
+            name, command.help
+        ));
+        if let Some(subcommands) = first_level_subcommands(command) {
+            for subcommand in subcommands {
+                script.push_str(&format!(
+                    "complete -c cli -n \"__fish_seen_subcommand_from {}\" -a '{}'\n",
+                    name, subcommand
+                ));
+            }
+        }
+    }
+    script
+}
+
+/// A single command or subcommand in the registry's completion tree, as
+/// serialized by [`generate_json_completion`]. `options` only ever holds
+/// what the root `Command` itself declares, since nested `suggestions1`
+/// chains don't carry their own per-level option text in this registry.
+#[derive(Serialize)]
+pub struct CommandNode {
+    pub name: String,
+    pub help: Option<String>,
+    pub options: Vec<String>,
+    pub children: Vec<CommandNode>,
+}
+
+fn insert_chain(children: &mut Vec<CommandNode>, tokens: &[&str]) {
+    let head = match tokens.first() {
+        Some(head) => *head,
+        None => return,
+    };
+    let child = match children.iter_mut().find(|node| node.name == head) {
+        Some(child) => child,
+        None => {
+            children.push(CommandNode {
+                name: head.to_string(),
+                help: None,
+                options: Vec::new(),
+                children: Vec::new(),
+            });
+            children.last_mut().unwrap()
+        }
+    };
+    insert_chain(&mut child.children, &tokens[1..]);
+}
+
+/// Builds the whole registry as a recursive tree: one root node per
+/// top-level command name, with its `suggestions1` (falling back to
+/// `suggestions`) chains nested underneath token by token.
+pub fn command_tree(commands: &HashMap<&'static str, Command>) -> Vec<CommandNode> {
+    let mut names: Vec<&str> = commands.keys().copied().collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let command = &commands[name];
+            let mut root = CommandNode {
+                name: name.to_string(),
+                help: Some(command.help.to_string()),
+                options: command
+                    .options
+                    .as_ref()
+                    .map(|opts| opts.iter().map(|&o| o.to_string()).collect())
+                    .unwrap_or_default(),
+                children: Vec::new(),
+            };
+            if let Some(chains) = command.suggestions1.as_ref().or(command.suggestions.as_ref()) {
+                for chain in chains {
+                    let tokens: Vec<&str> = chain.split_whitespace().collect();
+                    insert_chain(&mut root.children, &tokens);
+                }
+            }
+            root
+        })
+        .collect()
+}
+
+/// Serializes the full command tree as pretty-printed JSON, for tooling that
+/// wants a machine-readable view of the registry instead of a shell script.
+pub fn generate_json_completion(commands: &HashMap<&'static str, Command>) -> String {
+    serde_json::to_string_pretty(&command_tree(commands))
+        .unwrap_or_else(|err| format!("{{\"error\": \"failed to serialize command tree: {}\"}}", err))
 }
\ No newline at end of file
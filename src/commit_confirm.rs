@@ -0,0 +1,141 @@
+//! Timed configuration commit/rollback, the same "protect the operator from
+//! a bad change" idea as `reload`'s interactive save/confirm prompt, but for
+//! changes made while still inside ConfigMode: a snapshot of the
+//! configuration is taken on `configure terminal` and replaced by each plain
+//! `commit`; `commit confirmed <minutes>` schedules an automatic rollback to
+//! that snapshot unless a plain `commit` cancels it first, and `rollback`
+//! can discard uncommitted changes back to that snapshot at any time.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::network_config::{
+    AccessControlList, AdminState, BGPConfig, IfconfigEntry, ISISConfig, InterfaceConfig, OSPFConfig,
+    OSPFv3Config, OperState, PasswordStore, RIPConfig, ACL_STORE, BGP_CONFIG, IFCONFIG_STATE,
+    IP_ADDRESS_STATE, ISIS_CONFIG, LINK_CONFIG_STATE, OPER_STATE_MAP, OSPFV3_CONFIG, OSPF_CONFIG,
+    PASSWORD_STORAGE, RIP_CONFIG, RoutingTable, ROUTING_TABLE, STATUS_MAP, USER_CREDENTIALS,
+};
+
+/// A point-in-time copy of every piece of global configuration state a
+/// ConfigMode command can mutate, taken when `configure terminal` is
+/// entered and restored by `rollback` or an expired `commit confirmed`
+/// timer. Mirrors `batch::StateSnapshot`, but lives for the duration of a
+/// ConfigMode session rather than a single batch run.
+struct ConfigSnapshot {
+    ifconfig_state: HashMap<String, IfconfigEntry>,
+    status_map: HashMap<String, AdminState>,
+    oper_state_map: HashMap<String, OperState>,
+    ip_address_state: HashMap<String, (Ipv4Addr, Ipv4Addr)>,
+    routing_table: RoutingTable,
+    ospf_config: OSPFConfig,
+    ospfv3_config: OSPFv3Config,
+    bgp_config: BGPConfig,
+    rip_config: RIPConfig,
+    isis_config: ISISConfig,
+    acl_store: HashMap<String, AccessControlList>,
+    password_storage: PasswordStore,
+    link_config_state: HashMap<String, InterfaceConfig>,
+    user_credentials: HashMap<String, String>,
+}
+
+impl ConfigSnapshot {
+    fn capture() -> Self {
+        ConfigSnapshot {
+            ifconfig_state: IFCONFIG_STATE.lock().unwrap().clone(),
+            status_map: STATUS_MAP.lock().unwrap().clone(),
+            oper_state_map: OPER_STATE_MAP.lock().unwrap().clone(),
+            ip_address_state: IP_ADDRESS_STATE.lock().unwrap().clone(),
+            routing_table: ROUTING_TABLE.lock().unwrap().clone(),
+            ospf_config: OSPF_CONFIG.lock().unwrap().clone(),
+            ospfv3_config: OSPFV3_CONFIG.lock().unwrap().clone(),
+            bgp_config: BGP_CONFIG.lock().unwrap().clone(),
+            rip_config: RIP_CONFIG.lock().unwrap().clone(),
+            isis_config: ISIS_CONFIG.lock().unwrap().clone(),
+            acl_store: ACL_STORE.lock().unwrap().clone(),
+            password_storage: PASSWORD_STORAGE.lock().unwrap().clone(),
+            link_config_state: LINK_CONFIG_STATE.lock().unwrap().clone(),
+            user_credentials: USER_CREDENTIALS.lock().unwrap().clone(),
+        }
+    }
+
+    fn restore(&self) {
+        *IFCONFIG_STATE.lock().unwrap() = self.ifconfig_state.clone();
+        *STATUS_MAP.lock().unwrap() = self.status_map.clone();
+        *OPER_STATE_MAP.lock().unwrap() = self.oper_state_map.clone();
+        *IP_ADDRESS_STATE.lock().unwrap() = self.ip_address_state.clone();
+        *ROUTING_TABLE.lock().unwrap() = self.routing_table.clone();
+        *OSPF_CONFIG.lock().unwrap() = self.ospf_config.clone();
+        *OSPFV3_CONFIG.lock().unwrap() = self.ospfv3_config.clone();
+        *BGP_CONFIG.lock().unwrap() = self.bgp_config.clone();
+        *RIP_CONFIG.lock().unwrap() = self.rip_config.clone();
+        *ISIS_CONFIG.lock().unwrap() = self.isis_config.clone();
+        *ACL_STORE.lock().unwrap() = self.acl_store.clone();
+        *PASSWORD_STORAGE.lock().unwrap() = self.password_storage.clone();
+        *LINK_CONFIG_STATE.lock().unwrap() = self.link_config_state.clone();
+        *USER_CREDENTIALS.lock().unwrap() = self.user_credentials.clone();
+    }
+}
+
+lazy_static::lazy_static! {
+    /// The snapshot to roll back to, taken on `configure terminal` and
+    /// refreshed by `commit`. `None` once `rollback` or an expired timer has
+    /// consumed it, until the next `configure terminal` re-arms it.
+    static ref PENDING_SNAPSHOT: Mutex<Option<ConfigSnapshot>> = Mutex::new(None);
+}
+
+/// Identifies the most recently scheduled `commit confirmed` timer; a
+/// background timer thread only acts if its own id is still current when it
+/// wakes, so a later `commit`, `rollback`, or `commit confirmed` silently
+/// supersedes it instead of requiring an explicit cancellation handle.
+static TIMER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Captures a fresh baseline snapshot on entering ConfigMode, so `rollback`
+/// always has something to discard uncommitted changes back to.
+pub fn snapshot_on_enter_config() {
+    *PENDING_SNAPSHOT.lock().unwrap() = Some(ConfigSnapshot::capture());
+}
+
+/// Handles `commit confirmed <minutes>`: schedules an automatic rollback to
+/// the pending snapshot (taken on ConfigMode entry, or by the last plain
+/// `commit`), unless `commit` runs first.
+pub fn commit_confirmed(minutes: u64) {
+    let generation = TIMER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    thread::spawn(move || {
+        thread::sleep(Duration::from_secs(minutes * 60));
+        if TIMER_GENERATION.load(Ordering::SeqCst) == generation {
+            if let Some(snapshot) = PENDING_SNAPSHOT.lock().unwrap().take() {
+                snapshot.restore();
+                println!(
+                    "\n% commit confirmed timer expired -- configuration automatically rolled back"
+                );
+            }
+        }
+    });
+}
+
+/// Handles a plain `commit`: cancels any pending automatic rollback timer
+/// and re-baselines the snapshot to the now-permanent current state.
+pub fn commit() {
+    TIMER_GENERATION.fetch_add(1, Ordering::SeqCst);
+    *PENDING_SNAPSHOT.lock().unwrap() = Some(ConfigSnapshot::capture());
+}
+
+/// Handles `rollback`: discards uncommitted changes back to the last
+/// snapshot and cancels any pending automatic rollback timer.
+///
+/// # Returns
+/// `false` if there was no snapshot to roll back to.
+pub fn rollback() -> bool {
+    TIMER_GENERATION.fetch_add(1, Ordering::SeqCst);
+    match PENDING_SNAPSHOT.lock().unwrap().take() {
+        Some(snapshot) => {
+            snapshot.restore();
+            true
+        }
+        None => false,
+    }
+}
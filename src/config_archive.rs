@@ -0,0 +1,167 @@
+//config_archive.rs
+
+/// External crates for the CLI application
+use std::sync::Mutex;
+use crate::cliconfig::CliContext;
+use crate::clock_settings::Clock;
+use crate::run_config::{default_startup_config, get_running_config};
+
+/// A single captured revision of the running configuration, RANCID-style.
+///
+/// # Fields
+/// - `revision`: The 1-based sequence number of this snapshot.
+/// - `timestamp`: The device time (from the `Clock`) at which the snapshot was taken.
+/// - `config`: The serialized running configuration at the time of the snapshot.
+pub struct ConfigSnapshot {
+    pub revision: usize,
+    pub timestamp: String,
+    pub config: String,
+}
+
+lazy_static::lazy_static! {
+    /// A thread-safe, in-memory history of configuration snapshots, in the
+    /// order they were captured.
+    pub static ref CONFIG_ARCHIVE: Mutex<Vec<ConfigSnapshot>> = Mutex::new(Vec::new());
+}
+
+/// Captures the current running configuration as a new archive revision.
+///
+/// The snapshot is timestamped from `clock` when available, falling back to
+/// the local wall-clock time otherwise.
+///
+/// # Returns
+/// The revision number assigned to the new snapshot.
+pub fn snapshot_running_config(context: &CliContext, clock: &Option<Clock>) -> usize {
+    let timestamp = clock
+        .as_ref()
+        .map(|c| c.get_current_datetime().to_string())
+        .unwrap_or_else(|| chrono::Local::now().to_string());
+
+    let mut archive = CONFIG_ARCHIVE.lock().unwrap();
+    let revision = archive.len() + 1;
+    archive.push(ConfigSnapshot {
+        revision,
+        timestamp,
+        config: get_running_config(context),
+    });
+    revision
+}
+
+/// Lists the archive as `(revision, timestamp)` pairs, oldest first.
+pub fn list_archive() -> Vec<(usize, String)> {
+    CONFIG_ARCHIVE
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|snapshot| (snapshot.revision, snapshot.timestamp.clone()))
+        .collect()
+}
+
+/// Renders a unified, line-based diff between two archive revisions.
+///
+/// Builds the longest-common-subsequence table over the revisions' config
+/// lines, then walks it forward to emit shared context lines, `-` for lines
+/// only in `rev_a`, and `+` for lines only in `rev_b`.
+///
+/// # Errors
+/// Returns an error message if either revision does not exist in the archive.
+pub fn diff_revisions(rev_a: usize, rev_b: usize) -> Result<String, String> {
+    let archive = CONFIG_ARCHIVE.lock().unwrap();
+
+    let snapshot_a = archive
+        .iter()
+        .find(|s| s.revision == rev_a)
+        .ok_or_else(|| format!("No such archive revision: {}", rev_a))?;
+    let snapshot_b = archive
+        .iter()
+        .find(|s| s.revision == rev_b)
+        .ok_or_else(|| format!("No such archive revision: {}", rev_b))?;
+
+    let lines_a: Vec<&str> = snapshot_a.config.lines().collect();
+    let lines_b: Vec<&str> = snapshot_b.config.lines().collect();
+
+    Ok(unified_diff(&lines_a, &lines_b))
+}
+
+/// Renders a unified diff between the running configuration and the startup
+/// configuration, i.e. what `write memory` would persist if run now.
+///
+/// Lines are trimmed and the entries within each `ip access-list` block are
+/// sorted before comparing, so ACL entries added in a different order (an
+/// unordered set, not a meaningful sequence) don't show up as noise.
+pub fn diff_running_vs_startup(context: &mut CliContext) -> String {
+    let running = normalize_config_lines(&get_running_config(context));
+    let startup = normalize_config_lines(&default_startup_config(context));
+
+    let running: Vec<&str> = running.iter().map(String::as_str).collect();
+    let startup: Vec<&str> = startup.iter().map(String::as_str).collect();
+
+    unified_diff(&startup, &running)
+}
+
+/// Trims each config line and sorts the entries within each `ip
+/// access-list` block, so unordered sub-sections compare by content rather
+/// than by incidental insertion order.
+fn normalize_config_lines(config: &str) -> Vec<String> {
+    let mut lines: Vec<String> = config.lines().map(|line| line.trim().to_string()).collect();
+
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].starts_with("ip access-list") {
+            let start = i + 1;
+            let mut end = start;
+            while end < lines.len() && lines[end] != "!" {
+                end += 1;
+            }
+            lines[start..end].sort();
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+
+    lines
+}
+
+/// Computes a unified diff of two line sequences via the classic LCS
+/// dynamic-programming table.
+fn unified_diff(a: &[&str], b: &[&str]) -> String {
+    let (n, m) = (a.len(), b.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            output.push_str(&format!("  {}\n", a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            output.push_str(&format!("- {}\n", a[i]));
+            i += 1;
+        } else {
+            output.push_str(&format!("+ {}\n", b[j]));
+            j += 1;
+        }
+    }
+    while i < n {
+        output.push_str(&format!("- {}\n", a[i]));
+        i += 1;
+    }
+    while j < m {
+        output.push_str(&format!("+ {}\n", b[j]));
+        j += 1;
+    }
+
+    output
+}
@@ -0,0 +1,573 @@
+//! Structured JSON import/export of the device configuration, driving
+//! `write config json` / `copy json running-config`. Unlike
+//! [`crate::run_config::get_running_config`], which flattens the context to
+//! CLI text for a human to re-type, this module models the configuration as
+//! a `serde` document so it can be produced and consumed programmatically --
+//! the way a production network manager loads structured interface/config
+//! documents instead of parsing CLI text -- and validates an incoming
+//! document against a JSON Schema before anything is applied.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cliconfig::CliContext;
+use crate::network_config::{
+    AccessControlList, AclEntry, AdminState, NtpAssociation, OSPFConfig, OperState, Route,
+    RouteSource, RoutingTable, ACL_STORE, IP_ADDRESS_STATE, OPER_STATE_MAP, OSPF_CONFIG,
+    ROUTING_TABLE, STATUS_MAP,
+};
+use crate::ntp_auth::{NtpAuthAlgorithm, NtpAuthKey};
+
+/// Which text format a structured configuration document is read/written in,
+/// selected by file extension the same way [`crate::run_config::ConfigFormat`]
+/// picks a startup-config backend -- `.yaml`/`.yml` for YAML, JSON otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigDocumentFormat {
+    Json,
+    Yaml,
+}
+
+impl ConfigDocumentFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => ConfigDocumentFormat::Yaml,
+            _ => ConfigDocumentFormat::Json,
+        }
+    }
+
+    /// Serializes `document` in this format.
+    pub fn serialize(self, document: &NetworkConfigDocument) -> Result<String, String> {
+        match self {
+            ConfigDocumentFormat::Json => serde_json::to_string_pretty(document)
+                .map_err(|err| format!("Failed to serialize configuration document: {}", err)),
+            ConfigDocumentFormat::Yaml => serde_yaml::to_string(document)
+                .map_err(|err| format!("Failed to serialize configuration document: {}", err)),
+        }
+    }
+
+    /// Parses `contents` into a format-neutral [`Value`], so
+    /// [`validate_config_document`] only has to be written once.
+    pub fn parse(self, contents: &str) -> Result<Value, String> {
+        match self {
+            ConfigDocumentFormat::Json => serde_json::from_str(contents)
+                .map_err(|err| format!("Invalid JSON at line {}, column {}: {}", err.line(), err.column(), err)),
+            ConfigDocumentFormat::Yaml => serde_yaml::from_str(contents).map_err(|err| match err.location() {
+                Some(location) => format!(
+                    "Invalid YAML at line {}, column {}: {}",
+                    location.line(),
+                    location.column(),
+                    err
+                ),
+                None => format!("Invalid YAML: {}", err),
+            }),
+        }
+    }
+}
+
+/// OpenConfig-style interface type classification carried in the document.
+///
+/// Distinct from [`crate::network_config::InterfaceType`], which only
+/// classifies an existing interface's name for display: this one also
+/// distinguishes the tunnel's address family and the routed-VLAN/uplink
+/// cases, so an imported document says what kind of interface to create
+/// rather than just how to print one that already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonInterfaceType {
+    Ethernet,
+    Loopback,
+    RoutedVlan,
+    TunnelGre4,
+    TunnelGre6,
+    Uplink,
+}
+
+impl JsonInterfaceType {
+    /// Infers an interface's document type from its name, the same way
+    /// [`crate::network_config::InterfaceType::classify`] does for display --
+    /// this simulator has no separate interface-type registry.
+    fn classify(name: &str) -> Self {
+        let lower = name.to_lowercase();
+        if lower.starts_with("loopback") {
+            JsonInterfaceType::Loopback
+        } else if lower.starts_with("vlan") {
+            JsonInterfaceType::RoutedVlan
+        } else if lower.starts_with("tunnel") {
+            JsonInterfaceType::TunnelGre4
+        } else if lower.starts_with("uplink") {
+            JsonInterfaceType::Uplink
+        } else {
+            JsonInterfaceType::Ethernet
+        }
+    }
+}
+
+/// One interface's IP configuration and admin state, keyed by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceDocument {
+    pub name: String,
+    pub interface_type: JsonInterfaceType,
+    pub ip_address: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    pub admin_up: bool,
+}
+
+/// One VLAN's name and state, as configured under `vlan <id>` / `name` /
+/// `state`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VlanDocument {
+    pub id: u16,
+    pub name: String,
+    pub state: String,
+}
+
+/// One `ntp authentication-key` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NtpKeyDocument {
+    pub key_number: u32,
+    pub algorithm: String,
+    pub key: String,
+}
+
+/// One configured NTP server association, keyed by address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NtpAssociationDocument {
+    pub address: String,
+    pub key_id: Option<u32>,
+}
+
+/// The device's full NTP configuration: keys, trusted keys, whether
+/// authentication is enforced, and the configured server associations.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NtpDocument {
+    pub authentication_enabled: bool,
+    pub keys: Vec<NtpKeyDocument>,
+    pub trusted_keys: Vec<u32>,
+    pub associations: Vec<NtpAssociationDocument>,
+}
+
+/// One ACL rule, omitting the port-operator fields [`AclEntry`] carries --
+/// those are re-derived as "no port restriction" on import, matching how a
+/// hand-written document would describe a plain source/destination rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AclEntryDocument {
+    pub action: String,
+    pub source: String,
+    pub destination: String,
+    pub protocol: Option<String>,
+}
+
+/// One access control list and its rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AclDocument {
+    pub number_or_name: String,
+    pub entries: Vec<AclEntryDocument>,
+}
+
+/// The OSPF process configuration, limited to the fields that round-trip
+/// cleanly through JSON (areas/networks/neighbors are keyed by ad hoc
+/// strings derived from CLI parsing and aren't reconstructed here).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OspfDocument {
+    pub process_id: Option<u32>,
+    pub router_id: Option<String>,
+    pub passive_interfaces: Vec<String>,
+}
+
+/// One static route installed via `ip route <destination> <netmask>
+/// <next-hop-or-interface>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaticRouteDocument {
+    pub destination: String,
+    pub netmask: Ipv4Addr,
+    pub next_hop: String,
+}
+
+/// The full structured configuration document exchanged by `write config
+/// json` / `copy json running-config`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfigDocument {
+    pub hostname: String,
+    pub interfaces: Vec<InterfaceDocument>,
+    pub vlans: Vec<VlanDocument>,
+    pub ntp: NtpDocument,
+    pub acls: Vec<AclDocument>,
+    pub ospf: OspfDocument,
+    pub static_routes: Vec<StaticRouteDocument>,
+}
+
+/// Builds a [`NetworkConfigDocument`] snapshotting `context` and the global
+/// interface/route/ACL/OSPF state, the JSON counterpart to
+/// [`crate::run_config::get_running_config`].
+pub fn build_config_document(context: &CliContext) -> NetworkConfigDocument {
+    let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
+    let status_map = STATUS_MAP.lock().unwrap();
+    let routing_table = ROUTING_TABLE.lock().unwrap();
+    let ospf_config = OSPF_CONFIG.lock().unwrap();
+    let acl_store = ACL_STORE.lock().unwrap();
+
+    let mut interfaces: Vec<InterfaceDocument> = ip_address_state
+        .iter()
+        .map(|(name, (ip_address, netmask))| InterfaceDocument {
+            name: name.clone(),
+            interface_type: JsonInterfaceType::classify(name),
+            ip_address: *ip_address,
+            netmask: *netmask,
+            admin_up: status_map.get(name).copied().unwrap_or(AdminState::Down) == AdminState::Up,
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut vlans: Vec<VlanDocument> = Vec::new();
+    if let (Some(vlan_names), Some(vlan_states)) = (&context.vlan_names, &context.vlan_states) {
+        for (vlan_id_str, name) in vlan_names {
+            if let Ok(id) = vlan_id_str.parse::<u16>() {
+                let state = vlan_states.get(&id).cloned().unwrap_or_else(|| "active".to_string());
+                vlans.push(VlanDocument { id, name: name.clone(), state });
+            }
+        }
+    }
+    vlans.sort_by_key(|vlan| vlan.id);
+
+    let mut keys: Vec<NtpKeyDocument> = context
+        .ntp_authentication_keys
+        .iter()
+        .map(|(key_number, key)| NtpKeyDocument {
+            key_number: *key_number,
+            algorithm: key.algorithm.as_str().to_string(),
+            key: key.key.clone(),
+        })
+        .collect();
+    keys.sort_by_key(|key| key.key_number);
+
+    let mut trusted_keys: Vec<u32> = context.ntp_trusted_keys.iter().copied().collect();
+    trusted_keys.sort();
+
+    let associations: Vec<NtpAssociationDocument> = context
+        .ntp_associations
+        .iter()
+        .filter(|assoc| assoc.address != "127.127.1.1")
+        .map(|assoc| NtpAssociationDocument {
+            address: assoc.address.clone(),
+            key_id: assoc.key_id,
+        })
+        .collect();
+
+    let ntp = NtpDocument {
+        authentication_enabled: context.ntp_authentication_enabled,
+        keys,
+        trusted_keys,
+        associations,
+    };
+
+    let mut acls: Vec<AclDocument> = acl_store
+        .values()
+        .map(|acl| AclDocument {
+            number_or_name: acl.number_or_name.clone(),
+            entries: acl
+                .entries
+                .iter()
+                .map(|entry| AclEntryDocument {
+                    action: entry.action.clone(),
+                    source: entry.source.clone(),
+                    destination: entry.destination.clone(),
+                    protocol: entry.protocol.clone(),
+                })
+                .collect(),
+        })
+        .collect();
+    acls.sort_by(|a, b| a.number_or_name.cmp(&b.number_or_name));
+
+    let ospf = OspfDocument {
+        process_id: ospf_config.process_id,
+        router_id: ospf_config.router_id.clone(),
+        passive_interfaces: ospf_config.passive_interfaces.clone(),
+    };
+
+    let mut static_routes: Vec<StaticRouteDocument> = routing_table
+        .entries()
+        .into_iter()
+        .map(|(network, prefix_len, route)| StaticRouteDocument {
+            destination: network.to_string(),
+            netmask: crate::network_config::prefix_to_netmask(prefix_len as u32),
+            next_hop: route.next_hop,
+        })
+        .collect();
+    static_routes.sort_by(|a, b| a.destination.cmp(&b.destination));
+
+    NetworkConfigDocument {
+        hostname: context.config.hostname.clone(),
+        interfaces,
+        vlans,
+        ntp,
+        acls,
+        ospf,
+        static_routes,
+    }
+}
+
+/// Applies a validated [`NetworkConfigDocument`] to `context` and the global
+/// interface/route/ACL/OSPF state, overwriting whatever was previously
+/// configured for each field the document carries.
+pub fn apply_config_document(doc: &NetworkConfigDocument, context: &mut CliContext) {
+    context.config.hostname = doc.hostname.clone();
+
+    {
+        let mut ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
+        let mut status_map = STATUS_MAP.lock().unwrap();
+        let mut oper_state_map = OPER_STATE_MAP.lock().unwrap();
+        for interface in &doc.interfaces {
+            ip_address_state.insert(interface.name.clone(), (interface.ip_address, interface.netmask));
+            let admin_state = if interface.admin_up { AdminState::Up } else { AdminState::Down };
+            status_map.insert(interface.name.clone(), admin_state);
+            oper_state_map.insert(
+                interface.name.clone(),
+                if interface.admin_up { OperState::Up } else { OperState::Down },
+            );
+        }
+    }
+
+    if !doc.vlans.is_empty() {
+        let mut vlan_names: HashMap<String, String> = HashMap::new();
+        let mut vlan_states: HashMap<u16, String> = HashMap::new();
+        for vlan in &doc.vlans {
+            vlan_names.insert(vlan.id.to_string(), vlan.name.clone());
+            vlan_states.insert(vlan.id, vlan.state.clone());
+        }
+        context.vlan_names = Some(vlan_names);
+        context.vlan_states = Some(vlan_states);
+    }
+
+    context.ntp_authentication_enabled = doc.ntp.authentication_enabled;
+    context.ntp_authentication_keys = doc
+        .ntp
+        .keys
+        .iter()
+        .filter_map(|key| {
+            NtpAuthAlgorithm::from_str(&key.algorithm).map(|algorithm| {
+                (
+                    key.key_number,
+                    NtpAuthKey { algorithm, key: key.key.clone() },
+                )
+            })
+        })
+        .collect();
+    context.ntp_trusted_keys = doc.ntp.trusted_keys.iter().copied().collect();
+    context.ntp_associations = doc
+        .ntp
+        .associations
+        .iter()
+        .map(|assoc| NtpAssociation {
+            address: assoc.address.clone(),
+            key_id: assoc.key_id,
+            ..NtpAssociation::default()
+        })
+        .collect();
+
+    {
+        let mut acl_store = ACL_STORE.lock().unwrap();
+        acl_store.clear();
+        for acl in &doc.acls {
+            acl_store.insert(
+                acl.number_or_name.clone(),
+                AccessControlList {
+                    number_or_name: acl.number_or_name.clone(),
+                    entries: acl
+                        .entries
+                        .iter()
+                        .map(|entry| AclEntry {
+                            action: entry.action.clone(),
+                            source: entry.source.clone(),
+                            destination: entry.destination.clone(),
+                            protocol: entry.protocol.clone(),
+                            matches: None,
+                            source_operator: None,
+                            source_port: None,
+                            destination_operator: None,
+                            destination_port: None,
+                        })
+                        .collect(),
+                },
+            );
+        }
+    }
+
+    {
+        let mut ospf_config = OSPF_CONFIG.lock().unwrap();
+        let mut replacement = OSPFConfig::new();
+        replacement.process_id = doc.ospf.process_id;
+        replacement.router_id = doc.ospf.router_id.clone();
+        replacement.passive_interfaces = doc.ospf.passive_interfaces.clone();
+        *ospf_config = replacement;
+    }
+
+    {
+        let mut routing_table = ROUTING_TABLE.lock().unwrap();
+        *routing_table = RoutingTable::new();
+        for route in &doc.static_routes {
+            if let Ok(destination) = route.destination.parse() {
+                let prefix_len = crate::network_config::netmask_to_prefix(route.netmask) as u8;
+                routing_table.insert(
+                    destination,
+                    prefix_len,
+                    Route { next_hop: route.next_hop.clone(), source: RouteSource::Static, metric: 1, distance_override: None, tag: None },
+                );
+            }
+        }
+    }
+}
+
+/// The JSON Schema a [`NetworkConfigDocument`] must satisfy before
+/// [`apply_config_document`] runs: every object rejects unknown fields, and
+/// VLAN ids / NTP key numbers are range-checked the way a production config
+/// loader would, rather than letting a typo silently become a different
+/// field.
+fn config_document_schema() -> Value {
+    serde_json::json!({
+        "type": "object",
+        "required": ["hostname", "interfaces", "vlans", "ntp", "acls", "ospf", "static_routes"],
+        "additionalProperties": false,
+        "properties": {
+            "hostname": { "type": "string" },
+            "interfaces": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["name", "interface_type", "ip_address", "netmask", "admin_up"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "name": { "type": "string" },
+                        "interface_type": {
+                            "type": "string",
+                            "enum": ["ethernet", "loopback", "routed_vlan", "tunnel_gre4", "tunnel_gre6", "uplink"]
+                        },
+                        "ip_address": { "type": "string" },
+                        "netmask": { "type": "string" },
+                        "admin_up": { "type": "boolean" }
+                    }
+                }
+            },
+            "vlans": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["id", "name", "state"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "id": { "type": "integer", "minimum": 1, "maximum": 4094 },
+                        "name": { "type": "string" },
+                        "state": { "type": "string" }
+                    }
+                }
+            },
+            "ntp": {
+                "type": "object",
+                "required": ["authentication_enabled", "keys", "trusted_keys", "associations"],
+                "additionalProperties": false,
+                "properties": {
+                    "authentication_enabled": { "type": "boolean" },
+                    "keys": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["key_number", "algorithm", "key"],
+                            "additionalProperties": false,
+                            "properties": {
+                                "key_number": { "type": "integer", "minimum": 1 },
+                                "algorithm": { "type": "string", "enum": ["md5", "sha1"] },
+                                "key": { "type": "string" }
+                            }
+                        }
+                    },
+                    "trusted_keys": {
+                        "type": "array",
+                        "items": { "type": "integer", "minimum": 1 }
+                    },
+                    "associations": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["address", "key_id"],
+                            "additionalProperties": false,
+                            "properties": {
+                                "address": { "type": "string" },
+                                "key_id": { "type": ["integer", "null"], "minimum": 1 }
+                            }
+                        }
+                    }
+                }
+            },
+            "acls": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["number_or_name", "entries"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "number_or_name": { "type": "string" },
+                        "entries": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["action", "source", "destination", "protocol"],
+                                "additionalProperties": false,
+                                "properties": {
+                                    "action": { "type": "string", "enum": ["permit", "deny"] },
+                                    "source": { "type": "string" },
+                                    "destination": { "type": "string" },
+                                    "protocol": { "type": ["string", "null"] }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "ospf": {
+                "type": "object",
+                "required": ["process_id", "router_id", "passive_interfaces"],
+                "additionalProperties": false,
+                "properties": {
+                    "process_id": { "type": ["integer", "null"], "minimum": 1 },
+                    "router_id": { "type": ["string", "null"] },
+                    "passive_interfaces": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    }
+                }
+            },
+            "static_routes": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["destination", "netmask", "next_hop"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "destination": { "type": "string" },
+                        "netmask": { "type": "string" },
+                        "next_hop": { "type": "string" }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Validates a parsed document against [`config_document_schema`], returning
+/// a precise path-to-error message for every violation (rather than letting
+/// a malformed or hand-edited document parse into garbage config state).
+pub fn validate_config_document(value: &Value) -> Result<(), String> {
+    let schema = config_document_schema();
+    let compiled = JSONSchema::compile(&schema)
+        .map_err(|err| format!("Internal error: invalid config document schema: {}", err))?;
+    compiled.validate(value).map_err(|errors| {
+        errors
+            .map(|err| format!("{}: {}", err.instance_path, err))
+            .collect::<Vec<_>>()
+            .join("; ")
+    })
+}
@@ -0,0 +1,132 @@
+//! Layered resolution for a handful of crypto/tunnel config values that are
+//! awkward to set purely by interactively-entered command (scripted/CI
+//! runs in particular): command > environment variable (`PNF_<KEY>`) > an
+//! on-disk override file > a built-in default, the same precedence a build
+//! tool's global context layers explicit flags over environment over
+//! project file over tool default. `show config sources` reports which
+//! layer produced each effective value.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+/// The keys this resolver knows how to layer -- matching the `CliConfig`
+/// fields they back (`transform_set` reports the first configured crypto
+/// transform set's name, since transform sets themselves are a map rather
+/// than a single value).
+pub const OVERRIDABLE_KEYS: &[&str] = &[
+    "tunnel_source",
+    "tunnel_destination",
+    "crypto_engine_accelerator",
+    "transform_set",
+];
+
+/// Which layer produced an effective value, most to least specific.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    Command,
+    EnvVar,
+    File,
+    Default,
+}
+
+impl ConfigSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConfigSource::Command => "command",
+            ConfigSource::EnvVar => "environment variable",
+            ConfigSource::File => "override file",
+            ConfigSource::Default => "default",
+        }
+    }
+}
+
+lazy_static! {
+    /// Which of [`OVERRIDABLE_KEYS`] have been set by an explicit command
+    /// this session -- consulted by [`resolve`] so a key only counts as
+    /// [`ConfigSource::Command`] once a matching command closure has
+    /// actually run, rather than whenever `CliConfig`'s field happens to be
+    /// `Some` (e.g. because the wizard or a startup-config load set it).
+    static ref COMMAND_SET: Mutex<HashMap<&'static str, ()>> = Mutex::new(HashMap::new());
+}
+
+/// Records that `key` was just set by an explicit command (`tunnel
+/// source`, `tunnel destination`, `crypto engine accelerator`, or `crypto
+/// ipsec transform-set`), so [`resolve`] reports it as
+/// [`ConfigSource::Command`] instead of falling through to the environment
+/// variable/file/default layers beneath it.
+pub fn mark_set_by_command(key: &'static str) {
+    COMMAND_SET.lock().unwrap().insert(key, ());
+}
+
+/// The on-disk override file [`resolve`] consults beneath environment
+/// variables -- a flat key/value document, analogous to `run_config.rs`'s
+/// `StartupConfigFile` but for values an operator wants pinned outside of
+/// any interactively-entered command or session environment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OverridesFile {
+    pub tunnel_source: Option<String>,
+    pub tunnel_destination: Option<String>,
+    pub crypto_engine_accelerator: Option<String>,
+    pub transform_set: Option<String>,
+}
+
+/// Where [`load_overrides_file`] looks when a command closure doesn't have
+/// a more specific path to hand it.
+pub const DEFAULT_OVERRIDES_PATH: &str = "pnf_overrides.json";
+
+/// Loads `path` as an [`OverridesFile`]; a missing file or malformed JSON
+/// both resolve to an empty (all-`None`) document so the rest of the
+/// layers still apply rather than failing the whole resolution.
+pub fn load_overrides_file(path: &Path) -> OverridesFile {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn file_value<'a>(file: &'a OverridesFile, key: &str) -> Option<&'a str> {
+    match key {
+        "tunnel_source" => file.tunnel_source.as_deref(),
+        "tunnel_destination" => file.tunnel_destination.as_deref(),
+        "crypto_engine_accelerator" => file.crypto_engine_accelerator.as_deref(),
+        "transform_set" => file.transform_set.as_deref(),
+        _ => None,
+    }
+}
+
+/// Resolves one key through command > env var (`PNF_<KEY_UPPER>`) > `file`
+/// > `default`, returning the effective value and which layer produced it.
+/// `command_value` is only honored as the command layer once
+/// [`mark_set_by_command`] has been called for `key` this session --
+/// otherwise resolution falls through to the layers beneath it exactly as
+/// if `command_value` were `None`.
+pub fn resolve(
+    key: &str,
+    command_value: Option<&str>,
+    file: &OverridesFile,
+    default: Option<&str>,
+) -> (Option<String>, ConfigSource) {
+    if COMMAND_SET.lock().unwrap().contains_key(key) {
+        if let Some(value) = command_value {
+            return (Some(value.to_string()), ConfigSource::Command);
+        }
+    }
+
+    let env_name = format!("PNF_{}", key.to_uppercase());
+    if let Ok(value) = std::env::var(&env_name) {
+        if !value.is_empty() {
+            return (Some(value), ConfigSource::EnvVar);
+        }
+    }
+
+    if let Some(value) = file_value(file, key) {
+        return (Some(value.to_string()), ConfigSource::File);
+    }
+
+    (default.map(str::to_string), ConfigSource::Default)
+}
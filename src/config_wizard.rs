@@ -0,0 +1,417 @@
+//! Interactive `config wizard`: walks an operator through the handful of
+//! settings a freshly-imaged device needs (hostname, domain, one interface's
+//! IP and admin state, a default route, basic OSPF, a crypto key) instead of
+//! requiring every command to be typed and remembered individually. Each
+//! answer is applied as soon as it is given; the wizard then previews the
+//! resulting running-config and, on confirmation, offers to persist it with
+//! [`crate::run_config::save_config`]. Declining leaves the answers applied
+//! but unsaved.
+
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+use rpassword::read_password;
+
+use crate::cliconfig::CliContext;
+use crate::cryptocommands::{build_crypto_key, generate_crypto_key, CryptoMapEntry};
+use crate::keystore::KeyStore;
+use crate::network_config::{
+    AccessControlList, AclEntry, AdminState, OperState, Route, RouteSource, ACL_STORE,
+    IP_ADDRESS_STATE, OPER_STATE_MAP, OSPF_CONFIG, ROUTING_TABLE, STATUS_MAP,
+};
+use crate::run_config::{get_running_config, save_config};
+
+/// Reads one line from stdin, trimmed. Returns `default` unchanged if the
+/// operator just presses return.
+fn prompt(question: &str, default: &str) -> String {
+    if default.is_empty() {
+        println!("{}:", question);
+    } else {
+        println!("{} [{}]:", question, default);
+    }
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).expect("Failed to read input");
+    let input = input.trim();
+    if input.is_empty() {
+        default.to_string()
+    } else {
+        input.to_string()
+    }
+}
+
+fn prompt_yes_no(question: &str, default_yes: bool) -> bool {
+    let default = if default_yes { "yes" } else { "no" };
+    let answer = prompt(question, default);
+    answer.eq_ignore_ascii_case("yes") || answer.eq_ignore_ascii_case("y")
+}
+
+/// Runs the wizard against `context`, mutating its `CliConfig` and the shared
+/// `IP_ADDRESS_STATE`/`OSPF_CONFIG` state exactly like the equivalent
+/// `hostname`/`ip address`/`router ospf`/`network area`/`crypto key generate`
+/// commands would, then optionally calls [`save_config`].
+pub fn run_config_wizard(context: &mut CliContext) -> Result<(), String> {
+    println!("--- Configuration wizard ---");
+    println!("Press return to accept the bracketed default at each step.");
+
+    context.config.hostname = prompt("Hostname", &context.config.hostname);
+
+    let default_domain = context.config.domain_name.clone().unwrap_or_default();
+    let domain_name = prompt("Domain name", &default_domain);
+    if !domain_name.is_empty() {
+        context.config.domain_name = Some(domain_name);
+    }
+
+    if prompt_yes_no("Configure an interface's IP address now?", true) {
+        let interface = prompt("Interface name", "FastEthernet0/1");
+        loop {
+            let ip_input = prompt("IP address", "");
+            let netmask_input = prompt("Subnet mask", "255.255.255.0");
+            match (ip_input.parse::<Ipv4Addr>(), netmask_input.parse::<Ipv4Addr>()) {
+                (Ok(ip_address), Ok(netmask)) => {
+                    IP_ADDRESS_STATE.lock().unwrap().insert(interface.clone(), (ip_address, netmask));
+                    println!("Assigned IP {} and netmask {} to interface {}.", ip_address, netmask, interface);
+                    break;
+                }
+                _ => println!("Invalid IP address or netmask. Please try again."),
+            }
+        }
+
+        // Mirrors the `shutdown`/`no shutdown` commands: only the transition
+        // away from the freshly-inserted interface's default `OperState::Down`
+        // is logged.
+        let admin_up = prompt_yes_no("Bring this interface up now?", true);
+        let old_oper_state = OPER_STATE_MAP.lock().unwrap().get(&interface).copied().unwrap_or(OperState::Down);
+        let (new_admin_state, new_oper_state) = if admin_up {
+            (AdminState::Up, OperState::Up)
+        } else {
+            (AdminState::Down, OperState::Down)
+        };
+        STATUS_MAP.lock().unwrap().insert(interface.clone(), new_admin_state);
+        OPER_STATE_MAP.lock().unwrap().insert(interface.clone(), new_oper_state);
+        if old_oper_state != new_oper_state {
+            println!(
+                "%LINK-5-CHANGED: Interface {}, changed state to {}",
+                interface,
+                if admin_up { "up" } else { "administratively down" }
+            );
+        }
+    }
+
+    if prompt_yes_no("Configure a default route now?", false) {
+        loop {
+            let next_hop_input = prompt("Default route next-hop IP", "");
+            match next_hop_input.parse::<Ipv4Addr>() {
+                Ok(next_hop) => {
+                    ROUTING_TABLE.lock().unwrap().insert(
+                        Ipv4Addr::new(0, 0, 0, 0),
+                        0,
+                        Route { next_hop: next_hop.to_string(), source: RouteSource::Static, metric: 1, distance_override: None, tag: None },
+                    );
+                    println!("Added route: ip route 0.0.0.0 0.0.0.0 {}", next_hop);
+                    break;
+                }
+                _ => println!("Invalid IP address. Please try again."),
+            }
+        }
+    }
+
+    if prompt_yes_no("Enable OSPF routing?", false) {
+        loop {
+            let process_id_input = prompt("OSPF process ID", "1");
+            match process_id_input.parse::<u32>() {
+                Ok(process_id) if process_id > 0 => {
+                    OSPF_CONFIG.lock().unwrap().process_id = Some(process_id);
+                    println!("OSPF routing enabled with process ID {}.", process_id);
+                    break;
+                }
+                _ => println!("Invalid process ID. It must be a positive integer."),
+            }
+        }
+
+        if prompt_yes_no("Advertise a network into OSPF now?", true) {
+            loop {
+                let network_input = prompt("Network address", "");
+                let wildcard_input = prompt("Wildcard mask", "0.0.0.255");
+                let area_input = prompt("Area ID", "0");
+                match (
+                    network_input.parse::<Ipv4Addr>(),
+                    wildcard_input.parse::<Ipv4Addr>(),
+                    area_input.parse::<u32>(),
+                ) {
+                    (Ok(network), Ok(wildcard), Ok(area_id)) => {
+                        let key = format!("{} {}", network, wildcard);
+                        OSPF_CONFIG.lock().unwrap().networks.insert(key, area_id);
+                        println!("Network {} {} added to OSPF area {}.", network, wildcard, area_id);
+                        break;
+                    }
+                    _ => println!("Invalid network, wildcard mask, or area ID. Please try again."),
+                }
+            }
+        }
+    }
+
+    if prompt_yes_no("Generate an RSA key pair for this device?", true) {
+        let domain_name = context.config.domain_name.clone();
+        let key_name = format!(
+            "{}.{}",
+            context.config.hostname,
+            domain_name.unwrap_or_else(|| "default_domain".to_string())
+        );
+        let key_size = loop {
+            let size_input = prompt("RSA modulus size", "2048");
+            match size_input.parse::<u32>() {
+                Ok(bits) if (512..=4096).contains(&bits) => break bits,
+                _ => println!("Invalid modulus size. It must be between 512 and 4096."),
+            }
+        };
+        let exportable = prompt_yes_no("Allow this key to be exported later?", false);
+        match generate_crypto_key(&key_name, "rsa", key_size).and_then(|pem| build_crypto_key(pem, exportable)) {
+            Ok(key) => {
+                context.key_store.put(key_name.clone(), key)?;
+                println!("RSA key pair '{}' generated.", key_name);
+            }
+            Err(err) => println!("Skipping key generation: {}", err),
+        }
+    }
+
+    println!("\n--- Generated configuration preview ---");
+    println!("{}", get_running_config(context));
+
+    if prompt_yes_no("Commit this configuration?", true) {
+        if prompt_yes_no("Also save it to the startup config now?", true) {
+            let path_input = prompt("Startup config path", "startup-config.json");
+            println!("Enter a passphrase to encrypt any stored private keys:");
+            let passphrase = read_password().unwrap_or_default();
+            crate::keystore::sync_config_from_stores(context);
+            save_config(&context.config, Path::new(&path_input), &passphrase)?;
+            println!("Configuration saved to {}.", path_input);
+        } else {
+            println!("Wizard complete. Configuration applied but not saved to startup config.");
+        }
+    } else {
+        println!("Wizard complete. The answers above remain applied to the running configuration; nothing was saved.");
+    }
+
+    Ok(())
+}
+
+/// Runs the `config wizard ospf-acl` prompt sequence: an OSPF router-id,
+/// administrative distance, passive interfaces, `default-information
+/// originate`, and a set of numbered-ACL permit/deny rules. Every answer is
+/// validated as it is collected, and nothing touches [`OSPF_CONFIG`] or
+/// [`ACL_STORE`] until the whole sequence has validated successfully, so a
+/// cancelled or erroring run never leaves either store half-updated.
+pub fn run_ospf_acl_wizard(_context: &mut CliContext) -> Result<(), String> {
+    println!("--- OSPF & ACL configuration wizard ---");
+    println!("Press return to accept the bracketed default at each step.");
+
+    let router_id = loop {
+        let input = prompt("OSPF router-id", "");
+        match input.parse::<Ipv4Addr>() {
+            Ok(id) => break id,
+            Err(_) => println!("Invalid router-id. Enter a dotted-quad IPv4 address."),
+        }
+    };
+
+    let distance = loop {
+        let input = prompt("Administrative distance", "110");
+        match input.parse::<u32>() {
+            Ok(d) if (1..=255).contains(&d) => break d,
+            _ => println!("Invalid distance. It must be an integer between 1 and 255."),
+        }
+    };
+
+    let mut passive_interfaces = Vec::new();
+    if prompt_yes_no("Mark any interfaces as passive?", false) {
+        loop {
+            let interface = prompt("Passive interface name (blank to stop)", "");
+            if interface.is_empty() {
+                break;
+            }
+            passive_interfaces.push(interface);
+        }
+    }
+
+    let default_information_originate = prompt_yes_no("Originate a default route into OSPF?", false);
+
+    let mut acl_rules: Vec<(String, AclEntry)> = Vec::new();
+    if prompt_yes_no("Build any ACL rules now?", false) {
+        loop {
+            let acl_number = prompt("ACL number or name", "");
+            if acl_number.is_empty() {
+                break;
+            }
+            let action = loop {
+                let input = prompt("Action (permit/deny)", "permit");
+                let lowered = input.to_lowercase();
+                if lowered == "permit" || lowered == "deny" {
+                    break lowered;
+                }
+                println!("Invalid action. Enter 'permit' or 'deny'.");
+            };
+            let source = loop {
+                let input = prompt("Source address (or 'any')", "any");
+                if input == "any" || input.parse::<Ipv4Addr>().is_ok() {
+                    break input;
+                }
+                println!("Invalid source address. Enter a dotted-quad IPv4 address or 'any'.");
+            };
+            let destination = loop {
+                let input = prompt("Destination address (or 'any')", "any");
+                if input == "any" || input.parse::<Ipv4Addr>().is_ok() {
+                    break input;
+                }
+                println!("Invalid destination address. Enter a dotted-quad IPv4 address or 'any'.");
+            };
+            acl_rules.push((
+                acl_number,
+                AclEntry {
+                    action,
+                    source,
+                    destination,
+                    protocol: None,
+                    matches: None,
+                    source_operator: None,
+                    source_port: None,
+                    destination_operator: None,
+                    destination_port: None,
+                },
+            ));
+
+            if !prompt_yes_no("Add another ACL rule?", false) {
+                break;
+            }
+        }
+    }
+
+    // Every answer validated; apply the whole batch atomically.
+    {
+        let mut ospf_config = OSPF_CONFIG.lock().unwrap();
+        ospf_config.router_id = Some(router_id.to_string());
+        ospf_config.distance = Some(distance);
+        ospf_config.passive_interfaces.extend(passive_interfaces.clone());
+        ospf_config.default_information_originate = default_information_originate;
+    }
+    if !acl_rules.is_empty() {
+        let mut acl_store = ACL_STORE.lock().unwrap();
+        for (acl_number, entry) in &acl_rules {
+            acl_store
+                .entry(acl_number.clone())
+                .or_insert_with(|| AccessControlList { number_or_name: acl_number.clone(), entries: vec![] })
+                .entries
+                .push(entry.clone());
+        }
+    }
+
+    println!("\n--- Equivalent configuration ---");
+    println!("router ospf");
+    println!(" router-id {}", router_id);
+    println!(" distance {}", distance);
+    for interface in &passive_interfaces {
+        println!(" passive-interface {}", interface);
+    }
+    if default_information_originate {
+        println!(" default-information originate");
+    }
+    for (acl_number, entry) in &acl_rules {
+        println!("access-list {} {} {} {}", acl_number, entry.action, entry.source, entry.destination);
+    }
+
+    println!("\nOSPF and ACL configuration applied.");
+    Ok(())
+}
+
+/// Runs the `crypto wizard` prompt sequence: an IPsec profile name, a
+/// transform set, a peer/destination address, the local tunnel-source
+/// interface, and a crypto map binding them together -- the same entries
+/// `crypto ipsec profile`, `crypto ipsec transform-set`, `crypto map ...
+/// ipsec-isakmp`, `crypto map ... local-address`, and `tunnel source` /
+/// `tunnel destination` / `tunnel protection ipsec profile` would write one
+/// at a time. As with [`run_ospf_acl_wizard`], every answer is validated as
+/// it is collected and `context.config` is only touched once the whole
+/// sequence has validated successfully.
+pub fn run_crypto_wizard(context: &mut CliContext) -> Result<(), String> {
+    println!("--- IPsec crypto wizard ---");
+    println!("Press return to accept the bracketed default at each step.");
+
+    let profile_name = loop {
+        let input = prompt("IPsec profile name", "");
+        if !input.is_empty() {
+            break input;
+        }
+        println!("A profile name is required.");
+    };
+
+    let transform_set_name = prompt("Transform set name", &format!("{}-ts", profile_name));
+    let mut transforms = Vec::new();
+    println!("Enter the encryption/authentication transforms for '{}' one at a time (e.g. esp-aes, esp-sha-hmac); blank to stop.", transform_set_name);
+    loop {
+        let input = prompt("Transform (blank to stop)", "");
+        if input.is_empty() {
+            if transforms.is_empty() {
+                println!("At least one transform is required.");
+                continue;
+            }
+            break;
+        }
+        transforms.push(input);
+    }
+
+    let peer = loop {
+        let input = prompt("Peer / tunnel destination IP address", "");
+        match input.parse::<Ipv4Addr>() {
+            Ok(ip) => break ip,
+            Err(_) => println!("Invalid IP address. Enter a dotted-quad IPv4 address."),
+        }
+    };
+
+    let local_interface = prompt("Local tunnel-source interface", "FastEthernet0/1");
+
+    let map_name = prompt("Crypto map name", &format!("{}-map", profile_name));
+    let map_seq_num = loop {
+        let input = prompt("Crypto map sequence number", "10");
+        match input.parse::<u32>() {
+            Ok(seq) if seq > 0 => break seq,
+            _ => println!("Invalid sequence number. It must be a positive integer."),
+        }
+    };
+
+    println!("\n--- Summary ---");
+    println!("crypto ipsec profile {}", profile_name);
+    println!("crypto ipsec transform-set {} {}", transform_set_name, transforms.join(" "));
+    println!("crypto map {} {} ipsec-isakmp", map_name, map_seq_num);
+    println!(" peer {}", peer);
+    println!(" transform-set {}", transform_set_name);
+    println!("crypto map {} {} local-address {}", map_name, map_seq_num, local_interface);
+    println!("tunnel source {}", local_interface);
+    println!("tunnel destination {}", peer);
+    println!("tunnel protection ipsec profile {}", profile_name);
+
+    if !prompt_yes_no("Apply this configuration?", true) {
+        println!("Wizard cancelled. Nothing was applied.");
+        return Ok(());
+    }
+
+    // Every answer validated; apply the whole batch atomically.
+    context.config.crypto_ipsec_profile = Some(profile_name.clone());
+    context.config.crypto_transform_sets.insert(transform_set_name.clone(), transforms);
+    context.config.crypto_local_addresses.insert(map_name.clone(), local_interface.clone());
+    context.config.crypto_maps.insert(
+        map_name.clone(),
+        CryptoMapEntry {
+            name: map_name.clone(),
+            seq_num: map_seq_num,
+            interface_id: Some(local_interface.clone()),
+            map_type: Some("ipsec-isakmp".to_string()),
+            peer: Some(peer.to_string()),
+            transform_set: Some(transform_set_name),
+            match_acl: None,
+        },
+    );
+    context.config.tunnel_source = Some(local_interface);
+    context.config.tunnel_destination = Some(peer.to_string());
+    context.config.tunnel_protection_profile = Some(profile_name);
+
+    println!("\nIPsec profile, transform set, crypto map, and tunnel binding applied.");
+    Ok(())
+}
@@ -1,5 +1,18 @@
 use crate::cliconfig::CliConfig;
+use rand::rngs::OsRng;
+use rcgen::{
+    Certificate, CertificateParams, DistinguishedName, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose, SanType, PKCS_RSA_SHA256,
+};
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::traits::PublicKeyParts;
+use rsa::RsaPrivateKey;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::{Duration, OffsetDateTime};
+use x509_parser::pem::parse_x509_pem;
+use x509_parser::time::ASN1Time;
+use zeroize::Zeroize;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct DynamicMapEntry {
@@ -14,12 +27,22 @@ pub struct IPSecLifetime {
     pub kilobytes: Option<u32>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct CryptoMapEntry {
     pub name: String,
     pub seq_num: u32,
     pub interface_id: Option<String>,
-    // Add other relevant fields
+    /// The map type configured on this entry, e.g. `"ipsec-isakmp"`; `None`
+    /// for the original bare `crypto map <name> <seq-num>` form.
+    pub map_type: Option<String>,
+    /// The peer address configured via `crypto map ... ipsec-isakmp peer <ip>`.
+    pub peer: Option<String>,
+    /// The transform set bound via `crypto map ... transform-set <name>`,
+    /// validated against `CliConfig::crypto_transform_sets` when set.
+    pub transform_set: Option<String>,
+    /// The ACL bound via `crypto map ... match address <acl-name>`,
+    /// validated against `ACL_STORE` when set.
+    pub match_acl: Option<String>,
 }
 
 impl Default for IPSecLifetime {
@@ -31,86 +54,388 @@ impl Default for IPSecLifetime {
     }
 }
 
+/// An IKE (ISAKMP) policy configured via `crypto isakmp policy <n>`, which
+/// enters a sub-mode for setting `encryption`, `hash`, `authentication`,
+/// `group`, and `lifetime` one at a time -- mirrors the way [`IPSecLifetime`]
+/// fields are set incrementally rather than all at once.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct IsakmpPolicy {
+    pub encryption: Option<String>,
+    pub hash: Option<String>,
+    pub authentication: Option<String>,
+    pub group: Option<u32>,
+    pub lifetime: Option<u32>,
+}
+
+/// An IKE mode-config client group configured via `crypto isakmp client
+/// configuration group <name>`, which pushes an address pool, DNS server,
+/// and pre-shared key to a remote VPN client -- the strongSwan/ISAKMP
+/// mode-config flow this chunk is modeled on.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct IsakmpClientGroup {
+    pub pool: Option<String>,
+    pub dns: Option<String>,
+    pub key: Option<String>,
+}
+
+/// An address pool configured via `ip local pool <name> <start> <end>`,
+/// handed out to remote VPN clients by an [`IsakmpClientGroup`]'s `pool`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AddressPool {
+    pub start: String,
+    pub end: String,
+}
+
+/// A private-key PEM that zeroizes its bytes the moment it's dropped,
+/// instead of relying on every call site remembering to call `.zeroize()`
+/// manually. Serializes and `Deref`s transparently to the plain PEM `String`
+/// it wraps, so existing callers that just want `&str` (`inspect_crypto_key`,
+/// the WebSocket management channel's key lookup) don't need to change.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SensitivePem(String);
+
+impl std::ops::Deref for SensitivePem {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for SensitivePem {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// A key stored by `crypto key generate`/`crypto key import`: the PKCS#8 PEM
+/// wrapped in [`SensitivePem`], plus whether it was created with
+/// `exportable`, mirroring Cisco IOS's `crypto key generate rsa
+/// [exportable]`. Keys default to non-exportable, so `exportable` must be
+/// tracked alongside the PEM rather than inferred -- `crypto key export`
+/// refuses to emit anything for a key where this is `false`.
+///
+/// `algorithm`/`bits`/`fingerprint`/`created` are captured once, at
+/// [`build_crypto_key`] time, so `show crypto key` can display them without
+/// re-parsing the PEM (and without ever needing to touch the private bytes
+/// again).
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CryptoKey {
+    pub pem: SensitivePem,
+    pub exportable: bool,
+    pub algorithm: &'static str,
+    pub bits: usize,
+    pub fingerprint: String,
+    pub created: String,
+}
+
+/// Validates `pem` as a well-formed key (via [`inspect_crypto_key`]) and
+/// packages it into a [`CryptoKey`] with its metadata filled in, so every
+/// `crypto key generate`/`crypto key import` call site stores the same
+/// shape instead of hand-rolling a struct literal around an unvalidated PEM.
+pub fn build_crypto_key(pem: String, exportable: bool) -> Result<CryptoKey, String> {
+    let info = inspect_crypto_key(&pem)?;
+    Ok(CryptoKey {
+        pem: SensitivePem(pem),
+        exportable,
+        algorithm: info.algorithm,
+        bits: info.modulus_bits,
+        fingerprint: info.fingerprint_sha256,
+        created: chrono::Local::now().to_string(),
+    })
+}
+
+/// Builds the `DistinguishedName`/SAN/key-usage/validity parameters shared by
+/// both a self-signed certificate and a CSR, so the two stay in lockstep.
+/// `common_name` is used both as the subject CN and as the sole SAN DNS
+/// entry; `organization` becomes the `O` attribute; the certificate is valid
+/// from now for `validity_days`.
+fn certificate_params(common_name: &str, organization: &str, validity_days: u32) -> CertificateParams {
+    let mut distinguished_name = DistinguishedName::new();
+    distinguished_name.push(DnType::CommonName, common_name);
+    distinguished_name.push(DnType::OrganizationName, organization);
+
+    let mut params = CertificateParams::new(vec![common_name.to_string()]);
+    params.distinguished_name = distinguished_name;
+    params.subject_alt_names = vec![SanType::DnsName(common_name.to_string())];
+    params.key_usages = vec![
+        KeyUsagePurpose::DigitalSignature,
+        KeyUsagePurpose::KeyEncipherment,
+    ];
+    params.extended_key_usages = vec![
+        ExtendedKeyUsagePurpose::ServerAuth,
+        ExtendedKeyUsagePurpose::ClientAuth,
+    ];
+    params.is_ca = IsCa::NoCa;
+    let not_before = OffsetDateTime::now_utc();
+    params.not_before = not_before;
+    params.not_after = not_before + Duration::days(validity_days as i64);
+    params
+}
+
+/// Parses `key`'s stored PEM into an `rcgen::KeyPair` so a generated
+/// certificate/CSR is signed by -- and binds the public key of -- a key the
+/// operator already created with `crypto key generate`/`crypto key import`,
+/// rather than a throwaway keypair `rcgen` would otherwise generate for
+/// itself. Only RSA keys are stored today, so the signature algorithm is
+/// always [`PKCS_RSA_SHA256`].
+fn key_pair_from_crypto_key(key_name: &str, key: &CryptoKey) -> Result<(KeyPair, &'static rcgen::SignatureAlgorithm), String> {
+    let key_pair = KeyPair::from_pem(&key.pem)
+        .map_err(|err| format!("Key '{}' cannot be used to sign a certificate: {}", key_name, err))?;
+    Ok((key_pair, &PKCS_RSA_SHA256))
+}
+
+/// The hostname-derived common name every generated cert/CSR in this module
+/// is issued for, matching the `key_name` the `crypto key generate` command
+/// already builds from `config.hostname`/`config.domain_name`.
+fn device_common_name(config: &CliConfig) -> String {
+    format!(
+        "{}.{}",
+        config.hostname,
+        config
+            .domain_name
+            .clone()
+            .unwrap_or_else(|| "default_domain".to_string())
+    )
+}
 
 // Helper functions for key operations
 pub fn generate_crypto_key(key_name: &str, key_type: &str, key_size: u32) -> Result<String, String> {
-    // Simulate key generation - in production, use a crypto library
-    let key_data = format!("-----BEGIN {} PRIVATE KEY-----\n", key_type.to_uppercase()) +
-        &format!("Generated {} key for {} with size {}\n", key_type, key_name, key_size) +
-        &format!("-----END {} PRIVATE KEY-----", key_type.to_uppercase());
-    Ok(key_data)
+    match key_type {
+        "rsa" => {
+            let private_key = RsaPrivateKey::new(&mut OsRng, key_size as usize)
+                .map_err(|err| format!("Failed to generate RSA key for '{}': {}", key_name, err))?;
+            private_key
+                .to_pkcs8_pem(LineEnding::LF)
+                .map(|pem| pem.to_string())
+                .map_err(|err| format!("Failed to encode RSA key for '{}': {}", key_name, err))
+        }
+        "dsa" => Err(
+            "DSA key generation is not supported; no maintained crypto library in this project \
+             generates DSA keys. Use 'crypto key generate rsa' instead."
+                .to_string(),
+        ),
+        other => Err(format!("Unsupported key type '{}'. Use 'rsa'.", other)),
+    }
 }
 
-pub fn delete_crypto_key(key_name: &str) -> Result<(), String> {
-    // Simulate secure key deletion
+/// Drops `key` immediately, rather than just letting it fall out of scope
+/// whenever the caller's match arm happens to end: [`SensitivePem`]'s `Drop`
+/// impl overwrites the private key bytes in place before the allocator ever
+/// sees them as free.
+pub fn delete_crypto_key(key_name: &str, key: CryptoKey) -> Result<(), String> {
     println!("Securely deleting key: {}", key_name);
+    drop(key);
     Ok(())
 }
 
+/// Derives the SubjectPublicKeyInfo PEM for an exportable key. Callers must
+/// check `CryptoKey::exportable` themselves -- this function only knows how
+/// to encode, not whether it's allowed to.
+pub fn export_public_key(key_data: &str) -> Result<String, String> {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(key_data)
+        .map_err(|err| format!("Malformed or unsupported key data: {}", err))?;
+    private_key
+        .to_public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|err| format!("Failed to encode public key: {}", err))
+}
+
+/// Reads a pasted PEM from stdin line by line (matching the
+/// `io::stdin().read_line` loop the rest of this CLI already uses for
+/// multi-line prompts) until a blank line ends it, then validates it's a
+/// well-formed key via [`inspect_crypto_key`] instead of storing whatever
+/// text was pasted. Rejects anything that isn't a parseable PKCS#8 RSA key
+/// with a descriptive error rather than silently storing garbage.
 pub fn import_crypto_key(key_type: &str) -> Result<String, String> {
-    // Simulate key import - in production, validate and process the input
-    let key_data = format!("-----BEGIN {} PRIVATE KEY-----\n", key_type.to_uppercase()) +
-        "Imported key data would go here\n" +
-        &format!("-----END {} PRIVATE KEY-----", key_type.to_uppercase());
-    Ok(key_data)
+    if key_type != "rsa" {
+        return Err(format!(
+            "Unsupported key type '{}' for import. Only 'rsa' keys can be imported.",
+            key_type
+        ));
+    }
+
+    let mut pem = String::new();
+    loop {
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+        pem.push_str(&line);
+    }
+
+    inspect_crypto_key(&pem)?;
+    Ok(pem)
 }
 
 // Helper functions for certificate operations
-pub fn generate_self_signed_certificate(cert_name: &str, config: &CliConfig) -> Result<String, String> {
-    // Simulate certificate generation - in production, use a crypto library
-    let cert_data = format!(
-        "-----BEGIN CERTIFICATE-----\n\
-         Subject: CN={}.{}\n\
-         Issuer: Self Signed\n\
-         Valid: 1 year\n\
-         -----END CERTIFICATE-----",
-        config.hostname,
-        config.domain_name.clone().unwrap_or("default_domain".to_string())
-    );
-    Ok(cert_data)
-}
-
-pub fn generate_certificate_request(cert_name: &str, config: &CliConfig) -> Result<String, String> {
-    // Simulate CSR generation - in production, use a crypto library
-    let csr_data = format!(
-        "-----BEGIN CERTIFICATE REQUEST-----\n\
-         Subject: CN={}.{}\n\
-         Organization: {}\n\
-         Key Type: RSA 2048\n\
-         -----END CERTIFICATE REQUEST-----",
-        config.hostname,
-        config.domain_name.clone().unwrap_or("default_domain".to_string()),
-        cert_name
-    );
-    Ok(csr_data)
+
+/// Generates a self-signed certificate bound to `key` (looked up by the
+/// caller from whichever `KeyStore` is active -- see [`crate::keystore`] --
+/// under `key_name`), so the certificate's public key -- and the private key
+/// that can prove ownership of it -- is a key the operator actually holds,
+/// rather than a keypair invented just for this certificate. `common_name`
+/// defaults to [`device_common_name`] and `organization` defaults to
+/// `cert_name`, matching this command's behavior before subject fields were
+/// configurable.
+pub fn generate_self_signed_certificate(
+    cert_name: &str,
+    config: &CliConfig,
+    key_name: &str,
+    key: &CryptoKey,
+    common_name: Option<&str>,
+    organization: Option<&str>,
+    validity_days: u32,
+) -> Result<String, String> {
+    let (key_pair, alg) = key_pair_from_crypto_key(key_name, key)?;
+
+    let common_name = common_name.map(str::to_string).unwrap_or_else(|| device_common_name(config));
+    let organization = organization.unwrap_or(cert_name);
+    let mut params = certificate_params(&common_name, organization, validity_days);
+    params.alg = alg;
+    params.key_pair = Some(key_pair);
+
+    let cert = Certificate::from_params(params).map_err(|err| {
+        format!("Failed to generate self-signed certificate '{}': {}", cert_name, err)
+    })?;
+    cert.serialize_pem().map_err(|err| {
+        format!("Failed to serialize self-signed certificate '{}': {}", cert_name, err)
+    })
+}
+
+/// Generates a CSR bound to `key_name`, the same way
+/// [`generate_self_signed_certificate`] does for a self-signed certificate.
+pub fn generate_certificate_request(
+    cert_name: &str,
+    config: &CliConfig,
+    key_name: &str,
+    key: &CryptoKey,
+    common_name: Option<&str>,
+    organization: Option<&str>,
+    validity_days: u32,
+) -> Result<String, String> {
+    let (key_pair, alg) = key_pair_from_crypto_key(key_name, key)?;
+
+    let common_name = common_name.map(str::to_string).unwrap_or_else(|| device_common_name(config));
+    let organization = organization.unwrap_or(cert_name);
+    let mut params = certificate_params(&common_name, organization, validity_days);
+    params.alg = alg;
+    params.key_pair = Some(key_pair);
+
+    let cert = Certificate::from_params(params)
+        .map_err(|err| format!("Failed to build CSR '{}': {}", cert_name, err))?;
+    cert.serialize_request_pem()
+        .map_err(|err| format!("Failed to serialize CSR '{}': {}", cert_name, err))
 }
 
+/// Reads a pasted PEM certificate from stdin line by line until a blank line,
+/// the same way [`import_crypto_key`] reads a pasted key, then validates it
+/// with [`inspect_certificate`] -- rejecting malformed DER and certificates
+/// that are already expired -- instead of the previous stub, which stored a
+/// fabricated placeholder regardless of what (if anything) was pasted.
 pub fn import_certificate(cert_name: &str) -> Result<String, String> {
-    // Simulate certificate import - in production, validate and process the input
-    let cert_data = format!(
-        "-----BEGIN CERTIFICATE-----\n\
-         Imported certificate for: {}\n\
-         -----END CERTIFICATE-----",
-        cert_name
-    );
-    Ok(cert_data)
-}
-
-// Helper functions for certificate parsing
-pub fn extract_subject_from_cert(cert_data: &str) -> Option<String> {
-    // In a real implementation, properly parse the certificate
-    // This is a simple example that looks for the Subject line
-    cert_data
-        .lines()
-        .find(|line| line.contains("Subject:"))
-        .map(|line| line.trim().to_string())
-}
-
-pub fn extract_issuer_from_cert(cert_data: &str) -> Option<String> {
-    // In a real implementation, properly parse the certificate
-    // This is a simple example that looks for the Issuer line
-    cert_data
-        .lines()
-        .find(|line| line.contains("Issuer:"))
-        .map(|line| line.trim().to_string())
-}
\ No newline at end of file
+    let mut pem = String::new();
+    loop {
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+        pem.push_str(&line);
+    }
+
+    let info = inspect_certificate(&pem)
+        .map_err(|err| format!("Failed to import certificate '{}': {}", cert_name, err))?;
+    if info.status != "Active" {
+        return Err(format!(
+            "Certificate '{}' is not currently valid (valid {} to {}); refusing to import.",
+            cert_name, info.not_before, info.not_after
+        ));
+    }
+    Ok(pem)
+}
+
+/// What `show crypto key` reports for one stored key, parsed from the
+/// actual PKCS#8 DER rather than sniffed from the PEM banner text.
+pub struct KeyInfo {
+    pub algorithm: &'static str,
+    pub modulus_bits: usize,
+    pub fingerprint_sha256: String,
+}
+
+/// PEM-decodes `key_data` as a PKCS#8 RSA private key and reports its real
+/// modulus size and a SHA-256 fingerprint of its public key, the way `ssh-
+/// keygen -l`/`openssl rsa -noout -text` would -- rather than sniffing the
+/// PEM banner for "BEGIN RSA". Returns a descriptive error for anything
+/// that isn't a well-formed RSA PKCS#8 key instead of silently showing
+/// nothing.
+pub fn inspect_crypto_key(key_data: &str) -> Result<KeyInfo, String> {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(key_data)
+        .map_err(|err| format!("Malformed or unsupported key data: {}", err))?;
+
+    let public_key_der = private_key
+        .to_public_key()
+        .to_public_key_der()
+        .map_err(|err| format!("Failed to encode public key: {}", err))?;
+    let mut hasher = Sha256::new();
+    hasher.update(public_key_der.as_bytes());
+    let fingerprint_sha256 = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<String>>()
+        .join(":");
+
+    Ok(KeyInfo {
+        algorithm: "RSA",
+        modulus_bits: private_key.n().bits(),
+        fingerprint_sha256,
+    })
+}
+
+/// What `show crypto certificate` reports for one stored certificate,
+/// parsed from the actual X.509 structure.
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: String,
+    pub not_after: String,
+    /// `"Active"` if now falls within the certificate's validity window,
+    /// `"Expired"` otherwise (including not-yet-valid certificates).
+    pub status: &'static str,
+}
+
+/// PEM-decodes `cert_data` as an X.509 certificate and reports its parsed
+/// Subject/Issuer DN, serial number, validity window, and a computed
+/// Active/Expired status -- rather than the ad-hoc `extract_subject_from_cert`/
+/// `extract_issuer_from_cert` pair alone. Returns a descriptive error for
+/// malformed PEM instead of silently showing nothing.
+pub fn inspect_certificate(cert_data: &str) -> Result<CertificateInfo, String> {
+    let (_, pem) = parse_x509_pem(cert_data.as_bytes())
+        .map_err(|err| format!("Malformed certificate data: {}", err))?;
+    let cert = pem
+        .parse_x509()
+        .map_err(|err| format!("Malformed certificate data: {}", err))?;
+
+    let validity = cert.validity();
+    let now = ASN1Time::now();
+    let status = if now >= validity.not_before && now <= validity.not_after {
+        "Active"
+    } else {
+        "Expired"
+    };
+
+    Ok(CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        serial: cert.raw_serial_as_string(),
+        not_before: validity.not_before.to_string(),
+        not_after: validity.not_after.to_string(),
+        status,
+    })
+}
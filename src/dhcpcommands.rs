@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+
+/// A DHCP address pool configured via `ip dhcp pool <name>`, modeled on the
+/// per-host mac->ip leasing of tools like nfdhcpd/kea. Fields are set one at
+/// a time from the pool's sub-mode, mirroring [`crate::cryptocommands::IsakmpPolicy`].
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct DhcpPool {
+    /// The network address and subnet mask configured via `network <ip> <mask>`.
+    pub network: Option<(Ipv4Addr, Ipv4Addr)>,
+    pub default_router: Option<Ipv4Addr>,
+    pub dns_servers: Vec<Ipv4Addr>,
+    pub domain_name: Option<String>,
+    /// The lease lifetime configured via `lease <days> <hours> <minutes>`.
+    pub lease: Option<(u32, u32, u32)>,
+}
+
+/// A simulated DHCP lease recorded via `show ip dhcp binding`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DhcpBinding {
+    pub ip_address: Ipv4Addr,
+    pub mac_address: String,
+    pub lease_expires: String,
+}
+
+/// Renders a `(days, hours, minutes)` lease tuple the way `show ip dhcp
+/// binding` reports a lease's remaining time, e.g. `"1 days 0 hours 0 minutes"`.
+/// Defaults to the Cisco default lease of one day when the pool hasn't set one.
+pub fn format_lease_expiry(lease: Option<(u32, u32, u32)>) -> String {
+    let (days, hours, minutes) = lease.unwrap_or((1, 0, 0));
+    format!("{} days {} hours {} minutes", days, hours, minutes)
+}
+
+/// Derives a stable pseudo-MAC address for `ip_address`, so the same address
+/// always gets bound to the same simulated client -- there's no real DHCP
+/// discover/request exchange to assign one from.
+pub fn pseudo_mac_for(ip_address: &Ipv4Addr) -> String {
+    let octets = ip_address.octets();
+    format!(
+        "00{:02x}.{:02x}{:02x}.{:02x}{:02x}",
+        octets[0], octets[1], octets[2], octets[3], octets[0].wrapping_add(octets[3])
+    )
+}
+
+/// Finds the lowest address in `network`'s range (exclusive of the network
+/// and broadcast addresses) that isn't in `excluded_addresses` and doesn't
+/// already have a binding in `bound_addresses`. Returns `None` if the pool
+/// has no `network` configured or the range is exhausted.
+pub fn next_free_address(
+    network: Option<(Ipv4Addr, Ipv4Addr)>,
+    excluded_addresses: &[(Ipv4Addr, Ipv4Addr)],
+    bound_addresses: &[Ipv4Addr],
+) -> Option<Ipv4Addr> {
+    let (network_addr, netmask) = network?;
+    let network_bits = u32::from(network_addr) & u32::from(netmask);
+    let broadcast_bits = network_bits | !u32::from(netmask);
+
+    for candidate_bits in (network_bits + 1)..broadcast_bits {
+        let candidate = Ipv4Addr::from(candidate_bits);
+        let is_excluded = excluded_addresses
+            .iter()
+            .any(|(start, end)| u32::from(*start) <= candidate_bits && candidate_bits <= u32::from(*end));
+        let is_bound = bound_addresses.contains(&candidate);
+        if !is_excluded && !is_bound {
+            return Some(candidate);
+        }
+    }
+    None
+}
@@ -9,14 +9,25 @@ use crate::execute::{Command, Mode};
 use crate::walkup::ModeHierarchy;
 
 lazy_static! {
-    /// Global registry for dynamic commands with thread-safe access
-    pub static ref DYNAMIC_COMMANDS: RwLock<HashMap<&'static str, Command>> = RwLock::new(HashMap::new());
-    pub static ref MODE_PERMISSIONS: RwLock<HashMap<&'static str, Vec<Mode>>> = RwLock::new(HashMap::new());
+    /// Global registry for dynamic commands with thread-safe access.
+    ///
+    /// Keyed by owned `String` rather than `&'static str` so a command name
+    /// that only exists at runtime -- e.g. one reported by a JSON-RPC plugin's
+    /// `describe` handshake (see `plugins::discover_rpc_plugins`) -- can be
+    /// registered without needing a string literal baked into the binary.
+    pub static ref DYNAMIC_COMMANDS: RwLock<HashMap<String, Command>> = RwLock::new(HashMap::new());
+    pub static ref MODE_PERMISSIONS: RwLock<HashMap<String, Vec<Mode>>> = RwLock::new(HashMap::new());
 }
 
-/// Registers a new command dynamically with comprehensive configuration options
+/// Registers a new command dynamically with comprehensive configuration options.
+///
+/// `name` only needs to live as long as the call -- it's copied into the
+/// registry's own `String` key. `Command::name` itself is still `&'static
+/// str` (shared with every built-in command), so a `name` that isn't already
+/// `'static` is leaked once here; that's fine for a command registered for
+/// the life of the process, which is the only way this is ever used.
 pub fn register_command(
-    name: &'static str,
+    name: &str,
     description: &'static str,
     suggestions: Option<Vec<&'static str>>,
     suggestions1: Option<Vec<&'static str>>,
@@ -24,61 +35,73 @@ pub fn register_command(
     execute: fn(&[&str], &mut CliContext, &mut Option<Clock>) -> Result<(), String>,
     allowed_modes: Option<Vec<Mode>>, // New parameter to specify allowed modes
 ) -> Result<(), String> {
+    let require_subcommand = suggestions1.as_ref().map_or(false, |chains| !chains.is_empty());
+    let static_name: &'static str = Box::leak(name.to_string().into_boxed_str());
     let command = Command {
-        name,
+        name: static_name,
         description,
         suggestions,
         suggestions1,
         options,
+        require_subcommand,
+        aliases: Vec::new(),
+        args: Vec::new(),
+        help: description,
+        usage: None,
+        // Dynamic commands gate their mode availability through
+        // `MODE_PERMISSIONS`/`is_dynamic_command_allowed_in_mode` instead,
+        // since `allowed_modes` here is a runtime `Vec` and `modes` needs a
+        // `'static` slice.
+        modes: &[],
         execute,
     };
-    
+
     let mut commands = DYNAMIC_COMMANDS
         .write()
         .map_err(|_| "Failed to acquire write lock")?;
-    
+
     // Store the command with optional mode restrictions
-    commands.insert(name, command);
-    
+    commands.insert(name.to_string(), command);
+
     if let Some(modes) = allowed_modes {
         let mut permissions = MODE_PERMISSIONS
             .write()
             .map_err(|_| "Failed to acquire permissions write lock")?;
-        permissions.insert(name, modes);
+        permissions.insert(name.to_string(), modes);
     }
 
-    println!("Dynamic commands registry now contains: {:?}", 
+    println!("Dynamic commands registry now contains: {:?}",
         commands.keys().collect::<Vec<_>>()
     );
     // If modes are specified, you can add additional mode-based logic here
-    
+
     Ok(())
 }
 
 /// Retrieves all registered dynamic commands
-pub fn get_registered_commands() -> Result<HashMap<&'static str, Command>, String> {
+pub fn get_registered_commands() -> Result<HashMap<String, Command>, String> {
     let commands = DYNAMIC_COMMANDS
         .read()
         .map_err(|_| "Failed to acquire read lock")?;
-    
+
     Ok(commands.clone())
 }
 
 /// Checks if a command is allowed in a specific mode
 pub fn is_dynamic_command_allowed_in_mode(command_name: &str, mode: &Mode) -> bool {
     let mode_hierarchy = ModeHierarchy::new();
-    
+
     // First, check if the command exists in the dynamic registry
     let commands = match DYNAMIC_COMMANDS.read() {
         Ok(cmds) => cmds,
         Err(_) => return false,
     };
-    
+
     // If command doesn't exist, return false
     if !commands.contains_key(command_name) {
         return false;
     }
-    
+
     // Use the walkup method to determine command validity
     match mode_hierarchy.walkup_find_command(mode.clone(), command_name) {
         Some(_) => true,
@@ -86,45 +109,45 @@ pub fn is_dynamic_command_allowed_in_mode(command_name: &str, mode: &Mode) -> bo
     }
 }
 
-pub fn get_commands_for_mode(mode: &Mode) -> Vec<&'static str> {
+pub fn get_commands_for_mode(mode: &Mode) -> Vec<String> {
     let mut allowed_commands = Vec::new();
-    
+
     if let (Ok(permissions), Ok(commands)) = (MODE_PERMISSIONS.read(), DYNAMIC_COMMANDS.read()) {
         for (command_name, allowed_modes) in permissions.iter() {
-            if allowed_modes.contains(mode) || 
+            if allowed_modes.contains(mode) ||
                (mode == &Mode::PrivilegedMode && allowed_modes.contains(&Mode::UserMode)) ||
-               (mode == &Mode::ConfigMode && (allowed_modes.contains(&Mode::UserMode) || 
+               (mode == &Mode::ConfigMode && (allowed_modes.contains(&Mode::UserMode) ||
                                             allowed_modes.contains(&Mode::PrivilegedMode))) ||
-               (mode == &Mode::InterfaceMode && (allowed_modes.contains(&Mode::UserMode) || 
+               (mode == &Mode::InterfaceMode && (allowed_modes.contains(&Mode::UserMode) ||
                                                 allowed_modes.contains(&Mode::PrivilegedMode) ||
                                                 allowed_modes.contains(&Mode::ConfigMode))) {
                 if commands.contains_key(command_name) {
-                    allowed_commands.push(*command_name);
+                    allowed_commands.push(command_name.clone());
                 }
             }
         }
     }
-    
+
     allowed_commands
 }
 
-pub fn get_mode_commands_FNC<'a>(commands: &'a HashMap<&str, Command>, mode: &Mode) -> Vec<&'a str> {
+pub fn get_mode_commands_FNC<'a>(commands: &'a HashMap<String, Command>, mode: &Mode) -> Vec<&'a str> {
     if let Ok(permissions) = MODE_PERMISSIONS.read() {
         // Filter commands based on the mode permissions
         commands
             .keys()
-            .filter(|&cmd_name| {
-                if let Some(allowed_modes) = permissions.get(cmd_name) {
+            .filter(|cmd_name| {
+                if let Some(allowed_modes) = permissions.get(cmd_name.as_str()) {
                     allowed_modes.contains(mode)
                 } else {
                     false // If no permissions specified, command is not available
                 }
             })
-            .copied()
+            .map(|cmd_name| cmd_name.as_str())
             .collect()
     } else {
         // Return empty vec if we can't read the permissions
         Vec::new()
     }   //.into_iter().collect()
-    
-}
\ No newline at end of file
+
+}
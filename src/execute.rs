@@ -2,6 +2,7 @@
 
 /// External crates for the CLI application
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use crate::Clock;
 use crate::CliContext;
 use crate::commandcompleter::{CommandCompleter};
@@ -22,12 +23,20 @@ use crate::commandcompleter::{CommandCompleter};
 ///   An optional list of related or commonly used commands that can be suggested to the user.  
 ///   If `None`, no suggestions will be provided for the command.
 ///
-/// - `execute`:  
-///   A function pointer defining the command's logic. This function is executed when the command is invoked.  
+/// - `args`:
+///   A structured description of this command's expected arguments, in order.
+///   Drives the contextual help rendered alongside completion candidates.
+///
+/// - `help`:
+///   A one-line help string shown next to the command in the completion menu
+///   and by the `usage()` helper. Usually mirrors `description`.
+///
+/// - `execute`:
+///   A function pointer defining the command's logic. This function is executed when the command is invoked.
 ///   It accepts the following arguments:
 ///     - `&[&str]`: The list of arguments provided with the command.
 ///     - `&mut CliContext`: The current CLI context, including mode, configuration, and state.
-///     - `&mut Option<Clock>`: An optional mutable reference to the clock, allowing the command to manipulate system time settings if needed.  
+///     - `&mut Option<Clock>`: An optional mutable reference to the clock, allowing the command to manipulate system time settings if needed.
 ///   Returns a `Result<(), String>`, where `Ok(())` indicates success and `Err(String)` contains an error message if execution fails.
 pub struct Command {
     pub name: &'static str,
@@ -35,9 +44,251 @@ pub struct Command {
     pub suggestions: Option<Vec<&'static str>>,
     pub suggestions1: Option<Vec<&'static str>>,
     pub options: Option<Vec<&'static str>>,
+    /// Whether a bare invocation of this command (no subcommand token at
+    /// all) is an error. `true` for every existing command with a non-empty
+    /// `suggestions1` -- matching the dispatcher's historical behavior of
+    /// always demanding one -- but a command can opt out by setting this to
+    /// `false`, in which case an empty subcommand falls through to
+    /// `execute` with no args instead of erroring.
+    pub require_subcommand: bool,
+    /// Short, commonly-typed alternate spellings for this command's registry
+    /// key (e.g. `"conf"` for `"configure"`, `"sh"` for `"show"`). Empty when
+    /// the command has no established shorthand.
+    pub aliases: Vec<&'static str>,
+    /// Structured argument list, in the order the command expects them.
+    /// Empty when the command takes no arguments.
+    pub args: Vec<ArgSpec>,
+    /// One-line help text shown alongside completion candidates and by `usage()`.
+    pub help: &'static str,
+    /// An explicit usage string for the `help` command to show verbatim
+    /// (e.g. `"ping <ip-address>"`). `None` for most commands, in which case
+    /// `help` synthesizes one from `name`, `suggestions1`, and `args`.
+    pub usage: Option<&'static str>,
+    /// Modes this command is available in. Membership is checked by
+    /// discriminant (`std::mem::discriminant`) via `command_allowed_in_mode`,
+    /// since the NACL `Mode` variants carry a `String` payload that doesn't
+    /// participate in mode matching -- a placeholder like
+    /// `Mode::ConfigStdNaclMode(String::new())` is enough to declare them.
+    pub modes: &'static [Mode],
     pub execute: fn(&[&str], &mut CliContext, &mut Option<Clock>) -> Result<(), String>,
 }
 
+/// Returns whether `command` declares `mode` among its `modes`, comparing by
+/// discriminant so the NACL modes' `String` payload is ignored.
+pub fn command_allowed_in_mode(command: &Command, mode: &Mode) -> bool {
+    command
+        .modes
+        .iter()
+        .any(|m| std::mem::discriminant(m) == std::mem::discriminant(mode))
+}
+
+/// The expected shape of a positional argument's value, used to validate
+/// `args` before dispatch and to describe the argument in `?` help.
+///
+/// `Str` is the default and imposes no constraint, matching the historical
+/// behavior of commands that parse their own arguments by hand.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// Any value is accepted.
+    Str,
+    /// An IPv4 address in dotted-quad form.
+    Ip,
+    /// An unsigned 16-bit integer.
+    U16,
+    /// One of a fixed set of literal keywords.
+    Keyword(&'static [&'static str]),
+}
+
+/// Describes a single positional argument accepted by a `Command`.
+///
+/// # Fields
+/// - `name`: The argument's display name (e.g. `"interface-name"`).
+/// - `optional`: Whether the argument may be omitted.
+/// - `variadic`: Whether this argument consumes the remaining tokens
+///   (e.g. an address list or a multi-word range).
+/// - `kind`: The expected value shape, validated by `validate_args` before
+///   the command's `execute` function runs.
+#[derive(Clone, Copy)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub optional: bool,
+    pub variadic: bool,
+    pub kind: ArgKind,
+}
+
+impl ArgSpec {
+    pub const fn required(name: &'static str) -> Self {
+        ArgSpec { name, optional: false, variadic: false, kind: ArgKind::Str }
+    }
+
+    pub const fn optional(name: &'static str) -> Self {
+        ArgSpec { name, optional: true, variadic: false, kind: ArgKind::Str }
+    }
+
+    pub const fn variadic(name: &'static str) -> Self {
+        ArgSpec { name, optional: false, variadic: true, kind: ArgKind::Str }
+    }
+
+    /// Attaches a type constraint to this argument, e.g.
+    /// `ArgSpec::required("source-ip").of_kind(ArgKind::Ip)`.
+    pub const fn of_kind(mut self, kind: ArgKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+/// Describes what kind of value an argument expects, for use in generated
+/// `?` help (e.g. `"an IP address (dotted-quad)"`).
+fn describe_kind(kind: &ArgKind) -> String {
+    match kind {
+        ArgKind::Str => "any value".to_string(),
+        ArgKind::Ip => "an IP address (dotted-quad)".to_string(),
+        ArgKind::U16 => "a 16-bit unsigned integer".to_string(),
+        ArgKind::Keyword(options) => format!("one of: {}", options.join(", ")),
+    }
+}
+
+/// Whether an `Err` returned by [`execute_command`] means the command
+/// couldn't be *resolved* at all (unknown/ambiguous command, missing or
+/// invalid subcommand) rather than that a resolved command's own `execute`
+/// ran and failed. A batch/script harness uses this to tell "could not
+/// resolve command" apart from "ran but failed", matching the distinction
+/// every error message here already makes in its wording.
+pub fn is_resolution_error(err: &str) -> bool {
+    err.starts_with("Ambiguous command or command not available")
+        || err.starts_with("Incomplete command.")
+        || err.starts_with("Ambiguous or invalid subcommand:")
+}
+
+/// Validates already-present positional `args` against `spec`, checking only
+/// the type constraint (`ArgKind`) of each position that was actually
+/// supplied. Missing optional/required arguments are left to each command's
+/// own `execute` logic, which already reports tailored errors for that --
+/// this layer only catches a supplied value of the wrong shape, e.g. a
+/// non-numeric `u16` or an unrecognized keyword.
+///
+/// Returns a precise "expected X, got Y" message on the first mismatch.
+pub fn validate_args(spec: &[ArgSpec], args: &[&str]) -> Result<(), String> {
+    for (index, parameter) in spec.iter().enumerate() {
+        let value = match args.get(index) {
+            Some(value) => value,
+            None => break,
+        };
+
+        if let Err(err) = validate_kind(parameter, value) {
+            return Err(err);
+        }
+
+        if parameter.variadic {
+            for value in &args[index + 1..] {
+                validate_kind(parameter, value)?;
+            }
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Returns `cmd.usage` verbatim if the command declares one, otherwise
+/// synthesizes a usage string from its name, `suggestions1` chains, and
+/// structured `args` -- e.g. `"ping <ip-address>"` or
+/// `"configure {terminal|user}"`. Backs the `help` command.
+pub fn command_usage(cmd: &Command) -> String {
+    if let Some(usage) = cmd.usage {
+        return usage.to_string();
+    }
+
+    let mut usage = cmd.name.to_string();
+
+    if let Some(chains) = &cmd.suggestions1 {
+        if !chains.is_empty() {
+            usage.push_str(&format!(" {{{}}}", chains.join("|")));
+        }
+    }
+
+    for arg in &cmd.args {
+        let token = if arg.variadic {
+            format!("{}...", arg.name)
+        } else {
+            arg.name.to_string()
+        };
+        if arg.optional {
+            usage.push_str(&format!(" [{}]", token));
+        } else {
+            usage.push_str(&format!(" <{}>", token));
+        }
+    }
+
+    usage
+}
+
+/// Finds the `ArgSpec` describing the next argument a command expects at
+/// `next_index` (0-based into `cmd.args`), accounting for a trailing
+/// variadic argument that keeps applying past the end of the list. Used to
+/// render a type-aware description for `command arg ?`.
+fn next_arg_spec(cmd: &Command, next_index: usize) -> Option<&ArgSpec> {
+    cmd.args.get(next_index).or_else(|| {
+        cmd.args
+            .last()
+            .filter(|spec| spec.variadic && next_index >= cmd.args.len())
+    })
+}
+
+fn validate_kind(parameter: &ArgSpec, value: &str) -> Result<(), String> {
+    match parameter.kind {
+        ArgKind::Str => Ok(()),
+        ArgKind::Ip => std::net::Ipv4Addr::from_str(value).map(|_| ()).map_err(|_| {
+            format!("Expected {} for <{}>, got '{}'.", describe_kind(&ArgKind::Ip), parameter.name, value)
+        }),
+        ArgKind::U16 => value.parse::<u16>().map(|_| ()).map_err(|_| {
+            format!("Expected {} for <{}>, got '{}'.", describe_kind(&ArgKind::U16), parameter.name, value)
+        }),
+        ArgKind::Keyword(options) => {
+            if options.contains(&value) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected {} for <{}>, got '{}'.",
+                    describe_kind(&ArgKind::Keyword(options)),
+                    parameter.name,
+                    value
+                ))
+            }
+        }
+    }
+}
+
+/// Renders the full usage line for `command` as `"<name> <arg>... -- help"`,
+/// matching the format shown inline in the completion menu.
+///
+/// Required arguments are rendered as `<name>`, optional ones as `[<name>]`,
+/// and variadic ones get a trailing `...`. Returns an error message if
+/// `command` is not present in `registry`.
+pub fn usage(command: &str, registry: &HashMap<&'static str, Command>) -> String {
+    match registry.get(command) {
+        Some(cmd) => {
+            let mut line = cmd.name.to_string();
+            for arg in &cmd.args {
+                let rendered = if arg.variadic {
+                    format!("<{}>...", arg.name)
+                } else {
+                    format!("<{}>", arg.name)
+                };
+                line.push(' ');
+                if arg.optional {
+                    line.push('[');
+                    line.push_str(&rendered);
+                    line.push(']');
+                } else {
+                    line.push_str(&rendered);
+                }
+            }
+            format!("{} -- {}", line, cmd.help)
+        }
+        None => format!("No such command: {}", command),
+    }
+}
+
 
 /// Represents the various operational modes for the CLI.
 ///
@@ -55,14 +306,36 @@ pub struct Command {
 ///   Represents the interface configuration mode for managing individual network interfaces.
 /// - `VlanMode`:  
 ///   Represents the VLAN configuration mode for managing VLANs.
-/// - `RouterConfigMode`:  
-///   Represents the router configuration mode for managing routing protocols such as OSPF or BGP.
-/// - `ConfigStdNaclMode(String)`:  
+/// - `RouterConfigMode`:
+///   Represents the router configuration mode entered via `router ospf`, for managing OSPF.
+/// - `RouterBgpMode`:
+///   Represents the router configuration mode entered via `router bgp <asn>`.
+/// - `RouterRipMode`:
+///   Represents the router configuration mode entered via `router rip`.
+/// - `RouterIsisMode`:
+///   Represents the router configuration mode entered via `router isis <tag>`.
+/// - `RouterOspfv3Mode`:
+///   Represents the router configuration mode entered via `ipv6 router ospf <process-id>`, for managing OSPFv3.
+/// - `ConfigStdNaclMode(String)`:
 ///   Represents the configuration mode for standard Access Control Lists (ACLs). The `String` parameter 
 ///   specifies the ACL name or ID.
-/// - `ConfigExtNaclMode(String)`:  
-///   Represents the configuration mode for extended Access Control Lists (ACLs). The `String` parameter 
+/// - `ConfigExtNaclMode(String)`:
+///   Represents the configuration mode for extended Access Control Lists (ACLs). The `String` parameter
 ///   specifies the ACL name or ID.
+/// - `LineVtyMode(String)`:
+///   Represents the `line vty <start> <end>` configuration mode for the virtual terminal lines used by
+///   remote telnet/SSH sessions. The `String` parameter is the configured line range, e.g. `"0 4"`.
+/// - `CryptoIsakmpPolicyMode(u32)`:
+///   Represents the sub-mode entered via `crypto isakmp policy <n>`, for setting an IKE policy's
+///   encryption/hash/authentication/group/lifetime. The `u32` parameter is the policy number.
+/// - `CryptoIsakmpGroupMode(String)`:
+///   Represents the sub-mode entered via `crypto isakmp client configuration group <name>`, for
+///   configuring the address pool/DNS/key pushed to remote VPN clients. The `String` parameter is
+///   the group name.
+/// - `DhcpPoolMode(String)`:
+///   Represents the sub-mode entered via `ip dhcp pool <name>`, for configuring a DHCP pool's
+///   network, default router, DNS servers, domain name, and lease time. The `String` parameter is
+///   the pool name.
 ///
 /// # Example
 /// ```rust
@@ -74,8 +347,16 @@ pub struct Command {
 ///     Mode::InterfaceMode => println!("In interface configuration mode"),
 ///     Mode::VlanMode => println!("In VLAN configuration mode"),
 ///     Mode::RouterConfigMode => println!("In router configuration mode"),
+///     Mode::RouterBgpMode => println!("In router BGP configuration mode"),
+///     Mode::RouterRipMode => println!("In router RIP configuration mode"),
+///     Mode::RouterIsisMode => println!("In router IS-IS configuration mode"),
+///     Mode::RouterOspfv3Mode => println!("In router OSPFv3 configuration mode"),
 ///     Mode::ConfigStdNaclMode(acl) => println!("Configuring standard ACL: {}", acl),
 ///     Mode::ConfigExtNaclMode(acl) => println!("Configuring extended ACL: {}", acl),
+///     Mode::LineVtyMode(range) => println!("Configuring vty line(s): {}", range),
+///     Mode::CryptoIsakmpPolicyMode(policy) => println!("Configuring ISAKMP policy: {}", policy),
+///     Mode::CryptoIsakmpGroupMode(group) => println!("Configuring ISAKMP client group: {}", group),
+///     Mode::DhcpPoolMode(pool) => println!("Configuring DHCP pool: {}", pool),
 /// }
 /// ```
 #[derive(Clone, Debug)]
@@ -86,8 +367,16 @@ pub enum Mode {
     InterfaceMode,
     VlanMode,
     RouterConfigMode,
+    RouterBgpMode,
+    RouterRipMode,
+    RouterIsisMode,
+    RouterOspfv3Mode,
     ConfigStdNaclMode(String),
     ConfigExtNaclMode(String),
+    LineVtyMode(String),
+    CryptoIsakmpPolicyMode(u32),
+    CryptoIsakmpGroupMode(String),
+    DhcpPoolMode(String),
 }
 
 
@@ -126,10 +415,11 @@ pub enum Mode {
 /// ```
 ///
 /// # Errors
-/// - If an ambiguous or unrecognized command is entered, a message will be printed indicating the error.
-/// - If the command requires additional arguments or subcommands, appropriate messages will be shown.
-/// - Errors encountered during command execution will be printed.
-pub fn execute_command(input: &str, commands: &HashMap<&str, Command>, context: &mut CliContext, clock: &mut Option<Clock>, completer: &mut CommandCompleter) {
+/// - If an ambiguous or unrecognized command is entered, `Err` is returned describing the problem.
+/// - If the command requires additional arguments or subcommands, `Err` is returned accordingly.
+/// - Errors encountered while running the matched command's `execute` function are propagated as-is.
+/// Callers are responsible for reporting a returned `Err` to the user (e.g. by printing it).
+pub fn execute_command(input: &str, commands: &HashMap<&str, Command>, context: &mut CliContext, clock: &mut Option<Clock>, completer: &mut CommandCompleter) -> Result<(), String> {
     let mut normalized_input = input.trim();
     let showing_suggestions = normalized_input.ends_with('?');
     
@@ -138,155 +428,14 @@ pub fn execute_command(input: &str, commands: &HashMap<&str, Command>, context:
         normalized_input = normalized_input.trim_end_matches('?');
     }
 
-    // Get available commands for current mode
+    // Get available commands for current mode, derived from each command's
+    // own declared `modes` rather than a central per-mode switch.
     fn get_mode_commands<'a>(commands: &'a HashMap<&str, Command>, mode: &Mode) -> Vec<&'a str> {
-        match mode {
-            Mode::UserMode => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "enable" ||
-                        cmd == "ping" ||
-                        cmd == "help" ||
-                        cmd == "show" ||
-                        cmd == "clear" ||
-                        cmd == "reload" ||
-                        cmd == "exit"
-                    })
-                    .copied()
-                    .collect()
-            },
-            Mode::PrivilegedMode => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "configure" ||
-                        cmd == "ping" || 
-                        cmd == "exit" || 
-                        cmd == "write" ||
-                        cmd == "help" ||
-                        cmd == "show" ||
-                        cmd == "copy" ||
-                        cmd == "clock" ||
-                        cmd == "clear" ||
-                        cmd == "reload" ||
-                        cmd == "debug" ||
-                        cmd == "undebug" ||
-                        cmd == "ifconfig"
-                        
-                    })
-                    .copied()
-                    .collect()
-            },
-            Mode::ConfigMode => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "hostname" || 
-                        cmd == "interface" ||
-                        cmd == "ping" ||
-                        cmd == "exit" ||
-                        cmd == "clear" ||
-                        cmd == "tunnel" ||
-                        cmd == "access-list" ||
-                        cmd == "router" ||
-                        cmd == "virtual-template" ||
-                        cmd == "help" ||
-                        cmd == "write" ||
-                        cmd == "vlan" ||
-                        cmd == "ip" ||
-                        cmd == "service" ||
-                        cmd == "set" ||
-                        cmd == "enable" ||
-                        cmd == "ifconfig" ||  
-                        cmd == "ntp" ||
-                        cmd == "no" || 
-                        cmd == "reload" ||
-                        cmd == "crypto"
-                    })
-                    .copied()
-                    .collect()
-            },
-            Mode::InterfaceMode => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "shutdown" ||
-                        cmd == "no" ||
-                        cmd == "exit" ||
-                        cmd == "clear" ||
-                        cmd == "help" ||
-                        cmd == "switchport" ||
-                        cmd == "write" ||
-                        cmd == "reload" ||
-                        cmd == "ip" 
-
-                    })
-                    .copied()
-                    .collect()
-            }
-            Mode::VlanMode => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "name" ||
-                        cmd == "state" ||
-                        cmd == "clear" ||
-                        cmd == "exit" ||
-                        cmd == "help" ||
-                        cmd == "reload" ||
-                        cmd == "vlan" 
-
-                    })
-                    .copied()
-                    .collect()
-            }
-            Mode::RouterConfigMode => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "network" ||
-                        cmd == "neighbor" ||
-                        cmd == "exit" ||
-                        cmd == "clear" ||
-                        cmd == "area" ||
-                        cmd == "passive-interface" ||
-                        cmd == "distance" ||
-                        cmd == "help" ||
-                        cmd == "reload" ||
-                        cmd == "default-information" ||
-                        cmd == "router-id"
-
-                    })
-                    .copied()
-                    .collect()
-            }
-            Mode::ConfigStdNaclMode(_) => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "deny" ||
-                        cmd == "permit" ||
-                        cmd == "help" ||
-                        cmd == "exit" ||
-                        cmd == "clear" ||
-                        cmd == "reload" ||
-                        cmd == "ip"
-
-                    })
-                    .copied()
-                    .collect()
-            }
-            Mode::ConfigExtNaclMode(_) => {
-                commands.keys()
-                    .filter(|&&cmd| {
-                        cmd == "deny" ||
-                        cmd == "permit" ||
-                        cmd == "help" ||
-                        cmd == "exit" ||
-                        cmd == "clear" ||
-                        cmd == "reload" ||
-                        cmd == "ip"
-
-                    })
-                    .copied()
-                    .collect()
-            }
-
-        }
+        commands
+            .iter()
+            .filter(|(_, cmd)| command_allowed_in_mode(cmd, mode))
+            .map(|(&name, _)| name)
+            .collect()
     }
 
     // Function to find a unique command match
@@ -319,7 +468,58 @@ pub fn execute_command(input: &str, commands: &HashMap<&str, Command>, context:
         }
     }
 
-     
+    // Splits each `suggestions1` entry on whitespace, so multi-word chains
+    // (e.g. "ipsec security-association lifetime") can be walked token by
+    // token instead of matched as one opaque string.
+    fn subcommand_chains<'a>(suggestions: &[&'a str]) -> Vec<Vec<&'a str>> {
+        suggestions.iter().map(|chain| chain.split_whitespace().collect()).collect()
+    }
+
+    enum ChainResolution<'a> {
+        // Carries the matched chain's own tokens, fully expanded from
+        // whatever abbreviation the user typed (e.g. "te" -> "terminal"),
+        // so callers can splice them back in front of any leftover args.
+        Complete(Vec<&'a str>),
+        Incomplete,
+        Invalid(String),
+    }
+
+    // Walks `cmd.suggestions1`'s chains against the tokens following the
+    // command name, so a subcommand of any depth is validated the same way
+    // a single-word one already was. The first token is matched (with
+    // abbreviation, like `find_unique_subcommand`); once it resolves
+    // unambiguously, `given` only needs to cover the rest of some matching
+    // chain verbatim. Anything left over in `given` is still forwarded to
+    // `execute` untouched -- every existing command body parses its own
+    // remaining arguments from the full slice.
+    fn resolve_subcommand_chain<'a>(suggestions: &'a [&str], given: &[&str]) -> ChainResolution<'a> {
+        if given.is_empty() {
+            return ChainResolution::Incomplete;
+        }
+
+        let chains = subcommand_chains(suggestions);
+        let first_tokens: Vec<&str> = chains.iter().map(|chain| chain[0]).collect();
+        let matched_first = match find_unique_subcommand(given[0], &first_tokens) {
+            Some(first) => first,
+            None => return ChainResolution::Invalid(given[0].to_string()),
+        };
+
+        let candidates: Vec<&Vec<&str>> = chains.iter().filter(|chain| chain[0] == matched_first).collect();
+
+        let matched_chain = candidates
+            .iter()
+            .find(|chain| chain.len() <= given.len() && chain[1..] == given[1..chain.len()]);
+
+        if let Some(chain) = matched_chain {
+            ChainResolution::Complete((*chain).clone())
+        } else if candidates.iter().any(|chain| chain.len() > given.len()) {
+            ChainResolution::Incomplete
+        } else {
+            ChainResolution::Invalid(given.get(1).copied().unwrap_or(given[0]).to_string())
+        }
+    }
+
+
     let parts: Vec<&str> = normalized_input.split_whitespace().collect();
    
     let available_commands = get_mode_commands(commands, &context.current_mode);
@@ -344,112 +544,20 @@ Two styles of help are provided:
 "#);
                 println!("\nAvailable commands");
                 println!("\n ");
-                
-                if matches!(context.current_mode, Mode::UserMode) {
-                    println!("enable            - Enter privileged mode");
-                    println!("exit              - Exit current mode");
-                    println!("ping              - Send ICMP echo request");
-                    println!("help              - Display available commands");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                    println!("show              - Some available show commands are present. To view enter 'show ?'");
-                }
-                else if matches!(context.current_mode, Mode::PrivilegedMode) {
-                    println!("configure         - Enter configuration mode");
-                    println!("exit              - Exit to user mode");
-                    println!("help              - Display available commands");
-                    println!("write             - Save the configuration");
-                    println!("copy              - Copy configuration files");
-                    println!("clock             - Manage system clock");
-                    println!("clear ip ospf process - Clear all the ospf processes");
-                    println!("ping              - Send ICMP echo request");
-                    println!("show              - Some available show commands are present. To view enter 'show ?'");
-                    println!("ifconfig          - Display interface configuration");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                    println!("debug             - Debug the availbale processes");
-                    println!("undebug           - Undebug the availbale processes");
-                }
-                else if matches!(context.current_mode, Mode::ConfigMode) {
-                    println!("hostname          - Set system hostname");
-                    println!("interface         - Configure interface");
-                    println!("exit              - Exit to privileged mode");
-                    println!("tunnel            - Configure tunnel interface");
-                    println!("virtual-template  - Configure virtual template");
-                    println!("help              - Display available commands");
-                    println!("write             - Save the configuration");
-                    println!("ping              - Send ICMP echo request");
-                    println!("vlan              - Configure VLAN");
-                    println!("access-list       - Configure access list");
-                    println!("router            - Configure routing protocol");
-                    println!("enable            - Enter privileged mode");
-                    println!("ip route          - Configure static routes");
-                    println!("ip domain-name    - Configure DNS domain name");
-                    println!("ip access-list    - Configure IP access list");
-                    println!("service           - Configure system services");
-                    println!("set               - Set system parameters");
-                    println!("ifconfig          - Configure interface");
-                    println!("ntp               - Configure NTP");
-                    println!("crypto            - Configure encryption");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                }
-                else if matches!(context.current_mode, Mode::InterfaceMode) {
-                    println!("exit              - Exit to config mode");
-                    println!("shutdown          - Shutdown interface");
-                    println!("no                - Negate a command");
-                    println!("switchport        - Configure switching parameters");
-                    println!("help              - Display available commands");
-                    println!("write             - Save the configuration");
-                    println!("interface         - Select another interface");
-                    println!("ip address        - Set IP address");
-                    println!("ip ospf           - Configure OSPF protocol");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                }
-                else if matches!(context.current_mode, Mode::VlanMode) {
-                    println!("name              - Set VLAN name");
-                    println!("exit              - Exit to config mode");
-                    println!("state             - Set VLAN state");
-                    println!("vlan              - Configure VLAN parameters");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                    println!("help              - Display available commands");
-                }
-                else if matches!(context.current_mode, Mode::RouterConfigMode) {
-                    println!("network           - Configure network");
-                    println!("exit              - Exit to config mode");
-                    println!("neighbor          - Configure BGP neighbor");
-                    println!("area              - Configure OSPF area");
-                    println!("passive-interface - Configure passive interface");
-                    println!("distance          - Configure administrative distance");
-                    println!("default-information - Configure default route distribution");
-                    println!("router-id         - Configure router ID");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                    println!("help              - Display available commands");
-                }
-                else if matches!(context.current_mode, Mode::ConfigStdNaclMode(_)) {
-                    println!("deny              - Deny specific traffic");
-                    println!("permit            - Permit specific traffic");
-                    println!("exit              - Exit to config mode");
-                    println!("ip access-list    - Configure IP access list");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                    println!("help              - Display available commands");
-                }
-                else if matches!(context.current_mode, Mode::ConfigExtNaclMode(_)) {
-                    println!("deny              - Deny specific traffic");
-                    println!("permit            - Permit specific traffic");
-                    println!("exit              - Exit to config mode");
-                    println!("ip access-list    - Configure IP access list");
-                    println!("reload            - Reload the system");
-                    println!("clear             - Clear the terminal");
-                    println!("help              - Display available commands");
+
+                // Derived from each command's own declared `modes`, rather
+                // than a per-mode list duplicating `get_mode_commands`.
+                let mut mode_commands: Vec<&Command> = commands
+                    .values()
+                    .filter(|cmd| command_allowed_in_mode(cmd, &context.current_mode))
+                    .collect();
+                mode_commands.sort_by_key(|cmd| cmd.name);
+                for cmd in mode_commands {
+                    println!("{:<18}- {}", cmd.name, cmd.help);
                 }
                 println!("\n ");
-                
-            },            
+
+            },
             1 => {
                 let command_name = parts[0].trim();
                 // Handle single word with ? (e.g., "configure ?")
@@ -530,62 +638,112 @@ Two styles of help are provided:
                             for option in options {
                                 println!("  {}", option);
                             }
+                        } else if let Some(spec) = next_arg_spec(cmd, parts.len() - 1) {
+                            println!("<{}>  -- expects {}", spec.name, describe_kind(&spec.kind));
                         } else {
                             println!("No more options available");
-                            //(cmd.execute)(&parts[1..], context, clock);
                         }
                     }
                 }
             },
             _ => {
-                // Full command with ? (e.g., "configure terminal ?")
-                println!("No additional parameters available");
+                // Full command with ? (e.g., "configure terminal ?"), including
+                // deeper chains like "crypto ipsec security-association ?"
+                if let Some(cmd) = commands.get(parts[0]) {
+                    if let Some(suggestions) = &cmd.suggestions1 {
+                        let given = &parts[1..parts.len() - 1];
+                        let partial = parts[parts.len() - 1];
+                        let matching: Vec<&str> = subcommand_chains(suggestions)
+                            .iter()
+                            .filter(|tokens| {
+                                tokens.len() > given.len()
+                                    && &tokens[..given.len()] == given
+                                    && tokens[given.len()].starts_with(partial)
+                            })
+                            .map(|tokens| tokens[given.len()])
+                            .collect();
+
+                        if !matching.is_empty() {
+                            println!("Possible completions:");
+                            for suggestion in matching {
+                                println!("  {}", suggestion);
+                            }
+                            return Ok(());
+                        }
+                    }
+                }
+
+                match commands.get(parts[0]).and_then(|cmd| next_arg_spec(cmd, parts.len() - 1)) {
+                    Some(spec) => println!("<{}>  -- expects {}", spec.name, describe_kind(&spec.kind)),
+                    None => println!("No additional parameters available"),
+                }
             }
         }
-        return;
+        return Ok(());
     }
 
     // Handle command execution (when no '?' is present)
     let cmd_key = if let Some(matched_cmd) = find_unique_command(parts[0], &available_commands) {
         matched_cmd
     } else {
-        println!("Ambiguous command or command not available in current mode: {}", parts[0]);
-        return;
+        // No built-in resolves `parts[0]` -- fall back to an external
+        // `pnfbox-<name>` plugin discovered on $PATH/the plugin directory
+        // before giving up, the same way `cargo` extends itself with
+        // `cargo-<name>` executables.
+        let plugin_names = crate::plugins::discover_plugin_names();
+        let plugin_matches: Vec<&String> = plugin_names
+            .iter()
+            .filter(|name| name.starts_with(parts[0]))
+            .collect();
+
+        if plugin_matches.len() == 1 {
+            return crate::plugins::run_plugin(plugin_matches[0], &parts[1..]);
+        }
+
+        // Still unresolved -- try a JSON-RPC plugin discovered under
+        // `plugins/` at startup before giving up.
+        let rpc_plugin_names = crate::plugins::rpc_plugin_names();
+        let rpc_plugin_matches: Vec<&String> = rpc_plugin_names
+            .iter()
+            .filter(|name| name.starts_with(parts[0]))
+            .collect();
+
+        return if rpc_plugin_matches.len() == 1 {
+            crate::plugins::run_rpc_plugin(rpc_plugin_matches[0], &parts[1..], context)
+        } else {
+            Err(format!("Ambiguous command or command not available in current mode: {}", parts[0]))
+        };
     };
 
     if let Some(cmd) = commands.get(cmd_key) {
         if let Some(suggestions) = &cmd.suggestions1 {
-            match parts.len() {
-                1 => {
-                    println!("Incomplete command. Subcommand required.");
-                    //(cmd.execute)(&parts[1..], context, clock);
+            match resolve_subcommand_chain(suggestions, &parts[1..]) {
+                ChainResolution::Incomplete if !cmd.require_subcommand => {
+                    validate_args(&cmd.args, &parts[1..])?;
+                    return (cmd.execute)(&parts[1..], context, clock);
                 }
-                2 => {
-                    if suggestions.is_empty() {
-                        if let Err(err) = (cmd.execute)(&parts[1..], context, clock) {
-                            println!("Error: {}", err);
-                        }
-                    } else {
-                        // For commands with specific subcommands, require a match
-                        if let Some(matched_subcommand) = find_unique_subcommand(parts[1], suggestions) {
-                            if let Err(err) = (cmd.execute)(&[matched_subcommand], context, clock) {
-                                println!("Error: {}", err);
-                            }
-                        } else {
-                            println!("Ambiguous or invalid subcommand: {}", parts[1]);
-                        }
-                    }
+                ChainResolution::Incomplete => {
+                    return Err("Incomplete command. Subcommand required.".to_string());
                 }
-                _ => {
-                    if let Err(err) = (cmd.execute)(&parts[1..], context, clock) {
-                        println!("Error: {}", err);
-                    }
+                ChainResolution::Invalid(token) => {
+                    return Err(format!("Ambiguous or invalid subcommand: {}", token));
+                }
+                ChainResolution::Complete(chain) => {
+                    // Splice the chain's fully-expanded tokens in front of
+                    // whatever args followed it, so an abbreviated subcommand
+                    // (e.g. "configure t") still reaches `execute` as the
+                    // literal word ("terminal") it always has.
+                    let mut expanded: Vec<&str> = chain;
+                    expanded.extend_from_slice(&parts[1 + expanded.len()..]);
+                    validate_args(&cmd.args, &expanded)?;
+                    return (cmd.execute)(&expanded, context, clock);
                 }
             }
         } else {
-            if let Err(err) = (cmd.execute)(&parts[1..], context, clock) {
-                println!("Error: {}", err);
-            }
+            validate_args(&cmd.args, &parts[1..])?;
+            return (cmd.execute)(&parts[1..], context, clock);
         }
     }
+
+    Ok(())
 }
\ No newline at end of file
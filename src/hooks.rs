@@ -0,0 +1,39 @@
+//! Operator-supplied hook scripts run on notable config events (save,
+//! interface/OSPF state transitions) -- the way hook-script-capable VPN
+//! daemons let operators bolt on notifications, syslog forwarding, or other
+//! automation without touching this binary. Hook commands live in
+//! `CliConfig.hook_scripts` so they persist across reboots via
+//! `save_config`/`load_config`.
+
+use std::collections::HashMap;
+use std::process::Command as ProcessCommand;
+
+/// Runs the script registered for `event` in `hook_scripts`, if any, passing
+/// `metadata` as environment variables alongside `EVENT` (always set to
+/// `event` itself). Best-effort: a missing hook is not an error, and a hook
+/// that fails to spawn or exits non-zero is reported to stdout rather than
+/// propagated, so a broken notification script can't block the CLI action
+/// that triggered it.
+pub fn run_hook(hook_scripts: &HashMap<String, String>, event: &str, metadata: &[(&str, String)]) {
+    let script = match hook_scripts.get(event) {
+        Some(script) => script,
+        None => return,
+    };
+
+    let mut command = ProcessCommand::new(script);
+    command.env("EVENT", event);
+    for (key, value) in metadata {
+        command.env(key, value);
+    }
+
+    match command.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!(
+            "Warning: hook '{}' for event '{}' exited with status {}",
+            script,
+            event,
+            status.code().map_or("unknown".to_string(), |code| code.to_string())
+        ),
+        Err(err) => println!("Warning: failed to run hook '{}' for event '{}': {}", script, event, err),
+    }
+}
@@ -0,0 +1,175 @@
+//! Optional "apply to kernel" backend: translates the already-parsed
+//! interface/route commands in `clicommands.rs` into real kernel operations
+//! via rtnetlink, instead of only mutating the in-memory simulation state
+//! the way every command does by default. Kept as a dedicated, swappable
+//! backend -- mirroring [`crate::run_config`]'s `ConfigBackend` trait -- so
+//! the pure-simulation path is unchanged when this is never enabled, and so
+//! enabling it is a runtime choice (`kernel-apply enable`) rather than a
+//! compile-time fork.
+
+use std::net::Ipv4Addr;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+/// The kernel operations a backend can perform, mirrored against whatever
+/// `shutdown`/`no shutdown`/`ip address`/`ip route` already did to the
+/// simulated state.
+pub trait HostBackend: Send {
+    fn set_link_admin_state(&self, interface: &str, up: bool) -> Result<(), String>;
+    fn add_address(&self, interface: &str, address: Ipv4Addr, netmask: Ipv4Addr) -> Result<(), String>;
+    fn add_route(&self, destination: Ipv4Addr, prefix_len: u8, next_hop: Ipv4Addr) -> Result<(), String>;
+    fn remove_route(&self, destination: Ipv4Addr, prefix_len: u8) -> Result<(), String>;
+}
+
+/// The default backend: every operation is a no-op, since the in-memory
+/// globals in `network_config.rs` already *are* the device's state for a
+/// pure simulation. This exists so `ACTIVE_BACKEND` always has something to
+/// call through without every call site needing an `if let Some(backend)`.
+pub struct SimulationBackend;
+
+impl HostBackend for SimulationBackend {
+    fn set_link_admin_state(&self, _interface: &str, _up: bool) -> Result<(), String> {
+        Ok(())
+    }
+    fn add_address(&self, _interface: &str, _address: Ipv4Addr, _netmask: Ipv4Addr) -> Result<(), String> {
+        Ok(())
+    }
+    fn add_route(&self, _destination: Ipv4Addr, _prefix_len: u8, _next_hop: Ipv4Addr) -> Result<(), String> {
+        Ok(())
+    }
+    fn remove_route(&self, _destination: Ipv4Addr, _prefix_len: u8) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Real kernel backend, built on `rtnetlink`/`tokio` rather than shelling out
+/// to `ip`. Only compiled in when the `kernel-backend` feature is enabled --
+/// most installs never link against rtnetlink at all, so the default
+/// simulation-only build stays dependency-free.
+#[cfg(feature = "kernel-backend")]
+pub struct NetlinkBackend {
+    handle: rtnetlink::Handle,
+    runtime: tokio::runtime::Runtime,
+}
+
+#[cfg(feature = "kernel-backend")]
+impl NetlinkBackend {
+    /// Opens a netlink route socket. Fails (rather than panicking) when the
+    /// caller lacks `CAP_NET_ADMIN`, so [`enable_kernel_backend`] can
+    /// gracefully fall back to [`SimulationBackend`].
+    fn connect() -> Result<Self, String> {
+        let runtime = tokio::runtime::Runtime::new().map_err(|err| err.to_string())?;
+        let (connection, handle, _) = rtnetlink::new_connection().map_err(|err| err.to_string())?;
+        runtime.spawn(connection);
+        Ok(Self { handle, runtime })
+    }
+
+    fn link_index(&self, interface: &str) -> Result<u32, String> {
+        let handle = self.handle.clone();
+        let interface = interface.to_string();
+        self.runtime.block_on(async move {
+            use futures::TryStreamExt;
+            handle
+                .link()
+                .get()
+                .match_name(interface.clone())
+                .execute()
+                .try_next()
+                .await
+                .map_err(|err| err.to_string())?
+                .map(|link| link.header.index)
+                .ok_or_else(|| format!("No such kernel interface: {}", interface))
+        })
+    }
+}
+
+#[cfg(feature = "kernel-backend")]
+impl HostBackend for NetlinkBackend {
+    fn set_link_admin_state(&self, interface: &str, up: bool) -> Result<(), String> {
+        let index = self.link_index(interface)?;
+        let handle = self.handle.clone();
+        self.runtime.block_on(async move {
+            let request = handle.link().set(index);
+            let request = if up { request.up() } else { request.down() };
+            request.execute().await.map_err(|err| err.to_string())
+        })
+    }
+
+    fn add_address(&self, interface: &str, address: Ipv4Addr, netmask: Ipv4Addr) -> Result<(), String> {
+        let index = self.link_index(interface)?;
+        let prefix_len = crate::network_config::netmask_to_prefix(netmask) as u8;
+        let handle = self.handle.clone();
+        self.runtime.block_on(async move {
+            handle
+                .address()
+                .add(index, std::net::IpAddr::V4(address), prefix_len)
+                .execute()
+                .await
+                .map_err(|err| err.to_string())
+        })
+    }
+
+    fn add_route(&self, destination: Ipv4Addr, prefix_len: u8, next_hop: Ipv4Addr) -> Result<(), String> {
+        let handle = self.handle.clone();
+        self.runtime.block_on(async move {
+            handle
+                .route()
+                .add()
+                .v4()
+                .destination_prefix(destination, prefix_len)
+                .gateway(next_hop)
+                .execute()
+                .await
+                .map_err(|err| err.to_string())
+        })
+    }
+
+    fn remove_route(&self, destination: Ipv4Addr, prefix_len: u8) -> Result<(), String> {
+        let handle = self.handle.clone();
+        self.runtime.block_on(async move {
+            use futures::TryStreamExt;
+            let mut routes = handle.route().get(rtnetlink::IpVersion::V4).execute();
+            while let Some(route) = routes.try_next().await.map_err(|err| err.to_string())? {
+                if route.destination_prefix() == Some((std::net::IpAddr::V4(destination), prefix_len)) {
+                    handle.route().del(route).execute().await.map_err(|err| err.to_string())?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+lazy_static! {
+    /// The backend every interface/route command applies its change to,
+    /// after updating the simulated state the way it always has. Starts as
+    /// [`SimulationBackend`] and is only ever replaced by
+    /// [`enable_kernel_backend`].
+    pub static ref ACTIVE_BACKEND: Mutex<Box<dyn HostBackend>> = Mutex::new(Box::new(SimulationBackend));
+}
+
+/// Switches `ACTIVE_BACKEND` to a real netlink backend. Requires
+/// `CAP_NET_ADMIN` (effectively root); on any failure to open the netlink
+/// socket -- insufficient privilege, rtnetlink unavailable, or the
+/// `kernel-backend` feature not compiled in -- leaves `ACTIVE_BACKEND` as
+/// [`SimulationBackend`] and returns the reason so the caller can report it.
+pub fn enable_kernel_backend() -> Result<(), String> {
+    #[cfg(feature = "kernel-backend")]
+    {
+        if unsafe { libc::geteuid() } != 0 {
+            return Err("kernel-apply requires root (CAP_NET_ADMIN) privileges; staying in simulation mode.".into());
+        }
+        let backend = NetlinkBackend::connect()?;
+        *ACTIVE_BACKEND.lock().unwrap() = Box::new(backend);
+        Ok(())
+    }
+    #[cfg(not(feature = "kernel-backend"))]
+    {
+        Err("This build was compiled without the 'kernel-backend' feature; staying in simulation mode.".into())
+    }
+}
+
+/// Switches `ACTIVE_BACKEND` back to pure simulation.
+pub fn disable_kernel_backend() {
+    *ACTIVE_BACKEND.lock().unwrap() = Box::new(SimulationBackend);
+}
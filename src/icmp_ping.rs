@@ -0,0 +1,223 @@
+//! Real ICMP echo client backing the `ping` command, falling back to the
+//! historical simulated output (keyed off routing-table reachability) when
+//! a raw socket can't be opened -- e.g. the process isn't running as root,
+//! since an ICMP raw socket needs `CAP_NET_RAW` on Linux.
+
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::time::{Duration, Instant};
+
+/// Options parsed from the Cisco-style extended `ping` syntax: `ping <ip>
+/// repeat <n> size <bytes> timeout <sec> source <interface>`.
+#[derive(Clone)]
+pub struct PingOptions {
+    pub count: u32,
+    pub size: usize,
+    pub timeout: Duration,
+    pub source: Option<Ipv4Addr>,
+}
+
+impl Default for PingOptions {
+    fn default() -> Self {
+        PingOptions {
+            count: 4,
+            size: 32,
+            timeout: Duration::from_secs(2),
+            source: None,
+        }
+    }
+}
+
+/// One probe's outcome.
+pub enum ProbeResult {
+    Reply { rtt: Duration, ttl: u8 },
+    Timeout,
+}
+
+/// Aggregate statistics over every probe in a `ping` run, mirroring the
+/// Cisco `min/avg/max` summary line.
+pub struct PingSummary {
+    pub sent: u32,
+    pub received: u32,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+}
+
+impl PingSummary {
+    pub fn from_results(results: &[ProbeResult]) -> Self {
+        let rtts: Vec<Duration> = results
+            .iter()
+            .filter_map(|result| match result {
+                ProbeResult::Reply { rtt, .. } => Some(*rtt),
+                ProbeResult::Timeout => None,
+            })
+            .collect();
+
+        let sent = results.len() as u32;
+        let received = rtts.len() as u32;
+        if rtts.is_empty() {
+            return PingSummary {
+                sent,
+                received,
+                min: Duration::ZERO,
+                avg: Duration::ZERO,
+                max: Duration::ZERO,
+                stddev: Duration::ZERO,
+            };
+        }
+
+        let min = *rtts.iter().min().unwrap();
+        let max = *rtts.iter().max().unwrap();
+        let avg_nanos = rtts.iter().map(|d| d.as_nanos()).sum::<u128>() / rtts.len() as u128;
+        let variance = rtts
+            .iter()
+            .map(|d| {
+                let diff = d.as_nanos() as i128 - avg_nanos as i128;
+                (diff * diff) as u128
+            })
+            .sum::<u128>()
+            / rtts.len() as u128;
+
+        PingSummary {
+            sent,
+            received,
+            min,
+            max,
+            avg: Duration::from_nanos(avg_nanos as u64),
+            stddev: Duration::from_nanos((variance as f64).sqrt() as u64),
+        }
+    }
+
+    /// Percentage of probes that never got a reply, rounded down like
+    /// Cisco's `ping` summary.
+    pub fn loss_percent(&self) -> u32 {
+        if self.sent == 0 {
+            return 0;
+        }
+        ((self.sent - self.received) * 100) / self.sent
+    }
+}
+
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMP_ECHO_REPLY: u8 = 0;
+
+/// RFC 792 one's-complement checksum.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum = 0u32;
+    let mut chunks = data.chunks_exact(2);
+    for chunk in &mut chunks {
+        sum += u16::from_be_bytes([chunk[0], chunk[1]]) as u32;
+    }
+    if let [last] = chunks.remainder() {
+        sum += (*last as u32) << 8;
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// Builds an ICMP echo request of `payload_size` data bytes, identified by
+/// `identifier`/`sequence` so replies can be matched back to this probe.
+fn build_echo_request(identifier: u16, sequence: u16, payload_size: usize) -> Vec<u8> {
+    let mut packet = vec![0u8; 8 + payload_size];
+    packet[0] = ICMP_ECHO_REQUEST;
+    packet[1] = 0;
+    packet[4..6].copy_from_slice(&identifier.to_be_bytes());
+    packet[6..8].copy_from_slice(&sequence.to_be_bytes());
+    for (i, byte) in packet[8..].iter_mut().enumerate() {
+        *byte = (i % 256) as u8;
+    }
+    let csum = checksum(&packet);
+    packet[2..4].copy_from_slice(&csum.to_be_bytes());
+    packet
+}
+
+/// Opens a raw ICMP socket, binding it to `source` when given. Errors (so
+/// the caller falls back to the simulated path) when the process lacks
+/// `CAP_NET_RAW` -- the expected case without root.
+fn open_raw_socket(source: Option<Ipv4Addr>) -> std::io::Result<Socket> {
+    let socket = Socket::new(Domain::IPV4, Type::RAW, Some(Protocol::ICMPV4))?;
+    if let Some(source_ip) = source {
+        let bind_addr: SocketAddr = SocketAddrV4::new(source_ip, 0).into();
+        socket.bind(&bind_addr.into())?;
+    }
+    Ok(socket)
+}
+
+/// Sends one ICMP echo request to `target` and waits up to `timeout` for
+/// its reply, returning the round-trip time and the reply's TTL (read back
+/// from the IPv4 header the kernel delivers in front of the ICMP payload
+/// on a raw socket).
+fn send_probe(
+    socket: &Socket,
+    target: Ipv4Addr,
+    identifier: u16,
+    sequence: u16,
+    size: usize,
+    timeout: Duration,
+) -> std::io::Result<ProbeResult> {
+    let request = build_echo_request(identifier, sequence, size);
+    let dest: SocketAddr = SocketAddrV4::new(target, 0).into();
+    socket.set_read_timeout(Some(timeout))?;
+
+    let started = Instant::now();
+    socket.send_to(&request, &dest.into())?;
+
+    let mut buf = [std::mem::MaybeUninit::new(0u8); 1024];
+    loop {
+        if started.elapsed() >= timeout {
+            return Ok(ProbeResult::Timeout);
+        }
+
+        let (n, _) = match socket.recv_from(&mut buf) {
+            Ok(result) => result,
+            Err(err)
+                if err.kind() == std::io::ErrorKind::WouldBlock
+                    || err.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                return Ok(ProbeResult::Timeout);
+            }
+            Err(err) => return Err(err),
+        };
+
+        let bytes: Vec<u8> = buf[..n].iter().map(|b| unsafe { b.assume_init() }).collect();
+        // The IHL (low nibble of the first byte) gives the IPv4 header
+        // length in 32-bit words, ahead of the ICMP message itself.
+        if bytes.len() < 20 {
+            continue;
+        }
+        let ttl = bytes[8];
+        let ihl = (bytes[0] & 0x0F) as usize * 4;
+        if bytes.len() < ihl + 8 {
+            continue;
+        }
+        let icmp = &bytes[ihl..];
+        if icmp[0] != ICMP_ECHO_REPLY {
+            continue;
+        }
+        let reply_id = u16::from_be_bytes([icmp[4], icmp[5]]);
+        let reply_seq = u16::from_be_bytes([icmp[6], icmp[7]]);
+        if reply_id != identifier || reply_seq != sequence {
+            continue;
+        }
+        return Ok(ProbeResult::Reply { rtt: started.elapsed(), ttl });
+    }
+}
+
+/// Runs a full `ping` session against `target`: one ICMP echo request per
+/// probe in `options.count`. Returns `None` (so the caller falls back to
+/// the simulated path) if the raw socket can't be opened at all.
+pub fn run_icmp_ping(target: Ipv4Addr, options: &PingOptions) -> Option<Vec<ProbeResult>> {
+    let socket = open_raw_socket(options.source).ok()?;
+    let identifier = std::process::id() as u16;
+    let mut results = Vec::with_capacity(options.count as usize);
+    for sequence in 0..options.count {
+        let result = send_probe(&socket, target, identifier, sequence as u16, options.size, options.timeout)
+            .unwrap_or(ProbeResult::Timeout);
+        results.push(result);
+    }
+    Some(results)
+}
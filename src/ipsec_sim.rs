@@ -0,0 +1,186 @@
+//! Lightweight IPsec SA negotiation/tunnel-state simulator. `set
+//! transform-set`, `crypto map`, and `tunnel protection ipsec profile`
+//! previously only recorded strings on [`crate::cliconfig::CliConfig`] with
+//! no notion of a tunnel actually coming up. Once a `crypto map` with a
+//! bound transform set and a `tunnel destination` are both configured,
+//! [`try_negotiate`] picks the best mutually-supported proposal out of the
+//! bound transform set, derives a session key from whatever key material is
+//! configured, and walks the tunnel through `Down` -> `Negotiating` -> `Up`
+//! -- the same instantaneous "settle on success" simulation
+//! [`crate::network_config::TunnelInterface::up`] already uses for GRE/IPIP
+//! tunnels.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use sha2::{Digest, Sha256};
+
+use crate::cliconfig::CliContext;
+
+/// Encryption transforms this simulator recognizes, most to least preferred.
+const SUPPORTED_CIPHERS: &[&str] = &["esp-aes", "esp-3des", "esp-des"];
+/// Authentication transforms this simulator recognizes, most to least preferred.
+const SUPPORTED_AUTH: &[&str] = &["esp-sha-hmac", "esp-md5-hmac"];
+
+/// Rekey interval used when `crypto ipsec security-association lifetime
+/// seconds` hasn't been configured: one hour, the Cisco IOS default.
+const DEFAULT_REKEY_SECONDS: u32 = 3600;
+
+/// Where a simulated tunnel is in its SA lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunnelState {
+    Down,
+    Negotiating,
+    Up,
+}
+
+impl fmt::Display for TunnelState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TunnelState::Down => "DOWN",
+            TunnelState::Negotiating => "NEGOTIATING",
+            TunnelState::Up => "UP",
+        })
+    }
+}
+
+/// A successfully negotiated SA, as reported by `show crypto ipsec sa`.
+#[derive(Debug, Clone)]
+pub struct NegotiatedSa {
+    pub map_name: String,
+    pub peer: String,
+    pub local_address: String,
+    pub cipher: String,
+    pub auth: String,
+    pub rekey_seconds: u32,
+    /// First 16 hex characters of the derived session key's SHA-256
+    /// fingerprint -- enough to show the negotiation actually produced
+    /// distinct key material per peer, without printing anything sensitive.
+    pub key_fingerprint: String,
+    pub state: TunnelState,
+}
+
+lazy_static! {
+    /// The one simulated SA this subsystem tracks -- this crate models at
+    /// most one active crypto map / tunnel destination pairing at a time,
+    /// mirroring the single `crypto_ipsec_profile`/`tunnel_destination`
+    /// fields `CliConfig` already carries.
+    static ref ACTIVE_SA: Mutex<Option<NegotiatedSa>> = Mutex::new(None);
+}
+
+/// Picks the strongest cipher/auth pair this simulator supports out of
+/// `transforms`. `Err` if `transforms` doesn't contain at least one
+/// recognized transform of each kind.
+fn select_proposal(transforms: &[String]) -> Result<(String, String), String> {
+    let cipher = SUPPORTED_CIPHERS
+        .iter()
+        .find(|candidate| transforms.iter().any(|t| t == *candidate))
+        .ok_or_else(|| format!(
+            "No supported encryption transform in {:?}; this simulator supports {:?}.",
+            transforms, SUPPORTED_CIPHERS
+        ))?;
+    let auth = SUPPORTED_AUTH
+        .iter()
+        .find(|candidate| transforms.iter().any(|t| t == *candidate))
+        .ok_or_else(|| format!(
+            "No supported authentication transform in {:?}; this simulator supports {:?}.",
+            transforms, SUPPORTED_AUTH
+        ))?;
+    Ok((cipher.to_string(), auth.to_string()))
+}
+
+/// Derives a session-key fingerprint from whatever key material is
+/// configured: the device's first crypto key if one exists (an RSA-keyed
+/// SA), else the first ISAKMP client group's pre-shared key. `Err` if
+/// neither is configured -- there's nothing to derive a key from.
+fn derive_key_fingerprint(context: &CliContext, peer: &str) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    if let Some(key_name) = context.key_store.list().first() {
+        let key = context.key_store.get(key_name).expect("listed key must exist");
+        hasher.update(b"rsa");
+        hasher.update(key.fingerprint.as_bytes());
+    } else if let Some(psk) = context.config.isakmp_client_groups.values().find_map(|g| g.key.as_ref()) {
+        hasher.update(b"psk");
+        hasher.update(psk.as_bytes());
+    } else {
+        return Err("No crypto key or ISAKMP pre-shared key configured; nothing to derive a session key from.".into());
+    }
+    hasher.update(peer.as_bytes());
+    Ok(format!("{:x}", hasher.finalize())[..16].to_string())
+}
+
+/// Re-evaluates whether a simulated SA can be established, called after
+/// every command that touches a crypto map's transform set, a tunnel
+/// destination, or a tunnel protection profile. A no-op (`Ok(())`, nothing
+/// printed) until a crypto map with a bound transform set and a tunnel
+/// destination are both configured; once they are, negotiates and prints
+/// the `Down -> Negotiating -> Up` transition, or returns `Err` describing
+/// why negotiation failed (missing transform set, unsupported transform, or
+/// no key material).
+pub fn try_negotiate(context: &CliContext) -> Result<(), String> {
+    let destination = match &context.config.tunnel_destination {
+        Some(destination) => destination.clone(),
+        None => return Ok(()),
+    };
+
+    let map = match context.config.crypto_maps.values().find(|map| map.transform_set.is_some()) {
+        Some(map) => map.clone(),
+        None => return Ok(()),
+    };
+
+    let ts_name = map.transform_set.as_ref().expect("filtered on transform_set.is_some()");
+    let transforms = context.config.crypto_transform_sets.get(ts_name).ok_or_else(|| {
+        format!("Crypto map '{}' references transform set '{}', which no longer exists.", map.name, ts_name)
+    })?;
+
+    println!("IPsec SA for crypto map '{}': {}", map.name, TunnelState::Down);
+    println!("IPsec SA for crypto map '{}': {}", map.name, TunnelState::Negotiating);
+
+    let (cipher, auth) = select_proposal(transforms)
+        .map_err(|err| format!("IPsec SA for crypto map '{}' failed to negotiate: {}", map.name, err))?;
+
+    let peer = map.peer.clone().unwrap_or(destination);
+    let key_fingerprint = derive_key_fingerprint(context, &peer)
+        .map_err(|err| format!("IPsec SA for crypto map '{}' failed to negotiate: {}", map.name, err))?;
+
+    let local_address = context
+        .config
+        .crypto_local_addresses
+        .get(&map.name)
+        .cloned()
+        .or_else(|| context.config.tunnel_source.clone())
+        .unwrap_or_else(|| "unspecified".to_string());
+    let rekey_seconds = context.config.crypto_ipsec_lifetime.seconds.unwrap_or(DEFAULT_REKEY_SECONDS);
+
+    let sa = NegotiatedSa {
+        map_name: map.name.clone(),
+        peer,
+        local_address,
+        cipher,
+        auth,
+        rekey_seconds,
+        key_fingerprint,
+        state: TunnelState::Up,
+    };
+    println!(
+        "IPsec SA for crypto map '{}': {} (transform {}/{}, peer {})",
+        sa.map_name, TunnelState::Up, sa.cipher, sa.auth, sa.peer
+    );
+    *ACTIVE_SA.lock().unwrap() = Some(sa);
+    Ok(())
+}
+
+/// What `show crypto ipsec sa` reports, or `None` if no SA has negotiated.
+pub fn active_sa() -> Option<NegotiatedSa> {
+    ACTIVE_SA.lock().unwrap().clone()
+}
+
+/// Handles `clear crypto ipsec sa`: drops the simulated SA so the next
+/// `try_negotiate` call starts over from `Down`.
+///
+/// # Returns
+/// `false` if no SA was active.
+pub fn clear() -> bool {
+    ACTIVE_SA.lock().unwrap().take().is_some()
+}
@@ -0,0 +1,354 @@
+//! Pluggable storage for crypto keys and certificates, behind the
+//! [`KeyStore`]/[`CertStore`] traits -- the same "always-available default,
+//! swappable at runtime" shape as [`crate::host_backend`]'s `HostBackend`:
+//! an in-memory implementation that needs nothing, and an encrypted-file
+//! implementation an operator can switch to with `crypto key storage file
+//! <path>` / `crypto certificate storage file <path>` so imported material
+//! survives the process restarting, independent of a full `copy
+//! running-config startup-config`.
+//!
+//! `crypto key`/`crypto certificate` command closures go through
+//! [`CliContext::key_store`]/[`CliContext::cert_store`] instead of touching
+//! `CliConfig::crypto_keys`/`certificates` directly. Those `CliConfig`
+//! fields remain the snapshot `run_config.rs` serializes into
+//! `startup-config.json`; [`sync_config_from_stores`]/
+//! [`sync_stores_from_config`] keep the two in lockstep at the save/load
+//! boundary.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cliconfig::CliContext;
+use crate::cryptocommands::CryptoKey;
+use crate::run_config::{unwrap_key, wrap_key, WrappedKey};
+
+/// Storage for [`CryptoKey`]s, keyed by name. Implementations own their
+/// data outright (no borrowed state), so a `Box<dyn KeyStore>` can be
+/// swapped out on [`CliContext`] at any time without a lifetime tying it
+/// back to whatever backed the previous one.
+pub trait KeyStore: Send {
+    fn get(&self, name: &str) -> Option<&CryptoKey>;
+    fn put(&mut self, name: String, key: CryptoKey) -> Result<(), String>;
+    fn delete(&mut self, name: &str) -> Option<CryptoKey>;
+    fn list(&self) -> Vec<String>;
+    /// A full copy of every stored key, for `run_config.rs` to serialize
+    /// into `startup-config.json`.
+    fn snapshot(&self) -> HashMap<String, CryptoKey>;
+    /// Replaces every stored key with `keys`, e.g. after `load_config`
+    /// decrypts `startup-config.json`'s `crypto_keys_wrapped`.
+    fn load_snapshot(&mut self, keys: HashMap<String, CryptoKey>);
+    /// Clones the underlying store into a fresh trait object, so
+    /// `Box<dyn KeyStore>` can implement `Clone` -- needed because
+    /// `CliContext` (which holds one) is cloned wholesale to snapshot state
+    /// for `batch.rs`'s atomic rollback.
+    fn box_clone(&self) -> Box<dyn KeyStore>;
+}
+
+impl Clone for Box<dyn KeyStore> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Storage for certificate PEMs, keyed by name/FQDN. Certificates are public
+/// by definition, so unlike [`KeyStore`] neither implementation here needs
+/// to encrypt anything at rest.
+pub trait CertStore: Send {
+    fn get(&self, name: &str) -> Option<&String>;
+    fn put(&mut self, name: String, cert_pem: String);
+    fn delete(&mut self, name: &str) -> Option<String>;
+    fn list(&self) -> Vec<String>;
+    fn snapshot(&self) -> HashMap<String, String>;
+    fn load_snapshot(&mut self, certs: HashMap<String, String>);
+    /// See [`KeyStore::box_clone`].
+    fn box_clone(&self) -> Box<dyn CertStore>;
+}
+
+impl Clone for Box<dyn CertStore> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// The default [`KeyStore`]: process-lifetime only, exactly like
+/// `context.config.crypto_keys` behaved before this module existed.
+#[derive(Default)]
+pub struct InMemoryKeyStore {
+    keys: HashMap<String, CryptoKey>,
+}
+
+impl KeyStore for InMemoryKeyStore {
+    fn get(&self, name: &str) -> Option<&CryptoKey> {
+        self.keys.get(name)
+    }
+
+    fn put(&mut self, name: String, key: CryptoKey) -> Result<(), String> {
+        self.keys.insert(name, key);
+        Ok(())
+    }
+
+    fn delete(&mut self, name: &str) -> Option<CryptoKey> {
+        self.keys.remove(name)
+    }
+
+    fn list(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.keys.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn snapshot(&self) -> HashMap<String, CryptoKey> {
+        self.keys.clone()
+    }
+
+    fn load_snapshot(&mut self, keys: HashMap<String, CryptoKey>) {
+        self.keys = keys;
+    }
+
+    fn box_clone(&self) -> Box<dyn KeyStore> {
+        Box::new(InMemoryKeyStore { keys: self.keys.clone() })
+    }
+}
+
+/// The default [`CertStore`]: process-lifetime only.
+#[derive(Default)]
+pub struct InMemoryCertStore {
+    certs: HashMap<String, String>,
+}
+
+impl CertStore for InMemoryCertStore {
+    fn get(&self, name: &str) -> Option<&String> {
+        self.certs.get(name)
+    }
+
+    fn put(&mut self, name: String, cert_pem: String) {
+        self.certs.insert(name, cert_pem);
+    }
+
+    fn delete(&mut self, name: &str) -> Option<String> {
+        self.certs.remove(name)
+    }
+
+    fn list(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.certs.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn snapshot(&self) -> HashMap<String, String> {
+        self.certs.clone()
+    }
+
+    fn load_snapshot(&mut self, certs: HashMap<String, String>) {
+        self.certs = certs;
+    }
+
+    fn box_clone(&self) -> Box<dyn CertStore> {
+        Box::new(InMemoryCertStore { certs: self.certs.clone() })
+    }
+}
+
+/// On-disk shape of an [`EncryptedFileKeyStore`]'s file: every key wrapped
+/// under its own salt/nonce with [`wrap_key`], the identical per-key scheme
+/// `run_config.rs` already uses for `crypto_keys` inside
+/// `startup-config.json` -- an operator who trusts that wrapping doesn't
+/// need to trust a second one.
+#[derive(Serialize, Deserialize, Default)]
+struct WrappedKeyFile {
+    keys: HashMap<String, WrappedKey>,
+}
+
+/// A [`KeyStore`] that persists every key to `path`, encrypted under
+/// `passphrase`, re-writing the whole file on every mutation (the same
+/// whole-file persistence model `run_config.rs` uses for the startup
+/// config, rather than an incremental/append-only format).
+pub struct EncryptedFileKeyStore {
+    path: PathBuf,
+    passphrase: String,
+    keys: HashMap<String, CryptoKey>,
+}
+
+impl EncryptedFileKeyStore {
+    /// Opens (or initializes, if `path` doesn't exist yet) an encrypted key
+    /// store. Refuses an empty passphrase outright, and refuses to load an
+    /// existing file under the wrong passphrase -- either way this returns
+    /// `Err` rather than silently starting from an empty store.
+    pub fn open(path: PathBuf, passphrase: String) -> Result<Self, String> {
+        if passphrase.is_empty() {
+            return Err("Refusing to open an encrypted key store without a passphrase.".to_string());
+        }
+
+        let mut keys = HashMap::new();
+        if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read '{}': {}", path.display(), err))?;
+            let wrapped: WrappedKeyFile = serde_json::from_str(&contents)
+                .map_err(|err| format!("Malformed key store file '{}': {}", path.display(), err))?;
+            for (name, blob) in wrapped.keys {
+                let plaintext = unwrap_key(&blob, &passphrase)?;
+                let key: CryptoKey = serde_json::from_str(&plaintext)
+                    .map_err(|err| format!("Malformed key data for '{}' in '{}': {}", name, path.display(), err))?;
+                keys.insert(name, key);
+            }
+        }
+
+        Ok(Self { path, passphrase, keys })
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let mut wrapped = HashMap::new();
+        for (name, key) in &self.keys {
+            let plaintext = serde_json::to_string(key)
+                .map_err(|err| format!("Failed to serialize key '{}': {}", name, err))?;
+            wrapped.insert(name.clone(), wrap_key(&plaintext, &self.passphrase)?);
+        }
+        let contents = serde_json::to_string_pretty(&WrappedKeyFile { keys: wrapped })
+            .map_err(|err| format!("Failed to serialize key store: {}", err))?;
+        fs::write(&self.path, contents)
+            .map_err(|err| format!("Failed to write '{}': {}", self.path.display(), err))
+    }
+}
+
+impl KeyStore for EncryptedFileKeyStore {
+    fn get(&self, name: &str) -> Option<&CryptoKey> {
+        self.keys.get(name)
+    }
+
+    fn put(&mut self, name: String, key: CryptoKey) -> Result<(), String> {
+        self.keys.insert(name, key);
+        self.persist()
+    }
+
+    fn delete(&mut self, name: &str) -> Option<CryptoKey> {
+        let removed = self.keys.remove(name);
+        if removed.is_some() {
+            if let Err(err) = self.persist() {
+                eprintln!("Warning: failed to update encrypted key store after delete: {}", err);
+            }
+        }
+        removed
+    }
+
+    fn list(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.keys.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn snapshot(&self) -> HashMap<String, CryptoKey> {
+        self.keys.clone()
+    }
+
+    fn load_snapshot(&mut self, keys: HashMap<String, CryptoKey>) {
+        self.keys = keys;
+        if let Err(err) = self.persist() {
+            eprintln!("Warning: failed to persist encrypted key store: {}", err);
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn KeyStore> {
+        Box::new(EncryptedFileKeyStore {
+            path: self.path.clone(),
+            passphrase: self.passphrase.clone(),
+            keys: self.keys.clone(),
+        })
+    }
+}
+
+/// A [`CertStore`] that persists certificates to `path` as plain JSON --
+/// certificates are public, so unlike [`EncryptedFileKeyStore`] there's
+/// nothing here that needs wrapping.
+pub struct FileCertStore {
+    path: PathBuf,
+    certs: HashMap<String, String>,
+}
+
+impl FileCertStore {
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        let certs = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read '{}': {}", path.display(), err))?;
+            serde_json::from_str(&contents)
+                .map_err(|err| format!("Malformed certificate store file '{}': {}", path.display(), err))?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self { path, certs })
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(&self.certs)
+            .map_err(|err| format!("Failed to serialize certificate store: {}", err))?;
+        fs::write(&self.path, contents)
+            .map_err(|err| format!("Failed to write '{}': {}", self.path.display(), err))
+    }
+}
+
+impl CertStore for FileCertStore {
+    fn get(&self, name: &str) -> Option<&String> {
+        self.certs.get(name)
+    }
+
+    fn put(&mut self, name: String, cert_pem: String) {
+        self.certs.insert(name, cert_pem);
+        if let Err(err) = self.persist() {
+            eprintln!("Warning: failed to update certificate store file: {}", err);
+        }
+    }
+
+    fn delete(&mut self, name: &str) -> Option<String> {
+        let removed = self.certs.remove(name);
+        if removed.is_some() {
+            if let Err(err) = self.persist() {
+                eprintln!("Warning: failed to update certificate store file after delete: {}", err);
+            }
+        }
+        removed
+    }
+
+    fn list(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.certs.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn snapshot(&self) -> HashMap<String, String> {
+        self.certs.clone()
+    }
+
+    fn load_snapshot(&mut self, certs: HashMap<String, String>) {
+        self.certs = certs;
+        if let Err(err) = self.persist() {
+            eprintln!("Warning: failed to persist certificate store file: {}", err);
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn CertStore> {
+        Box::new(FileCertStore {
+            path: self.path.clone(),
+            certs: self.certs.clone(),
+        })
+    }
+}
+
+/// Copies `context.key_store`/`cert_store` into `context.config.crypto_keys`/
+/// `certificates`, so `run_config::save_config`/`get_running_config` (which
+/// both still serialize those `CliConfig` fields) reflect whatever backend
+/// is actually active, rather than a stale snapshot from before the store
+/// was last switched.
+pub fn sync_config_from_stores(context: &mut CliContext) {
+    context.config.crypto_keys = context.key_store.snapshot();
+    context.config.certificates = context.cert_store.snapshot();
+}
+
+/// Reverses [`sync_config_from_stores`]: pushes `context.config.crypto_keys`/
+/// `certificates` (just populated by `run_config::load_config`) into
+/// whichever stores are active, so a freshly loaded startup config is
+/// visible through `crypto key`/`crypto certificate` commands immediately.
+pub fn sync_stores_from_config(context: &mut CliContext) {
+    context.key_store.load_snapshot(context.config.crypto_keys.clone());
+    context.cert_store.load_snapshot(context.config.certificates.clone());
+}
@@ -13,9 +13,33 @@ mod run_config;
 mod execute;
 mod network_config;
 mod cryptocommands;
+mod dhcpcommands;
+mod ntp_auth;
+mod icmp_ping;
+mod config_io;
 mod dynamic_registry;
 mod new_commands;
 mod walkup;
+mod config_archive;
+mod batch;
+mod acl_eval;
+mod route_filter;
+mod plugins;
+mod config_wizard;
+mod hooks;
+mod terminal_settings;
+mod commit_confirm;
+mod vty_server;
+mod shell_completion;
+mod session_recorder;
+mod ws_server;
+mod natcommands;
+mod host_backend;
+mod acme;
+mod nat_traversal;
+mod config_resolve;
+mod keystore;
+mod ipsec_sim;
 
 
 /// Internal imports from the application's modules
@@ -35,8 +59,141 @@ use rustyline::error::ReadlineError;
 use rustyline::Editor;
 use rustyline::history::DefaultHistory;
 use std::collections::{HashSet, HashMap};
+use std::io::{IsTerminal, Read};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 use ctrlc;
 
+use crate::network_config::{advance_ntp_poll, verify_ntp_association};
+use crate::run_config::{load_config, LoadError, DEFAULT_STARTUP_CONFIG_PATH};
+
+/// Restores the device's startup configuration (and the device-state
+/// `lazy_static` stores it carries) from [`DEFAULT_STARTUP_CONFIG_PATH`] if
+/// one exists, falling back to [`CliConfig::default`] only on first boot or
+/// a genuinely corrupt file (see [`LoadError::Invalid`]) -- a boot should
+/// never block on input, so this always passes an empty passphrase, and any
+/// `crypto_keys` entry wrapped under a real one is dropped by `load_config`
+/// itself (with a warning) rather than taking the rest of the restored
+/// config down with it.
+fn load_startup_config() -> CliConfig {
+    match load_config(std::path::Path::new(DEFAULT_STARTUP_CONFIG_PATH), "") {
+        Ok(config) => {
+            println!("Restored startup configuration from '{}'.", DEFAULT_STARTUP_CONFIG_PATH);
+            config
+        }
+        Err(LoadError::NotFound) => CliConfig::default(),
+        Err(LoadError::Invalid(detail)) => {
+            eprintln!(
+                "Warning: could not restore '{}' ({}); starting from a default configuration.",
+                DEFAULT_STARTUP_CONFIG_PATH, detail
+            );
+            CliConfig::default()
+        }
+    }
+}
+
+/// Ticks every second on a background thread so NTP associations age
+/// toward a synchronized state between commands, rather than only
+/// advancing when `show ntp`/`show clock` happen to run -- the same idea
+/// as deno's REPL racing a blocking read against a periodic timer, adapted
+/// to rustyline's synchronous `readline`. Because `readline` blocks the
+/// main thread entirely, the ticks posted here can only be *drained* by
+/// the main loop (via [`apply_pending_ntp_ticks`]), not raced against it:
+/// a session idling at a bare prompt only catches up once the operator
+/// presses return, a gap of the same kind `vty_server`/`ws_server` already
+/// document for their own per-connection output capture. `ntp_associations`
+/// itself is still only ever mutated from the main thread, so there's no
+/// locking to get wrong -- the channel is purely a way to hand the tick
+/// *count* back to the single owner.
+fn spawn_ntp_ticker() -> mpsc::Receiver<()> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(1));
+        if tx.send(()).is_err() {
+            return;
+        }
+    });
+    rx
+}
+
+/// Applies every tick queued since the last call: one simulated NTP poll
+/// per tick for each configured association, ageing `reach`/`st`/etc. via
+/// the same [`advance_ntp_poll`]/[`verify_ntp_association`] pair `show ntp`
+/// already uses, so associations keep synchronizing across a long idle
+/// stretch at the prompt instead of jumping straight from tick 1 to fully
+/// reachable the next time `show ntp` happens to run.
+fn apply_pending_ntp_ticks(context: &mut CliContext, rx: &mpsc::Receiver<()>) {
+    while rx.try_recv().is_ok() {
+        for assoc in context.ntp_associations.iter_mut() {
+            if assoc.address == "127.127.1.1" {
+                advance_ntp_poll(assoc, 0, true);
+            } else {
+                advance_ntp_poll(assoc, 1, false);
+            }
+            verify_ntp_association(assoc, &context.ntp_authentication_keys, &context.ntp_trusted_keys);
+        }
+    }
+}
+
+/// Parses `--file <path>` and `--continue-on-error` out of the process
+/// arguments for non-interactive use; any other argument is ignored.
+///
+/// # Returns
+/// `(file, continue_on_error)` -- `file` is `Some` only when `--file` was
+/// given.
+fn parse_cli_args() -> (Option<String>, bool) {
+    let mut file = None;
+    let mut continue_on_error = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => file = args.next(),
+            "--continue-on-error" => continue_on_error = true,
+            _ => {}
+        }
+    }
+    (file, continue_on_error)
+}
+
+/// Feeds `lines` through [`execute_command`] with no terminal attached, for
+/// `--file`/stdin-piped invocation (mirroring nushell's `run_script_file`/
+/// `run_script_standalone` and Mentat's `--no-tty`). Lines starting with `!`
+/// (Cisco-style comments, skipped by [`batch::run_batch`] too) or `#`
+/// (shell-style comments, skipped only here) are skipped.
+///
+/// Stops at the first failing line unless `continue_on_error` is set.
+///
+/// # Returns
+/// The process exit code: `0` if every line ran successfully, `1` if any
+/// line failed.
+fn run_script(
+    lines: &[String],
+    commands: &HashMap<&str, Command>,
+    context: &mut CliContext,
+    clock: &mut Option<Clock>,
+    completer: &mut CommandCompleter,
+    continue_on_error: bool,
+) -> i32 {
+    let mut exit_code = 0;
+    for raw_line in lines {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('!') || line.starts_with('#') {
+            continue;
+        }
+
+        if let Err(err) = execute_command(line, commands, context, clock, completer) {
+            eprintln!("Error: {}", err);
+            exit_code = 1;
+            if !continue_on_error {
+                break;
+            }
+        }
+        completer.current_mode = context.current_mode.clone();
+    }
+    exit_code
+}
+
 
 /// Main function of the CLI application.
 ///
@@ -79,6 +236,10 @@ use ctrlc;
 /// - Command history is stored in `history.txt` and is reloaded on subsequent runs.
 fn main() {
 
+    // Discover JSON-RPC plugins under `plugins/`/`PNFBOX_PLUGIN_DIR` before
+    // anything gets dispatched, so they're available from the first command.
+    plugins::discover_rpc_plugins();
+
     // Build the registry of commands and retrieve their names
     let commands = build_command_registry();
     let command_names: Vec<String> = commands.keys().cloned().map(String::from).collect();
@@ -86,11 +247,16 @@ fn main() {
     // Define the initial hostname as "SEM"
     let initial_hostname = "SEM".to_string();
     
+    // Restore the startup configuration (and its device-state stores) if one
+    // was saved by a previous session's `write memory`/`copy running-config
+    // startup-config`, otherwise start from defaults.
+    let startup_config = load_startup_config();
+
     // Define the context for the CLI
     let mut context = CliContext {
         current_mode: Mode::UserMode,
-        config: CliConfig::default(),
-        prompt: format!("{}>", CliConfig::default().hostname),
+        prompt: format!("{}>", startup_config.hostname),
+        config: startup_config,
         selected_interface: None,
         selected_vlan: None,
         vlan_names: None,
@@ -104,27 +270,51 @@ fn main() {
         ntp_authentication_enabled: false,   
         ntp_authentication_keys: HashMap::new(), 
         ntp_trusted_keys: HashSet::new(),     
-        ntp_master: false,   
+        ntp_master: false,
     };
 
-    // Configure the Rustyline editor with history behavior
-    let config = rustyline::Config::builder()
-    .history_ignore_space(true) 
-    .completion_type(rustyline::CompletionType::List)
-    .build();
+    let mut commands_map: HashMap<String, Vec<String>> = HashMap::new();
+    for command in &command_names {
+        commands_map.insert(command.clone(), vec![command.clone()]);
+    }
+
+    // Non-interactive execution: a `--file <path>` argument or a stdin that
+    // isn't a TTY (e.g. `cat config.sem | pnf_box_sem`) means there's a
+    // prepared script to run instead of an interactive session.
+    let (script_file, continue_on_error) = parse_cli_args();
+    if script_file.is_some() || !std::io::stdin().is_terminal() {
+        let contents = match &script_file {
+            Some(path) => std::fs::read_to_string(path).unwrap_or_else(|err| {
+                eprintln!("Error: could not read '{}': {}", path, err);
+                std::process::exit(2);
+            }),
+            None => {
+                let mut buf = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut buf)
+                    .expect("failed to read stdin");
+                buf
+            }
+        };
+        let lines: Vec<String> = contents.lines().map(String::from).collect();
+
+        let mut completer = CommandCompleter::new(commands_map, Mode::UserMode);
+        let mut clock = Some(Clock::new());
+        let exit_code = run_script(&lines, &commands, &mut context, &mut clock, &mut completer, continue_on_error);
+        std::process::exit(exit_code);
+    }
+
+    // Configure the Rustyline editor from the `terminal`-configurable settings
+    let mut terminal_settings = context.config.terminal_settings.clone();
+    let config = terminal_settings.to_rustyline_config();
 
     // Initialize the command-line editor with a custom command completer
     let mut rl = Editor::<CommandCompleter, DefaultHistory>::with_config(config)
         .expect("Failed to initialize editor");
 
-    let mut commands_map: HashMap<String, Vec<String>> = HashMap::new();
-    for command in command_names {
-        commands_map.insert(command.clone(), vec![command.clone()]);
-    }
-    
     let completer = CommandCompleter::new(commands_map, Mode::UserMode);
     rl.set_helper(Some(completer));
-    rl.load_history("history.txt").ok();
+    rl.load_history(&terminal_settings.history_file).ok();
 
     // Set up the initial clock settings
     let mut clock = Some(Clock::new());
@@ -136,9 +326,16 @@ fn main() {
         println!("\nCtrl+C pressed, but waiting for 'exit cli' command to exit...");
     }).expect("Error setting Ctrl+C handler");
 
+    // Ticks `context.ntp_associations` toward a synchronized state once a
+    // second in the background -- see `spawn_ntp_ticker`'s doc comment for
+    // why this is drained rather than raced against the blocking `readline`
+    // below.
+    let ntp_ticks = spawn_ntp_ticker();
+
     // Main REPL loop for processing user input
     loop {
-        
+        apply_pending_ntp_ticks(&mut context, &ntp_ticks);
+
         let prompt = context.prompt.clone();
         println!();
         match rl.readline(&prompt) {
@@ -149,18 +346,48 @@ fn main() {
                 }
 
                 rl.add_history_entry(input);
-                
+
                 if input == "exit cli" {
                     println!("Exiting CLI...");
                     break;
                 }
 
+                let mut tokens = input.split_whitespace();
+                if matches!(tokens.next(), Some("load") | Some("source")) {
+                    let file_args: Vec<&str> = tokens.collect();
+                    if let Some(helper) = rl.helper_mut() {
+                        batch::handle_load_command(&file_args, &commands, &mut context, &mut clock, helper);
+                        helper.current_mode = context.current_mode.clone();
+                        helper.record_history(input);
+                        helper.refresh_completions().ok();
+                    }
+                    continue;
+                }
+
                 if let Some(helper) = rl.helper_mut() {
-                    execute_command(input, &commands, &mut context, &mut clock, helper);
+                    if let Err(err) = execute_command(input, &commands, &mut context, &mut clock, helper) {
+                        println!("Error: {}", err);
+                    }
                     helper.current_mode = context.current_mode.clone();
+                    helper.record_history(input);
                     helper.refresh_completions().ok();
                 }
-                      
+
+                // A `terminal` command may have just changed the editor
+                // settings -- re-apply them the same way the helper's mode
+                // is kept in sync above.
+                if context.config.terminal_settings != terminal_settings {
+                    if context.config.terminal_settings.history_file != terminal_settings.history_file {
+                        rl.save_history(&terminal_settings.history_file).ok();
+                        terminal_settings = context.config.terminal_settings.clone();
+                        rl.load_history(&terminal_settings.history_file).ok();
+                    } else {
+                        terminal_settings = context.config.terminal_settings.clone();
+                    }
+                    rl.set_config(terminal_settings.to_rustyline_config());
+                    rl.history_mut().set_max_len(terminal_settings.max_history_size).ok();
+                }
+
             }
 
             Err(ReadlineError::Interrupted) => {
@@ -176,5 +403,5 @@ fn main() {
 
     }
     // Save the command history before exiting
-    rl.save_history("history.txt").ok();
+    rl.save_history(&terminal_settings.history_file).ok();
 }
\ No newline at end of file
@@ -0,0 +1,206 @@
+//! Optional UPnP/IGD (Internet Gateway Device) port-mapping backend for
+//! `crypto ipsec nat-traversal igd`: requests the upstream router map the
+//! IKE (UDP/500) and NAT-T (UDP/4500) ports an active IPsec profile needs,
+//! renews the lease on a background timer, and tears the mapping down when
+//! the profile is removed or cleared. Structured the same way
+//! `host_backend.rs` keeps kernel-apply optional -- a trait with a
+//! dependency-free default impl, and a real impl compiled in only behind a
+//! feature flag -- so the default build never links against a UPnP crate.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+
+/// The two UDP ports an active IPsec profile needs forwarded for NAT
+/// traversal, per RFC 3947: IKE negotiation and the NAT-T-encapsulated ESP
+/// stream once a NAT is detected between the peers.
+const IKE_PORT: u16 = 500;
+const NAT_T_PORT: u16 = 4500;
+
+/// How long a requested mapping is leased for before it must be renewed,
+/// matching the conservative default most IGD gateways accept.
+const DEFAULT_LEASE_SECONDS: u32 = 3600;
+
+/// A UPnP/IGD control-point client: discovers the gateway and adds/removes
+/// the forwarded ports an IPsec profile needs.
+pub trait IgdClient: Send {
+    /// Requests the gateway map `external_port`/`protocol` to this host's
+    /// `internal_port`, returning the gateway-assigned external address.
+    fn add_port_mapping(&self, external_port: u16, internal_port: u16, lease_seconds: u32, description: &str) -> Result<Ipv4Addr, String>;
+    /// Removes a previously requested mapping.
+    fn remove_port_mapping(&self, external_port: u16) -> Result<(), String>;
+}
+
+/// The default client: no real gateway is contacted, so every mapping
+/// "succeeds" against a fixed external address -- mirroring how
+/// `host_backend::SimulationBackend` keeps the simulated device state
+/// authoritative when no real backend is enabled.
+pub struct SimulatedIgd;
+
+impl IgdClient for SimulatedIgd {
+    fn add_port_mapping(&self, _external_port: u16, _internal_port: u16, _lease_seconds: u32, _description: &str) -> Result<Ipv4Addr, String> {
+        Ok(Ipv4Addr::new(203, 0, 113, 1))
+    }
+
+    fn remove_port_mapping(&self, _external_port: u16) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Real IGD client, built on the `igd` crate's SSDP discovery and SOAP
+/// control calls. Only compiled in when the `igd-nat` feature is enabled --
+/// most installs never link against it.
+#[cfg(feature = "igd-nat")]
+pub struct UpnpIgd {
+    gateway: igd::Gateway,
+    local_addr: Ipv4Addr,
+}
+
+#[cfg(feature = "igd-nat")]
+impl UpnpIgd {
+    fn discover() -> Result<Self, String> {
+        let gateway = igd::search_gateway(Default::default()).map_err(|err| err.to_string())?;
+        let local_addr = match gateway.get_external_ip() {
+            Ok(_) => igd::local_ip().map_err(|err| err.to_string())?,
+            Err(err) => return Err(err.to_string()),
+        };
+        Ok(Self { gateway, local_addr })
+    }
+}
+
+#[cfg(feature = "igd-nat")]
+impl IgdClient for UpnpIgd {
+    fn add_port_mapping(&self, external_port: u16, internal_port: u16, lease_seconds: u32, description: &str) -> Result<Ipv4Addr, String> {
+        self.gateway
+            .add_port(
+                igd::PortMappingProtocol::UDP,
+                external_port,
+                std::net::SocketAddrV4::new(self.local_addr, internal_port),
+                lease_seconds,
+                description,
+            )
+            .map_err(|err| err.to_string())?;
+        self.gateway.get_external_ip().map_err(|err| err.to_string())
+    }
+
+    fn remove_port_mapping(&self, external_port: u16) -> Result<(), String> {
+        self.gateway
+            .remove_port(igd::PortMappingProtocol::UDP, external_port)
+            .map_err(|err| err.to_string())
+    }
+}
+
+lazy_static! {
+    /// The IGD client every `crypto ipsec nat-traversal igd` mapping is
+    /// requested through. Starts as [`SimulatedIgd`] and is only ever
+    /// replaced by [`discover_gateway`].
+    static ref ACTIVE_IGD: Mutex<Box<dyn IgdClient>> = Mutex::new(Box::new(SimulatedIgd));
+
+    /// The mapping acquired for each IPsec profile with NAT traversal
+    /// enabled, keyed by profile name.
+    static ref ACTIVE_MAPPINGS: Mutex<HashMap<String, NatMapping>> = Mutex::new(HashMap::new());
+}
+
+/// The external address/port pair an IGD gateway granted a profile for the
+/// IKE and NAT-T ports.
+#[derive(Debug, Clone)]
+pub struct NatMapping {
+    pub external_address: Ipv4Addr,
+    pub ike_external_port: u16,
+    pub nat_t_external_port: u16,
+}
+
+/// Identifies the most recently started renewal timer for a given profile; a
+/// background renewal thread only acts if its own id is still current when
+/// it wakes, the same supersede-don't-cancel idiom `commit_confirm.rs` uses
+/// for its rollback timer.
+static TIMER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Tries to replace [`ACTIVE_IGD`] with a real UPnP/IGD gateway discovered
+/// on the local network. On any failure -- SSDP discovery timeout, or the
+/// `igd-nat` feature not compiled in -- leaves [`ACTIVE_IGD`] as
+/// [`SimulatedIgd`] and returns the reason.
+pub fn discover_gateway() -> Result<(), String> {
+    #[cfg(feature = "igd-nat")]
+    {
+        let gateway = UpnpIgd::discover()?;
+        *ACTIVE_IGD.lock().unwrap() = Box::new(gateway);
+        Ok(())
+    }
+    #[cfg(not(feature = "igd-nat"))]
+    {
+        Err("This build was compiled without the 'igd-nat' feature; staying in simulation mode.".into())
+    }
+}
+
+/// Handles `crypto ipsec nat-traversal igd`: requests IKE/NAT-T port
+/// mappings for `profile_name` and spawns a background thread that renews
+/// them at two-thirds of the lease interval until [`disable`] is called.
+pub fn enable(profile_name: &str) -> Result<NatMapping, String> {
+    let mapping = request_mapping(profile_name)?;
+    ACTIVE_MAPPINGS.lock().unwrap().insert(profile_name.to_string(), mapping.clone());
+
+    let generation = TIMER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let renewed_profile_name = profile_name.to_string();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs((DEFAULT_LEASE_SECONDS as u64 * 2) / 3));
+        if TIMER_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if !ACTIVE_MAPPINGS.lock().unwrap().contains_key(&renewed_profile_name) {
+            return;
+        }
+        match request_mapping(&renewed_profile_name) {
+            Ok(renewed) => {
+                ACTIVE_MAPPINGS.lock().unwrap().insert(renewed_profile_name.clone(), renewed);
+            }
+            Err(err) => {
+                eprintln!("nat-traversal: failed to renew mapping for profile '{}': {}", renewed_profile_name, err);
+            }
+        }
+    });
+
+    Ok(mapping)
+}
+
+fn request_mapping(profile_name: &str) -> Result<NatMapping, String> {
+    let igd = ACTIVE_IGD.lock().unwrap();
+    let description = format!("PNF IPsec profile {}", profile_name);
+    let external_address = igd.add_port_mapping(IKE_PORT, IKE_PORT, DEFAULT_LEASE_SECONDS, &description)?;
+    igd.add_port_mapping(NAT_T_PORT, NAT_T_PORT, DEFAULT_LEASE_SECONDS, &description)?;
+    Ok(NatMapping {
+        external_address,
+        ike_external_port: IKE_PORT,
+        nat_t_external_port: NAT_T_PORT,
+    })
+}
+
+/// Handles `no crypto ipsec nat-traversal igd`, the removal of an IPsec
+/// profile with it enabled, or `clear crypto ipsec sa`: tears down the
+/// gateway mapping and stops any pending renewal thread for `profile_name`.
+///
+/// # Returns
+/// `false` if `profile_name` had no active mapping.
+pub fn disable(profile_name: &str) -> bool {
+    TIMER_GENERATION.fetch_add(1, Ordering::SeqCst);
+    match ACTIVE_MAPPINGS.lock().unwrap().remove(profile_name) {
+        Some(mapping) => {
+            let igd = ACTIVE_IGD.lock().unwrap();
+            let _ = igd.remove_port_mapping(mapping.ike_external_port);
+            let _ = igd.remove_port_mapping(mapping.nat_t_external_port);
+            true
+        }
+        None => false,
+    }
+}
+
+/// What `show crypto ipsec nat-traversal` reports for a profile, or `None`
+/// if it doesn't have NAT traversal enabled.
+pub fn mapping_for(profile_name: &str) -> Option<NatMapping> {
+    ACTIVE_MAPPINGS.lock().unwrap().get(profile_name).cloned()
+}
@@ -0,0 +1,99 @@
+//! NAT configuration structures and pure translation-table construction for
+//! `ip nat inside`/`ip nat outside`, static one-to-one mappings, and PAT
+//! overload, backing `show ip nat translations` / `clear ip nat translation
+//! *`. Modeled on [`crate::acl_eval`]: the CLI commands in `clicommands.rs`
+//! only parse arguments and hold the global state (in
+//! [`crate::network_config`]); the actual translation logic lives here as
+//! plain functions so it can be tested without a `CliContext`.
+
+use std::net::Ipv4Addr;
+
+use crate::network_config::AccessControlList;
+
+/// Whether an interface faces the inside (private) or outside (public)
+/// network, configured via `ip nat inside` / `ip nat outside` in Interface
+/// Configuration mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NatSide {
+    Inside,
+    Outside,
+}
+
+impl std::fmt::Display for NatSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NatSide::Inside => write!(f, "inside"),
+            NatSide::Outside => write!(f, "outside"),
+        }
+    }
+}
+
+/// A one-to-one static mapping configured via `ip nat inside source static
+/// <local> <global>`.
+#[derive(Debug, Clone)]
+pub struct NatStaticMapping {
+    pub local: Ipv4Addr,
+    pub global: Ipv4Addr,
+}
+
+/// A PAT (overload) rule configured via `ip nat inside source list <acl>
+/// interface <interface> overload`: every address `acl` permits is
+/// translated to `interface`'s own address, distinguished only by source
+/// port.
+#[derive(Debug, Clone)]
+pub struct NatOverloadRule {
+    pub acl: String,
+    pub interface: String,
+}
+
+/// One row of the translation table printed by `show ip nat translations`,
+/// keyed the way a real NAT table is: `(protocol, inside-local addr/port)` ->
+/// `(inside-global addr/port)`. Static entries carry no port (`None`) since
+/// they translate every port for that address; overload entries always carry
+/// one, since the port is what distinguishes them.
+#[derive(Debug, Clone)]
+pub struct NatTranslation {
+    pub protocol: &'static str,
+    pub inside_local: (Ipv4Addr, Option<u16>),
+    pub inside_global: (Ipv4Addr, Option<u16>),
+}
+
+/// The first port PAT hands out to a translated flow, mirroring the low end
+/// of Cisco's default PAT port range.
+const FIRST_OVERLOAD_PORT: u16 = 1024;
+
+/// Builds the static one-to-one translation rows for `mappings`.
+pub fn static_translations(mappings: &[NatStaticMapping]) -> Vec<NatTranslation> {
+    mappings
+        .iter()
+        .map(|mapping| NatTranslation {
+            protocol: "---",
+            inside_local: (mapping.local, None),
+            inside_global: (mapping.global, None),
+        })
+        .collect()
+}
+
+/// Builds PAT translation rows for every source address `acl` permits,
+/// translating each to `outside_address` (the `overload` rule's outside
+/// interface address) and assigning it a distinct port starting at
+/// [`FIRST_OVERLOAD_PORT`] -- this simulator has no live packet flows, so one
+/// row stands in for "the first flow seen from that host".
+pub fn overload_translations(acl: &AccessControlList, outside_address: Ipv4Addr) -> Vec<NatTranslation> {
+    let mut translations = Vec::new();
+    let mut next_port = FIRST_OVERLOAD_PORT;
+    for entry in &acl.entries {
+        if !entry.action.eq_ignore_ascii_case("permit") {
+            continue;
+        }
+        if let Ok(local_address) = entry.source.parse::<Ipv4Addr>() {
+            translations.push(NatTranslation {
+                protocol: "tcp",
+                inside_local: (local_address, Some(next_port)),
+                inside_global: (outside_address, Some(next_port)),
+            });
+            next_port += 1;
+        }
+    }
+    translations
+}
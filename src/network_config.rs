@@ -1,45 +1,727 @@
 /// External crates for the CLI application
 use std::str::FromStr;
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::sync::{Mutex, Arc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use rand::Rng;
+use scrypt::{scrypt, Params};
+use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
+use crate::ntp_auth::{self, NtpAuthKey};
+use crate::natcommands::{NatSide, NatStaticMapping, NatOverloadRule, NatTranslation, static_translations, overload_translations};
+use crate::route_filter::{RouteFilter, FilterDecision};
 
 
 /// Represents the configuration of a network interface.
-/// 
+///
 /// # Fields
 /// - `ip_address`: The IPv4 address of the interface.
 /// - `is_up`: A boolean indicating whether the interface is active.
+/// - `encapsulation`: The data-link encapsulation configured via `encapsulation`.
+/// - `ppp_authentication`: The PPP authentication method configured via `ppp authentication`, if any.
+/// - `ppp_multilink`: Whether `ppp multilink` has been enabled.
+/// - `compression`: The link compression algorithm configured via `compress`, if any.
+/// - `ppp_quality`: The minimum link quality percentage configured via `ppp quality`, if any.
+/// - `ipv6_addresses`: The IPv6 addresses (with prefix length) assigned via `ipv6 address`.
+/// - `ipv6_enabled`: Whether IPv6 processing has been enabled via `ipv6 enable` (implied by a
+///   configured address, but settable on its own for link-local-only operation).
+/// - `ospfv3_area`: The OSPFv3 area this interface was assigned to via `ipv6 ospf <pid> area <area>`.
+#[derive(Clone)]
 pub struct InterfaceConfig {
-    pub ip_address: Ipv4Addr,  
-    pub is_up: bool,  
+    pub ip_address: Ipv4Addr,
+    pub is_up: bool,
+    pub encapsulation: Encapsulation,
+    pub ppp_authentication: Option<PppAuthentication>,
+    pub ppp_multilink: bool,
+    pub compression: Option<CompressionAlgorithm>,
+    pub ppp_quality: Option<u8>,
+    pub ipv6_addresses: Vec<(Ipv6Addr, u8)>,
+    pub ipv6_enabled: bool,
+    pub ospfv3_area: Option<u32>,
+}
+
+impl Default for InterfaceConfig {
+    fn default() -> Self {
+        Self {
+            ip_address: Ipv4Addr::new(0, 0, 0, 0),
+            is_up: false,
+            encapsulation: Encapsulation::Hdlc,
+            ppp_authentication: None,
+            ppp_multilink: false,
+            compression: None,
+            ppp_quality: None,
+            ipv6_addresses: Vec::new(),
+            ipv6_enabled: false,
+            ospfv3_area: None,
+        }
+    }
+}
+
+/// The GRE/IPIP overlay settings of a tunnel interface, configured under
+/// `interface tunnel <n>` via `tunnel mode`/`source`/`destination`/`key`/
+/// `ttl`, modeled on net-tools `iptunnel`.
+///
+/// # Fields
+/// - `mode`: The encapsulation configured via `tunnel mode`, e.g. `"gre"`, `"ipip"`, or `"gre multipoint"`.
+/// - `source`: The tunnel source configured via `tunnel source`, either an IP address or an interface name.
+/// - `destination`: The tunnel destination IP address configured via `tunnel destination`.
+/// - `key`: The GRE key configured via `tunnel key`, if any.
+/// - `ttl`: The TTL configured via `tunnel ttl`, if any.
+/// - `up`: Whether the tunnel is up, which this simulator treats as true once both `source` and `destination` are set.
+#[derive(Debug, Clone, Default)]
+pub struct TunnelInterface {
+    pub mode: Option<String>,
+    pub source: Option<String>,
+    pub destination: Option<Ipv4Addr>,
+    pub key: Option<u32>,
+    pub ttl: Option<u8>,
+    pub up: bool,
+}
+
+/// The data-link encapsulation of a serial/WAN interface, configured via the
+/// `encapsulation` command. Cisco serial interfaces default to HDLC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encapsulation {
+    Hdlc,
+    Ppp,
+}
+
+impl Encapsulation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encapsulation::Hdlc => "HDLC",
+            Encapsulation::Ppp => "PPP",
+        }
+    }
+}
+
+impl std::fmt::Display for Encapsulation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The PPP authentication method(s) configured via `ppp authentication`. The
+/// two-method variants try the first method and fall back to the second if
+/// the peer refuses it, mirroring IOS's `ppp authentication chap pap` order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PppAuthentication {
+    Chap,
+    Pap,
+    ChapThenPap,
+    PapThenChap,
+}
+
+impl PppAuthentication {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PppAuthentication::Chap => "CHAP",
+            PppAuthentication::Pap => "PAP",
+            PppAuthentication::ChapThenPap => "CHAP PAP",
+            PppAuthentication::PapThenChap => "PAP CHAP",
+        }
+    }
+}
+
+impl std::fmt::Display for PppAuthentication {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The link compression algorithm configured via `compress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    Predictor,
+    Stack,
+}
+
+impl CompressionAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Predictor => "predictor",
+            CompressionAlgorithm::Stack => "stack",
+        }
+    }
+}
+
+impl std::fmt::Display for CompressionAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+
+/// Administrative state of an interface (RFC 2863 `ifAdminStatus`): what the
+/// operator has configured, independent of whether the link actually works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdminState {
+    Up,
+    Down,
+    Testing,
+}
+
+impl AdminState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminState::Up => "up",
+            AdminState::Down => "down",
+            AdminState::Testing => "testing",
+        }
+    }
+}
+
+impl std::fmt::Display for AdminState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+
+/// Operational state of an interface (RFC 2863 `ifOperStatus`): whether the
+/// interface is actually passing traffic. Tracked separately from
+/// [`AdminState`] so a link can be administratively up while the lower
+/// layer (cable, neighbor, etc.) is down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperState {
+    Up,
+    Down,
+    Testing,
+    Unknown,
+    NotPresent,
+    LowerLayerDown,
+}
+
+impl OperState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperState::Up => "up",
+            OperState::Down => "down",
+            OperState::Testing => "testing",
+            OperState::Unknown => "unknown",
+            OperState::NotPresent => "not present",
+            OperState::LowerLayerDown => "lower layer down",
+        }
+    }
+}
+
+impl std::fmt::Display for OperState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Renders the Cisco `is <admin>, line protocol is <protocol>` status line
+/// printed by `show interfaces`: administratively down always reports both
+/// states down, and a dead lower layer is called out separately from a
+/// merely-down protocol, the way a real router distinguishes "the operator
+/// shut this down" from "the cable/neighbor isn't there".
+pub fn interface_status_line(admin: AdminState, oper: OperState) -> String {
+    if admin != AdminState::Up {
+        return "administratively down, line protocol is down".to_string();
+    }
+    match oper {
+        OperState::Up => "up, line protocol is up".to_string(),
+        OperState::LowerLayerDown => "up, line protocol is down (lower layer down)".to_string(),
+        OperState::Testing => "up, line protocol is testing".to_string(),
+        OperState::Unknown | OperState::NotPresent | OperState::Down => "up, line protocol is down".to_string(),
+    }
+}
+
+/// Per-interface traffic counters, advanced by one simulated tick's worth
+/// of activity each time `show interfaces` runs -- the same "one tick per
+/// show" pattern as [`advance_ntp_poll`]/[`advance_snmp_stats`] -- so the
+/// printed counters reflect accumulated activity rather than a fixed
+/// literal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InterfaceCounters {
+    pub input_packets: u64,
+    pub input_bytes: u64,
+    pub output_packets: u64,
+    pub output_bytes: u64,
+}
+
+/// Advances `counters` by one simulated tick of traffic, but only while
+/// the interface is actually passing traffic (`OperState::Up`) -- a
+/// down/administratively-down interface's counters stay frozen, as on a
+/// real router.
+pub fn advance_interface_counters(counters: &mut InterfaceCounters, oper_state: OperState) {
+    if oper_state != OperState::Up {
+        return;
+    }
+    counters.input_packets += 20;
+    counters.input_bytes += 20 * 128;
+    counters.output_packets += 10;
+    counters.output_bytes += 10 * 128;
+}
+
+
+/// Coarse interface classification, following the OpenConfig `ietf-interfaces`
+/// type model (a simplified subset relevant to this simulator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceType {
+    Ethernet,
+    Loopback,
+    Vlan,
+    Tunnel,
+    Other,
+}
+
+impl InterfaceType {
+    /// Classifies an interface purely from its name, e.g. `"Loopback0"` or
+    /// `"FastEthernet0/1"` -- this simulator has no separate interface-type
+    /// registry, so the name is the only signal available.
+    pub fn classify(interface_name: &str) -> Self {
+        let lower = interface_name.to_lowercase();
+        if lower.starts_with("loopback") {
+            InterfaceType::Loopback
+        } else if lower.starts_with("vlan") {
+            InterfaceType::Vlan
+        } else if lower.starts_with("tunnel") {
+            InterfaceType::Tunnel
+        } else if lower.contains("ethernet") || lower.starts_with("ens") || lower.starts_with("eth") {
+            InterfaceType::Ethernet
+        } else {
+            InterfaceType::Other
+        }
+    }
+
+    /// The IANA `ifType` name OpenConfig tooling would report for this class.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterfaceType::Ethernet => "ethernetCsmacd",
+            InterfaceType::Loopback => "softwareLoopback",
+            InterfaceType::Vlan => "l2vlan",
+            InterfaceType::Tunnel => "tunnel",
+            InterfaceType::Other => "other",
+        }
+    }
+}
+
+impl std::fmt::Display for InterfaceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+
+/// The full per-interface record tracked by `ifconfig`, mirroring net-tools:
+/// the IPv4 address/netmask/broadcast (broadcast recomputed from the actual
+/// prefix length, not hardcoded to /24), any IPv6 addresses added via
+/// `ifconfig <if> add <ipv6>/<prefix>`, the MTU, hardware address, and
+/// whether the interface is administratively up (reflected in the rendered
+/// flags word).
+///
+/// Kept distinct from [`InterfaceConfig`]/[`IP_ADDRESS_STATE`], which back
+/// the separate `ip address` / interface-mode configuration workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IfconfigEntry {
+    pub ip_address: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    pub broadcast: Ipv4Addr,
+    pub ipv6_addresses: Vec<(Ipv6Addr, u8)>,
+    pub mtu: u16,
+    pub hw_address: String,
+    pub up: bool,
+}
+
+impl IfconfigEntry {
+    pub fn new(ip_address: Ipv4Addr, prefix_length: u32) -> Self {
+        Self {
+            ip_address,
+            netmask: prefix_to_netmask(prefix_length),
+            broadcast: calculate_broadcast(ip_address, prefix_length),
+            ipv6_addresses: Vec::new(),
+            mtu: 1500,
+            hw_address: "00:0c:29:16:30:92".to_string(),
+            up: true,
+        }
+    }
+
+    /// Recomputes `netmask`/`broadcast` after the address or prefix length changes.
+    pub fn set_address(&mut self, ip_address: Ipv4Addr, prefix_length: u32) {
+        self.ip_address = ip_address;
+        self.netmask = prefix_to_netmask(prefix_length);
+        self.broadcast = calculate_broadcast(ip_address, prefix_length);
+    }
+
+    /// Renders the net-tools-style flags word, e.g.
+    /// `4163<UP,BROADCAST,RUNNING,MULTICAST>` when up, dropping `UP` and
+    /// `RUNNING` when administratively down.
+    pub fn flags(&self) -> String {
+        if self.up {
+            "4163<UP,BROADCAST,RUNNING,MULTICAST>".to_string()
+        } else {
+            "4098<BROADCAST,MULTICAST>".to_string()
+        }
+    }
+}
+
+/// Converts a dotted-decimal netmask (e.g. `255.255.255.0`) to its CIDR
+/// prefix length by counting the leading one-bits.
+pub fn netmask_to_prefix(mask: Ipv4Addr) -> u32 {
+    u32::from(mask).count_ones()
+}
+
+/// Whether `mask` is a valid netmask: some number of leading one-bits
+/// followed only by zero-bits, with no one-bits after a zero. Rejects
+/// malformed masks like `255.0.255.0` that `netmask_to_prefix` would
+/// otherwise silently miscount by just summing set bits.
+pub fn is_contiguous_netmask(mask: Ipv4Addr) -> bool {
+    let bits = u32::from(mask);
+    let ones = bits.count_ones();
+    let expected = if ones == 0 { 0 } else { !0u32 << (32 - ones) };
+    bits == expected
+}
+
+/// Converts a CIDR prefix length to its dotted-decimal netmask.
+pub fn prefix_to_netmask(prefix_len: u32) -> Ipv4Addr {
+    if prefix_len == 0 {
+        Ipv4Addr::new(0, 0, 0, 0)
+    } else {
+        Ipv4Addr::from(!0u32 << (32 - prefix_len))
+    }
+}
+
+/// Where a route in the [`RoutingTable`] came from, carrying the
+/// administrative distance and one-letter `show ip route` code Cisco
+/// assigns to each source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RouteSource {
+    Connected,
+    Static,
+    Ospf,
+    Rip,
+}
+
+impl RouteSource {
+    pub fn distance(&self) -> u32 {
+        match self {
+            RouteSource::Connected => 0,
+            RouteSource::Static => 1,
+            RouteSource::Ospf => 110,
+            RouteSource::Rip => 120,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            RouteSource::Connected => "C",
+            RouteSource::Static => "S",
+            RouteSource::Ospf => "O",
+            RouteSource::Rip => "R",
+        }
+    }
+}
+
+/// One routing table entry: where traffic for the covered prefix goes, via
+/// which outgoing interface or next-hop IP, how it was learned, and its
+/// metric (hop count/cost, `0` for a directly connected route).
+///
+/// `distance_override` and `tag` are both `None` for every route except one
+/// that passed through a [`RouteFilter`] clause with a `set distance`/`set
+/// tag` action -- see [`Route::distance`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Route {
+    pub next_hop: String,
+    pub source: RouteSource,
+    pub metric: u32,
+    pub distance_override: Option<u32>,
+    pub tag: Option<u32>,
+}
+
+impl Route {
+    /// This route's effective administrative distance: `distance_override`
+    /// if a route filter's `set distance` action set one, otherwise
+    /// `source`'s default.
+    pub fn distance(&self) -> u32 {
+        self.distance_override.unwrap_or_else(|| self.source.distance())
+    }
+}
+
+/// A node of the binary trie [`RoutingTable`] indexes routes by: each level
+/// consumes one more bit of the destination address, so a lookup walks at
+/// most 32 levels deep regardless of how many routes are installed.
+#[derive(Default, Clone)]
+struct TrieNode {
+    children: [Option<Box<TrieNode>>; 2],
+    route: Option<Route>,
+}
+
+/// A longest-prefix-match IPv4 routing table, replacing a flat
+/// exact-destination-string map: every lookup walks the trie as far as the
+/// queried address's bits match an installed prefix, returning the most
+/// specific (and, at equal prefix length, lowest administrative distance)
+/// covering route -- the way a real router's FIB resolves a destination
+/// that falls inside a subnet but isn't a route's exact key. Overlapping
+/// prefixes (including a default `0.0.0.0/0`) coexist as separate trie
+/// nodes rather than overwriting each other, and `show ip route <address>`
+/// (below) is the longest-prefix-match lookup over this structure.
+#[derive(Default, Clone)]
+pub struct RoutingTable {
+    root: TrieNode,
+}
+
+impl RoutingTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Installs `route` for `network/prefix_len`, replacing whatever was
+    /// there only if `route` has an equal or lower administrative distance
+    /// -- e.g. a connected route always wins over a static one for the same
+    /// prefix, matching Cisco's route-selection behavior.
+    pub fn insert(&mut self, network: Ipv4Addr, prefix_len: u8, route: Route) {
+        let bits = u32::from(network);
+        let mut node = &mut self.root;
+        for i in 0..prefix_len {
+            let bit = ((bits >> (31 - i)) & 1) as usize;
+            node = node.children[bit as usize].get_or_insert_with(|| Box::new(TrieNode::default()));
+        }
+        let should_replace = match &node.route {
+            Some(existing) => route.distance() <= existing.distance(),
+            None => true,
+        };
+        if should_replace {
+            node.route = Some(route);
+        }
+    }
+
+    /// Removes whatever route is installed at exactly `network/prefix_len`.
+    pub fn remove(&mut self, network: Ipv4Addr, prefix_len: u8) {
+        let bits = u32::from(network);
+        let mut node = &mut self.root;
+        for i in 0..prefix_len {
+            let bit = ((bits >> (31 - i)) & 1) as usize;
+            match node.children[bit as usize].as_mut() {
+                Some(child) => node = child,
+                None => return,
+            }
+        }
+        node.route = None;
+    }
+
+    /// Longest-prefix-match lookup for `address`, returning the matched
+    /// network, its prefix length, and the covering route -- or `None` if
+    /// no route (not even a default route) covers it.
+    pub fn lookup(&self, address: Ipv4Addr) -> Option<(Ipv4Addr, u8, &Route)> {
+        let bits = u32::from(address);
+        let mut node = &self.root;
+        let mut best: Option<(u8, &Route)> = node.route.as_ref().map(|route| (0, route));
+        for i in 0..32u8 {
+            let bit = ((bits >> (31 - i)) & 1) as usize;
+            match node.children[bit as usize].as_ref() {
+                Some(child) => {
+                    node = child;
+                    if let Some(route) = node.route.as_ref() {
+                        best = Some((i + 1, route));
+                    }
+                }
+                None => break,
+            }
+        }
+        best.map(|(prefix_len, route)| {
+            let mask = if prefix_len == 0 { 0 } else { !0u32 << (32 - prefix_len) };
+            (Ipv4Addr::from(bits & mask), prefix_len, route)
+        })
+    }
+
+    /// Every installed route, as `(network, prefix_len, route)`, in no
+    /// particular order -- callers (e.g. `show ip route`) sort as needed.
+    pub fn entries(&self) -> Vec<(Ipv4Addr, u8, Route)> {
+        let mut out = Vec::new();
+        Self::walk(&self.root, 0, 0, &mut out);
+        out
+    }
+
+    fn walk(node: &TrieNode, bits: u32, depth: u8, out: &mut Vec<(Ipv4Addr, u8, Route)>) {
+        if let Some(route) = &node.route {
+            out.push((Ipv4Addr::from(bits), depth, route.clone()));
+        }
+        for (bit, child) in node.children.iter().enumerate() {
+            if let Some(child) = child {
+                let child_bits = bits | ((bit as u32) << (31 - depth));
+                Self::walk(child, child_bits, depth + 1, out);
+            }
+        }
+    }
+}
+
+/// Builds the full effective routing table for a lookup or `show ip route`:
+/// `static_routes`' installed entries, plus a freshly-derived `Connected`
+/// route for every administratively-up interface's configured subnet --
+/// recomputed on every call (the same "derive from source-of-truth globals
+/// each time" pattern as [`interface_status_line`]) rather than kept
+/// reactively in sync across every mutation site.
+pub fn effective_routing_table(
+    static_routes: &RoutingTable,
+    ip_address_state: &HashMap<String, (Ipv4Addr, Ipv4Addr)>,
+    status_map: &HashMap<String, AdminState>,
+) -> RoutingTable {
+    let mut table = RoutingTable::new();
+    for (network, prefix_len, route) in static_routes.entries() {
+        table.insert(network, prefix_len, route);
+    }
+    for (interface, (ip_address, netmask)) in ip_address_state.iter() {
+        if status_map.get(interface).copied().unwrap_or(AdminState::Down) != AdminState::Up {
+            continue;
+        }
+        let prefix_len = netmask_to_prefix(*netmask) as u8;
+        let mask_bits = if prefix_len == 0 { 0 } else { !0u32 << (32 - prefix_len) };
+        let network = Ipv4Addr::from(u32::from(*ip_address) & mask_bits);
+        table.insert(
+            network,
+            prefix_len,
+            Route {
+                next_hop: interface.clone(),
+                source: RouteSource::Connected,
+                metric: 0,
+                distance_override: None,
+                tag: None,
+            },
+        );
+    }
+    table
+}
+
+/// Runs [`OSPFConfig::run_spf`] from `ospf_config`'s configured router ID
+/// and installs the resulting routes into `table`, tagged
+/// [`RouteSource::Ospf`] so they coexist with (and lose ties to) connected
+/// and static routes already installed. A no-op if no router ID has been
+/// configured yet, since SPF has no local identity to run from.
+///
+/// Each computed route is first run through `ospf_config.import_filter`
+/// (looked up in [`ROUTE_FILTERS`]), if one is attached -- a route a deny
+/// clause matches never reaches `table`, and a permit clause's set-actions
+/// (administrative distance/metric/tag overrides) are folded into the
+/// installed [`Route`]. With no `import_filter` attached every computed
+/// route is installed unchanged, matching this function's behavior before
+/// route filters existed.
+///
+/// `default_information_originate` additionally gates on
+/// `ospf_config.export_filter`: a default route (`0.0.0.0/0`) is only
+/// injected when either no export filter is attached or the attached one
+/// permits `0.0.0.0/0` (see [`RouteFilter::permits_default_route`]).
+pub fn install_ospf_routes(table: &mut RoutingTable, ospf_config: &OSPFConfig) {
+    let Some(router_id) = ospf_config.router_id.as_deref().and_then(|id| Ipv4Addr::from_str(id).ok()) else {
+        return;
+    };
+    let filters = ROUTE_FILTERS.lock().unwrap();
+    let import_filter = ospf_config.import_filter.as_deref().and_then(|name| filters.get(name));
+    let export_filter = ospf_config.export_filter.as_deref().and_then(|name| filters.get(name));
+
+    for route in ospf_config.run_spf(router_id) {
+        let computed = Route {
+            next_hop: route.next_hops.iter().map(|ip| ip.to_string()).collect::<Vec<_>>().join(", "),
+            source: RouteSource::Ospf,
+            metric: route.cost,
+            distance_override: None,
+            tag: None,
+        };
+        let decision = match import_filter {
+            Some(filter) => filter.apply(route.network, route.prefix_len, &computed),
+            None => FilterDecision::Permit(computed),
+        };
+        if let FilterDecision::Permit(installed) = decision {
+            table.insert(route.network, route.prefix_len, installed);
+        }
+    }
+
+    if ospf_config.default_information_originate
+        && export_filter.map(|filter| filter.permits_default_route()).unwrap_or(true)
+    {
+        table.insert(
+            Ipv4Addr::new(0, 0, 0, 0),
+            0,
+            Route {
+                next_hop: router_id.to_string(),
+                source: RouteSource::Ospf,
+                metric: 1,
+                distance_override: None,
+                tag: None,
+            },
+        );
+    }
+}
+
+/// The lowest and highest VLAN id Cisco IOS accepts in `switchport access
+/// vlan`/`switchport trunk allowed vlan`/`vlan <id>`.
+pub const MIN_VLAN_ID: u16 = 1;
+pub const MAX_VLAN_ID: u16 = 4094;
+
+/// Whether an interface switches traffic for a single VLAN or carries
+/// several over 802.1Q tags, configured via `switchport mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchportMode {
+    Access,
+    Trunk,
+}
+
+/// An interface's Layer 2 switchport configuration: which mode it's in,
+/// its access VLAN (meaningful only in [`SwitchportMode::Access`]), and its
+/// allowed-VLAN set (meaningful only in [`SwitchportMode::Trunk`]) -- an
+/// empty allowed list means "all VLANs", matching a trunk's Cisco default.
+#[derive(Debug, Clone)]
+pub struct SwitchportConfig {
+    pub mode: SwitchportMode,
+    pub access_vlan: u16,
+    pub trunk_allowed_vlans: Vec<u16>,
+}
+
+impl Default for SwitchportConfig {
+    fn default() -> Self {
+        SwitchportConfig {
+            mode: SwitchportMode::Access,
+            access_vlan: 1,
+            trunk_allowed_vlans: Vec::new(),
+        }
+    }
+}
+
+/// The member interfaces of `vlan_id`, derived on demand from every
+/// interface's [`SwitchportConfig`] -- the same "recompute from
+/// source-of-truth globals each time" pattern as
+/// [`effective_routing_table`], rather than a VLAN-to-members map kept
+/// reactively in sync across every `switchport` command.
+pub fn vlan_members(vlan_id: u16, switchport_state: &HashMap<String, SwitchportConfig>) -> Vec<String> {
+    let mut members: Vec<String> = switchport_state
+        .iter()
+        .filter(|(_, config)| match config.mode {
+            SwitchportMode::Access => config.access_vlan == vlan_id,
+            SwitchportMode::Trunk => {
+                config.trunk_allowed_vlans.is_empty() || config.trunk_allowed_vlans.contains(&vlan_id)
+            }
+        })
+        .map(|(interface, _)| interface.clone())
+        .collect();
+    members.sort();
+    members
 }
 
 
 lazy_static::lazy_static! {
 
-    /// A thread-safe, globally accessible state that stores network interface configurations.
-    /// 
-    /// The `NETWORK_STATE` is an `Arc<Mutex<HashMap>>` where:
+    /// A thread-safe, globally accessible state that stores network interface configurations
+    /// as managed by the `ifconfig` command.
+    ///
+    /// The `IFCONFIG_STATE` is an `Arc<Mutex<HashMap>>` where:
     /// - The key is the name of the interface (e.g., "ens33").
-    /// - The value is a tuple containing:
-    ///     - The IPv4 address of the interface.
-    ///     - The broadcast address for the interface, calculated based on the subnet prefix length.
-    /// 
-    /// By default, the `ens33` interface is initialized with the IP `192.168.253.135` 
+    /// - The value is the interface's full [`IfconfigEntry`] record (IPv4/IPv6
+    ///   addresses, MTU, hardware address, and up/down flag).
+    ///
+    /// By default, the `ens33` interface is initialized with the IP `192.168.253.135`
     /// and a subnet prefix of 24.
-    /// 
-    pub static ref IFCONFIG_STATE: Arc<Mutex<HashMap<String, (Ipv4Addr, Ipv4Addr)>>> = Arc::new(Mutex::new({
+    pub static ref IFCONFIG_STATE: Arc<Mutex<HashMap<String, IfconfigEntry>>> = Arc::new(Mutex::new({
         let mut map = HashMap::new();
 
         // Default interface and its configuration
         let default_interface = "ens33".to_string();
         let default_ip = Ipv4Addr::from_str("192.168.253.135").expect("Invalid IP address format");
-        let default_broadcast = calculate_broadcast(default_ip, 24);
-        
-        map.insert(default_interface, (default_ip, default_broadcast));
-        
+
+        map.insert(default_interface, IfconfigEntry::new(default_ip, 24));
+
         map
     }));
 
@@ -61,15 +743,35 @@ lazy_static::lazy_static! {
     /// # Thread Safety
     /// The use of `Arc<Mutex<...>>` ensures that multiple threads can safely
     /// access and modify the map, avoiding race conditions.
-    pub static ref STATUS_MAP: Arc<Mutex<HashMap<String, bool>>> = Arc::new(Mutex::new({
+    pub static ref STATUS_MAP: Arc<Mutex<HashMap<String, AdminState>>> = Arc::new(Mutex::new({
         let mut map = HashMap::new();
-    
+
         // Default interface status (administratively down)
-        map.insert("ens33".to_string(), false); // Modify as per your setup
-    
+        map.insert("ens33".to_string(), AdminState::Down); // Modify as per your setup
+
+        map
+    }));
+
+
+    /// A thread-safe global map that tracks the operational status of network
+    /// interfaces, independently of [`STATUS_MAP`]'s administrative status.
+    ///
+    /// Kept as its own map (rather than folded into `STATUS_MAP`) so an
+    /// interface can be administratively up while its operational state
+    /// lags or diverges -- e.g. a dead lower layer -- the way real routers
+    /// report `line protocol is down` under an `up` admin line.
+    pub static ref OPER_STATE_MAP: Arc<Mutex<HashMap<String, OperState>>> = Arc::new(Mutex::new({
+        let mut map = HashMap::new();
+
+        map.insert("ens33".to_string(), OperState::Down);
+
         map
     }));
 
+    /// Per-interface traffic counters printed by `show interfaces`, keyed
+    /// by interface name and advanced via [`advance_interface_counters`].
+    pub static ref INTERFACE_COUNTERS: Mutex<HashMap<String, InterfaceCounters>> = Mutex::new(HashMap::new());
+
     /// A global, thread-safe state that holds the configuration of network interfaces 
     /// updated via the `ip address` command.
     ///
@@ -88,15 +790,21 @@ lazy_static::lazy_static! {
     pub static ref IP_ADDRESS_STATE: Mutex<HashMap<String, (Ipv4Addr, Ipv4Addr)>> = Mutex::new(HashMap::new());
 
 
-    /// A global, thread-safe container for storing static routing information.
-    ///
-    /// This `Mutex<HashMap<String, (Ipv4Addr, String)>>` is used to hold the static routes in a routing table, 
-    /// where the key is the destination IP address (as a string) and the value is a tuple containing:
-    /// - the network mask (`Ipv4Addr`), 
-    /// - the next-hop IP address or the exit interface (stored as a `String`).
-    /// 
-    /// It is wrapped in a `Mutex` to ensure safe, mutable access from multiple threads.
-    pub static ref ROUTE_TABLE: Mutex<HashMap<String, (Ipv4Addr, String)>> = Mutex::new(HashMap::new());
+    /// The device's static/learned routes, as a longest-prefix-match
+    /// [`RoutingTable`] rather than a flat exact-destination-string map.
+    /// Connected routes aren't stored here -- they're derived on demand by
+    /// [`effective_routing_table`] from [`IP_ADDRESS_STATE`]/[`STATUS_MAP`],
+    /// so an interface's subnet is always reachable without a separate
+    /// insert/remove call keeping it in sync.
+    pub static ref ROUTING_TABLE: Mutex<RoutingTable> = Mutex::new(RoutingTable::new());
+
+
+    /// The device's static IPv6 routes, configured via `ipv6 route`. Kept as
+    /// a flat map rather than [`RoutingTable`]'s bitwise trie -- a 128-bit
+    /// Patricia trie is more machinery than this simulator's IPv6 support
+    /// needs yet, so `show ipv6 route` does an exact-prefix lookup here
+    /// alongside the connected/OSPFv3-derived routes it already prints.
+    pub static ref ROUTE_TABLE_V6: Mutex<HashMap<(Ipv6Addr, u8), Route>> = Mutex::new(HashMap::new());
 
 
     /// A global configuration for the OSPF (Open Shortest Path First) protocol, 
@@ -112,6 +820,36 @@ lazy_static::lazy_static! {
     pub static ref OSPF_CONFIG: Mutex<OSPFConfig> = Mutex::new(OSPFConfig::new());
 
 
+    /// A global configuration for the OSPFv3 process, entered via `ipv6
+    /// router ospf <process-id>`, wrapped in a `Mutex` to allow safe
+    /// concurrent access. Mirrors [`OSPF_CONFIG`], but tracks areas by
+    /// interface (via each interface's [`InterfaceConfig::ospfv3_area`])
+    /// rather than by network statement, since OSPFv3 enables routing
+    /// directly on an interface instead of matching network ranges.
+    pub static ref OSPFV3_CONFIG: Mutex<OSPFv3Config> = Mutex::new(OSPFv3Config::new());
+
+
+    /// A global configuration for the BGP (Border Gateway Protocol) process,
+    /// entered via `router bgp <asn>`, wrapped in a `Mutex` to allow safe
+    /// concurrent access. Mirrors [`OSPF_CONFIG`]: `asn` being `None` means
+    /// no BGP process has been configured yet.
+    pub static ref BGP_CONFIG: Mutex<BGPConfig> = Mutex::new(BGPConfig::new());
+
+
+    /// A global configuration for the RIP (Routing Information Protocol)
+    /// process, entered via `router rip`, wrapped in a `Mutex` to allow safe
+    /// concurrent access. Mirrors [`OSPF_CONFIG`]: `enabled` being `false`
+    /// means `router rip` has not been entered yet.
+    pub static ref RIP_CONFIG: Mutex<RIPConfig> = Mutex::new(RIPConfig::new());
+
+
+    /// A global configuration for the IS-IS process, entered via
+    /// `router isis <tag>`, wrapped in a `Mutex` to allow safe concurrent
+    /// access. Mirrors [`OSPF_CONFIG`]: `tag` being `None` means `router
+    /// isis` has not been entered yet.
+    pub static ref ISIS_CONFIG: Mutex<ISISConfig> = Mutex::new(ISISConfig::new());
+
+
     /// A global store for access control lists (ACLs), wrapped in a `Mutex` to ensure thread-safe access.
     ///
     /// This `ACL_STORE` holds a collection of ACLs, indexed by a unique string identifier (either by name or number). 
@@ -123,6 +861,14 @@ lazy_static::lazy_static! {
     ///
     pub static ref ACL_STORE: Mutex<HashMap<String, AccessControlList>> = Mutex::new(HashMap::new());
 
+    /// A global store for named [`RouteFilter`]s (BIRD-style import/export
+    /// filters, Cisco-style route-maps), indexed by name, wrapped in a
+    /// `Mutex` like [`ACL_STORE`] for safe concurrent access. [`OSPFConfig`]
+    /// attaches a filter by name via `import_filter`/`export_filter` rather
+    /// than embedding it, so the same filter can be shared across
+    /// redistribution points.
+    pub static ref ROUTE_FILTERS: Mutex<HashMap<String, RouteFilter>> = Mutex::new(HashMap::new());
+
 
     /// A static, thread-safe reference to a `PasswordStore` instance, protected by a `Mutex`.
     /// 
@@ -138,6 +884,163 @@ lazy_static::lazy_static! {
     /// ```
     pub static ref PASSWORD_STORAGE: Mutex<PasswordStore> = Mutex::new(PasswordStore::default());
 
+
+    /// A thread-safe global map holding the data-link layer configuration
+    /// (`encapsulation`, `ppp authentication`, `ppp multilink`, `compress`,
+    /// `ppp quality`) of each interface, keyed by interface name.
+    ///
+    /// Kept as its own map, alongside [`IP_ADDRESS_STATE`] and [`STATUS_MAP`],
+    /// rather than folded into either of them, since it is populated lazily
+    /// only once an operator actually configures a link-layer setting.
+    pub static ref LINK_CONFIG_STATE: Mutex<HashMap<String, InterfaceConfig>> = Mutex::new(HashMap::new());
+
+
+    /// A thread-safe global map of GRE/IPIP tunnel overlay settings
+    /// (`tunnel mode`/`source`/`destination`/`key`/`ttl`), keyed by the
+    /// tunnel's interface name, configured under `interface tunnel <n>`.
+    /// Kept separate from [`LINK_CONFIG_STATE`] since only tunnel
+    /// interfaces ever populate it; the tunnel's overlay IP address still
+    /// lives in [`IP_ADDRESS_STATE`] like any other interface's.
+    pub static ref TUNNEL_CONFIG: Mutex<HashMap<String, TunnelInterface>> = Mutex::new(HashMap::new());
+
+
+    /// The simulated SNMP agent's packet counters, advanced by
+    /// [`advance_snmp_stats`] each time `show snmp` is displayed.
+    pub static ref SNMP_STATS: Mutex<SnmpStats> = Mutex::new(SnmpStats::default());
+
+
+    /// A thread-safe global map of local usernames to their SHA-256 hashed
+    /// password, configured via the global `username <name> password <pass>`
+    /// command. Backs PPP PAP/CHAP authentication and, like the enable
+    /// secret in [`PASSWORD_STORAGE`], stores only the hash.
+    pub static ref USER_CREDENTIALS: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+
+
+    /// A thread-safe global holding the `line vty` configuration: which
+    /// line range is configured, which transports (`telnet`/`ssh`/`all`)
+    /// it accepts, and whether it authenticates against [`USER_CREDENTIALS`]
+    /// (`login local`). A single instance, like [`OSPF_CONFIG`], since this
+    /// simulator models one vty line range at a time.
+    pub static ref VTY_CONFIG: Mutex<VtyConfig> = Mutex::new(VtyConfig::default());
+
+    /// A thread-safe global map of each interface's Layer 2 switchport
+    /// configuration (`switchport mode`/`switchport access vlan`/
+    /// `switchport trunk allowed vlan`), keyed by interface name. An
+    /// interface absent from this map has never had `switchport` configured
+    /// and is treated as an access port on VLAN 1, Cisco's default.
+    pub static ref SWITCHPORT_STATE: Mutex<HashMap<String, SwitchportConfig>> = Mutex::new(HashMap::new());
+
+    /// A thread-safe global map of each interface's NAT role, configured via
+    /// `ip nat inside` / `ip nat outside` in Interface Configuration mode. An
+    /// interface absent from this map has no NAT role.
+    pub static ref NAT_INTERFACE_ROLE: Mutex<HashMap<String, NatSide>> = Mutex::new(HashMap::new());
+
+    /// Static one-to-one mappings configured via `ip nat inside source
+    /// static <local> <global>`.
+    pub static ref NAT_STATIC_MAPPINGS: Mutex<Vec<NatStaticMapping>> = Mutex::new(Vec::new());
+
+    /// PAT overload rules configured via `ip nat inside source list <acl>
+    /// interface <interface> overload`.
+    pub static ref NAT_OVERLOAD_RULES: Mutex<Vec<NatOverloadRule>> = Mutex::new(Vec::new());
+
+    /// The translation table printed by `show ip nat translations`, rebuilt
+    /// from [`NAT_STATIC_MAPPINGS`]/[`NAT_OVERLOAD_RULES`] by
+    /// [`rebuild_nat_translations`] every time NAT configuration changes --
+    /// the same "recompute from source of truth" pattern used by
+    /// [`effective_routing_table`].
+    pub static ref NAT_TRANSLATIONS: Mutex<Vec<NatTranslation>> = Mutex::new(Vec::new());
+
+}
+
+/// Rebuilds [`NAT_TRANSLATIONS`] from [`NAT_STATIC_MAPPINGS`] and
+/// [`NAT_OVERLOAD_RULES`], resolving each overload rule's ACL against
+/// [`ACL_STORE`] and its outside interface's address against
+/// [`IP_ADDRESS_STATE`]. An overload rule referencing an ACL or interface
+/// that doesn't exist (yet) contributes no rows rather than erroring, the
+/// same tolerance [`effective_routing_table`] shows a route with an unknown
+/// next hop.
+pub fn rebuild_nat_translations() {
+    let static_mappings = NAT_STATIC_MAPPINGS.lock().unwrap();
+    let overload_rules = NAT_OVERLOAD_RULES.lock().unwrap();
+    let acl_store = ACL_STORE.lock().unwrap();
+    let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
+
+    let mut translations = static_translations(&static_mappings);
+    for rule in overload_rules.iter() {
+        let Some(acl) = acl_store.get(&rule.acl) else { continue };
+        let Some((outside_address, _)) = ip_address_state.get(&rule.interface) else { continue };
+        translations.extend(overload_translations(acl, *outside_address));
+    }
+
+    *NAT_TRANSLATIONS.lock().unwrap() = translations;
+}
+
+/// The transports a `line vty` accepts, configured via `transport input`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportInput {
+    Telnet,
+    Ssh,
+    All,
+}
+
+impl TransportInput {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransportInput::Telnet => "telnet",
+            TransportInput::Ssh => "ssh",
+            TransportInput::All => "all",
+        }
+    }
+}
+
+impl std::fmt::Display for TransportInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The `line vty <start> <end>` configuration: the line range, the
+/// transports `transport input` allows, and whether `login local` is set.
+#[derive(Debug, Clone)]
+pub struct VtyConfig {
+    pub line_range: Option<(u32, u32)>,
+    pub transport_input: TransportInput,
+    pub login_local: bool,
+}
+
+impl Default for VtyConfig {
+    fn default() -> Self {
+        Self {
+            line_range: None,
+            transport_input: TransportInput::Telnet,
+            login_local: false,
+        }
+    }
+}
+
+
+/// Sets the password for a local user in [`USER_CREDENTIALS`], hashing it
+/// with [`encrypt_password`] (unsalted SHA-256), so it can be checked by
+/// [`verify_user_password`]'s plain hash comparison.
+///
+/// # Parameters
+/// - `username`: The local username to set or update.
+/// - `password`: The plaintext password to hash and store.
+pub fn set_user_password(username: &str, password: &str) {
+    let mut credentials = USER_CREDENTIALS.lock().unwrap();
+    credentials.insert(username.to_string(), encrypt_password(password));
+}
+
+/// Verifies a plaintext password against the hash stored for `username` in
+/// [`USER_CREDENTIALS`].
+///
+/// # Returns
+/// `true` if `username` is known and `password` hashes to the stored value.
+pub fn verify_user_password(username: &str, password: &str) -> bool {
+    let credentials = USER_CREDENTIALS.lock().unwrap();
+    credentials
+        .get(username)
+        .map_or(false, |stored_hash| *stored_hash == encrypt_password(password))
 }
 
 
@@ -165,6 +1068,17 @@ pub fn calculate_broadcast(ip: Ipv4Addr, prefix_len: u32) -> Ipv4Addr {
     Ipv4Addr::from(broadcast_u32)           // Convert back to an Ipv4Addr
 }
 
+/// Prefix-aware broadcast calculation that also accepts IPv6: IPv4 gets a
+/// real broadcast address via [`calculate_broadcast`], while IPv6 has no
+/// broadcast concept at all (RFC 4291 replaced it with multicast), so this
+/// returns `None` for a [`IpAddr::V6`] rather than fabricating one.
+pub fn calculate_broadcast_for(ip: IpAddr, prefix_len: u8) -> Option<IpAddr> {
+    match ip {
+        IpAddr::V4(v4) => Some(IpAddr::V4(calculate_broadcast(v4, prefix_len as u32))),
+        IpAddr::V6(_) => None,
+    }
+}
+
 
 /// Encrypts a password using the SHA-256 hashing algorithm.
 ///
@@ -181,7 +1095,69 @@ pub fn encrypt_password(password: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(password);
     let result = hasher.finalize();
-    format!("{:x}", result)  
+    format!("{:x}", result)
+}
+
+/// scrypt cost parameters for [`hash_secret`], matching Cisco "Type 9"
+/// (`N=16384, r=1, p=1`).
+const SCRYPT_LOG_N: u8 = 14;
+const SCRYPT_R: u32 = 1;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_OUTPUT_LEN: usize = 32;
+
+/// Alphabet Cisco draws `enable secret`'s Type 9 salt from.
+const SALT_ALPHABET: &[u8] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Generates a fresh 14-character salt from [`SALT_ALPHABET`].
+fn generate_salt() -> String {
+    let mut rng = rand::thread_rng();
+    (0..14)
+        .map(|_| SALT_ALPHABET[rng.gen_range(0..SALT_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Compares two byte strings in constant time, so a mismatching hash can't
+/// leak how many leading bytes matched through a timing side channel.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Hashes `secret` Cisco "Type 9" style: scrypt with `N=16384, r=1, p=1`
+/// over a fresh 14-character salt, stored as `$9$<salt>$<base64-hash>` so
+/// the parameters needed to re-derive it travel with the hash itself,
+/// replacing [`encrypt_password`]'s unsalted single SHA-256 pass for
+/// `PasswordStore.enable_secret`.
+pub fn hash_secret(secret: &str) -> String {
+    let salt = generate_salt();
+    let params = Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, SCRYPT_OUTPUT_LEN)
+        .expect("fixed scrypt parameters are always valid");
+    let mut output = [0u8; SCRYPT_OUTPUT_LEN];
+    scrypt(secret.as_bytes(), salt.as_bytes(), &params, &mut output)
+        .expect("fixed-size output buffer always satisfies scrypt's length limit");
+    format!("$9${}${}", salt, BASE64.encode(output))
+}
+
+/// Verifies `candidate` against a `stored` secret from `PasswordStore.enable_secret`.
+/// Handles both a [`hash_secret`] Type 9 string and, for configs saved
+/// before Type 9 existed, a legacy bare-hex [`encrypt_password`] digest.
+/// Returns `false` for a `stored` value in neither shape, rather than
+/// erroring -- a corrupted or foreign value simply never matches.
+pub fn verify_secret(candidate: &str, stored: &str) -> bool {
+    if let Some(rest) = stored.strip_prefix("$9$") {
+        let Some((salt, encoded_hash)) = rest.split_once('$') else { return false };
+        let Ok(expected) = BASE64.decode(encoded_hash) else { return false };
+        let Ok(params) = Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, expected.len()) else { return false };
+        let mut output = vec![0u8; expected.len()];
+        if scrypt(candidate.as_bytes(), salt.as_bytes(), &params, &mut output).is_err() {
+            return false;
+        }
+        constant_time_eq(&output, &expected)
+    } else {
+        constant_time_eq(encrypt_password(candidate).as_bytes(), stored.as_bytes())
+    }
 }
 
 
@@ -202,8 +1178,13 @@ pub fn encrypt_password(password: &str) -> String {
 /// - `networks`: A mapping of network prefixes to their associated subnet masks.
 /// - `neighbors`: A mapping of OSPF neighbor IPv4 addresses to their optional priority values.
 /// - `process_id`: An optional identifier for the OSPF routing process.
+/// - `import_filter`: The name of a [`RouteFilter`] in [`ROUTE_FILTERS`] applied to routes SPF
+///   computes before they're installed into the routing table, or `None` to accept every
+///   computed route.
+/// - `export_filter`: The name of a [`RouteFilter`] in [`ROUTE_FILTERS`] that gates default-route
+///   origination (see [`install_ospf_routes`]), or `None` to impose no restriction.
 ///
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OSPFConfig {
     pub passive_interfaces: Vec<String>,
     pub distance: Option<u32>,
@@ -213,6 +1194,8 @@ pub struct OSPFConfig {
     pub networks: HashMap<String, u32>,
     pub neighbors: HashMap<Ipv4Addr, Option<u32>>,
     pub process_id: Option<u32>,
+    pub import_filter: Option<String>,
+    pub export_filter: Option<String>,
 }
 
 
@@ -226,7 +1209,7 @@ pub struct OSPFConfig {
 /// - `stub`: Indicates whether this area is configured as a stub area.
 /// - `default_cost`: An optional cost value for routes advertised into this stub area.
 ///
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AreaConfig {
     pub authentication: bool,
     pub stub: bool,
@@ -258,6 +1241,297 @@ impl OSPFConfig {
             networks: HashMap::new(),
             neighbors: HashMap::new(),
             process_id: None,
+            import_filter: None,
+            export_filter: None,
+        }
+    }
+}
+
+impl Default for OSPFConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts an OSPF wildcard mask (the inverse of a netmask, as given to
+/// `network <ip> <wildcard-mask> area <area-id>`) to its CIDR prefix length.
+fn wildcard_to_prefix_len(wildcard: Ipv4Addr) -> u8 {
+    (32 - u32::from(wildcard).count_ones()) as u8
+}
+
+/// A node in the link-state graph [`OSPFConfig::run_spf`] builds: either a
+/// router -- the local router or a neighbor, identified by router
+/// ID/address -- or a network one or more routers are directly attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SpfNode {
+    Router(Ipv4Addr),
+    Network(Ipv4Addr, u8),
+}
+
+/// One route computed by [`OSPFConfig::run_spf`]: the destination prefix,
+/// its total SPF cost from the local router, and every equal-cost next-hop
+/// toward it (ECMP).
+#[derive(Debug, Clone)]
+pub struct OspfRoute {
+    pub network: Ipv4Addr,
+    pub prefix_len: u8,
+    pub cost: u32,
+    pub next_hops: Vec<Ipv4Addr>,
+}
+
+impl OSPFConfig {
+    /// Runs Dijkstra's algorithm over this process's link-state graph, from
+    /// `local_router_id`, to compute the shortest-cost route toward every
+    /// other router and every OSPF-enabled network it can reach.
+    ///
+    /// The graph's nodes are the local router and each configured
+    /// `neighbor`, plus every network enabled for OSPF via `network ...
+    /// area <area-id>`; an edge joins the local router to each of its
+    /// networks at that network's area cost (`AreaConfig::default_cost`,
+    /// defaulting to `1`), and joins a network to any neighbor whose
+    /// address falls inside it, at cost 0. A binary-heap priority
+    /// queue keyed by cumulative cost relaxes neighbors in the usual
+    /// Dijkstra fashion; a reachable network's route then lists every
+    /// reachable router attached to it as an equal-cost next-hop (ECMP),
+    /// a network with no neighbor on it gets no route, and a neighbor with
+    /// no path back to the local router (a disconnected graph) is simply
+    /// absent from the result.
+    pub fn run_spf(&self, local_router_id: Ipv4Addr) -> Vec<OspfRoute> {
+        let area_cost = |area_id: u32| -> u32 {
+            self.areas
+                .get(&area_id.to_string())
+                .and_then(|area| area.default_cost)
+                .unwrap_or(1)
+        };
+
+        // Directed edges, mirroring a real router LSA's link to each
+        // attached network (at the interface/area cost) and a network
+        // LSA's link back down to every router on it (cost 0, the
+        // transit-network pseudonode OSPF itself uses).
+        let mut adjacency: HashMap<SpfNode, Vec<(SpfNode, u32)>> = HashMap::new();
+        for (key, &area_id) in &self.networks {
+            let mut parts = key.split_whitespace();
+            let (Some(ip_str), Some(wildcard_str)) = (parts.next(), parts.next()) else { continue };
+            let (Ok(ip), Ok(wildcard)) = (Ipv4Addr::from_str(ip_str), Ipv4Addr::from_str(wildcard_str)) else { continue };
+            let prefix_len = wildcard_to_prefix_len(wildcard);
+            let mask = if prefix_len == 0 { 0 } else { !0u32 << (32 - prefix_len) };
+            let network = Ipv4Addr::from(u32::from(ip) & mask);
+            let network_node = SpfNode::Network(network, prefix_len);
+
+            adjacency
+                .entry(SpfNode::Router(local_router_id))
+                .or_insert_with(Vec::new)
+                .push((network_node, area_cost(area_id)));
+
+            for &neighbor_ip in self.neighbors.keys() {
+                if (u32::from(neighbor_ip) & mask) == u32::from(network) {
+                    adjacency.entry(network_node).or_insert_with(Vec::new).push((SpfNode::Router(neighbor_ip), 0));
+                }
+            }
+        }
+
+        let source = SpfNode::Router(local_router_id);
+        let mut dist: HashMap<SpfNode, u32> = HashMap::new();
+        dist.insert(source, 0);
+        let mut heap = std::collections::BinaryHeap::new();
+        heap.push(std::cmp::Reverse((0u32, source)));
+
+        while let Some(std::cmp::Reverse((cost, node))) = heap.pop() {
+            if cost > *dist.get(&node).unwrap_or(&u32::MAX) {
+                continue;
+            }
+            for &(next, edge_cost) in adjacency.get(&node).into_iter().flatten() {
+                let new_cost = cost + edge_cost;
+                if new_cost < *dist.get(&next).unwrap_or(&u32::MAX) {
+                    dist.insert(next, new_cost);
+                    heap.push(std::cmp::Reverse((new_cost, next)));
+                }
+            }
+        }
+
+        // Every reachable neighbor sits exactly one transit network away
+        // from the local router, so its own address is always the
+        // next-hop; a neighbor attached via more than one equal-cost
+        // network simply has that one next-hop counted once (retained as
+        // a single-element ECMP set rather than deduplication losing the
+        // "all equal-cost next-hops" guarantee).
+        //
+        // A reachable `SpfNode::Network` gets a route too, with its
+        // next-hop(s) resolved from `adjacency` to the routers the graph
+        // attaches it to -- never the network's own address, which isn't
+        // forwardable -- so a neighbor's whole subnet is reachable, not
+        // just the neighbor's own router ID. A network with no other
+        // router on it (a local stub network with nothing attached) has
+        // no such router to resolve and is left for the connected-route
+        // table to cover instead.
+        let mut routes: Vec<OspfRoute> = Vec::new();
+        for (&node, &cost) in &dist {
+            match node {
+                SpfNode::Router(router_id) if router_id != local_router_id => {
+                    routes.push(OspfRoute { network: router_id, prefix_len: 32, cost, next_hops: vec![router_id] });
+                }
+                SpfNode::Network(network, prefix_len) => {
+                    let mut next_hops: Vec<Ipv4Addr> = adjacency
+                        .get(&node)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|&(next, _)| match next {
+                            SpfNode::Router(router_id) if dist.contains_key(&SpfNode::Router(router_id)) => Some(router_id),
+                            _ => None,
+                        })
+                        .collect();
+                    next_hops.sort();
+                    next_hops.dedup();
+                    if !next_hops.is_empty() {
+                        routes.push(OspfRoute { network, prefix_len, cost, next_hops });
+                    }
+                }
+                _ => {}
+            }
+        }
+        routes.sort_by_key(|route| route.network);
+        routes
+    }
+}
+
+
+/// Configuration for the OSPFv3 (OSPF for IPv6) routing protocol, entered
+/// via `ipv6 router ospf <process-id>`.
+///
+/// Unlike [`OSPFConfig`], OSPFv3 has no `network` statement -- an interface
+/// is enrolled directly with `ipv6 ospf <process-id> area <area-id>`, so the
+/// area assignment lives on [`InterfaceConfig::ospfv3_area`] instead of a
+/// `networks` map here.
+///
+/// # Fields
+/// - `process_id`: An optional process ID for the OSPFv3 instance.
+/// - `router_id`: An optional router ID used in the OSPFv3 process.
+#[derive(Debug, Clone)]
+pub struct OSPFv3Config {
+    pub process_id: Option<u32>,
+    pub router_id: Option<String>,
+}
+
+impl OSPFv3Config {
+    pub fn new() -> Self {
+        Self {
+            process_id: None,
+            router_id: None,
+        }
+    }
+}
+
+
+/// Represents the configuration for a BGP (Border Gateway Protocol) process,
+/// entered via `router bgp <asn>`.
+///
+/// # Fields
+/// - `asn`: The autonomous system number the process was configured with, or `None` if `router bgp` hasn't run yet.
+/// - `neighbors`: A `HashMap` of neighbor IP addresses to their configured remote AS number.
+/// - `neighbor_descriptions`: A `HashMap` of neighbor IP addresses to their `neighbor <ip> description` text, kept separate from `neighbors` so a description can be set before or after the `remote-as` is known.
+/// - `networks`: A `HashMap` mapping an advertised network prefix to its mask.
+/// - `redistribute_ospf`: Whether `redistribute ospf` has been configured.
+/// - `redistribute_connected`: Whether `redistribute connected` has been configured.
+/// - `redistribute_static`: Whether `redistribute static` has been configured.
+#[derive(Debug, Clone)]
+pub struct BGPConfig {
+    pub asn: Option<u32>,
+    pub neighbors: HashMap<Ipv4Addr, u32>,
+    pub neighbor_descriptions: HashMap<Ipv4Addr, String>,
+    pub networks: HashMap<String, String>,
+    pub redistribute_ospf: bool,
+    pub redistribute_connected: bool,
+    pub redistribute_static: bool,
+}
+
+impl BGPConfig {
+    pub fn new() -> Self {
+        Self {
+            asn: None,
+            neighbors: HashMap::new(),
+            neighbor_descriptions: HashMap::new(),
+            networks: HashMap::new(),
+            redistribute_ospf: false,
+            redistribute_connected: false,
+            redistribute_static: false,
+        }
+    }
+}
+
+
+/// Represents the configuration for a RIP (Routing Information Protocol)
+/// process, entered via `router rip`.
+///
+/// # Fields
+/// - `enabled`: Whether `router rip` has been configured.
+/// - `version`: The RIP version configured via `version {1 | 2}`. Defaults to `1`.
+/// - `networks`: The classful networks advertised via `network <classful-address>`.
+/// - `auto_summary`: Whether automatic route summarization is enabled. Defaults to `true`, matching Cisco IOS.
+#[derive(Debug, Clone)]
+pub struct RIPConfig {
+    pub enabled: bool,
+    pub version: u8,
+    pub networks: Vec<String>,
+    pub auto_summary: bool,
+}
+
+impl RIPConfig {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            version: 1,
+            networks: Vec::new(),
+            auto_summary: true,
+        }
+    }
+}
+
+
+/// The IS-IS level(s) a process operates at, configured via `is-type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsisLevel {
+    Level1,
+    Level2,
+    Level1Level2,
+}
+
+impl IsisLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IsisLevel::Level1 => "level-1",
+            IsisLevel::Level2 => "level-2",
+            IsisLevel::Level1Level2 => "level-1-2",
+        }
+    }
+}
+
+impl std::fmt::Display for IsisLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+
+/// Represents the configuration for an IS-IS process, entered via
+/// `router isis <tag>`.
+///
+/// # Fields
+/// - `tag`: The process tag it was configured with, or `None` if `router isis` hasn't run yet.
+/// - `net`: The Network Entity Title configured via `net <nsap>`.
+/// - `is_type`: The level(s) the process operates at, configured via `is-type`. Defaults to `Level1Level2`, matching Cisco IOS.
+#[derive(Debug, Clone)]
+pub struct ISISConfig {
+    pub tag: Option<String>,
+    pub net: Option<String>,
+    pub is_type: IsisLevel,
+}
+
+impl ISISConfig {
+    pub fn new() -> Self {
+        Self {
+            tag: None,
+            net: None,
+            is_type: IsisLevel::Level1Level2,
         }
     }
 }
@@ -280,7 +1554,7 @@ impl OSPFConfig {
 /// - `destination_operator`: An optional operator (e.g., "gt", "lt") for comparing destination values.
 /// - `destination_port`: An optional destination port to match, typically used with TCP or UDP.
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AclEntry {
     pub action: String,
     pub source: String,
@@ -303,7 +1577,7 @@ pub struct AclEntry {
 /// - `number_or_name`: The unique identifier for the ACL, either as a number or a name.
 /// - `entries`: A list of [`AclEntry`] objects, each representing a specific rule in the ACL.
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessControlList {
     pub number_or_name: String,
     pub entries: Vec<AclEntry>,
@@ -314,7 +1588,7 @@ pub struct AccessControlList {
 /// 
 /// This structure holds information related to the NTP association, such as the server's
 /// address, reference clock, synchronization status, and time offset values.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct NtpAssociation {
     pub address: String,
     pub ref_clock: String,
@@ -325,6 +1599,106 @@ pub struct NtpAssociation {
     pub delay: f64,
     pub offset: f64,
     pub disp: f64,
+    /// The authentication key number bound via `ntp server <ip> key <n>`,
+    /// or `None` if the server was configured without one.
+    pub key_id: Option<u32>,
+    /// Whether this association's `key_id` verified against the device's
+    /// configured keys the last time [`verify_ntp_association`] ran, per
+    /// RFC 5905 symmetric-key authentication.
+    pub authenticated: bool,
+}
+
+/// Advances one simulated NTP poll for `assoc`.
+///
+/// Models `reach` as an 8-bit shift register: every simulated poll shifts
+/// it left by one and ORs in a 1, since this simulator always gets a
+/// reply, so `reach` climbs 1 -> 3 -> 7 -> ... -> 377 octal (0xFF) over the
+/// first eight polls and then holds there. Once `reach` is non-zero the
+/// association is considered synchronized, so `st` is set to
+/// `peer_stratum + 1` and `ref_clock`/`delay`/`offset`/`disp`/`when` are
+/// filled in -- `.LOCL.` for the device's own reference clock (`ntp
+/// master`), otherwise the peer's address. The delay/offset/disp values
+/// are derived from the address so they stay stable across repeated
+/// `show ntp associations` calls instead of jumping around on every poll.
+pub fn advance_ntp_poll(assoc: &mut NtpAssociation, peer_stratum: u8, local_clock: bool) {
+    assoc.reach = ((assoc.reach << 1) | 1) & 0xFF;
+    if assoc.reach != 0 {
+        assoc.st = peer_stratum + 1;
+        assoc.ref_clock = if local_clock {
+            ".LOCL.".to_string()
+        } else {
+            assoc.address.clone()
+        };
+        let seed = assoc
+            .address
+            .bytes()
+            .fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64));
+        assoc.delay = ((seed % 50) as f64) / 10.0 + 1.0;
+        assoc.offset = (((seed / 50) % 20) as f64) / 10.0 - 1.0;
+        assoc.disp = ((seed % 10) as f64) / 100.0 + 0.01;
+        assoc.when = ((seed % 64) + 1).to_string();
+    }
+}
+
+/// Builds the deterministic 48-byte NTP header MAC'd over by
+/// [`verify_ntp_association`], in lieu of a real packet -- this emulator
+/// doesn't exchange NTP datagrams -- so the same association produces the
+/// same header across repeated `show ntp associations` calls: the
+/// association's address bytes repeated out to the header length, nudged
+/// by its stratum so distinct associations don't collide.
+fn ntp_packet_header(assoc: &NtpAssociation) -> [u8; ntp_auth::NTP_AUTH_HEADER_LEN] {
+    let mut header = [0u8; ntp_auth::NTP_AUTH_HEADER_LEN];
+    let address_bytes = assoc.address.as_bytes();
+    for (i, byte) in header.iter_mut().enumerate() {
+        *byte = address_bytes[i % address_bytes.len().max(1)];
+    }
+    header[0] = header[0].wrapping_add(assoc.st);
+    header
+}
+
+/// Simulates assembling then verifying an authenticated NTP packet for
+/// `assoc`, setting `assoc.authenticated` from real MD5/HMAC-SHA1
+/// verification rather than the mere presence of a key string: a key id
+/// must be both configured in `keys` and listed in `trusted_keys` for the
+/// association to authenticate, per RFC 5905.
+pub fn verify_ntp_association(
+    assoc: &mut NtpAssociation,
+    keys: &HashMap<u32, NtpAuthKey>,
+    trusted_keys: &HashSet<u32>,
+) {
+    assoc.authenticated = match assoc.key_id {
+        Some(key_id) => match keys.get(&key_id) {
+            Some(auth_key) => {
+                let header = ntp_packet_header(assoc);
+                let trailer = ntp_auth::build_trailer(key_id, auth_key.algorithm, &auth_key.key, &header);
+                ntp_auth::verify_trailer(&trailer, &header, keys, trusted_keys)
+            }
+            None => false,
+        },
+        None => false,
+    };
+}
+
+
+/// Simulated SNMP agent packet counters, printed by `show snmp`. There is
+/// no real SNMP traffic in this simulator, so [`advance_snmp_stats`] bumps
+/// them by a small fixed amount each time they're displayed, the same
+/// "one simulated tick per `show`" idea as [`advance_ntp_poll`].
+#[derive(Debug, Clone, Default)]
+pub struct SnmpStats {
+    pub packets_in: u64,
+    pub packets_out: u64,
+    pub get_requests: u64,
+    pub get_nexts: u64,
+    pub bad_community_errors: u64,
+}
+
+/// Advances the simulated SNMP counters by one poll's worth of traffic.
+pub fn advance_snmp_stats(stats: &mut SnmpStats) {
+    stats.packets_in += 3;
+    stats.packets_out += 3;
+    stats.get_requests += 2;
+    stats.get_nexts += 1;
 }
 
 
@@ -358,6 +1732,7 @@ pub struct NtpAssociation {
 /// # Usage
 /// This struct can be used to store and retrieve passwords securely within a CLI context. 
 /// You can initialize it with default values or specify the passwords during creation.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PasswordStore {
     pub enable_password: Option<String>,
     pub enable_secret: Option<String>,
@@ -389,14 +1764,17 @@ pub fn set_enable_password(password: &str) {
 
 
 /// Sets the enable secret in the `PasswordStore`.
-/// 
-/// This function updates the stored `enable_secret` to the provided value.
+///
+/// The secret is never stored in the clear: it's hashed via [`hash_secret`]
+/// (Cisco Type 9 / scrypt) before being stored, so the value in
+/// `PasswordStore.enable_secret` is always a `$9$<salt>$<hash>` string.
+/// Callers verifying a login attempt against it should use [`verify_secret`].
 ///
 /// # Parameters
-/// - `secret`: A reference to the secret string to set as the enable secret.
+/// - `secret`: A reference to the plaintext secret to hash and store.
 pub fn set_enable_secret(secret: &str) {
     let mut storage = PASSWORD_STORAGE.lock().unwrap();
-    storage.enable_secret = Some(secret.to_string());
+    storage.enable_secret = Some(hash_secret(secret));
 }
 
 
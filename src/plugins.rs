@@ -0,0 +1,343 @@
+//! Discovery and execution of external `pnfbox-<name>` subcommands, found on
+//! `$PATH` or in an optional plugin directory -- the same convention `cargo`
+//! uses for `cargo-<name>` executables. This lets the shell gain new
+//! top-level commands without recompiling the crate.
+//!
+//! Alongside that convention, this module also supports a richer JSON-RPC
+//! plugin protocol (see [`discover_rpc_plugins`]) for plugins that want to
+//! declare their own description/suggestions/allowed modes instead of
+//! showing up as a bare `pnfbox-<name>` passthrough.
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command as ProcessCommand, Stdio};
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::cliconfig::CliContext;
+use crate::execute::Mode;
+
+const PLUGIN_PREFIX: &str = "pnfbox-";
+
+/// Directory (relative to the working directory) scanned for JSON-RPC
+/// plugin executables at startup, in addition to `PNFBOX_PLUGIN_DIR` if set.
+const RPC_PLUGIN_DIR: &str = "plugins";
+
+/// The `describe` handshake a JSON-RPC plugin executable must answer on
+/// startup, over a single line of JSON written to its stdin.
+#[derive(Serialize)]
+struct DescribeRequest {
+    jsonrpc: &'static str,
+    method: &'static str,
+    id: u32,
+}
+
+/// A plugin's self-reported metadata, read back as the single-line JSON
+/// `result` of the `describe` handshake.
+#[derive(Clone, Deserialize)]
+struct DescribeResult {
+    name: String,
+    description: String,
+    #[serde(default)]
+    suggestions: Vec<String>,
+    #[serde(default)]
+    allowed_modes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct DescribeResponse {
+    result: Option<DescribeResult>,
+    error: Option<String>,
+}
+
+/// Everything learned about a JSON-RPC plugin during discovery, kept around
+/// so `describe`/`help` output and mode checks don't need to re-spawn it.
+#[derive(Clone)]
+pub struct RpcPluginInfo {
+    pub path: PathBuf,
+    pub description: String,
+    pub suggestions: Vec<String>,
+    pub allowed_modes: Vec<Mode>,
+}
+
+lazy_static! {
+    /// JSON-RPC plugins discovered by [`discover_rpc_plugins`] at startup,
+    /// keyed by each executable's file name; the `name` a plugin reports in
+    /// its `describe` response is not used as the key.
+    static ref RPC_PLUGINS: RwLock<std::collections::HashMap<String, RpcPluginInfo>> =
+        RwLock::new(std::collections::HashMap::new());
+}
+
+/// Parses a plugin's `allowed_modes` strings (e.g. `"ConfigMode"`) into
+/// [`Mode`]; unrecognized names (including the parameterized ACL modes,
+/// which a plugin has no way to name a specific list for) are skipped.
+fn parse_mode_name(name: &str) -> Option<Mode> {
+    match name {
+        "UserMode" => Some(Mode::UserMode),
+        "PrivilegedMode" => Some(Mode::PrivilegedMode),
+        "ConfigMode" => Some(Mode::ConfigMode),
+        "InterfaceMode" => Some(Mode::InterfaceMode),
+        "VlanMode" => Some(Mode::VlanMode),
+        "RouterConfigMode" => Some(Mode::RouterConfigMode),
+        _ => None,
+    }
+}
+
+/// Sends the `describe` handshake to the plugin executable at `path` and
+/// parses its response, returning `None` on any I/O error, malformed JSON,
+/// or an explicit `error` field -- discovery simply skips plugins that don't
+/// answer correctly rather than failing startup.
+fn describe_plugin(path: &Path) -> Option<RpcPluginInfo> {
+    let mut child = ProcessCommand::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let request = DescribeRequest { jsonrpc: "2.0", method: "describe", id: 1 };
+    let mut line = serde_json::to_string(&request).ok()?;
+    line.push('\n');
+    child.stdin.take()?.write_all(line.as_bytes()).ok()?;
+
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let response: DescribeResponse = serde_json::from_slice(&output.stdout).ok()?;
+    let result = response.result?;
+    if response.error.is_some() {
+        return None;
+    }
+
+    Some(RpcPluginInfo {
+        path: path.to_path_buf(),
+        description: result.description,
+        suggestions: result.suggestions,
+        allowed_modes: result.allowed_modes.iter().filter_map(|m| parse_mode_name(m)).collect(),
+    })
+}
+
+/// Scans [`RPC_PLUGIN_DIR`] (and `PNFBOX_PLUGIN_DIR`, if set) for executables,
+/// handshakes with each over JSON-RPC via [`describe_plugin`], and populates
+/// [`RPC_PLUGINS`] with the ones that answer. Meant to be called once from
+/// `main()` at startup; a plugin added afterwards isn't picked up until the
+/// next run.
+pub fn discover_rpc_plugins() {
+    let mut dirs = vec![PathBuf::from(RPC_PLUGIN_DIR)];
+    if let Ok(dir) = env::var("PNFBOX_PLUGIN_DIR") {
+        dirs.push(PathBuf::from(dir));
+    }
+
+    let mut discovered = std::collections::HashMap::new();
+    for dir in dirs {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(info) = describe_plugin(&path) {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                discovered.insert(name, info);
+            }
+        }
+    }
+
+    if let Ok(mut plugins) = RPC_PLUGINS.write() {
+        *plugins = discovered;
+    }
+}
+
+/// The names of every discovered JSON-RPC plugin, for folding into
+/// completion/`help tree` output alongside built-in and `pnfbox-` commands.
+pub fn rpc_plugin_names() -> Vec<String> {
+    RPC_PLUGINS.read().map(|plugins| plugins.keys().cloned().collect()).unwrap_or_default()
+}
+
+/// The metadata a discovered JSON-RPC plugin reported, if any plugin is
+/// registered under `name`.
+pub fn rpc_plugin_info(name: &str) -> Option<RpcPluginInfo> {
+    RPC_PLUGINS.read().ok().and_then(|plugins| plugins.get(name).cloned())
+}
+
+/// The `invoke` request sent to a JSON-RPC plugin: the command-line `args`
+/// it was called with, plus the slice of [`CliContext`] a plugin plausibly
+/// needs to behave mode-appropriately.
+#[derive(Serialize)]
+struct InvokeRequest<'a> {
+    jsonrpc: &'static str,
+    method: &'static str,
+    id: u32,
+    params: InvokeParams<'a>,
+}
+
+#[derive(Serialize)]
+struct InvokeParams<'a> {
+    args: &'a [&'a str],
+    mode: String,
+    hostname: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Deserialize)]
+struct InvokeResponse {
+    result: Option<InvokeResult>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct InvokeResult {
+    #[serde(default)]
+    output: String,
+}
+
+/// Invokes the JSON-RPC plugin registered under `name` with `args`,
+/// serializing `args` and the relevant parts of `context` into a single-line
+/// `invoke` request written to the child's stdin, and prints whatever
+/// `output` it returns over stdout.
+///
+/// Returns `Err` if the plugin isn't registered, can't be spawned, or
+/// answers with a JSON-RPC `error` -- the same shape a built-in command's
+/// `execute` returns, so callers can forward it into the shell's ordinary
+/// error path.
+pub fn run_rpc_plugin(name: &str, args: &[&str], context: &CliContext) -> Result<(), String> {
+    let info = rpc_plugin_info(name).ok_or_else(|| format!("No plugin found for '{}'.", name))?;
+
+    let mut child = ProcessCommand::new(&info.path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("Failed to run plugin '{}': {}", name, err))?;
+
+    let request = InvokeRequest {
+        jsonrpc: "2.0",
+        method: "invoke",
+        id: 1,
+        params: InvokeParams {
+            args,
+            mode: context.current_mode.to_string(),
+            hostname: &context.config.hostname,
+            prompt: &context.prompt,
+        },
+    };
+    let mut line = serde_json::to_string(&request)
+        .map_err(|err| format!("Failed to serialize request for plugin '{}': {}", name, err))?;
+    line.push('\n');
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| format!("Failed to open stdin for plugin '{}'", name))?
+        .write_all(line.as_bytes())
+        .map_err(|err| format!("Failed to write to plugin '{}': {}", name, err))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Failed to run plugin '{}': {}", name, err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Plugin '{}' exited with status {}",
+            name,
+            output.status.code().map_or("unknown".to_string(), |code| code.to_string())
+        ));
+    }
+
+    let response: InvokeResponse = serde_json::from_slice(&output.stdout)
+        .map_err(|err| format!("Malformed response from plugin '{}': {}", name, err))?;
+    if let Some(error) = response.error {
+        return Err(format!("Plugin '{}' reported an error: {}", name, error));
+    }
+    if let Some(result) = response.result {
+        if !result.output.is_empty() {
+            println!("{}", result.output);
+        }
+    }
+    Ok(())
+}
+
+/// Directories searched for plugin executables: an optional
+/// `PNFBOX_PLUGIN_DIR` override, followed by every directory on `$PATH`.
+fn plugin_search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(dir) = env::var("PNFBOX_PLUGIN_DIR") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(path) = env::var_os("PATH") {
+        dirs.extend(env::split_paths(&path));
+    }
+    dirs
+}
+
+/// Locates the `pnfbox-<name>` executable for `name`, if one exists in any
+/// search directory.
+fn plugin_path(name: &str) -> Option<PathBuf> {
+    let file_name = format!("{}{}", PLUGIN_PREFIX, name);
+    plugin_search_dirs()
+        .into_iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Whether a `pnfbox-<name>` executable exists for `name`, for folding
+/// plugin names into `available_commands` during ambiguity checks without
+/// spawning anything.
+pub fn plugin_exists(name: &str) -> bool {
+    plugin_path(name).is_some()
+}
+
+/// Scans every plugin directory and returns the discovered subcommand names
+/// (with the `pnfbox-` prefix stripped), deduplicated, for listing alongside
+/// built-in commands in `?`/Tab completion.
+pub fn discover_plugin_names() -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for dir in plugin_search_dirs() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            if let Some(name) = file_name.strip_prefix(PLUGIN_PREFIX) {
+                if entry.path().is_file() && !names.iter().any(|existing| existing == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Spawns the `pnfbox-<name>` executable with `args` as argv, inheriting
+/// stdio so its stdout/stderr reach the user directly. Returns `Ok(())` on a
+/// zero exit status, otherwise an `Err` describing the failure -- the same
+/// shape a built-in command's `execute` returns, so callers can forward it
+/// into the shell's ordinary error path.
+pub fn run_plugin(name: &str, args: &[&str]) -> Result<(), String> {
+    let path = plugin_path(name).ok_or_else(|| format!("No plugin found for '{}'.", name))?;
+
+    let status = ProcessCommand::new(&path)
+        .args(args)
+        .status()
+        .map_err(|err| format!("Failed to run plugin '{}': {}", name, err))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Plugin '{}' exited with status {}",
+            name,
+            status.code().map_or("unknown".to_string(), |code| code.to_string())
+        ))
+    }
+}
@@ -0,0 +1,187 @@
+//route_filter.rs
+
+//! BIRD-style route filters ("route-maps"): an ordered list of
+//! `permit`/`deny` clauses, each with match conditions and optional
+//! set-actions, evaluated first-match-wins with an implicit deny tail --
+//! the same shape as [`crate::acl_eval`]'s packet-filter engine, applied
+//! to routes instead of packets.
+//!
+//! [`crate::network_config::OSPFConfig`] attaches one at each of its two
+//! filter points: `import_filter` gates routes its own SPF computation
+//! installs, and `export_filter` gates `0.0.0.0/0` default-route
+//! origination. There is no static-into-OSPF/OSPF-into-static
+//! redistribution path in this simulator (the only `redistribute`
+//! command is BGP's, a separate on/off flag with no filter hook) for a
+//! filter to attach to there.
+
+use crate::network_config::{Route, RouteSource};
+use std::net::Ipv4Addr;
+
+/// A single condition a [`FilterClause`] must match, evaluated against the
+/// prefix being redistributed and the route installed for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchCondition {
+    /// The route's prefix is exactly `network/prefix_len`.
+    PrefixEquals(Ipv4Addr, u8),
+    /// The route's prefix is `network/prefix_len` or a more specific
+    /// prefix nested inside it (a supernet match, e.g. `is-within
+    /// 10.0.0.0/8` also matches `10.1.0.0/16`).
+    PrefixIsWithin(Ipv4Addr, u8),
+    /// The route's prefix length is longer (more specific) than
+    /// `prefix_len`.
+    PrefixLongerThan(u8),
+    /// The route's next-hop equals the given string exactly.
+    NextHopEquals(String),
+    /// The route was learned from the given protocol.
+    SourceProtocolEquals(RouteSource),
+}
+
+impl MatchCondition {
+    fn matches(&self, network: Ipv4Addr, prefix_len: u8, route: &Route) -> bool {
+        match self {
+            MatchCondition::PrefixEquals(net, len) => network == *net && prefix_len == *len,
+            MatchCondition::PrefixIsWithin(net, len) => {
+                if prefix_len < *len {
+                    return false;
+                }
+                let mask = if *len == 0 { 0 } else { !0u32 << (32 - len) };
+                (u32::from(network) & mask) == (u32::from(*net) & mask)
+            }
+            MatchCondition::PrefixLongerThan(len) => prefix_len > *len,
+            MatchCondition::NextHopEquals(next_hop) => &route.next_hop == next_hop,
+            MatchCondition::SourceProtocolEquals(source) => route.source == *source,
+        }
+    }
+}
+
+/// The `set` actions a matching [`FilterClause`] applies to the route
+/// before it's installed: override its administrative distance, override
+/// its metric, or attach a tag -- the fields [`Route`] itself carries, so
+/// a clause writes straight through to them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetActions {
+    pub distance: Option<u32>,
+    pub metric: Option<u32>,
+    pub tag: Option<u32>,
+}
+
+impl SetActions {
+    fn apply(&self, route: &mut Route) {
+        if let Some(distance) = self.distance {
+            route.distance_override = Some(distance);
+        }
+        if let Some(metric) = self.metric {
+            route.metric = metric;
+        }
+        if let Some(tag) = self.tag {
+            route.tag = Some(tag);
+        }
+    }
+}
+
+/// Whether a [`FilterClause`] lets a matching route through or blocks it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClauseAction {
+    Permit,
+    Deny,
+}
+
+/// One ordered clause of a [`RouteFilter`]: `permit`/`deny` plus the match
+/// conditions that must ALL hold (an AND, Cisco `route-map` style rather
+/// than BIRD's boolean expressions) for the clause to apply, and the
+/// set-actions to apply when it does.
+#[derive(Debug, Clone, Default)]
+pub struct FilterClause {
+    pub sequence: u32,
+    pub action: ClauseAction,
+    pub conditions: Vec<MatchCondition>,
+    pub set: SetActions,
+}
+
+impl Default for ClauseAction {
+    fn default() -> Self {
+        ClauseAction::Deny
+    }
+}
+
+impl FilterClause {
+    fn matches(&self, network: Ipv4Addr, prefix_len: u8, route: &Route) -> bool {
+        self.conditions.iter().all(|condition| condition.matches(network, prefix_len, route))
+    }
+}
+
+/// The outcome of [`RouteFilter::apply`]: either the route is permitted
+/// through, with its set-actions already folded in, or it's denied and
+/// should never reach the table/protocol it was being redistributed into.
+#[derive(Debug, Clone)]
+pub enum FilterDecision {
+    Permit(Route),
+    Deny,
+}
+
+/// A named, ordered list of [`FilterClause`]s -- BIRD's `filter`, Cisco's
+/// `route-map` -- attachable to
+/// [`crate::network_config::OSPFConfig`]'s import/export points. Clauses
+/// are evaluated first-match-wins (ordered by [`FilterClause::sequence`])
+/// with an implicit `deny` tail, so an unmatched route never crosses a
+/// boundary it wasn't explicitly permitted across.
+#[derive(Debug, Clone, Default)]
+pub struct RouteFilter {
+    pub name: String,
+    pub clauses: Vec<FilterClause>,
+}
+
+impl RouteFilter {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), clauses: Vec::new() }
+    }
+
+    /// Inserts or replaces the clause at `clause.sequence`, keeping
+    /// `clauses` sorted by sequence number -- the same "re-entering a
+    /// `route-map <name> <seq>` edits that clause" semantics Cisco
+    /// route-maps use.
+    pub fn set_clause(&mut self, clause: FilterClause) {
+        match self.clauses.iter().position(|existing| existing.sequence == clause.sequence) {
+            Some(index) => self.clauses[index] = clause,
+            None => {
+                self.clauses.push(clause);
+                self.clauses.sort_by_key(|clause| clause.sequence);
+            }
+        }
+    }
+
+    /// Evaluates every clause in sequence order against `network/prefix_len`
+    /// and `route`, applying the first one whose conditions all match. No
+    /// match at all is an implicit deny, the same default every ACL and
+    /// route-map in this simulator ends with.
+    pub fn apply(&self, network: Ipv4Addr, prefix_len: u8, route: &Route) -> FilterDecision {
+        for clause in &self.clauses {
+            if clause.matches(network, prefix_len, route) {
+                return match clause.action {
+                    ClauseAction::Deny => FilterDecision::Deny,
+                    ClauseAction::Permit => {
+                        let mut route = route.clone();
+                        clause.set.apply(&mut route);
+                        FilterDecision::Permit(route)
+                    }
+                };
+            }
+        }
+        FilterDecision::Deny
+    }
+
+    /// Whether this filter permits `0.0.0.0/0` specifically -- what
+    /// `default_information_originate` checks before injecting a default
+    /// route, so an export filter can suppress default-route origination
+    /// the same way it suppresses any other redistributed prefix.
+    pub fn permits_default_route(&self) -> bool {
+        let probe = Route {
+            next_hop: String::new(),
+            source: RouteSource::Ospf,
+            metric: 0,
+            distance_override: None,
+            tag: None,
+        };
+        matches!(self.apply(Ipv4Addr::new(0, 0, 0, 0), 0, &probe), FilterDecision::Permit(_))
+    }
+}
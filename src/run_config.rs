@@ -1,67 +1,632 @@
 /// External crates for the CLI application
 use crate::cliconfig::{CliConfig, CliContext};
-use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
-use crate::network_config::{STATUS_MAP, IP_ADDRESS_STATE, ROUTE_TABLE, OSPF_CONFIG, ACL_STORE};
+use crate::cryptocommands::CryptoKey;
+use crate::keystore::CertStore;
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use crate::network_config::{AdminState, AccessControlList, IfconfigEntry, InterfaceType, OSPFConfig, OperState, PasswordStore, Route, RoutingTable, ACL_STORE, IFCONFIG_STATE, IP_ADDRESS_STATE, OPER_STATE_MAP, OSPF_CONFIG, PASSWORD_STORAGE, ROUTING_TABLE, prefix_to_netmask, STATUS_MAP, TUNNEL_CONFIG};
 
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use jsonschema::JSONSchema;
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
 
-/// Saves the given `CliConfig` to a file named `startup-config.json`.
-/// 
-/// This function serializes the provided configuration into JSON format and writes it
-/// to a file. If the file already exists, it will be overwritten. If the file does
-/// not exist, it will be created. The JSON is formatted for readability (pretty-printed).
-/// 
+/// PBKDF2-HMAC-SHA256 rounds used to stretch the operator passphrase into the
+/// AES-256-GCM key that wraps each stored private key. 100k matches current
+/// OWASP guidance for PBKDF2-SHA256 at time of writing.
+const KEY_WRAP_ITERATIONS: u32 = 100_000;
+
+/// Where `write memory` / `copy running-config startup-config` persist the
+/// device's startup configuration by default, and where the CLI looks for
+/// one to restore at boot.
+pub const DEFAULT_STARTUP_CONFIG_PATH: &str = "startup-config.json";
+
+/// A single `crypto_keys` entry as stored on disk: `salt`/`nonce`/`ciphertext`
+/// are base64 so the whole thing still round-trips through `startup-config.json`'s
+/// plain JSON. `iterations` travels with the blob so a future bump to
+/// [`KEY_WRAP_ITERATIONS`] doesn't break decrypting keys wrapped under the old count.
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct WrappedKey {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    iterations: u32,
+}
+
+/// On-disk shape of `startup-config.json`: identical to `CliConfig` except
+/// `crypto_keys` is replaced by its encrypted form, so private key material
+/// never touches the file in the clear.
+#[derive(Serialize, Deserialize)]
+struct StartupConfigFile {
+    #[serde(flatten)]
+    config: CliConfig,
+    crypto_keys_wrapped: HashMap<String, WrappedKey>,
+    /// Snapshot of the `lazy_static` device-state stores that live outside
+    /// `CliConfig` (interface/address/route/ACL/password state). Defaulted
+    /// so a startup-config written before this field existed still loads.
+    #[serde(default)]
+    device_state: DeviceState,
+}
+
+/// A snapshot of every `lazy_static` global this crate uses to hold
+/// simulated device state that isn't already a `CliConfig` field --
+/// `IFCONFIG_STATE`, `IP_ADDRESS_STATE`, `ROUTING_TABLE`, `OSPF_CONFIG`,
+/// `ACL_STORE`, `STATUS_MAP`, and `PASSWORD_STORAGE`. [`capture_device_state`]
+/// and [`restore_device_state`] are the only things that build/consume one.
+#[derive(Default, Serialize, Deserialize)]
+struct DeviceState {
+    ifconfig: HashMap<String, IfconfigEntry>,
+    ip_addresses: HashMap<String, (Ipv4Addr, Ipv4Addr)>,
+    routes: Vec<(Ipv4Addr, u8, Route)>,
+    ospf: OSPFConfig,
+    acls: HashMap<String, AccessControlList>,
+    interface_admin_state: HashMap<String, AdminState>,
+    passwords: PasswordStore,
+}
+
+/// Snapshots every device-state store into a [`DeviceState`], locking them in
+/// a fixed order (`IFCONFIG_STATE` -> `IP_ADDRESS_STATE` -> `ROUTING_TABLE` ->
+/// `OSPF_CONFIG` -> `ACL_STORE` -> `STATUS_MAP` -> `PASSWORD_STORAGE`) so a
+/// concurrent reader of these same globals (e.g. `get_running_config`, which
+/// takes several of the same locks) can never deadlock against this call.
+fn capture_device_state() -> DeviceState {
+    let ifconfig = IFCONFIG_STATE.lock().unwrap().clone();
+    let ip_addresses = IP_ADDRESS_STATE.lock().unwrap().clone();
+    let routes = ROUTING_TABLE.lock().unwrap().entries();
+    let ospf = OSPF_CONFIG.lock().unwrap().clone();
+    let acls = ACL_STORE.lock().unwrap().clone();
+    let interface_admin_state = STATUS_MAP.lock().unwrap().clone();
+    let passwords = PASSWORD_STORAGE.lock().unwrap().clone();
+
+    DeviceState { ifconfig, ip_addresses, routes, ospf, acls, interface_admin_state, passwords }
+}
+
+/// Repopulates every device-state store from a loaded [`DeviceState`], in the
+/// same lock order [`capture_device_state`] uses. Replaces each store's
+/// contents wholesale -- a `load_config` is meant to restore the device to
+/// exactly what was saved, not merge with whatever was already running.
+fn restore_device_state(state: DeviceState) {
+    *IFCONFIG_STATE.lock().unwrap() = state.ifconfig;
+    *IP_ADDRESS_STATE.lock().unwrap() = state.ip_addresses;
+    let mut routing_table = ROUTING_TABLE.lock().unwrap();
+    *routing_table = RoutingTable::new();
+    for (network, prefix_len, route) in state.routes {
+        routing_table.insert(network, prefix_len, route);
+    }
+    drop(routing_table);
+    *OSPF_CONFIG.lock().unwrap() = state.ospf;
+    *ACL_STORE.lock().unwrap() = state.acls;
+    *STATUS_MAP.lock().unwrap() = state.interface_admin_state;
+    *PASSWORD_STORAGE.lock().unwrap() = state.passwords;
+}
+
+fn derive_wrapping_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+/// Encrypts one key's PEM text under a fresh salt and nonce derived from
+/// `passphrase`, analogous to a "wrap-key-to-file" scheme.
+pub(crate) fn wrap_key(plaintext: &str, passphrase: &str) -> Result<WrappedKey, String> {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let key = derive_wrapping_key(passphrase, &salt, KEY_WRAP_ITERATIONS);
+    let cipher = Aes256Gcm::new_from_slice(&key)
+        .map_err(|err| format!("Failed to initialize key wrapping cipher: {}", err))?;
+
+    let mut nonce_bytes = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext.as_bytes())
+        .map_err(|err| format!("Failed to encrypt key material: {}", err))?;
+
+    Ok(WrappedKey {
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce_bytes),
+        ciphertext: BASE64.encode(ciphertext),
+        iterations: KEY_WRAP_ITERATIONS,
+    })
+}
+
+/// Reverses [`wrap_key`], failing loudly (rather than returning empty/garbled
+/// text) if `passphrase` is wrong or the blob was tampered with -- either way
+/// AES-GCM's authentication tag won't verify.
+pub(crate) fn unwrap_key(wrapped: &WrappedKey, passphrase: &str) -> Result<String, String> {
+    let salt = BASE64.decode(&wrapped.salt).map_err(|err| format!("Corrupt key salt: {}", err))?;
+    let nonce_bytes = BASE64.decode(&wrapped.nonce).map_err(|err| format!("Corrupt key nonce: {}", err))?;
+    let ciphertext = BASE64
+        .decode(&wrapped.ciphertext)
+        .map_err(|err| format!("Corrupt key ciphertext: {}", err))?;
+
+    let key = derive_wrapping_key(passphrase, &salt, wrapped.iterations);
+    let cipher = Aes256Gcm::new_from_slice(&key)
+        .map_err(|err| format!("Failed to initialize key wrapping cipher: {}", err))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let plaintext = cipher
+        .decrypt(nonce, ciphertext.as_slice())
+        .map_err(|_| "Incorrect passphrase, or key data has been tampered with (authentication tag mismatch).".to_string())?;
+    String::from_utf8(plaintext).map_err(|err| format!("Decrypted key material is not valid UTF-8: {}", err))
+}
+
+/// Which serialization a startup-config file is written/read in. Selected from
+/// the file's extension, the same way sibling tooling picks a format by the
+/// config file it's handed rather than a separate flag.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Defaults to `Json` for an unrecognized or missing extension, matching
+    /// this file's historical hard-coded `startup-config.json` behavior.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => ConfigFormat::Toml,
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+/// A pluggable serialization backend for the startup config, so `save_config`/
+/// `load_config` aren't hard-wired to `serde_json`. Every backend works in
+/// terms of `serde_json::Value` so schema validation only needs to be written
+/// once, against the format-neutral value.
+trait ConfigBackend {
+    fn serialize(&self, value: &Value) -> Result<String, String>;
+    fn parse(&self, contents: &str) -> Result<Value, String>;
+}
+
+struct JsonBackend;
+impl ConfigBackend for JsonBackend {
+    fn serialize(&self, value: &Value) -> Result<String, String> {
+        serde_json::to_string_pretty(value).map_err(|err| format!("Failed to serialize JSON: {}", err))
+    }
+
+    fn parse(&self, contents: &str) -> Result<Value, String> {
+        serde_json::from_str(contents)
+            .map_err(|err| format!("Invalid JSON at line {}, column {}: {}", err.line(), err.column(), err))
+    }
+}
+
+struct TomlBackend;
+impl ConfigBackend for TomlBackend {
+    fn serialize(&self, value: &Value) -> Result<String, String> {
+        toml::to_string_pretty(value).map_err(|err| format!("Failed to serialize TOML: {}", err))
+    }
+
+    fn parse(&self, contents: &str) -> Result<Value, String> {
+        let parsed: toml::Value = toml::from_str(contents).map_err(|err| format!("Invalid TOML: {}", err))?;
+        serde_json::to_value(parsed).map_err(|err| format!("Failed to normalize TOML: {}", err))
+    }
+}
+
+struct YamlBackend;
+impl ConfigBackend for YamlBackend {
+    fn serialize(&self, value: &Value) -> Result<String, String> {
+        serde_yaml::to_string(value).map_err(|err| format!("Failed to serialize YAML: {}", err))
+    }
+
+    fn parse(&self, contents: &str) -> Result<Value, String> {
+        serde_yaml::from_str(contents).map_err(|err| match err.location() {
+            Some(location) => format!(
+                "Invalid YAML at line {}, column {}: {}",
+                location.line(),
+                location.column(),
+                err
+            ),
+            None => format!("Invalid YAML: {}", err),
+        })
+    }
+}
+
+fn backend_for(format: ConfigFormat) -> Box<dyn ConfigBackend> {
+    match format {
+        ConfigFormat::Json => Box::new(JsonBackend),
+        ConfigFormat::Toml => Box::new(TomlBackend),
+        ConfigFormat::Yaml => Box::new(YamlBackend),
+    }
+}
+
+/// A minimal JSON Schema for [`StartupConfigFile`], covering the fields a
+/// hand-edited config is most likely to get wrong (missing hostname, a
+/// `crypto_keys_wrapped` entry that isn't an object, etc.) rather than
+/// mechanically restating every `CliConfig` field.
+fn startup_config_schema() -> Value {
+    serde_json::json!({
+        "type": "object",
+        "required": ["hostname", "crypto_keys_wrapped"],
+        "properties": {
+            "hostname": { "type": "string" },
+            "password_encryption": { "type": "boolean" },
+            "crypto_keys_wrapped": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "required": ["salt", "nonce", "ciphertext", "iterations"],
+                    "properties": {
+                        "salt": { "type": "string" },
+                        "nonce": { "type": "string" },
+                        "ciphertext": { "type": "string" },
+                        "iterations": { "type": "integer" }
+                    }
+                }
+            },
+            "certificates": { "type": "object" }
+        }
+    })
+}
+
+/// Validates a parsed startup config against [`startup_config_schema`],
+/// returning a precise path-to-error message (rather than letting a malformed
+/// or partially-hand-edited file parse into garbage `CliConfig` fields).
+fn validate_startup_config(value: &Value) -> Result<(), String> {
+    let schema = startup_config_schema();
+    let compiled = JSONSchema::compile(&schema)
+        .map_err(|err| format!("Internal error: invalid startup-config schema: {}", err))?;
+    compiled.validate(value).map_err(|errors| {
+        errors
+            .map(|err| format!("{}: {}", err.instance_path, err))
+            .collect::<Vec<_>>()
+            .join("; ")
+    })
+}
+
+/// Why [`load_config`] failed, distinguishing a legitimately-missing file
+/// (first boot, no startup config saved yet) from one that exists but
+/// couldn't be trusted as-is. Callers should fall back to
+/// `CliConfig::default()` for [`LoadError::NotFound`], but must refuse to
+/// proceed and surface [`LoadError::Invalid`] rather than silently wiping the
+/// device's configuration.
+#[derive(Debug)]
+pub enum LoadError {
+    /// No file exists at the given path.
+    NotFound,
+    /// The file exists but isn't trustworthy: a read failure, a parse error
+    /// (with line/column when the backend reports one), or a
+    /// schema-validation failure. A `crypto_keys` entry that fails to
+    /// unwrap does *not* fall in here -- [`load_config`] drops just that
+    /// key and keeps going, since the rest of the config is still sound.
+    Invalid(String),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::NotFound => write!(f, "no startup configuration file found"),
+            LoadError::Invalid(detail) => write!(f, "{}", detail),
+        }
+    }
+}
+
+/// How many rotated backups [`rotate_backups`] keeps around; older ones are
+/// deleted as new ones are made, giving operators an NVRAM-history-style
+/// rollback path without the backup directory growing without bound.
+const MAX_STARTUP_CONFIG_BACKUPS: usize = 5;
+
+/// Builds the sibling path a backup of `path` is written to, named
+/// `<file-stem>.<timestamp>.bak` (e.g. `startup-config.20260730153012.bak`
+/// for `startup-config.json`).
+fn backup_path(path: &Path, timestamp: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("startup-config");
+    path.with_file_name(format!("{}.{}.bak", stem, timestamp))
+}
+
+/// Copies `path`'s current contents into a timestamped `.bak` sibling before
+/// it's overwritten, then trims old backups down to
+/// [`MAX_STARTUP_CONFIG_BACKUPS`]. A no-op if `path` doesn't exist yet (first
+/// save, nothing to roll back to).
+fn rotate_backups(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S").to_string();
+    let backup = backup_path(path, &timestamp);
+    std::fs::copy(path, &backup)
+        .map_err(|err| format!("Failed to create backup '{}': {}", backup.display(), err))?;
+
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("startup-config").to_string();
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+    let prefix = format!("{}.", stem);
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|err| format!("Failed to list backups in '{}': {}", dir.display(), err))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|candidate| {
+            candidate
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".bak"))
+        })
+        .collect();
+    backups.sort();
+
+    if backups.len() > MAX_STARTUP_CONFIG_BACKUPS {
+        for stale in &backups[..backups.len() - MAX_STARTUP_CONFIG_BACKUPS] {
+            let _ = std::fs::remove_file(stale);
+        }
+    }
+    Ok(())
+}
+
+/// Saves the given `CliConfig` to `path`, in the format selected by its file
+/// extension (`.toml`, `.yaml`/`.yml`, or JSON otherwise). If the file already
+/// exists, its previous contents are rotated into a timestamped backup (see
+/// [`rotate_backups`]) before being overwritten; if it does not exist, it
+/// will be created.
+///
+/// `config.crypto_keys` is never written in the clear: each entry is wrapped with
+/// AES-256-GCM under a key derived from `passphrase` via PBKDF2-HMAC-SHA256 (fresh
+/// 16-byte salt per key), and the resulting `{salt, nonce, ciphertext}` replaces it
+/// on disk. This mirrors the `service password-encryption` toggle already applied
+/// to passwords in `get_running_config`, extended to cover key material at rest.
+///
+/// Alongside `config`, a snapshot of the device-state `lazy_static`s that
+/// live outside `CliConfig` (interface/address/route/ACL/admin-state/password
+/// stores) is captured via [`capture_device_state`] and written in the clear,
+/// so [`load_config`] can repopulate them on the next boot.
+///
 /// # Parameters
 /// - `config`: The `CliConfig` object that contains the configuration to be saved.
-/// 
+/// - `path`: Where to write the config; its extension selects the backend.
+/// - `passphrase`: The operator passphrase used to wrap `config.crypto_keys`.
+///
 /// # Returns
-/// This function returns a `Result<(), std::io::Error>`. It will return `Ok(())` if the
-/// file is successfully written, or an error if something goes wrong (e.g., file write failure).
-/// 
+/// `Ok(())` if the file is successfully written, or an error describing what went
+/// wrong (wrapping failure, serialization failure, or file write failure).
+///
 /// # Example
 /// ```
 /// use crate::cliconfig::CliConfig;
+/// use std::path::Path;
 /// let config = CliConfig::default(); // Example config
-/// if let Err(e) = save_config(&config) {
+/// if let Err(e) = save_config(&config, Path::new("startup-config.json"), "correct horse battery staple") {
 ///     eprintln!("Failed to save config: {}", e);
 /// }
 /// ```
-pub fn save_config(config: &CliConfig) -> std::io::Result<()> {
-    let serialized = serde_json::to_string_pretty(config)?;
-    let mut file = OpenOptions::new()
-        .create(true) 
-        .write(true)  
-        .truncate(true) 
-        .open("startup-config.json")?;
-    file.write_all(serialized.as_bytes())
+pub fn save_config(config: &CliConfig, path: &Path, passphrase: &str) -> Result<(), String> {
+    rotate_backups(path)?;
+
+    let mut config = config.clone();
+    let mut crypto_keys_wrapped = HashMap::new();
+    for (name, key) in config.crypto_keys.drain() {
+        let plaintext = serde_json::to_string(&key)
+            .map_err(|err| format!("Failed to serialize key '{}': {}", name, err))?;
+        crypto_keys_wrapped.insert(name, wrap_key(&plaintext, passphrase)?);
+    }
+
+    let on_disk = StartupConfigFile { config, crypto_keys_wrapped, device_state: capture_device_state() };
+    let value = serde_json::to_value(&on_disk)
+        .map_err(|err| format!("Failed to serialize startup configuration: {}", err))?;
+
+    let serialized = backend_for(ConfigFormat::from_path(path)).serialize(&value)?;
+    std::fs::write(path, serialized)
+        .map_err(|err| format!("Failed to write '{}': {}", path.display(), err))?;
+
+    crate::hooks::run_hook(
+        &on_disk.config.hook_scripts,
+        "config-saved",
+        &[("CONFIG_PATH", path.display().to_string())],
+    );
+
+    Ok(())
 }
 
 
-/// Loads the configuration from the `startup-config.json` file.
-/// 
-/// This function attempts to read the `startup-config.json` file and deserialize its
-/// contents into a `CliConfig` object. If the file cannot be opened, read, or parsed,
-/// a default configuration will be returned.
-/// 
+/// Loads the configuration from `path`, in the format selected by its file
+/// extension (`.toml`, `.yaml`/`.yml`, or JSON otherwise).
+///
+/// A missing file is reported as [`LoadError::NotFound`] -- legitimate on
+/// first boot, and the caller's call to fall back to `CliConfig::default()`.
+/// An unreadable file, a parse failure, or a schema-validation failure comes
+/// back as [`LoadError::Invalid`] so a single bad hand-edit is refused and
+/// reported rather than silently wiping the device's configuration. The
+/// parsed content is validated against [`startup_config_schema`] before
+/// anything else, so the error points at a precise path rather than letting
+/// a malformed file parse into garbage `CliConfig` fields.
+///
+/// The per-key AES-256-GCM wrapping applied by [`save_config`] is reversed
+/// per entry using `passphrase`: a key that was wrapped under a different
+/// passphrase (or is otherwise malformed) fails to unwrap on its own and is
+/// dropped with a warning on stderr, rather than failing this whole load --
+/// `passphrase` is empty on every non-interactive boot, so a key an operator
+/// wrapped with a real passphrase would otherwise take every other setting
+/// (interfaces, routes, OSPF, ACLs, passwords, ...) down with it. The
+/// device-state snapshot [`save_config`] wrote alongside `config` is
+/// restored into its `lazy_static` stores via [`restore_device_state`]
+/// before this returns, independent of how many keys unwrapped.
+///
 /// # Returns
-/// The function returns a `CliConfig` object. If loading the configuration fails, it
-/// will return the default configuration as defined by `CliConfig::default()`.
-/// 
+/// `Ok(CliConfig)` on success (possibly missing some `crypto_keys` entries
+/// that failed to unwrap -- see above), or the [`LoadError`] explaining why
+/// the load as a whole was refused.
+///
 /// # Example
 /// ```
-/// let config = load_config();
-/// println!("Loaded config: {:?}", config);
+/// use std::path::Path;
+/// match load_config(Path::new("startup-config.json"), "correct horse battery staple") {
+///     Ok(config) => println!("Loaded config: {:?}", config.hostname),
+///     Err(LoadError::NotFound) => println!("No startup config yet; using defaults."),
+///     Err(LoadError::Invalid(detail)) => eprintln!("Refusing to load startup config: {}", detail),
+/// }
 /// ```
-pub fn load_config() -> CliConfig {
-    if let Ok(mut file) = File::open("startup-config.json") {
-        let mut contents = String::new();
-        if file.read_to_string(&mut contents).is_ok() {
-            if let Ok(config) = serde_json::from_str::<CliConfig>(&contents) {
-                return config;
+pub fn load_config(path: &Path, passphrase: &str) -> Result<CliConfig, LoadError> {
+    if !path.exists() {
+        return Err(LoadError::NotFound);
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| LoadError::Invalid(format!("Failed to open '{}': {}", path.display(), err)))?;
+
+    let value = backend_for(ConfigFormat::from_path(path))
+        .parse(&contents)
+        .map_err(|err| LoadError::Invalid(format!("'{}': {}", path.display(), err)))?;
+    validate_startup_config(&value)
+        .map_err(|err| LoadError::Invalid(format!("'{}' failed schema validation: {}", path.display(), err)))?;
+
+    let on_disk: StartupConfigFile = serde_json::from_value(value)
+        .map_err(|err| LoadError::Invalid(format!("Failed to parse '{}': {}", path.display(), err)))?;
+    let mut config = on_disk.config;
+    let mut crypto_keys = HashMap::new();
+    for (name, wrapped) in on_disk.crypto_keys_wrapped {
+        let unwrapped = unwrap_key(&wrapped, passphrase).and_then(|plaintext| {
+            serde_json::from_str::<CryptoKey>(&plaintext)
+                .map_err(|err| format!("Malformed key data for '{}': {}", name, err))
+        });
+        match unwrapped {
+            Ok(key) => {
+                crypto_keys.insert(name, key);
+            }
+            Err(err) => {
+                eprintln!("Warning: dropping crypto key '{}' from '{}': {}", name, path.display(), err);
+            }
+        }
+    }
+    config.crypto_keys = crypto_keys;
+    restore_device_state(on_disk.device_state);
+    Ok(config)
+}
+
+/// Validates that a [`CliConfig`] is internally consistent enough to become
+/// the live crypto/tunnel state: every crypto map's bound transform set
+/// must exist in its own `crypto_transform_sets`, and a configured
+/// `tunnel_protection_profile` must match its own `crypto_ipsec_profile`.
+/// Checked against the candidate config alone (not the live one), so a
+/// `reload` never partially applies a config whose own cross-references
+/// don't line up.
+fn validate_crypto_tunnel_config(config: &CliConfig) -> Result<(), String> {
+    for (map_name, entry) in &config.crypto_maps {
+        if let Some(transform_set) = &entry.transform_set {
+            if !config.crypto_transform_sets.contains_key(transform_set) {
+                return Err(format!(
+                    "Crypto map '{}' references transform set '{}', which isn't defined.",
+                    map_name, transform_set
+                ));
             }
         }
     }
-    CliConfig::default()
+    if let Some(profile) = &config.tunnel_protection_profile {
+        if config.crypto_ipsec_profile.as_deref() != Some(profile.as_str()) {
+            return Err(format!(
+                "Tunnel protection references IPsec profile '{}', which isn't the configured 'crypto ipsec profile'.",
+                profile
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// One crypto-map/transform-set/certificate key's reload delta, as `config
+/// reload` reports it.
+enum ReloadChange {
+    Added,
+    Changed,
+    Removed,
+}
+
+impl std::fmt::Display for ReloadChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReloadChange::Added => write!(f, "added"),
+            ReloadChange::Changed => write!(f, "changed"),
+            ReloadChange::Removed => write!(f, "removed"),
+        }
+    }
+}
+
+/// Diffs two key/value maps by key, reporting every key that's new,
+/// changed, or gone in `new` relative to `old`, in that order.
+fn diff_map<V: PartialEq>(old: &HashMap<String, V>, new: &HashMap<String, V>) -> Vec<(String, ReloadChange)> {
+    let mut changes = Vec::new();
+    for (key, new_value) in new {
+        match old.get(key) {
+            None => changes.push((key.clone(), ReloadChange::Added)),
+            Some(old_value) if old_value != new_value => changes.push((key.clone(), ReloadChange::Changed)),
+            Some(_) => {}
+        }
+    }
+    for key in old.keys() {
+        if !new.contains_key(key) {
+            changes.push((key.clone(), ReloadChange::Removed));
+        }
+    }
+    changes
+}
+
+/// Handles `config reload <path>`: re-reads `path` the same way a startup
+/// config is loaded, validates the candidate config with
+/// [`validate_crypto_tunnel_config`] before touching anything, then -- only
+/// on success -- replaces just `crypto_maps`, `crypto_transform_sets`,
+/// `certificates`, and the `tunnel_*` fields on the live `context.config`,
+/// printing what was added/changed/removed in each. Every other field
+/// (hostname, interfaces, ACLs, NTP, ...) is left exactly as it was, the
+/// same "hot-reload settings, not the whole process" contract a mail
+/// server's config reload gives. A failed validation leaves `context`
+/// completely untouched -- there's nothing to roll back because nothing
+/// was ever applied.
+pub fn reload_crypto_and_tunnel_config(context: &mut CliContext, path: &Path, passphrase: &str) -> Result<(), String> {
+    let new_config = load_config(path, passphrase).map_err(|err| err.to_string())?;
+    validate_crypto_tunnel_config(&new_config)?;
+
+    let map_changes = diff_map(&context.config.crypto_maps, &new_config.crypto_maps);
+    let transform_set_changes = diff_map(&context.config.crypto_transform_sets, &new_config.crypto_transform_sets);
+    let certificate_changes = diff_map(&context.cert_store.snapshot(), &new_config.certificates);
+
+    if map_changes.is_empty() && transform_set_changes.is_empty() && certificate_changes.is_empty()
+        && context.config.tunnel_source == new_config.tunnel_source
+        && context.config.tunnel_destination == new_config.tunnel_destination
+        && context.config.tunnel_mode == new_config.tunnel_mode
+        && context.config.tunnel_protection_profile == new_config.tunnel_protection_profile
+    {
+        println!("No changes to crypto maps, transform sets, certificates, or tunnel settings.");
+        return Ok(());
+    }
+
+    println!("Reloading crypto/tunnel configuration from '{}':", path.display());
+    for (name, change) in &map_changes {
+        println!("  crypto map {}: {}", name, change);
+    }
+    for (name, change) in &transform_set_changes {
+        println!("  crypto transform-set {}: {}", name, change);
+    }
+    for (name, change) in &certificate_changes {
+        println!("  certificate {}: {}", name, change);
+    }
+    for (label, old, new) in [
+        ("tunnel source", &context.config.tunnel_source, &new_config.tunnel_source),
+        ("tunnel destination", &context.config.tunnel_destination, &new_config.tunnel_destination),
+        ("tunnel mode", &context.config.tunnel_mode, &new_config.tunnel_mode),
+        ("tunnel protection profile", &context.config.tunnel_protection_profile, &new_config.tunnel_protection_profile),
+    ] {
+        if old != new {
+            println!("  {}: {:?} -> {:?}", label, old, new);
+        }
+    }
+
+    context.config.crypto_maps = new_config.crypto_maps;
+    context.config.crypto_transform_sets = new_config.crypto_transform_sets;
+    context.cert_store.load_snapshot(new_config.certificates);
+    context.config.tunnel_source = new_config.tunnel_source;
+    context.config.tunnel_destination = new_config.tunnel_destination;
+    context.config.tunnel_mode = new_config.tunnel_mode;
+    context.config.tunnel_protection_profile = new_config.tunnel_protection_profile;
+    crate::keystore::sync_config_from_stores(context);
+
+    println!("Reload complete.");
+    Ok(())
 }
 
 
@@ -88,7 +653,8 @@ pub fn get_running_config(context: &CliContext) -> String {
     // Access global states
     let ip_address_state = IP_ADDRESS_STATE.lock().unwrap();
     let status_map = STATUS_MAP.lock().unwrap();
-    let route_table = ROUTE_TABLE.lock().unwrap();
+    let oper_state_map = OPER_STATE_MAP.lock().unwrap();
+    let routing_table = ROUTING_TABLE.lock().unwrap();
     let ospf_config = OSPF_CONFIG.lock().unwrap();
     let acl_store = ACL_STORE.lock().unwrap();
 
@@ -105,18 +671,22 @@ pub fn get_running_config(context: &CliContext) -> String {
         .unwrap_or_else(|| "no ip address".to_string());
 
     let mut route_entries = String::new();
-    for (destination, (netmask, next_hop_or_iface)) in route_table.iter() {
+    for (network, prefix_len, route) in routing_table.entries() {
+        let netmask = prefix_to_netmask(prefix_len as u32);
         route_entries.push_str(&format!(
             "ip route {} {} {}\n",
-            destination, netmask, next_hop_or_iface
+            network, netmask, route.next_hop
         ));
     }
 
-    let shutdown_status = if status_map.get(&interface).copied().unwrap_or(false) {
+    let admin_state = status_map.get(&interface).copied().unwrap_or(AdminState::Down);
+    let shutdown_status = if admin_state == AdminState::Up {
         "no shutdown"
     } else {
         "shutdown"
     };
+    let oper_state = oper_state_map.get(&interface).copied().unwrap_or(OperState::NotPresent);
+    let interface_type = InterfaceType::classify(&interface);
 
     let ospf_process_id = ospf_config.process_id.map_or("N/A".to_string(), |id| id.to_string());
     let ospf_interface = ospf_config.passive_interfaces.join(", ");
@@ -130,6 +700,161 @@ pub fn get_running_config(context: &CliContext) -> String {
         }
     }
 
+    let mut ntp_configs = String::new();
+    for (key_number, key) in context.ntp_authentication_keys.iter() {
+        ntp_configs.push_str(&format!("ntp authentication-key {} {} {}\n", key_number, key.algorithm.as_str(), key.key));
+    }
+    for key_number in context.ntp_trusted_keys.iter() {
+        ntp_configs.push_str(&format!("ntp trusted-key {}\n", key_number));
+    }
+    if context.ntp_authentication_enabled {
+        ntp_configs.push_str("ntp authenticate\n");
+    }
+    if context.ntp_master {
+        ntp_configs.push_str("ntp master\n");
+    }
+    for assoc in context.ntp_associations.iter().filter(|assoc| assoc.address != "127.127.1.1") {
+        match assoc.key_id {
+            Some(key_id) => ntp_configs.push_str(&format!("ntp server {} key {}\n", assoc.address, key_id)),
+            None => ntp_configs.push_str(&format!("ntp server {}\n", assoc.address)),
+        }
+    }
+
+    let tunnel_config = TUNNEL_CONFIG.lock().unwrap();
+    let mut tunnel_configs = String::new();
+    for (tunnel_interface, tunnel) in tunnel_config.iter() {
+        tunnel_configs.push_str(&format!("!\ninterface {}\n", tunnel_interface));
+        if let Some((ip, netmask)) = ip_address_state.get(tunnel_interface) {
+            tunnel_configs.push_str(&format!(" ip address {} {}\n", ip, netmask));
+        }
+        if let Some(mode) = &tunnel.mode {
+            tunnel_configs.push_str(&format!(" tunnel mode {}\n", mode));
+        }
+        if let Some(source) = &tunnel.source {
+            tunnel_configs.push_str(&format!(" tunnel source {}\n", source));
+        }
+        if let Some(destination) = &tunnel.destination {
+            tunnel_configs.push_str(&format!(" tunnel destination {}\n", destination));
+        }
+        if let Some(key) = tunnel.key {
+            tunnel_configs.push_str(&format!(" tunnel key {}\n", key));
+        }
+        if let Some(ttl) = tunnel.ttl {
+            tunnel_configs.push_str(&format!(" tunnel ttl {}\n", ttl));
+        }
+    }
+
+    let mut snmp_configs = String::new();
+    for (community, access) in context.config.snmp_communities.iter() {
+        snmp_configs.push_str(&format!("snmp-server community {} {}\n", community, access));
+    }
+    if let Some(location) = &context.config.snmp_location {
+        snmp_configs.push_str(&format!("snmp-server location {}\n", location));
+    }
+    if let Some(contact) = &context.config.snmp_contact {
+        snmp_configs.push_str(&format!("snmp-server contact {}\n", contact));
+    }
+    if context.config.snmp_traps_enabled {
+        snmp_configs.push_str("snmp-server enable traps\n");
+    }
+    for host in context.config.snmp_hosts.iter() {
+        snmp_configs.push_str(&format!(
+            "snmp-server host {} version {} {}\n",
+            host.address, host.version, host.community
+        ));
+    }
+
+    let mut crypto_configs = String::new();
+    for (pool_name, pool) in context.config.local_pools.iter() {
+        crypto_configs.push_str(&format!("ip local pool {} {} {}\n", pool_name, pool.start, pool.end));
+    }
+    for (name, transforms) in context.config.crypto_transform_sets.iter() {
+        crypto_configs.push_str(&format!("crypto ipsec transform-set {} {}\n", name, transforms.join(" ")));
+    }
+    let mut isakmp_priorities: Vec<&u32> = context.config.isakmp_policies.keys().collect();
+    isakmp_priorities.sort();
+    for priority in isakmp_priorities {
+        let policy = &context.config.isakmp_policies[priority];
+        crypto_configs.push_str(&format!("crypto isakmp policy {}\n", priority));
+        if let Some(encryption) = &policy.encryption {
+            crypto_configs.push_str(&format!(" encryption {}\n", encryption));
+        }
+        if let Some(hash) = &policy.hash {
+            crypto_configs.push_str(&format!(" hash {}\n", hash));
+        }
+        if let Some(authentication) = &policy.authentication {
+            crypto_configs.push_str(&format!(" authentication {}\n", authentication));
+        }
+        if let Some(group) = policy.group {
+            crypto_configs.push_str(&format!(" group {}\n", group));
+        }
+        if let Some(lifetime) = policy.lifetime {
+            crypto_configs.push_str(&format!(" lifetime {}\n", lifetime));
+        }
+    }
+    for (group_name, group) in context.config.isakmp_client_groups.iter() {
+        crypto_configs.push_str(&format!("crypto isakmp client configuration group {}\n", group_name));
+        if let Some(pool) = &group.pool {
+            crypto_configs.push_str(&format!(" pool {}\n", pool));
+        }
+        if let Some(dns) = &group.dns {
+            crypto_configs.push_str(&format!(" dns {}\n", dns));
+        }
+        if let Some(key) = &group.key {
+            crypto_configs.push_str(&format!(" key {}\n", key));
+        }
+    }
+    for (_, entry) in context.config.crypto_maps.iter() {
+        match &entry.map_type {
+            Some(map_type) => {
+                crypto_configs.push_str(&format!("crypto map {} {} {}\n", entry.name, entry.seq_num, map_type));
+            }
+            None => {
+                crypto_configs.push_str(&format!("crypto map {} {}\n", entry.name, entry.seq_num));
+            }
+        }
+        if let Some(local_addr) = context.config.crypto_local_addresses.get(&entry.name) {
+            crypto_configs.push_str(&format!(" local-address {}\n", local_addr));
+        }
+        if let Some(peer) = &entry.peer {
+            crypto_configs.push_str(&format!(" set peer {}\n", peer));
+        }
+        if let Some(transform_set) = &entry.transform_set {
+            crypto_configs.push_str(&format!(" set transform-set {}\n", transform_set));
+        }
+        if let Some(match_acl) = &entry.match_acl {
+            crypto_configs.push_str(&format!(" match address {}\n", match_acl));
+        }
+    }
+
+    let mut dhcp_configs = String::new();
+    for (start, end) in context.config.dhcp_excluded_addresses.iter() {
+        if start == end {
+            dhcp_configs.push_str(&format!("ip dhcp excluded-address {}\n", start));
+        } else {
+            dhcp_configs.push_str(&format!("ip dhcp excluded-address {} {}\n", start, end));
+        }
+    }
+    for (pool_name, pool) in context.config.dhcp_pools.iter() {
+        dhcp_configs.push_str(&format!("ip dhcp pool {}\n", pool_name));
+        if let Some((network, netmask)) = pool.network {
+            dhcp_configs.push_str(&format!(" network {} {}\n", network, netmask));
+        }
+        if let Some(default_router) = pool.default_router {
+            dhcp_configs.push_str(&format!(" default-router {}\n", default_router));
+        }
+        if !pool.dns_servers.is_empty() {
+            let dns_servers: Vec<String> = pool.dns_servers.iter().map(|dns| dns.to_string()).collect();
+            dhcp_configs.push_str(&format!(" dns-server {}\n", dns_servers.join(" ")));
+        }
+        if let Some(domain_name) = &pool.domain_name {
+            dhcp_configs.push_str(&format!(" domain-name {}\n", domain_name));
+        }
+        if let Some((days, hours, minutes)) = pool.lease {
+            dhcp_configs.push_str(&format!(" lease {} {} {}\n", days, hours, minutes));
+        }
+    }
+
     let mut acl_configs = String::new();
     for acl in acl_store.values() {
         acl_configs.push_str(&format!("!\nip access-list extended {}\n", acl.number_or_name));
@@ -163,12 +888,13 @@ interface {}
  duplex auto
  speed auto
  {}
+ ! type {}, admin state {}, oper state {}
 !
 interface Vlan1
  no ip address
  shutdown
 !
-ip classes
+{}ip classes
 {}
 !
 router ospf {}
@@ -178,7 +904,10 @@ router ospf {}
 !
 {}
 !
-!
+{}!
+{}!
+{}!
+{}!
 end
 "#,
         if context.config.password_encryption {
@@ -192,11 +921,19 @@ end
         interface,
         ip_address,
         shutdown_status,
+        interface_type,
+        admin_state,
+        oper_state,
+        tunnel_configs,
         route_entries,
         ospf_process_id,
         ospf_interface,
         ospf_network_configs,
         acl_configs,
+        ntp_configs,
+        snmp_configs,
+        crypto_configs,
+        dhcp_configs,
     )
 }
 
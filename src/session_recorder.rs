@@ -0,0 +1,180 @@
+//! Records interactive sessions to asciicast v2
+//! (<https://docs.asciinema.org/manual/asciicast/v2/>), the same
+//! session-capture idea as the external VPN crate's asciinema tooling,
+//! backing the `terminal monitor record <file>` / `terminal monitor stop`
+//! command pair and the companion `replay <file>` command.
+//!
+//! Every command's `execute` closure prints its output with an
+//! unconditional `println!` rather than through a context-held sink (the
+//! same limitation `vty_server.rs` documents), so the only way to capture
+//! it without rewriting every command is to redirect the process's real
+//! stdout file descriptor to a pipe for the duration of the recording,
+//! tee each chunk back to the original terminal so the operator still sees
+//! it live, and timestamp it into the asciicast file as it arrives.
+
+use lazy_static::lazy_static;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The terminal size reported in the asciicast header when the session
+/// doesn't otherwise track one.
+const DEFAULT_WIDTH: u16 = 80;
+const DEFAULT_HEIGHT: u16 = 24;
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+struct ActiveRecording {
+    saved_stdout_fd: RawFd,
+    pipe_write_fd: RawFd,
+    reader_handle: JoinHandle<()>,
+}
+
+lazy_static! {
+    static ref ACTIVE: Mutex<Option<ActiveRecording>> = Mutex::new(None);
+}
+
+/// Whether a `terminal monitor record` session is currently capturing
+/// output.
+pub fn is_recording() -> bool {
+    RECORDING.load(Ordering::SeqCst)
+}
+
+/// Starts capturing every line this process prints to stdout into `path` as
+/// an asciicast v2 recording, until [`stop_recording`] is called.
+///
+/// # Errors
+/// Fails if a recording is already in progress, `path` can't be created, or
+/// the stdout redirect (`pipe`/`dup`/`dup2`) fails.
+pub fn start_recording(path: &str) -> Result<(), String> {
+    if is_recording() {
+        return Err("A recording is already in progress. Use 'terminal monitor stop' first.".to_string());
+    }
+
+    let mut cast_file = File::create(path).map_err(|err| format!("Could not create '{}': {}", path, err))?;
+    writeln!(
+        cast_file,
+        "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+        DEFAULT_WIDTH, DEFAULT_HEIGHT
+    )
+    .map_err(|err| format!("Could not write to '{}': {}", path, err))?;
+
+    let mut fds = [0i32; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(format!("Could not open a pipe: {}", io::Error::last_os_error()));
+    }
+    let (read_fd, write_fd) = (fds[0], fds[1]);
+
+    let saved_stdout_fd = unsafe { libc::dup(libc::STDOUT_FILENO) };
+    if saved_stdout_fd < 0 {
+        return Err(format!("Could not save stdout: {}", io::Error::last_os_error()));
+    }
+    if unsafe { libc::dup2(write_fd, libc::STDOUT_FILENO) } < 0 {
+        return Err(format!("Could not redirect stdout: {}", io::Error::last_os_error()));
+    }
+
+    let started = Instant::now();
+    let reader_handle = thread::spawn(move || {
+        let mut pipe_reader = unsafe { File::from_raw_fd(read_fd) };
+        let mut terminal = unsafe { File::from_raw_fd(saved_stdout_fd) };
+        let mut buf = [0u8; 4096];
+        loop {
+            let bytes_read = match pipe_reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            let chunk = &buf[..bytes_read];
+            terminal.write_all(chunk).ok();
+            terminal.flush().ok();
+
+            let text = String::from_utf8_lossy(chunk).into_owned();
+            let elapsed = started.elapsed().as_secs_f64();
+            if let Ok(event) = serde_json::to_string(&(elapsed, "o", text)) {
+                writeln!(cast_file, "{}", event).ok();
+                cast_file.flush().ok();
+            }
+        }
+        // `saved_stdout_fd` is restored onto real stdout and closed by
+        // `stop_recording`, not by this `File`'s `Drop` -- forget it here so
+        // that doesn't happen twice.
+        std::mem::forget(terminal);
+    });
+
+    *ACTIVE.lock().unwrap() = Some(ActiveRecording {
+        saved_stdout_fd,
+        pipe_write_fd: write_fd,
+        reader_handle,
+    });
+    RECORDING.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Stops a recording started with [`start_recording`], restoring the
+/// process's real stdout and finishing the asciicast file.
+///
+/// # Errors
+/// Fails if no recording is in progress.
+pub fn stop_recording() -> Result<(), String> {
+    let active = ACTIVE.lock().unwrap().take();
+    let active = match active {
+        Some(active) => active,
+        None => return Err("No recording in progress.".to_string()),
+    };
+    RECORDING.store(false, Ordering::SeqCst);
+
+    // Restoring stdout first closes whatever `dup2` put there (the pipe's
+    // write end); closing our own handle to that write end then drops its
+    // last reference, so the reader thread's next `read` sees EOF.
+    unsafe {
+        libc::dup2(active.saved_stdout_fd, libc::STDOUT_FILENO);
+        libc::close(active.pipe_write_fd);
+    }
+    active.reader_handle.join().ok();
+    unsafe { libc::close(active.saved_stdout_fd) };
+    Ok(())
+}
+
+/// Re-emits a session recorded by [`start_recording`], printing each
+/// `"o"` event's text and sleeping the original inter-event delay first, so
+/// a shared recording reproduces its original pacing.
+///
+/// # Errors
+/// Fails if `path` can't be opened, its header line is missing/malformed,
+/// or an event line isn't valid JSON.
+pub fn replay_session(path: &str) -> Result<(), String> {
+    let file = File::open(path).map_err(|err| format!("Could not open '{}': {}", path, err))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header = lines
+        .next()
+        .ok_or_else(|| format!("'{}' is empty.", path))?
+        .map_err(|err| format!("Could not read '{}': {}", path, err))?;
+    let header: serde_json::Value = serde_json::from_str(&header)
+        .map_err(|err| format!("'{}' has a malformed asciicast header: {}", path, err))?;
+    if header.get("version").and_then(|v| v.as_u64()) != Some(2) {
+        return Err(format!("'{}' is not an asciicast v2 recording.", path));
+    }
+
+    let mut previous_elapsed = 0.0;
+    for line in lines {
+        let line = line.map_err(|err| format!("Could not read '{}': {}", path, err))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event: (f64, String, String) = serde_json::from_str(&line)
+            .map_err(|err| format!("'{}' has a malformed event: {}", path, err))?;
+        let (elapsed, stream, text) = event;
+        if stream == "o" {
+            let delay = (elapsed - previous_elapsed).max(0.0);
+            thread::sleep(Duration::from_secs_f64(delay));
+            print!("{}", text);
+            io::stdout().flush().ok();
+        }
+        previous_elapsed = elapsed;
+    }
+    Ok(())
+}
@@ -0,0 +1,168 @@
+//! Generates bash/zsh/fish completion scripts straight from the `Command`
+//! registry's keys and `suggestions1` lists -- the same data the interactive
+//! `?` help renders -- so the two can never drift apart. Backs the
+//! `complete <shell>` command.
+//!
+//! An external shell completes the *first* word (launching the binary)
+//! before this program ever runs, so it has no way to know which CLI mode
+//! the user will be in once the REPL starts. The generated script therefore
+//! offers every registered command's full subcommand vocabulary regardless
+//! of mode, rather than trying to mirror the REPL's mode-gated `?` help.
+
+use crate::execute::Command;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// The shell a `complete <shell>` invocation generates a script for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            other => Err(format!(
+                "Unsupported shell '{}'. Use 'bash', 'zsh', or 'fish'.",
+                other
+            )),
+        }
+    }
+}
+
+/// One registered command's typed name and the subcommand words completed
+/// after it, taken from [`Command::suggestions1`] -- the same list used for
+/// the second word of the interactive `?` help.
+struct CommandCompletion {
+    name: &'static str,
+    subcommands: Vec<&'static str>,
+}
+
+/// Walks `commands`, collecting each entry's registry key (the word the user
+/// actually types, which isn't always `Command::name` -- e.g. `"no"` is
+/// keyed by `"no"` but its `name` field reads `"no shutdown"`) and
+/// subcommand vocabulary, sorted by name for a stable, diffable script.
+fn collect_completions(commands: &HashMap<&'static str, Command>) -> Vec<CommandCompletion> {
+    let mut entries: Vec<CommandCompletion> = commands
+        .iter()
+        .map(|(&name, command)| CommandCompletion {
+            name,
+            subcommands: command.suggestions1.clone().unwrap_or_default(),
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.name);
+    entries
+}
+
+/// Builds the bash/zsh/fish completion script for `shell`, offering
+/// `program_name` as the completed command and every entry in `commands`'
+/// registry as its top-level vocabulary, with each command's
+/// `suggestions1` completed as its subcommand.
+pub fn generate_completion_script(
+    shell: Shell,
+    program_name: &str,
+    commands: &HashMap<&'static str, Command>,
+) -> String {
+    let entries = collect_completions(commands);
+    match shell {
+        Shell::Bash => generate_bash(program_name, &entries),
+        Shell::Zsh => generate_zsh(program_name, &entries),
+        Shell::Fish => generate_fish(program_name, &entries),
+    }
+}
+
+fn generate_bash(program_name: &str, entries: &[CommandCompletion]) -> String {
+    let function_name = format!("_{}_complete", program_name);
+    let top_level = entries
+        .iter()
+        .map(|entry| entry.name)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut script = String::new();
+    let _ = writeln!(script, "# bash completion for {}", program_name);
+    let _ = writeln!(script, "# Generated from the command registry; do not edit by hand.");
+    let _ = writeln!(script, "{}() {{", function_name);
+    let _ = writeln!(script, "    local cur prev words cword");
+    let _ = writeln!(script, "    _init_completion || return");
+    let _ = writeln!(script, "    if [[ $cword -eq 1 ]]; then");
+    let _ = writeln!(script, "        COMPREPLY=( $(compgen -W \"{}\" -- \"$cur\") )", top_level);
+    let _ = writeln!(script, "        return");
+    let _ = writeln!(script, "    fi");
+    let _ = writeln!(script, "    case \"${{words[1]}}\" in");
+    for entry in entries {
+        if entry.subcommands.is_empty() {
+            continue;
+        }
+        let subcommands = entry.subcommands.join(" ");
+        let _ = writeln!(script, "        {})", entry.name);
+        let _ = writeln!(script, "            COMPREPLY=( $(compgen -W \"{}\" -- \"$cur\") )", subcommands);
+        let _ = writeln!(script, "            ;;");
+    }
+    let _ = writeln!(script, "    esac");
+    let _ = writeln!(script, "}}");
+    let _ = writeln!(script, "complete -F {} {}", function_name, program_name);
+    script
+}
+
+fn generate_zsh(program_name: &str, entries: &[CommandCompletion]) -> String {
+    let mut script = String::new();
+    let _ = writeln!(script, "#compdef {}", program_name);
+    let _ = writeln!(script, "# zsh completion for {}", program_name);
+    let _ = writeln!(script, "# Generated from the command registry; do not edit by hand.");
+    let _ = writeln!(script, "_{}() {{", program_name);
+    let _ = writeln!(script, "    local -a top_level_commands");
+    let _ = writeln!(script, "    top_level_commands=(");
+    for entry in entries {
+        let _ = writeln!(script, "        '{}'", entry.name);
+    }
+    let _ = writeln!(script, "    )");
+    let _ = writeln!(script, "    if (( CURRENT == 2 )); then");
+    let _ = writeln!(script, "        _describe 'command' top_level_commands");
+    let _ = writeln!(script, "        return");
+    let _ = writeln!(script, "    fi");
+    let _ = writeln!(script, "    case \"${{words[2]}}\" in");
+    for entry in entries {
+        if entry.subcommands.is_empty() {
+            continue;
+        }
+        let subcommands = entry.subcommands.join(" ");
+        let _ = writeln!(script, "        {})", entry.name);
+        let _ = writeln!(script, "            _values 'subcommand' {}", subcommands);
+        let _ = writeln!(script, "            ;;");
+    }
+    let _ = writeln!(script, "    esac");
+    let _ = writeln!(script, "}}");
+    let _ = writeln!(script, "_{}", program_name);
+    script
+}
+
+fn generate_fish(program_name: &str, entries: &[CommandCompletion]) -> String {
+    let mut script = String::new();
+    let _ = writeln!(script, "# fish completion for {}", program_name);
+    let _ = writeln!(script, "# Generated from the command registry; do not edit by hand.");
+    for entry in entries {
+        let _ = writeln!(
+            script,
+            "complete -c {} -n \"__fish_use_subcommand\" -a \"{}\"",
+            program_name, entry.name
+        );
+    }
+    for entry in entries {
+        if entry.subcommands.is_empty() {
+            continue;
+        }
+        let subcommands = entry.subcommands.join(" ");
+        let _ = writeln!(
+            script,
+            "complete -c {} -n \"__fish_seen_subcommand_from {}\" -a \"{}\"",
+            program_name, entry.name, subcommands
+        );
+    }
+    script
+}
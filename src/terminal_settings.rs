@@ -0,0 +1,81 @@
+//! Persisted rustyline editor settings, configured via the `terminal` CLI
+//! command and re-applied to the running `Editor` after every command (see
+//! `main`'s REPL loop) the same way the prompt and helper mode are kept in
+//! sync after each line.
+
+use serde::{Deserialize, Serialize};
+
+/// The editing keybinding style, mirroring `rustyline::EditMode`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditMode {
+    Emacs,
+    Vi,
+}
+
+/// Whether completion/hint output uses ANSI color, mirroring
+/// `rustyline::ColorMode`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorMode {
+    Enabled,
+    Forced,
+    Disabled,
+}
+
+/// How Tab-completion candidates are cycled, mirroring
+/// `rustyline::CompletionType`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompletionStyle {
+    List,
+    Circular,
+}
+
+/// Every rustyline-facing setting the `terminal` command can change,
+/// persisted in [`crate::cliconfig::CliConfig`] so it survives a reload.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TerminalSettings {
+    pub edit_mode: EditMode,
+    pub color_mode: ColorMode,
+    pub completion_type: CompletionStyle,
+    pub max_history_size: usize,
+    /// Whether consecutive duplicate lines are both kept in history.
+    pub history_duplicates: bool,
+    pub history_file: String,
+}
+
+impl Default for TerminalSettings {
+    fn default() -> Self {
+        Self {
+            edit_mode: EditMode::Emacs,
+            color_mode: ColorMode::Enabled,
+            completion_type: CompletionStyle::List,
+            max_history_size: 1000,
+            history_duplicates: true,
+            history_file: "history.txt".to_string(),
+        }
+    }
+}
+
+impl TerminalSettings {
+    /// Builds the `rustyline::Config` the current settings describe, for
+    /// `Editor::set_config` to apply.
+    pub fn to_rustyline_config(&self) -> rustyline::Config {
+        rustyline::Config::builder()
+            .history_ignore_space(true)
+            .edit_mode(match self.edit_mode {
+                EditMode::Emacs => rustyline::EditMode::Emacs,
+                EditMode::Vi => rustyline::EditMode::Vi,
+            })
+            .color_mode(match self.color_mode {
+                ColorMode::Enabled => rustyline::ColorMode::Enabled,
+                ColorMode::Forced => rustyline::ColorMode::Forced,
+                ColorMode::Disabled => rustyline::ColorMode::Disabled,
+            })
+            .completion_type(match self.completion_type {
+                CompletionStyle::List => rustyline::CompletionType::List,
+                CompletionStyle::Circular => rustyline::CompletionType::Circular,
+            })
+            .history_ignore_dups(!self.history_duplicates)
+            .max_history_size(self.max_history_size)
+            .build()
+    }
+}
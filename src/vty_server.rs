@@ -0,0 +1,166 @@
+//! A TCP listener for the `line vty` configuration, so that a remote
+//! telnet/SSH-style client can drive the CLI the same way an interactive
+//! local session would -- each accepted connection gets its own
+//! [`CliContext`], [`CommandCompleter`], and `Mode`, independent of every
+//! other connection and of the local REPL in `main`.
+//!
+//! Authentication mirrors `login local` (checked against
+//! [`USER_CREDENTIALS`] via `verify_user_password`) or, when `login local`
+//! hasn't been configured, the shared `enable` password in
+//! [`PASSWORD_STORAGE`].
+//!
+//! # Limitation
+//! Every command's `execute` closure writes its output with an unconditional
+//! `println!`, and Rust has no stable way to capture another thread's
+//! stdout. A connected client therefore sees its own prompt, echoed input,
+//! and `Err` messages, but not the `println!` output a command produces --
+//! that still goes to the process's own terminal. Fully mirroring command
+//! output would need every command rewritten to write through a context-held
+//! sink instead of `println!`, which is out of scope here.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Once;
+use std::thread;
+
+use crate::cliconfig::CliContext;
+use crate::clicommands::build_command_registry;
+use crate::clock_settings::Clock;
+use crate::commandcompleter::CommandCompleter;
+use crate::execute::{execute_command, Mode};
+use crate::network_config::{verify_user_password, PASSWORD_STORAGE, VTY_CONFIG};
+
+/// The port the vty listener binds to. Chosen above the well-known range so
+/// it doesn't collide with a host's real telnet/SSH daemon.
+const VTY_PORT: u16 = 2323;
+
+static START: Once = Once::new();
+
+/// Starts the vty listener the first time `line vty` is configured. Safe to
+/// call more than once -- only the first call actually binds the socket.
+pub fn ensure_started() {
+    START.call_once(|| {
+        thread::spawn(|| {
+            let listener = match TcpListener::bind(("0.0.0.0", VTY_PORT)) {
+                Ok(listener) => listener,
+                Err(err) => {
+                    eprintln!("vty: failed to bind port {}: {}", VTY_PORT, err);
+                    return;
+                }
+            };
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        thread::spawn(move || handle_connection(stream));
+                    }
+                    Err(err) => eprintln!("vty: failed to accept connection: {}", err),
+                }
+            }
+        });
+    });
+}
+
+/// Prompts for and checks a username/password over `stream`, per the
+/// listener's current `login local` configuration; `transport input` is
+/// not consulted here.
+///
+/// # Returns
+/// `true` if the client authenticated successfully.
+fn authenticate(stream: &mut TcpStream, reader: &mut BufReader<TcpStream>) -> bool {
+    let login_local = VTY_CONFIG.lock().unwrap().login_local;
+
+    if login_local {
+        write!(stream, "Username: ").ok();
+        stream.flush().ok();
+        let mut username = String::new();
+        if reader.read_line(&mut username).unwrap_or(0) == 0 {
+            return false;
+        }
+        let username = username.trim();
+
+        write!(stream, "Password: ").ok();
+        stream.flush().ok();
+        let mut password = String::new();
+        if reader.read_line(&mut password).unwrap_or(0) == 0 {
+            return false;
+        }
+        let password = password.trim();
+
+        verify_user_password(username, password)
+    } else {
+        write!(stream, "Password: ").ok();
+        stream.flush().ok();
+        let mut password = String::new();
+        if reader.read_line(&mut password).unwrap_or(0) == 0 {
+            return false;
+        }
+        let password = password.trim();
+
+        match PASSWORD_STORAGE.lock().unwrap().enable_password.clone() {
+            Some(expected) => password == expected,
+            None => true,
+        }
+    }
+}
+
+/// Runs a single vty connection's authentication and REPL loop, each with
+/// its own `CliContext` starting in `UserMode` -- mirroring a freshly opened
+/// local session.
+fn handle_connection(mut stream: TcpStream) {
+    let peer = stream
+        .peer_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(clone) => clone,
+        Err(_) => return,
+    });
+
+    if !authenticate(&mut stream, &mut reader) {
+        writeln!(stream, "% Authentication failed").ok();
+        return;
+    }
+
+    let commands = build_command_registry();
+    let mut commands_map: HashMap<String, Vec<String>> = HashMap::new();
+    for name in commands.keys() {
+        commands_map.insert(name.to_string(), vec![name.to_string()]);
+    }
+
+    let mut context = CliContext::default();
+    let mut clock = Some(Clock::new());
+    let mut completer = CommandCompleter::new(commands_map, Mode::UserMode);
+
+    loop {
+        write!(stream, "\n{}", context.prompt).ok();
+        if stream.flush().is_err() {
+            break;
+        }
+
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+        if input == "exit cli" {
+            writeln!(stream, "Exiting CLI...").ok();
+            break;
+        }
+
+        match execute_command(input, &commands, &mut context, &mut clock, &mut completer) {
+            Ok(()) => {}
+            Err(err) => {
+                writeln!(stream, "Error: {}", err).ok();
+            }
+        }
+        completer.current_mode = context.current_mode.clone();
+    }
+
+    let _ = peer;
+}
@@ -6,7 +6,7 @@
 
 use crate::execute::{Mode, Command, get_mode_commands};
 use crate::dynamic_registry::{get_mode_commands_FNC, DYNAMIC_COMMANDS};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 impl fmt::Display for Mode {
@@ -24,6 +24,13 @@ pub struct ModeHierarchy {
     /// A mapping of each mode to its parent mode.
     /// If a mode has no parent, the value will be `None`.
     pub parent_map: HashMap<Mode, Option<Mode>>,
+    /// The commands allowed in each mode, consulted by
+    /// `is_command_allowed_in_mode` alongside the `dynamic_registry`. Built
+    /// from `register_mode`'s `base_commands` and extendable afterwards with
+    /// `bind_command`, replacing a hardcoded per-mode `match` so new modes
+    /// (e.g. the router protocol sub-modes) can be introduced without
+    /// recompiling.
+    mode_commands: HashMap<Mode, HashSet<String>>,
 }
 
 impl ModeHierarchy {
@@ -33,22 +40,91 @@ impl ModeHierarchy {
     /// # Returns
     /// A new instance of `ModeHierarchy` with the initialized parent map.
     pub fn new() -> Self {
-        let mut parent_map = HashMap::new();
-        
-        parent_map.insert(Mode::UserMode, None);
-        parent_map.insert(Mode::PrivilegedMode, Some(Mode::UserMode));
-        parent_map.insert(Mode::ConfigMode, Some(Mode::PrivilegedMode));
-        parent_map.insert(Mode::InterfaceMode, Some(Mode::ConfigMode));
-        parent_map.insert(Mode::VlanMode, Some(Mode::ConfigMode));
-        parent_map.insert(Mode::RouterConfigMode, Some(Mode::ConfigMode));
-        //parent_map.insert(Mode::RouterRIPMode, Some(Mode::ConfigMode));
-        //parent_map.insert(Mode::RouterISISMode, Some(Mode::ConfigMode));  
-        //parent_map.insert(Mode::RouterEIGRPMode, Some(Mode::ConfigMode));
-        //parent_map.insert(Mode::RouterBGPMode, Some(Mode::ConfigMode));
-        parent_map.insert(Mode::ConfigStdNaclMode("default".to_string()), Some(Mode::ConfigMode));  
-        parent_map.insert(Mode::ConfigExtNaclMode("default".to_string()), Some(Mode::ConfigMode));    
-        
-        Self { parent_map }
+        let mut hierarchy = Self {
+            parent_map: HashMap::new(),
+            mode_commands: HashMap::new(),
+        };
+
+        hierarchy.register_mode(Mode::UserMode, None, &[
+            "enable", "ping", "help", "show", "clear", "reload", "exit",
+        ]);
+        hierarchy.register_mode(Mode::PrivilegedMode, Some(Mode::UserMode), &[
+            "configure", "ping", "exit", "write", "help", "show", "copy",
+            "clock", "clear", "reload", "debug", "undebug", "ifconfig",
+        ]);
+        hierarchy.register_mode(Mode::ConfigMode, Some(Mode::PrivilegedMode), &[
+            "hostname", "interface", "ping", "exit", "clear", "tunnel",
+            "access-list", "router", "virtual-template", "help", "write",
+            "vlan", "ip", "ipv6", "service", "set", "enable", "ifconfig", "ntp",
+            "snmp-server", "no", "reload", "crypto",
+        ]);
+        hierarchy.register_mode(Mode::InterfaceMode, Some(Mode::ConfigMode), &[
+            "shutdown", "no", "exit", "clear", "help", "switchport",
+            "write", "reload", "ip", "ipv6", "tunnel",
+        ]);
+        hierarchy.register_mode(Mode::VlanMode, Some(Mode::ConfigMode), &[
+            "name", "state", "clear", "exit", "help", "reload", "vlan",
+        ]);
+        hierarchy.register_mode(Mode::RouterConfigMode, Some(Mode::ConfigMode), &[
+            "network", "neighbor", "exit", "clear", "area",
+            "passive-interface", "distance", "help", "reload",
+            "default-information", "router-id",
+        ]);
+        hierarchy.register_mode(Mode::RouterBgpMode, Some(Mode::ConfigMode), &[
+            "network", "neighbor", "redistribute", "exit", "clear", "help", "reload",
+        ]);
+        hierarchy.register_mode(Mode::RouterRipMode, Some(Mode::ConfigMode), &[
+            "version", "network", "no", "exit", "clear", "help", "reload",
+        ]);
+        hierarchy.register_mode(Mode::RouterIsisMode, Some(Mode::ConfigMode), &[
+            "net", "is-type", "exit", "clear", "help", "reload",
+        ]);
+        hierarchy.register_mode(Mode::RouterOspfv3Mode, Some(Mode::ConfigMode), &[
+            "router-id", "exit", "clear", "help", "reload",
+        ]);
+        // The protocol sub-modes (RIP/ISIS/EIGRP/BGP) used to be
+        // commented-out `parent_map` entries that couldn't be turned on
+        // without editing this file. Now they -- or any other mode -- can be
+        // added the same way at runtime via `register_mode`.
+        hierarchy.register_mode(Mode::ConfigStdNaclMode("default".to_string()), Some(Mode::ConfigMode), &[
+            "deny", "permit", "help", "exit", "clear", "reload", "ip",
+        ]);
+        hierarchy.register_mode(Mode::ConfigExtNaclMode("default".to_string()), Some(Mode::ConfigMode), &[
+            "deny", "permit", "help", "exit", "clear", "reload", "ip",
+        ]);
+        hierarchy.register_mode(Mode::LineVtyMode("0 4".to_string()), Some(Mode::ConfigMode), &[
+            "transport", "login", "exit", "clear", "reload", "help",
+        ]);
+        hierarchy.register_mode(Mode::CryptoIsakmpPolicyMode(0), Some(Mode::ConfigMode), &[
+            "encryption", "hash", "authentication", "group", "lifetime",
+            "exit", "clear", "reload", "help",
+        ]);
+        hierarchy.register_mode(Mode::CryptoIsakmpGroupMode("default".to_string()), Some(Mode::ConfigMode), &[
+            "pool", "dns", "key", "exit", "clear", "reload", "help",
+        ]);
+        hierarchy.register_mode(Mode::DhcpPoolMode("default".to_string()), Some(Mode::ConfigMode), &[
+            "network", "default-router", "dns-server", "domain-name", "lease",
+            "exit", "clear", "reload", "help",
+        ]);
+
+        hierarchy
+    }
+
+    /// Registers `mode` as a child of `parent` (`None` for a root mode) with
+    /// `base_commands` as its initially allowed command set, alongside the
+    /// existing `dynamic_registry`. Lets new modes be introduced at runtime
+    /// -- e.g. the router protocol sub-modes (BGP/EIGRP/ISIS) -- instead of
+    /// adding another arm to a hardcoded match.
+    pub fn register_mode(&mut self, mode: Mode, parent: Option<Mode>, base_commands: &[&str]) {
+        self.parent_map.insert(mode.clone(), parent);
+        self.mode_commands.insert(mode, base_commands.iter().map(|c| c.to_string()).collect());
+    }
+
+    /// Allows `command` in `mode`, in addition to whatever it already
+    /// allows. Registers an empty command set for `mode` first if it hasn't
+    /// been seen via `register_mode`.
+    pub fn bind_command(&mut self, mode: Mode, command: &str) {
+        self.mode_commands.entry(mode).or_insert_with(HashSet::new).insert(command.to_string());
     }
 
     /// Finds the mode in which a given command is valid, starting from the
@@ -67,7 +143,7 @@ impl ModeHierarchy {
         
         loop {
             // Try to match the command in the current mode
-            if Self::is_command_allowed_in_mode(command, &current_mode) || 
+            if self.is_command_allowed_in_mode(command, &current_mode) ||
                 get_mode_commands_FNC(&DYNAMIC_COMMANDS.read().unwrap(), &current_mode)
                     .contains(&command){
                 return Some(current_mode);
@@ -91,6 +167,11 @@ impl ModeHierarchy {
 
     /// Checks if a command is allowed in a specific mode.
     ///
+    /// Consults `mode_commands`, populated by `register_mode`/`bind_command`,
+    /// rather than a hardcoded per-mode match -- so a mode registered at
+    /// runtime (e.g. a protocol sub-mode) is checked the same way as a
+    /// built-in one.
+    ///
     /// # Arguments
     /// * `command` - The command to check.
     /// * `mode` - The mode to check the command against.
@@ -98,103 +179,10 @@ impl ModeHierarchy {
     /// # Returns
     /// * `true` - If the command is allowed in the mode.
     /// * `false` - Otherwise.
-    pub fn is_command_allowed_in_mode(command: &str, mode: &Mode) -> bool {
-        match mode {
-            Mode::UserMode => 
-                command == "enable" ||
-                command == "ping" ||
-                command == "help" ||
-                command == "show" ||
-                command == "clear" ||
-                command == "reload" ||
-                command == "exit",
-            Mode::PrivilegedMode => 
-                command == "configure" ||
-                command == "ping" || 
-                command == "exit" || 
-                command == "write" ||
-                command == "help" ||
-                command == "show" ||
-                command == "copy" ||
-                command == "clock" ||
-                command == "clear" ||
-                command == "reload" ||
-                command == "debug" ||
-                command == "undebug" ||
-                command == "ifconfig",
-            Mode::ConfigMode => 
-                command == "hostname" || 
-                command == "interface" ||
-                command == "ping" ||
-                command == "exit" ||
-                command == "clear" ||
-                command == "tunnel" ||
-                command == "access-list" ||
-                command == "router" ||
-                command == "virtual-template" ||
-                command == "help" ||
-                command == "write" ||
-                command == "vlan" ||
-                command == "ip" ||
-                command == "service" ||
-                command == "set" ||
-                command == "enable" ||
-                command == "ifconfig" ||  
-                command == "ntp" ||
-                command == "no" || 
-                command == "reload" ||
-                command == "crypto",
-            Mode::InterfaceMode => 
-                command == "shutdown" ||
-                command == "no" ||
-                command == "exit" ||
-                command == "clear" ||
-                command == "help" ||
-                command == "switchport" ||
-                command == "write" ||
-                command == "reload" ||
-                command == "ip" ,
-            Mode::VlanMode => 
-                command == "name" ||
-                command == "state" ||
-                command == "clear" ||
-                command == "exit" ||
-                command == "help" ||
-                command == "reload" ||
-                command == "vlan",
-            Mode::CryptoUserMode => 
-                command == "exit",
-            Mode::RouterConfigMode => 
-                command == "network" ||
-                command == "neighbor" ||
-                command == "exit" ||
-                command == "clear" ||
-                command == "area" ||
-                command == "passive-interface" ||
-                command == "distance" ||
-                command == "help" ||
-                command == "reload" ||
-                command == "default-information" ||
-                command == "router-id", 
-            Mode::ConfigStdNaclMode(_) => 
-                command == "deny" ||
-                command == "permit" ||
-                command == "help" ||
-                command == "exit" ||
-                command == "clear" ||
-                command == "reload" ||
-                command == "ip",
-            Mode::ConfigExtNaclMode(_) => 
-                command == "deny" ||
-                command == "permit" ||
-                command == "help" ||
-                command == "exit" ||
-                command == "clear" ||
-                command == "reload" ||
-                command == "ip",
-    
-        }
-        
+    pub fn is_command_allowed_in_mode(&self, command: &str, mode: &Mode) -> bool {
+        self.mode_commands
+            .get(mode)
+            .map_or(false, |commands| commands.contains(command))
     }
 
 }
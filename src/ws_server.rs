@@ -0,0 +1,216 @@
+//! A WebSocket listener exposing the same command dispatch that backs local
+//! and vty sessions, the way the external VPN crate's WebSocket proxy mode
+//! exposes its own control plane -- each accepted connection gets its own
+//! [`CliContext`], [`CommandCompleter`], and `Mode`, independent of every
+//! other connection, the local REPL in `main`, and any `vty_server`
+//! connection, so concurrent clients never clobber each other's mode.
+//!
+//! Unlike `vty_server`, a client here has no interactive terminal: each
+//! incoming text frame is one command, and the response -- its `println!`
+//! output is still subject to the same limitation `vty_server.rs` documents,
+//! so only the command's own `Err` message, or the session's new prompt on
+//! success, is returned -- comes back as one text frame.
+//!
+//! `management websocket <port> key <key-name>` starts an additional,
+//! independently-portable instance of this same channel that requires a
+//! client to authenticate with one of the keys configured via `crypto key`
+//! before any command frame is accepted: the server challenges with a
+//! random nonce and the client must sign it with the key's private half,
+//! proving possession of the key rather than just its public fingerprint
+//! -- see [`start_management_channel`].
+
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::Once;
+use std::thread;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+use tungstenite::{Message, WebSocket};
+
+use crate::cliconfig::CliContext;
+use crate::clicommands::build_command_registry;
+use crate::clock_settings::Clock;
+use crate::commandcompleter::CommandCompleter;
+use crate::execute::{execute_command, Mode};
+
+/// The port the WebSocket listener binds to.
+const WS_PORT: u16 = 8765;
+
+static START: Once = Once::new();
+
+/// Starts the WebSocket listener the first time it's configured. Safe to
+/// call more than once -- only the first call actually binds the socket.
+pub fn ensure_started() {
+    START.call_once(|| {
+        thread::spawn(|| {
+            let listener = match TcpListener::bind(("0.0.0.0", WS_PORT)) {
+                Ok(listener) => listener,
+                Err(err) => {
+                    eprintln!("websocket: failed to bind port {}: {}", WS_PORT, err);
+                    return;
+                }
+            };
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        thread::spawn(move || {
+                            let socket = match tungstenite::accept(stream) {
+                                Ok(socket) => socket,
+                                Err(err) => {
+                                    eprintln!("websocket: handshake failed: {}", err);
+                                    return;
+                                }
+                            };
+                            handle_connection(socket);
+                        });
+                    }
+                    Err(err) => eprintln!("websocket: failed to accept connection: {}", err),
+                }
+            }
+        });
+    });
+}
+
+/// Runs a single WebSocket connection's command loop: each incoming text
+/// frame is dispatched through [`execute_command`] against a `CliContext`
+/// that starts in `UserMode`, mirroring a freshly opened local session, and
+/// the session's new prompt (or the command's error) is sent back as one
+/// text frame.
+fn handle_connection(mut socket: WebSocket<std::net::TcpStream>) {
+    let commands = build_command_registry();
+    let mut commands_map: HashMap<String, Vec<String>> = HashMap::new();
+    for name in commands.keys() {
+        commands_map.insert(name.to_string(), vec![name.to_string()]);
+    }
+
+    let mut context = CliContext::default();
+    let mut clock = Some(Clock::new());
+    let mut completer = CommandCompleter::new(commands_map, Mode::UserMode);
+
+    loop {
+        let message = match socket.read() {
+            Ok(message) => message,
+            Err(_) => break,
+        };
+
+        let input = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        let reply = match execute_command(input, &commands, &mut context, &mut clock, &mut completer) {
+            Ok(()) => format!("{}", context.prompt),
+            Err(err) => format!("Error: {}", err),
+        };
+        completer.current_mode = context.current_mode.clone();
+
+        if socket.send(Message::Text(reply)).is_err() {
+            break;
+        }
+    }
+}
+
+/// Ports a `management websocket` channel has already been started on, so
+/// that re-running the command for a port that's already listening is a
+/// no-op rather than a second `bind` that just fails -- the same
+/// safe-to-call-more-than-once contract [`ensure_started`] gives the fixed
+/// `websocket-server enable` channel.
+static MANAGEMENT_PORTS: std::sync::Mutex<Vec<u16>> = std::sync::Mutex::new(Vec::new());
+
+/// Starts a `management websocket <port>` channel authenticated against the
+/// named crypto key, the first time that port is configured. The key's
+/// public half is derived once at start time and used to verify every
+/// connection's signed nonce afterwards -- rotating or deleting the key
+/// only takes effect the next time the channel is (re-)started, the same
+/// lag `vty_server`'s captured `login_local` flag has within a single
+/// connection's `authenticate` call.
+pub fn start_management_channel(port: u16, key_name: &str, key_pem: &str) -> Result<(), String> {
+    let public_key = RsaPrivateKey::from_pkcs8_pem(key_pem)
+        .map_err(|err| format!("Key '{}' cannot be used for authentication: {}", key_name, err))?
+        .to_public_key();
+
+    let mut started = MANAGEMENT_PORTS.lock().unwrap();
+    if started.contains(&port) {
+        return Ok(());
+    }
+
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .map_err(|err| format!("management websocket: failed to bind port {}: {}", port, err))?;
+    started.push(port);
+    drop(started);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let public_key = public_key.clone();
+                    thread::spawn(move || {
+                        let socket = match tungstenite::accept(stream) {
+                            Ok(socket) => socket,
+                            Err(err) => {
+                                eprintln!("management websocket: handshake failed: {}", err);
+                                return;
+                            }
+                        };
+                        handle_authenticated_connection(socket, &public_key);
+                    });
+                }
+                Err(err) => eprintln!("management websocket: failed to accept connection: {}", err),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Runs a single `management websocket` connection: the server sends
+/// `NONCE <base64>` with a fresh random 32-byte nonce, and the client's
+/// next text frame must be `AUTH <base64-signature>` -- a PKCS#1v1.5/
+/// SHA-256 signature of that nonce (the same scheme `acme.rs` signs ACME
+/// requests with) made with the private half of the crypto key the
+/// channel was started with. Only a signature [`RsaPublicKey::verify`]
+/// accepts against that exact nonce proves possession of the key; a
+/// replayed signature from an earlier connection is worthless since each
+/// connection gets its own nonce. Only then is every subsequent frame
+/// dispatched through [`execute_command`] exactly like [`handle_connection`].
+fn handle_authenticated_connection(mut socket: WebSocket<std::net::TcpStream>, public_key: &RsaPublicKey) {
+    let mut nonce = [0u8; 32];
+    OsRng.fill_bytes(&mut nonce);
+    if socket.send(Message::Text(format!("NONCE {}", BASE64.encode(nonce)))).is_err() {
+        return;
+    }
+
+    let message = match socket.read() {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+    let Message::Text(auth_line) = message else {
+        socket.send(Message::Text("Error: expected AUTH <signature>".into())).ok();
+        return;
+    };
+    let digest = Sha256::digest(nonce);
+    let authenticated = auth_line
+        .trim()
+        .strip_prefix("AUTH ")
+        .and_then(|signature_b64| BASE64.decode(signature_b64).ok())
+        .map(|signature| public_key.verify(Pkcs1v15Sign::new::<Sha256>(), &digest, &signature).is_ok())
+        .unwrap_or(false);
+    if !authenticated {
+        socket.send(Message::Text("Error: authentication failed".into())).ok();
+        return;
+    }
+    socket.send(Message::Text("Authenticated.".into())).ok();
+
+    handle_connection(socket);
+}